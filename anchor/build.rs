@@ -0,0 +1,70 @@
+//! Build metadata for `GET /version`
+//!
+//! Embeds the git commit, build time and enabled cargo features as
+//! compile-time environment variables. `GIT_SHA` overrides the commit for
+//! builds without a checkout (e.g. the Docker image), and `SOURCE_DATE_EPOCH`
+//! pins the build time for reproducible builds.
+
+use std::env;
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=GIT_SHA");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    let git_sha = env::var("GIT_SHA")
+        .ok()
+        .filter(|sha| !sha.is_empty())
+        .or_else(git_head)
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=SET_ANCHOR_GIT_SHA={}", git_sha);
+
+    let build_timestamp = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or(0)
+        });
+    println!(
+        "cargo:rustc-env=SET_ANCHOR_BUILD_TIMESTAMP={}",
+        build_timestamp
+    );
+
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|feature| feature.to_lowercase().replace('_', "-"))
+        })
+        .collect();
+    features.sort();
+    println!("cargo:rustc-env=SET_ANCHOR_FEATURES={}", features.join(","));
+}
+
+/// Commit checked out, rebuilding when it moves
+fn git_head() -> Option<String> {
+    let git = |args: &[&str]| -> Option<String> {
+        let output = Command::new("git").args(args).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+    };
+
+    let head = git(&["rev-parse", "--git-path", "HEAD"])?;
+    println!("cargo:rerun-if-changed={}", head);
+    if let Some(branch) = git(&["symbolic-ref", "-q", "HEAD"]) {
+        // A packed ref has no file of its own to watch
+        if let Some(reference) = git(&["rev-parse", "--git-path", &branch])
+            .filter(|reference| Path::new(reference).exists())
+        {
+            println!("cargo:rerun-if-changed={}", reference);
+        }
+    }
+    git(&["rev-parse", "HEAD"])
+}
@@ -0,0 +1,120 @@
+//! Operator alert webhook
+//!
+//! Conditions that need a human before they turn into failed anchors (the
+//! signer running low on gas funds, or being de-authorized in the registry) are
+//! POSTed as JSON to a configured URL:
+//!
+//! ```json
+//! {
+//!   "alert": "low_balance",
+//!   "message": "Signer balance below threshold",
+//!   "signer": "0x...",
+//!   "balance_wei": "40000000000000000",
+//!   "threshold_wei": "50000000000000000",
+//!   "timestamp": "2026-10-16T09:30:00+00:00"
+//! }
+//! ```
+
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::Serialize;
+
+/// Request timeout for alert delivery
+const ALERT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Alert posted to the webhook
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    pub alert: &'static str,
+    pub message: String,
+    pub signer: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance_wei: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threshold_wei: Option<String>,
+    pub timestamp: String,
+}
+
+/// HTTP client for the alert webhook
+#[derive(Debug)]
+pub struct AlertHook {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl AlertHook {
+    pub fn new(url: &str) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(ALERT_TIMEOUT)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            url: url.to_string(),
+            client,
+        }
+    }
+
+    /// Endpoint alerts are posted to
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Deliver `alert`; a non-2xx response is an error
+    pub async fn send(&self, alert: &Alert) -> Result<()> {
+        let response = self.client.post(&self.url).json(alert).send().await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Alert webhook returned {}", response.status());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::{
+        matchers::{body_partial_json, method},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    fn low_balance_alert() -> Alert {
+        Alert {
+            alert: "low_balance",
+            message: "Signer balance below threshold".to_string(),
+            signer: "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".to_string(),
+            balance_wei: Some("1".to_string()),
+            threshold_wei: Some("2".to_string()),
+            timestamp: "2026-10-16T09:30:00+00:00".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_posts_alert_json() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({
+                "alert": "low_balance",
+                "balance_wei": "1",
+            })))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let hook = AlertHook::new(&server.uri());
+        hook.send(&low_balance_alert()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_send_errors_on_rejection() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let hook = AlertHook::new(&server.uri());
+        assert!(hook.send(&low_balance_alert()).await.is_err());
+    }
+}
@@ -0,0 +1,358 @@
+//! Object-storage archive of anchored batches
+//!
+//! With `ARCHIVE_BUCKET` set, every anchored batch is written as a JSON
+//! artifact (commitment and roots, transaction hash, receipt, and the
+//! notification sent to the sequencer) to an S3-compatible bucket under a
+//! date-partitioned key:
+//!
+//! ```text
+//! {ARCHIVE_PREFIX}/{yyyy}/{mm}/{dd}/{batch_id}.json
+//! ```
+//!
+//! Uploads are signed with AWS Signature V4, which S3, Google Cloud Storage
+//! (XML API with HMAC keys), MinIO and R2 all accept. Objects are written with
+//! `If-None-Match: *`, so an artifact is never overwritten once archived.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::config::AnchorConfig;
+use crate::types::{AnchorNotification, BatchCommitment, BatchIdScheme};
+
+/// Signing algorithm of AWS Signature V4
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+/// JSON artifact written for an anchored batch
+#[derive(Debug, Clone, Serialize)]
+pub struct AnchorArchive {
+    pub batch_id: Uuid,
+    pub commitment: BatchCommitment,
+    pub chain_id: u64,
+    pub tx_hash: String,
+    pub block_number: u64,
+    pub gas_used: u64,
+    pub batch_id_scheme: BatchIdScheme,
+    /// `bytes32` key the batch is stored under in the registry
+    pub chain_batch_id: String,
+    /// Hex-encoded transaction input, if known
+    pub calldata: Option<String>,
+    /// Transaction receipt as returned by the node
+    pub receipt: Option<serde_json::Value>,
+    /// Notification sent to the sequencer, with any data availability references
+    pub notification: AnchorNotification,
+    pub archived_at: DateTime<Utc>,
+}
+
+/// Whether an upload wrote a new object
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveOutcome {
+    Archived,
+    /// The key already held an artifact, which was left untouched
+    AlreadyArchived,
+}
+
+/// Credentials for signing uploads
+#[derive(Clone)]
+pub struct ArchiveCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+impl std::fmt::Debug for ArchiveCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArchiveCredentials")
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Client writing artifacts to an S3-compatible bucket
+#[derive(Debug)]
+pub struct ObjectArchive {
+    endpoint: String,
+    bucket: String,
+    region: String,
+    prefix: String,
+    credentials: ArchiveCredentials,
+    client: reqwest::Client,
+}
+
+impl ObjectArchive {
+    /// Create a client for `bucket` at `endpoint` (path-style addressing)
+    pub fn new(
+        endpoint: &str,
+        bucket: &str,
+        region: &str,
+        prefix: &str,
+        credentials: ArchiveCredentials,
+        timeout: Duration,
+    ) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            bucket: bucket.to_string(),
+            region: region.to_string(),
+            prefix: prefix.trim_matches('/').to_string(),
+            credentials,
+            client,
+        }
+    }
+
+    /// Build the archive configured by `ARCHIVE_BUCKET`, if any
+    pub fn from_config(config: &AnchorConfig) -> Option<Self> {
+        let bucket = config.archive_bucket.as_deref()?;
+        let credentials = ArchiveCredentials {
+            access_key_id: config.archive_access_key_id.clone()?,
+            secret_access_key: config.archive_secret_access_key.clone()?,
+        };
+        let endpoint = config
+            .archive_endpoint
+            .clone()
+            .unwrap_or_else(|| format!("https://s3.{}.amazonaws.com", config.archive_region));
+        Some(Self::new(
+            &endpoint,
+            bucket,
+            &config.archive_region,
+            &config.archive_prefix,
+            credentials,
+            Duration::from_secs(config.archive_timeout_secs),
+        ))
+    }
+
+    /// Bucket artifacts are written to
+    pub fn bucket(&self) -> &str {
+        &self.bucket
+    }
+
+    /// Object key of a batch anchored at `anchored_at`
+    pub fn key(&self, batch_id: Uuid, anchored_at: DateTime<Utc>) -> String {
+        let partition = anchored_at.format("%Y/%m/%d");
+        if self.prefix.is_empty() {
+            format!("{}/{}.json", partition, batch_id)
+        } else {
+            format!("{}/{}/{}.json", self.prefix, partition, batch_id)
+        }
+    }
+
+    /// Write `artifact` under its date-partitioned key, returning the key
+    pub async fn put(&self, artifact: &AnchorArchive) -> Result<(String, ArchiveOutcome)> {
+        let key = self.key(artifact.batch_id, artifact.archived_at);
+        let body = serde_json::to_vec_pretty(artifact)?;
+        let path = format!("/{}/{}", uri_encode(&self.bucket), uri_encode(&key));
+        let url = format!("{}{}", self.endpoint, path);
+        let parsed = reqwest::Url::parse(&url)?;
+        let host = match (parsed.host_str(), parsed.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => anyhow::bail!("archive endpoint has no host"),
+        };
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let payload_hash = hex::encode(Sha256::digest(&body));
+        let authorization = self.authorization(&path, &host, &amz_date, &payload_hash);
+
+        let response = self
+            .client
+            .put(&url)
+            .header("content-type", "application/json")
+            .header("if-none-match", "*")
+            .header("x-amz-content-sha256", &payload_hash)
+            .header("x-amz-date", &amz_date)
+            .header("authorization", authorization)
+            .body(body)
+            .send()
+            .await?;
+        match response.status() {
+            status if status.is_success() => Ok((key, ArchiveOutcome::Archived)),
+            reqwest::StatusCode::PRECONDITION_FAILED => Ok((key, ArchiveOutcome::AlreadyArchived)),
+            status => {
+                let body = response.text().await.unwrap_or_default();
+                anyhow::bail!("Object storage returned {}: {}", status, body)
+            }
+        }
+    }
+
+    /// `Authorization` header for a PUT of `path` (AWS Signature V4)
+    fn authorization(&self, path: &str, host: &str, amz_date: &str, payload_hash: &str) -> String {
+        let date = &amz_date[..8];
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "PUT\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            path, host, payload_hash, amz_date, signed_headers, payload_hash
+        );
+        let string_to_sign = format!(
+            "{}\n{}\n{}\n{}",
+            ALGORITHM,
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let secret = format!("AWS4{}", self.credentials.secret_access_key);
+        let key = [date, self.region.as_str(), "s3", "aws4_request"]
+            .iter()
+            .fold(secret.into_bytes(), |key, part| hmac(&key, part.as_bytes()));
+        let signature = hex::encode(hmac(&key, string_to_sign.as_bytes()));
+
+        format!(
+            "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+            ALGORITHM, self.credentials.access_key_id, scope, signed_headers, signature
+        )
+    }
+}
+
+fn hmac(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Percent-encode a path for the canonical request, keeping `/` separators
+fn uri_encode(path: &str) -> String {
+    path.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                (byte as char).to_string()
+            }
+            other => format!("%{:02X}", other),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use wiremock::{
+        matchers::{header, header_exists, method, path_regex},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    fn archive_for(server: &MockServer) -> ObjectArchive {
+        ObjectArchive::new(
+            &server.uri(),
+            "audit",
+            "us-east-1",
+            "anchors/",
+            ArchiveCredentials {
+                access_key_id: "AKIDEXAMPLE".to_string(),
+                secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+            },
+            Duration::from_secs(2),
+        )
+    }
+
+    fn artifact() -> AnchorArchive {
+        let commitment: BatchCommitment = serde_json::from_value(serde_json::json!({
+            "batch_id": Uuid::new_v4(),
+            "tenant_id": Uuid::new_v4(),
+            "store_id": Uuid::new_v4(),
+            "prev_state_root": format!("0x{}", "00".repeat(32)),
+            "new_state_root": format!("0x{}", "22".repeat(32)),
+            "events_root": format!("0x{}", "11".repeat(32)),
+            "sequence_start": 1,
+            "sequence_end": 10,
+            "event_count": 10,
+            "committed_at": Utc::now(),
+        }))
+        .unwrap();
+        AnchorArchive {
+            batch_id: commitment.batch_id,
+            commitment,
+            chain_id: 84532001,
+            tx_hash: format!("0x{}", "ab".repeat(32)),
+            block_number: 42,
+            gas_used: 90_000,
+            batch_id_scheme: BatchIdScheme::Padded,
+            chain_batch_id: format!("0x{}", "cd".repeat(32)),
+            calldata: None,
+            receipt: None,
+            notification: AnchorNotification {
+                chain_tx_hash: format!("0x{}", "ab".repeat(32)),
+                chain_id: 84532001,
+                block_number: Some(42),
+                gas_used: Some(90_000),
+                blob_versioned_hashes: Vec::new(),
+                ipfs_cid: None,
+                celestia: None,
+            },
+            archived_at: Utc.with_ymd_and_hms(2026, 3, 7, 12, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_keys_are_date_partitioned() {
+        let archive = ObjectArchive::new(
+            "https://s3.us-east-1.amazonaws.com",
+            "audit",
+            "us-east-1",
+            "",
+            ArchiveCredentials {
+                access_key_id: String::new(),
+                secret_access_key: String::new(),
+            },
+            Duration::from_secs(1),
+        );
+        let batch_id = Uuid::nil();
+        let at = Utc.with_ymd_and_hms(2026, 3, 7, 23, 59, 0).unwrap();
+        assert_eq!(
+            archive.key(batch_id, at),
+            format!("2026/03/07/{}.json", batch_id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_put_signs_and_never_overwrites() {
+        let server = MockServer::start().await;
+        let artifact = artifact();
+        Mock::given(method("PUT"))
+            .and(path_regex(format!(
+                "^/audit/anchors/2026/03/07/{}.json$",
+                artifact.batch_id
+            )))
+            .and(header("if-none-match", "*"))
+            .and(header_exists("x-amz-date"))
+            .respond_with(ResponseTemplate::new(200))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .respond_with(ResponseTemplate::new(412))
+            .mount(&server)
+            .await;
+
+        let archive = archive_for(&server);
+        let (key, outcome) = archive.put(&artifact).await.unwrap();
+        assert_eq!(outcome, ArchiveOutcome::Archived);
+        assert!(key.starts_with("anchors/2026/03/07/"));
+
+        let requests = server.received_requests().await.unwrap();
+        // wiremock splits header values on commas; the credential is the first part
+        let authorization = requests[0].headers[&"authorization".parse().unwrap()][0]
+            .as_str()
+            .to_string();
+        assert!(authorization.starts_with("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/"));
+        assert!(authorization.contains("/us-east-1/s3/aws4_request"));
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["block_number"], 42);
+        assert_eq!(
+            body["commitment"]["events_root"],
+            format!("0x{}", "11".repeat(32))
+        );
+
+        let (_, outcome) = archive.put(&artifact).await.unwrap();
+        assert_eq!(outcome, ArchiveOutcome::AlreadyArchived);
+    }
+}
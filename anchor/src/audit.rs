@@ -0,0 +1,363 @@
+//! Tamper-evident audit log
+//!
+//! With `AUDIT_LOG_PATH` set, every anchor attempt, its result and the
+//! sequencer notification that follows are appended to a JSON-lines file. Each
+//! record carries the hash of the one before it, so editing, reordering or
+//! dropping a record breaks the chain from that point on:
+//!
+//! ```text
+//! hash = sha256(canonical JSON of the record without its "hash" field)
+//! ```
+//!
+//! The canonical form is the record serialized with object keys sorted, which
+//! keeps old records verifiable as fields are added. The first record's
+//! `prev_hash` is [`GENESIS_HASH`].
+//!
+//! `set-anchor audit verify [PATH]` checks a log and prints its head hash;
+//! `set-anchor audit export [PATH]` prints the verified records as a JSON array.
+//! Keep the head hash somewhere the service cannot write to (a ticket, a
+//! notarized store) to also detect truncation.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::config::AnchorConfig;
+use crate::types::{AnchorNotification, BatchCommitment};
+
+/// `prev_hash` of the first record
+pub const GENESIS_HASH: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
+
+/// What an audit record attests to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditEvent {
+    /// A commitment is about to be submitted
+    Attempt {
+        batch_id: Uuid,
+        attempt: u32,
+        events_root: String,
+        new_state_root: String,
+        sequence_start: u64,
+        sequence_end: u64,
+    },
+    /// A commitment was anchored on-chain
+    Anchored {
+        batch_id: Uuid,
+        chain_id: u64,
+        tx_hash: String,
+        block_number: u64,
+        gas_used: u64,
+    },
+    /// A commitment was abandoned for this cycle
+    Failed { batch_id: Uuid, error: String },
+    /// A commitment was left for a later cycle because gas was too expensive
+    Deferred { batch_id: Uuid, reason: String },
+    /// The sequencer was told about an anchor
+    Notification {
+        batch_id: Uuid,
+        notification: AnchorNotification,
+        /// `false` when the sequencer did not acknowledge it and it was queued for retry
+        acknowledged: bool,
+    },
+}
+
+impl AuditEvent {
+    /// Attempt record for `commitment`
+    pub fn attempt(commitment: &BatchCommitment, attempt: u32) -> Self {
+        AuditEvent::Attempt {
+            batch_id: commitment.batch_id,
+            attempt,
+            events_root: commitment.events_root.clone(),
+            new_state_root: commitment.new_state_root.clone(),
+            sequence_start: commitment.sequence_start,
+            sequence_end: commitment.sequence_end,
+        }
+    }
+
+    /// Anchored record for the transaction `notification` reports
+    pub fn anchored(batch_id: Uuid, notification: &AnchorNotification) -> Self {
+        AuditEvent::Anchored {
+            batch_id,
+            chain_id: notification.chain_id,
+            tx_hash: notification.chain_tx_hash.clone(),
+            block_number: notification.block_number.unwrap_or_default(),
+            gas_used: notification.gas_used.unwrap_or_default(),
+        }
+    }
+}
+
+/// One line of the audit log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub seq: u64,
+    pub recorded_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub event: AuditEvent,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+/// Result of verifying a log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditSummary {
+    pub records: u64,
+    /// Hash of the last record, or [`GENESIS_HASH`] for an empty log
+    pub head: String,
+}
+
+/// Append-only, hash-chained audit log
+pub struct AuditLog {
+    path: PathBuf,
+    /// Sequence number and hash of the last record, read from disk on first append
+    head: Mutex<Option<(u64, String)>>,
+}
+
+impl AuditLog {
+    /// Create a log appending to `path`
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            head: Mutex::new(None),
+        }
+    }
+
+    /// Log configured by `AUDIT_LOG_PATH`, if any
+    pub fn from_config(config: &AnchorConfig) -> Option<Self> {
+        config
+            .audit_log_path
+            .as_ref()
+            .map(|path| Self::new(PathBuf::from(path)))
+    }
+
+    /// File the records are appended to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a record chained to the previous one
+    pub async fn append(&self, event: AuditEvent) -> Result<AuditRecord> {
+        let mut head = self.head.lock().await;
+        if head.is_none() {
+            *head = Some(read_head(&self.path).await?);
+        }
+        let (last_seq, prev_hash) = head.clone().unwrap_or_default();
+
+        let seq = last_seq + 1;
+        let mut record = AuditRecord {
+            seq,
+            recorded_at: Utc::now(),
+            event,
+            prev_hash,
+            hash: String::new(),
+        };
+        let mut value = serde_json::to_value(&record)?;
+        record.hash = record_hash(&mut value)?;
+        value["hash"] = Value::String(record.hash.clone());
+
+        let mut line = serde_json::to_vec(&value)?;
+        line.push(b'\n');
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .with_context(|| format!("Failed to open audit log at {}", self.path.display()))?;
+        file.write_all(&line)
+            .await
+            .with_context(|| format!("Failed to append to audit log at {}", self.path.display()))?;
+        file.sync_data().await?;
+
+        *head = Some((seq, record.hash.clone()));
+        Ok(record)
+    }
+}
+
+/// Hash of a record, ignoring any `hash` field it already has
+fn record_hash(value: &mut Value) -> Result<String> {
+    let object = value
+        .as_object_mut()
+        .context("audit record is not a JSON object")?;
+    object.remove("hash");
+    // serde_json maps keep keys sorted, so this is the canonical form
+    let canonical = serde_json::to_vec(object)?;
+    Ok(format!("0x{}", hex::encode(Sha256::digest(canonical))))
+}
+
+/// Sequence number and hash of the last record in the log at `path`
+async fn read_head(path: &Path) -> Result<(u64, String)> {
+    match tokio::fs::read(path).await {
+        Ok(bytes) => {
+            let last = bytes
+                .split(|byte| *byte == b'\n')
+                .rev()
+                .find(|line| !line.iter().all(u8::is_ascii_whitespace));
+            match last {
+                Some(line) => {
+                    let record: AuditRecord = serde_json::from_slice(line).with_context(|| {
+                        format!("Failed to parse last record of {}", path.display())
+                    })?;
+                    Ok((record.seq, record.hash))
+                }
+                None => Ok((0, GENESIS_HASH.to_string())),
+            }
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok((0, GENESIS_HASH.to_string())),
+        Err(e) => Err(e).with_context(|| format!("Failed to read audit log at {}", path.display())),
+    }
+}
+
+/// Parse and verify a log, returning its records
+///
+/// Fails at the first record that does not parse, is out of sequence, does not
+/// point at its predecessor's hash, or whose hash does not match its contents.
+pub fn verify(bytes: &[u8]) -> Result<(Vec<AuditRecord>, AuditSummary)> {
+    let mut records = Vec::new();
+    let mut head = GENESIS_HASH.to_string();
+
+    for (index, line) in bytes.split(|byte| *byte == b'\n').enumerate() {
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        let line_number = index + 1;
+        let mut value: Value = serde_json::from_slice(line)
+            .with_context(|| format!("line {}: not valid JSON", line_number))?;
+        let record: AuditRecord = serde_json::from_value(value.clone())
+            .with_context(|| format!("line {}: not an audit record", line_number))?;
+
+        let expected_seq = records.len() as u64 + 1;
+        if record.seq != expected_seq {
+            anyhow::bail!(
+                "line {}: expected record {}, found {}",
+                line_number,
+                expected_seq,
+                record.seq
+            );
+        }
+        if record.prev_hash != head {
+            anyhow::bail!(
+                "line {}: record {} does not follow record {} (prev_hash {}, expected {})",
+                line_number,
+                record.seq,
+                record.seq - 1,
+                record.prev_hash,
+                head
+            );
+        }
+        let hash = record_hash(&mut value)?;
+        if record.hash != hash {
+            anyhow::bail!(
+                "line {}: record {} was modified (hash {}, contents hash to {})",
+                line_number,
+                record.seq,
+                record.hash,
+                hash
+            );
+        }
+
+        head = hash;
+        records.push(record);
+    }
+
+    let summary = AuditSummary {
+        records: records.len() as u64,
+        head,
+    };
+    Ok((records, summary))
+}
+
+/// Run `set-anchor audit <verify|export> [PATH]`
+///
+/// `PATH` defaults to `AUDIT_LOG_PATH`.
+pub async fn run_command(args: &[String]) -> Result<()> {
+    let usage = "usage: set-anchor audit <verify|export> [PATH]";
+    let command = args.first().context(usage)?;
+    let path = match args.get(1) {
+        Some(path) => path.clone(),
+        None => std::env::var("AUDIT_LOG_PATH")
+            .context("no PATH given and AUDIT_LOG_PATH is not set")?,
+    };
+    let bytes = tokio::fs::read(&path)
+        .await
+        .with_context(|| format!("Failed to read audit log at {}", path))?;
+    let (records, summary) =
+        verify(&bytes).with_context(|| format!("Audit log {} failed verification", path))?;
+
+    match command.as_str() {
+        "verify" => {
+            println!(
+                "{}: {} records verified, head {}",
+                path, summary.records, summary.head
+            );
+        }
+        "export" => {
+            println!("{}", serde_json::to_string_pretty(&records)?);
+        }
+        other => anyhow::bail!("unknown audit command {:?}; {}", other, usage),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failed(error: &str) -> AuditEvent {
+        AuditEvent::Failed {
+            batch_id: Uuid::nil(),
+            error: error.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_records_chain_across_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+
+        let log = AuditLog::new(path.clone());
+        let first = log.append(failed("first")).await.unwrap();
+        assert_eq!(first.seq, 1);
+        assert_eq!(first.prev_hash, GENESIS_HASH);
+
+        // A restarted service continues the chain from the file
+        let log = AuditLog::new(path.clone());
+        let second = log.append(failed("second")).await.unwrap();
+        assert_eq!(second.seq, 2);
+        assert_eq!(second.prev_hash, first.hash);
+
+        let (records, summary) = verify(&std::fs::read(&path).unwrap()).unwrap();
+        let hashes: Vec<&str> = records.iter().map(|record| record.hash.as_str()).collect();
+        assert_eq!(hashes, vec![first.hash.as_str(), second.hash.as_str()]);
+        assert_eq!(summary.records, 2);
+        assert_eq!(summary.head, second.hash);
+    }
+
+    #[tokio::test]
+    async fn test_verify_detects_tampering() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let log = AuditLog::new(path.clone());
+        for error in ["a", "b", "c"] {
+            log.append(failed(error)).await.unwrap();
+        }
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+
+        let edited = contents.replace("\"error\":\"b\"", "\"error\":\"x\"");
+        let error = verify(edited.as_bytes()).unwrap_err();
+        assert!(error.to_string().contains("record 2 was modified"));
+
+        let dropped = format!("{}\n{}\n", lines[0], lines[2]);
+        let error = verify(dropped.as_bytes()).unwrap_err();
+        assert!(error.to_string().contains("expected record 2, found 3"));
+
+        assert!(verify(b"").unwrap().1.head == GENESIS_HASH);
+    }
+}
@@ -0,0 +1,194 @@
+//! Gas spend budget
+//!
+//! Sums what anchoring costs (`gas_used × effective gas price`) over a daily or
+//! weekly UTC window. Once the configured budget is spent the service stops
+//! submitting and reports itself not ready until the next window opens, so an
+//! L2 fee spike cannot drain the signer.
+
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::AnchorConfig;
+
+/// Wei per ETH
+const WEI_PER_ETH: f64 = 1e18;
+
+/// Length of a budget window
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetPeriod {
+    /// Resets at 00:00 UTC
+    #[default]
+    Daily,
+    /// Resets at 00:00 UTC on Monday
+    Weekly,
+}
+
+impl BudgetPeriod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetPeriod::Daily => "daily",
+            BudgetPeriod::Weekly => "weekly",
+        }
+    }
+
+    /// Start of the window containing `now`
+    pub fn window_start(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let day = now.date_naive();
+        let day = match self {
+            BudgetPeriod::Daily => day,
+            BudgetPeriod::Weekly => {
+                day - Duration::days(i64::from(now.weekday().num_days_from_monday()))
+            }
+        };
+        day.and_time(NaiveTime::MIN).and_utc()
+    }
+
+    fn length(&self) -> Duration {
+        match self {
+            BudgetPeriod::Daily => Duration::days(1),
+            BudgetPeriod::Weekly => Duration::weeks(1),
+        }
+    }
+}
+
+impl std::str::FromStr for BudgetPeriod {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "daily" => Ok(BudgetPeriod::Daily),
+            "weekly" => Ok(BudgetPeriod::Weekly),
+            other => anyhow::bail!("expected daily or weekly, got: {}", other),
+        }
+    }
+}
+
+/// Gas spent in the current window against its limit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasBudget {
+    period: BudgetPeriod,
+    limit_wei: u128,
+    spent_wei: u128,
+    window_start: DateTime<Utc>,
+}
+
+impl GasBudget {
+    pub fn new(period: BudgetPeriod, limit_wei: u128, now: DateTime<Utc>) -> Self {
+        Self {
+            period,
+            limit_wei,
+            spent_wei: 0,
+            window_start: period.window_start(now),
+        }
+    }
+
+    /// Budget configured by `GAS_BUDGET_ETH`, if any
+    pub fn from_config(config: &AnchorConfig) -> Option<Self> {
+        (config.gas_budget_eth > 0.0).then(|| {
+            Self::new(
+                config.gas_budget_period,
+                (config.gas_budget_eth * WEI_PER_ETH).round() as u128,
+                Utc::now(),
+            )
+        })
+    }
+
+    /// Add the cost of a mined transaction
+    pub fn record(&mut self, cost_wei: u128, now: DateTime<Utc>) {
+        self.roll(now);
+        self.spent_wei = self.spent_wei.saturating_add(cost_wei);
+    }
+
+    /// Whether the window containing `now` has its budget used up
+    pub fn is_exhausted(&self, now: DateTime<Utc>) -> bool {
+        self.spent_wei(now) >= self.limit_wei
+    }
+
+    /// Snapshot for `/ready` and metrics
+    pub fn status(&self, now: DateTime<Utc>) -> BudgetStatus {
+        let window_start = self.period.window_start(now);
+        let spent_wei = self.spent_wei(now);
+        BudgetStatus {
+            period: self.period,
+            limit_wei: self.limit_wei.to_string(),
+            spent_wei: spent_wei.to_string(),
+            exhausted: spent_wei >= self.limit_wei,
+            window_start: window_start.to_rfc3339(),
+            resets_at: (window_start + self.period.length()).to_rfc3339(),
+        }
+    }
+
+    pub fn limit_wei(&self) -> u128 {
+        self.limit_wei
+    }
+
+    /// Spent in the window containing `now`; zero once that window has moved on
+    pub fn spent_wei(&self, now: DateTime<Utc>) -> u128 {
+        if self.period.window_start(now) == self.window_start {
+            self.spent_wei
+        } else {
+            0
+        }
+    }
+
+    /// Start a fresh window once `now` has moved past the current one
+    fn roll(&mut self, now: DateTime<Utc>) {
+        let window_start = self.period.window_start(now);
+        if window_start != self.window_start {
+            self.window_start = window_start;
+            self.spent_wei = 0;
+        }
+    }
+}
+
+/// Budget state reported by `/ready`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BudgetStatus {
+    pub period: BudgetPeriod,
+    pub limit_wei: String,
+    pub spent_wei: String,
+    pub exhausted: bool,
+    pub window_start: String,
+    pub resets_at: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_window_start() {
+        // Thursday
+        let now = Utc.with_ymd_and_hms(2026, 10, 15, 13, 45, 0).unwrap();
+        assert_eq!(
+            BudgetPeriod::Daily.window_start(now),
+            Utc.with_ymd_and_hms(2026, 10, 15, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            BudgetPeriod::Weekly.window_start(now),
+            Utc.with_ymd_and_hms(2026, 10, 12, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_budget_exhausts_and_resets_with_window() {
+        let morning = Utc.with_ymd_and_hms(2026, 10, 15, 8, 0, 0).unwrap();
+        let mut budget = GasBudget::new(BudgetPeriod::Daily, 1_000, morning);
+
+        budget.record(600, morning);
+        assert!(!budget.is_exhausted(morning));
+        budget.record(400, morning + Duration::hours(2));
+        assert!(budget.is_exhausted(morning + Duration::hours(3)));
+
+        let status = budget.status(morning + Duration::hours(3));
+        assert!(status.exhausted);
+        assert_eq!(status.spent_wei, "1000");
+        assert_eq!(status.resets_at, "2026-10-16T00:00:00+00:00");
+
+        let next_day = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 1).unwrap();
+        assert!(!budget.is_exhausted(next_day));
+        assert_eq!(budget.spent_wei(next_day), 0);
+    }
+}
@@ -0,0 +1,230 @@
+//! Celestia data availability
+//!
+//! With `CELESTIA_RPC_URL` set, each batch's events (the payload of
+//! [`da::encode_events`](crate::da::encode_events)) are submitted as a blob to
+//! `CELESTIA_NAMESPACE` through a celestia-node JSON-RPC API before the batch is
+//! anchored. The inclusion height and share commitment are journaled and sent
+//! to the sequencer with the anchor notification, so a verifier can fetch the
+//! blob with `blob.Get(height, namespace, commitment)` and check it against the
+//! on-chain `events_root`.
+//!
+//! The namespace is a version-0 user namespace: up to 10 bytes of hex,
+//! left-padded to the 28-byte namespace id.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::config::AnchorConfig;
+use crate::types::CelestiaBlobRef;
+
+/// Namespace version byte plus the 28-byte namespace id
+pub const NAMESPACE_LEN: usize = 29;
+
+/// Bytes of a version-0 namespace id available to users
+pub const MAX_USER_NAMESPACE_BYTES: usize = 10;
+
+/// Parse `CELESTIA_NAMESPACE` (hex, 1 to 10 bytes) into a version-0 namespace
+pub fn parse_namespace(hex_id: &str) -> Result<[u8; NAMESPACE_LEN]> {
+    let id = hex::decode(hex_id.trim_start_matches("0x")).context("namespace is not hex")?;
+    if id.is_empty() || id.len() > MAX_USER_NAMESPACE_BYTES {
+        anyhow::bail!(
+            "namespace must be 1 to {} bytes, got {}",
+            MAX_USER_NAMESPACE_BYTES,
+            id.len()
+        );
+    }
+    let mut namespace = [0u8; NAMESPACE_LEN];
+    namespace[NAMESPACE_LEN - id.len()..].copy_from_slice(&id);
+    Ok(namespace)
+}
+
+/// JSON-RPC response envelope
+#[derive(Debug, Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<Value>,
+}
+
+/// Blob as returned by `blob.GetAll`
+#[derive(Debug, Deserialize)]
+struct Blob {
+    data: String,
+    commitment: String,
+}
+
+/// Client for a celestia-node JSON-RPC API
+#[derive(Debug)]
+pub struct CelestiaClient {
+    url: String,
+    token: Option<String>,
+    namespace: [u8; NAMESPACE_LEN],
+    client: reqwest::Client,
+}
+
+impl CelestiaClient {
+    /// Create a client submitting to `namespace` through the node at `url`
+    pub fn new(
+        url: &str,
+        token: Option<String>,
+        namespace: [u8; NAMESPACE_LEN],
+        timeout: Duration,
+    ) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            url: url.to_string(),
+            token,
+            namespace,
+            client,
+        }
+    }
+
+    /// Build the client configured by `CELESTIA_RPC_URL`, if any
+    ///
+    /// `None` as well when `CELESTIA_NAMESPACE` is invalid, which `validate` refuses.
+    pub fn from_config(config: &AnchorConfig) -> Option<Self> {
+        let url = config.celestia_rpc_url.as_deref()?;
+        let namespace = parse_namespace(config.celestia_namespace.as_deref()?).ok()?;
+        Some(Self::new(
+            url,
+            config.celestia_auth_token.clone(),
+            namespace,
+            Duration::from_secs(config.celestia_timeout_secs),
+        ))
+    }
+
+    /// Node the blobs are submitted through
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Submit `payload` as a blob and wait for its inclusion height and commitment
+    pub async fn submit(&self, payload: &[u8]) -> Result<CelestiaBlobRef> {
+        let namespace = BASE64.encode(self.namespace);
+        let data = BASE64.encode(payload);
+        let height: u64 = self
+            .call(
+                "blob.Submit",
+                json!([[{ "namespace": namespace, "data": data, "share_version": 0 }], {}]),
+            )
+            .await?
+            .context("blob.Submit returned no height")?;
+
+        // The node computes the share commitment; read it back from the block
+        let blobs: Vec<Blob> = self
+            .call("blob.GetAll", json!([height, [namespace]]))
+            .await?
+            .unwrap_or_default();
+        let blob = blobs
+            .into_iter()
+            .find(|blob| blob.data == data)
+            .with_context(|| format!("submitted blob not found at height {}", height))?;
+        let commitment = BASE64
+            .decode(&blob.commitment)
+            .context("blob commitment is not base64")?;
+
+        Ok(CelestiaBlobRef {
+            height,
+            namespace: format!("0x{}", hex::encode(self.namespace)),
+            commitment: format!("0x{}", hex::encode(commitment)),
+        })
+    }
+
+    async fn call<T: serde::de::DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<Option<T>> {
+        let mut request = self.client.post(&self.url).json(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        }));
+        if let Some(ref token) = self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Celestia node returned {} for {}",
+                response.status(),
+                method
+            );
+        }
+        let response: RpcResponse<T> = response.json().await?;
+        if let Some(error) = response.error {
+            anyhow::bail!("{} failed: {}", method, error);
+        }
+        Ok(response.result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_namespace_pads_user_id() {
+        let namespace = parse_namespace("0x5e7a").unwrap();
+        assert_eq!(namespace[0], 0);
+        assert_eq!(&namespace[27..], &[0x5e, 0x7a]);
+        assert!(namespace[..27].iter().all(|byte| *byte == 0));
+
+        assert!(parse_namespace("").is_err());
+        assert!(parse_namespace("zz").is_err());
+        assert!(parse_namespace(&"ab".repeat(11)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_submit_returns_height_and_commitment() {
+        let server = crate::tests::rpc_mock::start(|method, params| match method {
+            "blob.Submit" => {
+                assert_eq!(params[0][0]["data"], BASE64.encode(b"[]"));
+                Ok(json!(4242))
+            }
+            "blob.GetAll" => {
+                assert_eq!(params[0], 4242);
+                Ok(json!([
+                    { "data": BASE64.encode(b"other"), "commitment": BASE64.encode([1u8; 32]) },
+                    { "data": BASE64.encode(b"[]"), "commitment": BASE64.encode([2u8; 32]) },
+                ]))
+            }
+            other => Err(format!("unexpected method {}", other)),
+        })
+        .await;
+
+        let client = CelestiaClient::new(
+            &server.uri(),
+            None,
+            parse_namespace("5e7a").unwrap(),
+            Duration::from_secs(2),
+        );
+        let blob = client.submit(b"[]").await.unwrap();
+        assert_eq!(blob.height, 4242);
+        assert_eq!(blob.commitment, format!("0x{}", "02".repeat(32)));
+        assert!(blob.namespace.ends_with("5e7a"));
+    }
+
+    #[tokio::test]
+    async fn test_submit_surfaces_node_errors() {
+        let server =
+            crate::tests::rpc_mock::start(|_, _| Err("not enough funds".to_string())).await;
+
+        let client = CelestiaClient::new(
+            &server.uri(),
+            None,
+            parse_namespace("5e7a").unwrap(),
+            Duration::from_secs(2),
+        );
+        let error = client.submit(b"[]").await.unwrap_err();
+        assert!(error.to_string().contains("not enough funds"));
+    }
+}
@@ -0,0 +1,209 @@
+//! Data availability for batch payloads
+//!
+//! The registry only stores a batch's `events_root`; the events themselves live
+//! in the sequencer's database. With `DA_MODE=blob` the batch's raw events are
+//! posted as EIP-4844 blobs on the same type-3 transaction as its `commitBatch`
+//! call. The blobs' versioned hashes are recorded in the journal and sent to the
+//! sequencer with the anchor notification, so a verifier can fetch the blobs
+//! from a beacon node, decode the events and recompute `events_root` without
+//! trusting the sequencer.
+//!
+//! The blob payload is the JSON array of [`BatchEvent`]s as returned by the
+//! sequencer, packed with alloy's `SimpleCoder`.
+//!
+//! Chains without blob support can use `DA_MODE=calldata` instead: the same
+//! JSON, zstd-compressed, is either appended to the `commitBatch` calldata
+//! (the registry ignores trailing bytes; see
+//! [`commit_batch_da_payload`](crate::client::commit_batch_da_payload)) or,
+//! with `DA_CONTRACT_ADDRESS` set, posted to that contract's
+//! `postBatchData(bytes32,bytes)` in the same cycle, before the commitment.
+
+use std::io::Read;
+
+use alloy::{
+    consensus::{BlobTransactionSidecar, SidecarBuilder, SidecarCoder, SimpleCoder},
+    eips::eip4844::{FIELD_ELEMENTS_PER_BLOB, MAX_BLOBS_PER_BLOCK},
+    primitives::Bytes,
+};
+
+use crate::error::{AnchorResult, TransactionError};
+use crate::types::BatchEvent;
+
+/// zstd level for calldata payloads; calldata costs far more than the CPU time
+const ZSTD_LEVEL: i32 = 19;
+
+/// Gas per zero byte of calldata
+const ZERO_BYTE_GAS: u64 = 4;
+
+/// Gas per non-zero byte of calldata
+const NONZERO_BYTE_GAS: u64 = 16;
+
+/// Batch data carried by the `commitBatch` transaction itself
+#[derive(Debug, Clone, PartialEq)]
+pub enum DaAttachment {
+    /// EIP-4844 blobs on a type-3 transaction
+    Blobs(BlobTransactionSidecar),
+    /// Compressed payload appended to the calldata
+    Calldata(Bytes),
+}
+
+impl DaAttachment {
+    /// Versioned hashes of attached blobs, as hex strings
+    pub fn blob_versioned_hashes(&self) -> Vec<String> {
+        match self {
+            DaAttachment::Blobs(sidecar) => sidecar
+                .versioned_hashes()
+                .map(|hash| hash.to_string())
+                .collect(),
+            DaAttachment::Calldata(_) => Vec::new(),
+        }
+    }
+}
+
+/// Serialize a batch's events into the payload posted for data availability
+pub fn encode_events(events: &[BatchEvent]) -> AnchorResult<Vec<u8>> {
+    serde_json::to_vec(events)
+        .map_err(|e| TransactionError::EncodingError(format!("batch events: {}", e)).into())
+}
+
+/// Decode a payload written by [`encode_events`]
+pub fn decode_events(payload: &[u8]) -> AnchorResult<Vec<BatchEvent>> {
+    serde_json::from_slice(payload)
+        .map_err(|e| TransactionError::EncodingError(format!("batch events: {}", e)).into())
+}
+
+/// Pack `payload` into a blob sidecar with KZG commitments and proofs
+///
+/// Fails with `DaPayloadTooLarge` if it needs more blobs than fit in one block.
+pub fn blob_sidecar(payload: &[u8]) -> AnchorResult<BlobTransactionSidecar> {
+    if payload.len() > max_blob_payload_bytes() {
+        return Err(TransactionError::DaPayloadTooLarge {
+            bytes: payload.len(),
+            max: max_blob_payload_bytes(),
+        }
+        .into());
+    }
+    SidecarBuilder::<SimpleCoder>::from_slice(payload)
+        .build()
+        .map_err(|e| TransactionError::EncodingError(format!("blob sidecar: {}", e)).into())
+}
+
+/// Recover the payload packed into a sidecar's blobs
+pub fn blob_payload(sidecar: &BlobTransactionSidecar) -> Option<Vec<u8>> {
+    SimpleCoder::default()
+        .decode_all(&sidecar.blobs)
+        .map(|chunks| chunks.concat())
+}
+
+/// Largest payload that fits in one transaction's blobs
+pub fn max_blob_payload_bytes() -> usize {
+    // SimpleCoder packs 31 bytes per field element, after one holding the length
+    (MAX_BLOBS_PER_BLOCK * FIELD_ELEMENTS_PER_BLOB as usize - 1) * 31
+}
+
+/// zstd-compress `payload` for calldata, refusing results over `max_bytes`
+pub fn compress(payload: &[u8], max_bytes: usize) -> AnchorResult<Bytes> {
+    let compressed = zstd::encode_all(payload, ZSTD_LEVEL)
+        .map_err(|e| TransactionError::EncodingError(format!("zstd: {}", e)))?;
+    if compressed.len() > max_bytes {
+        return Err(TransactionError::DaPayloadTooLarge {
+            bytes: compressed.len(),
+            max: max_bytes,
+        }
+        .into());
+    }
+    Ok(compressed.into())
+}
+
+/// Decompress a calldata payload, reading at most `max_bytes` of output
+pub fn decompress(data: &[u8], max_bytes: usize) -> AnchorResult<Vec<u8>> {
+    let decoder = zstd::Decoder::new(data)
+        .map_err(|e| TransactionError::EncodingError(format!("zstd: {}", e)))?;
+    let mut payload = Vec::new();
+    decoder
+        .take(max_bytes as u64 + 1)
+        .read_to_end(&mut payload)
+        .map_err(|e| TransactionError::EncodingError(format!("zstd: {}", e)))?;
+    if payload.len() > max_bytes {
+        return Err(TransactionError::DaPayloadTooLarge {
+            bytes: payload.len(),
+            max: max_bytes,
+        }
+        .into());
+    }
+    Ok(payload)
+}
+
+/// Intrinsic gas of `data` as calldata
+pub fn calldata_gas(data: &[u8]) -> u64 {
+    data.iter()
+        .map(|byte| match byte {
+            0 => ZERO_BYTE_GAS,
+            _ => NONZERO_BYTE_GAS,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(sequence: u64) -> BatchEvent {
+        BatchEvent {
+            sequence,
+            event_type: "OrderCreated".to_string(),
+            payload: format!("0x{}", "ab".repeat(64)),
+            metadata: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_events_round_trip_through_blobs() {
+        let events: Vec<BatchEvent> = (1..=20).map(event).collect();
+        let sidecar = blob_sidecar(&encode_events(&events).unwrap()).unwrap();
+        assert_eq!(sidecar.blobs.len(), 1);
+        assert_eq!(sidecar.versioned_hashes().count(), 1);
+
+        let decoded = decode_events(&blob_payload(&sidecar).unwrap()).unwrap();
+        assert_eq!(decoded.len(), 20);
+        assert_eq!(decoded[19].sequence, 20);
+        assert_eq!(decoded[0].payload, events[0].payload);
+    }
+
+    #[test]
+    fn test_oversized_payload_is_refused() {
+        let payload = vec![0x7b; max_blob_payload_bytes() + 1];
+        match blob_sidecar(&payload) {
+            Err(crate::error::AnchorError::Transaction(TransactionError::DaPayloadTooLarge {
+                bytes,
+                ..
+            })) => assert_eq!(bytes, payload.len()),
+            other => panic!("expected DaPayloadTooLarge, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_events_round_trip_through_compression() {
+        let events: Vec<BatchEvent> = (1..=50).map(event).collect();
+        let payload = encode_events(&events).unwrap();
+        let compressed = compress(&payload, 96_000).unwrap();
+        assert!(compressed.len() < payload.len() / 4);
+        assert!(calldata_gas(&compressed) < calldata_gas(&payload));
+
+        let decoded = decode_events(&decompress(&compressed, 1 << 20).unwrap()).unwrap();
+        assert_eq!(decoded.len(), 50);
+
+        assert!(matches!(
+            compress(&payload, 16),
+            Err(crate::error::AnchorError::Transaction(
+                TransactionError::DaPayloadTooLarge { max: 16, .. }
+            ))
+        ));
+        assert!(decompress(&compressed, 64).is_err());
+    }
+
+    #[test]
+    fn test_calldata_gas() {
+        assert_eq!(calldata_gas(&[0, 0, 1, 0xff]), 4 + 4 + 16 + 16);
+    }
+}
@@ -0,0 +1,142 @@
+//! Recently anchored batch ids
+//!
+//! The sequencer re-delivers a commitment until it hears it was anchored, so a
+//! lost acknowledgement or a restart brings the same batch back. Remembering
+//! what was anchored lets the service re-send the acknowledgement straight
+//! away instead of asking the registry about every re-delivery.
+
+use std::collections::{HashMap, VecDeque};
+
+use uuid::Uuid;
+
+use crate::types::AnchorNotification;
+
+/// Bounded least-recently-used map of anchored batch ids to their notification
+#[derive(Debug)]
+pub struct AnchoredCache {
+    capacity: usize,
+    entries: HashMap<Uuid, (AnchorNotification, u64)>,
+    /// Use order, oldest first; entries whose stamp is stale were touched again
+    order: VecDeque<(Uuid, u64)>,
+    clock: u64,
+}
+
+impl AnchoredCache {
+    /// A cache holding at most `capacity` ids (0 remembers nothing)
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            clock: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remember an anchored batch, evicting the least recently used if full
+    pub fn insert(&mut self, batch_id: Uuid, notification: AnchorNotification) {
+        if self.capacity == 0 {
+            return;
+        }
+        let stamp = self.tick(batch_id);
+        self.entries.insert(batch_id, (notification, stamp));
+        while self.entries.len() > self.capacity {
+            let Some((oldest, stamp)) = self.order.pop_front() else {
+                break;
+            };
+            if self.entries.get(&oldest).is_some_and(|(_, s)| *s == stamp) {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.compact();
+    }
+
+    /// The notification for an anchored batch, marking it recently used
+    pub fn get(&mut self, batch_id: &Uuid) -> Option<AnchorNotification> {
+        if !self.entries.contains_key(batch_id) {
+            return None;
+        }
+        let stamp = self.tick(*batch_id);
+        let (notification, used) = self.entries.get_mut(batch_id)?;
+        *used = stamp;
+        let notification = notification.clone();
+        self.compact();
+        Some(notification)
+    }
+
+    fn tick(&mut self, batch_id: Uuid) -> u64 {
+        self.clock += 1;
+        self.order.push_back((batch_id, self.clock));
+        self.clock
+    }
+
+    /// Forget a batch so its next delivery is checked against the registry again
+    pub fn remove(&mut self, batch_id: &Uuid) -> bool {
+        self.entries.remove(batch_id).is_some()
+    }
+
+    /// Drop stale order entries once they outnumber the live ones
+    fn compact(&mut self) {
+        if self.order.len() <= self.capacity.saturating_mul(2).max(16) {
+            return;
+        }
+        let entries = &self.entries;
+        self.order
+            .retain(|(id, stamp)| entries.get(id).is_some_and(|(_, s)| s == stamp));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(block_number: u64) -> AnchorNotification {
+        AnchorNotification {
+            chain_tx_hash: format!("0x{:064x}", block_number),
+            chain_id: 84532001,
+            block_number: Some(block_number),
+            gas_used: Some(21_000),
+            blob_versioned_hashes: Vec::new(),
+            ipfs_cid: None,
+            celestia: None,
+        }
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache = AnchoredCache::new(2);
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        cache.insert(a, notification(1));
+        cache.insert(b, notification(2));
+        assert_eq!(cache.get(&a).unwrap().block_number, Some(1));
+        cache.insert(c, notification(3));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&b).is_none());
+        assert!(cache.get(&a).is_some());
+        assert!(cache.get(&c).is_some());
+
+        // Repeated touches don't let the order queue grow without bound
+        for _ in 0..100 {
+            cache.get(&a);
+        }
+        assert!(cache.order.len() <= 16);
+    }
+
+    #[test]
+    fn test_zero_capacity_remembers_nothing() {
+        let mut cache = AnchoredCache::new(0);
+        let batch_id = Uuid::new_v4();
+        cache.insert(batch_id, notification(1));
+        assert!(cache.is_empty());
+        assert!(cache.get(&batch_id).is_none());
+    }
+}
@@ -6,7 +6,7 @@
 use thiserror::Error;
 
 /// Main error type for the anchor service
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum AnchorError {
     /// Configuration errors
     #[error("Configuration error: {0}")]
@@ -28,13 +28,17 @@ pub enum AnchorError {
     #[error("Authorization error: {0}")]
     Authorization(#[from] AuthorizationError),
 
+    /// Batches that don't continue their store's on-chain or pending sequence
+    #[error("Validation error: {0}")]
+    Validation(#[from] ValidationError),
+
     /// Generic internal error
     #[error("Internal error: {0}")]
     Internal(String),
 }
 
 /// Configuration-related errors
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum ConfigError {
     #[error("Missing required environment variable: {0}")]
     MissingEnvVar(String),
@@ -53,7 +57,7 @@ pub enum ConfigError {
 }
 
 /// L2 chain connection errors
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum L2Error {
     #[error("Failed to connect to L2 RPC at {url}: {message}")]
     ConnectionFailed { url: String, message: String },
@@ -75,7 +79,7 @@ pub enum L2Error {
 }
 
 /// Sequencer API errors
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum SequencerApiError {
     #[error("Failed to connect to sequencer API at {url}: {message}")]
     ConnectionFailed { url: String, message: String },
@@ -83,6 +87,15 @@ pub enum SequencerApiError {
     #[error("Sequencer API returned error status {status}: {body}")]
     HttpError { status: u16, body: String },
 
+    #[error("Sequencer API rejected the anchor's credentials ({status}): {body}")]
+    Unauthorized { status: u16, body: String },
+
+    #[error("Failed to obtain a sequencer access token: {message}")]
+    TokenRequestFailed {
+        status: Option<u16>,
+        message: String,
+    },
+
     #[error("Failed to parse sequencer API response: {0}")]
     ParseError(String),
 
@@ -97,7 +110,7 @@ pub enum SequencerApiError {
 }
 
 /// Transaction-related errors
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum TransactionError {
     #[error("Transaction failed to submit: {0}")]
     SubmissionFailed(String),
@@ -108,6 +121,9 @@ pub enum TransactionError {
     #[error("Transaction timed out waiting for confirmation")]
     ConfirmationTimeout,
 
+    #[error("Transaction {tx_hash} stuck in the mempool for {blocks} blocks")]
+    Stuck { tx_hash: String, blocks: u64 },
+
     #[error("Gas price {current_gwei} gwei exceeds maximum {max_gwei} gwei")]
     GasPriceTooHigh { current_gwei: u64, max_gwei: u64 },
 
@@ -122,10 +138,65 @@ pub enum TransactionError {
 
     #[error("Invalid bytes32 value: {0}")]
     InvalidBytes32(String),
+
+    #[error("Registry rejected the signer: NotAuthorizedSequencer")]
+    NotAuthorizedSequencer,
+
+    #[error("Registry rejected the batch: InvalidSequenceRange")]
+    InvalidSequenceRange,
+
+    #[error("Registry rejected the batch: EmptyEventsRoot")]
+    EmptyEventsRoot,
+
+    #[error("Batch is already committed: BatchAlreadyCommitted")]
+    BatchAlreadyCommitted,
+
+    #[error("Estimated gas {estimate} exceeds the maximum gas limit {max}")]
+    GasLimitExceeded { estimate: u64, max: u64 },
+
+    #[error("Data availability payload of {bytes} bytes exceeds the {max} byte limit")]
+    DaPayloadTooLarge { bytes: usize, max: usize },
+}
+
+/// Batch continuity errors, caught before a submission would revert
+#[derive(Error, Debug, Clone)]
+pub enum ValidationError {
+    #[error("Batch is out of order or forked: registry head state root is {expected}, batch builds on {provided}")]
+    StateRootMismatch { expected: String, provided: String },
+
+    #[error("Sequence gap: batch starts at {provided}, expected {expected}")]
+    SequenceGap { expected: u64, provided: u64 },
+
+    #[error("Sequence overlap: batch starts at {provided}, but sequences before {expected} are already covered")]
+    SequenceOverlap { expected: u64, provided: u64 },
+
+    #[error("Events root mismatch: batch claims {expected}, its events hash to {computed}")]
+    EventsRootMismatch { expected: String, computed: String },
+
+    #[error("Invalid {field}: {reason}")]
+    InvalidRoot { field: &'static str, reason: String },
+
+    #[error("Events root is zero")]
+    EmptyEventsRoot,
+
+    #[error("Invalid sequence range: {start}..={end}")]
+    InvalidSequenceRange { start: u64, end: u64 },
+
+    #[error("Event count {provided} doesn't match the {expected} sequences in the batch")]
+    EventCountMismatch { expected: u64, provided: u32 },
+
+    #[error("Batch committed in the future: {committed_at}")]
+    CommittedInFuture { committed_at: String },
+
+    #[error("Batch {batch_id} delivered more than once")]
+    DuplicateBatchId { batch_id: String },
+
+    #[error("Commitment {batch_id} can't be read: {reason}")]
+    UnreadableCommitment { batch_id: String, reason: String },
 }
 
 /// Authorization-related errors
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum AuthorizationError {
     #[error("Sequencer address {address} is not authorized in SetRegistry")]
     NotAuthorized { address: String },
@@ -133,6 +204,9 @@ pub enum AuthorizationError {
     #[error("Failed to check authorization: {0}")]
     CheckFailed(String),
 
+    #[error("SetRegistry ownership changed from {expected} to {actual}")]
+    OwnershipChanged { expected: String, actual: String },
+
     #[error("Invalid private key")]
     InvalidPrivateKey,
 }
@@ -159,6 +233,8 @@ impl AnchorError {
             AnchorError::L2Connection(e) => e.severity(),
             AnchorError::SequencerApi(e) => e.severity(),
             AnchorError::Transaction(e) => e.severity(),
+            // The sequencer has to re-order or repair the stream; resubmitting won't help
+            AnchorError::Validation(_) => ErrorSeverity::Warning,
             AnchorError::Internal(_) => ErrorSeverity::Critical,
         }
     }
@@ -168,6 +244,54 @@ impl AnchorError {
         matches!(self.severity(), ErrorSeverity::Transient)
     }
 
+    /// Recover the structured error behind an `anyhow` error
+    ///
+    /// Typed errors anywhere in the chain are returned as-is. Node rejections,
+    /// which alloy surfaces as plain RPC errors, are classified by message;
+    /// anything unrecognised is treated as a transient submission failure.
+    pub fn from_anyhow(error: &anyhow::Error) -> Self {
+        for cause in error.chain() {
+            if let Some(e) = cause.downcast_ref::<AnchorError>() {
+                return e.clone();
+            }
+            if let Some(e) = cause.downcast_ref::<ConfigError>() {
+                return e.clone().into();
+            }
+            if let Some(e) = cause.downcast_ref::<L2Error>() {
+                return e.clone().into();
+            }
+            if let Some(e) = cause.downcast_ref::<SequencerApiError>() {
+                return e.clone().into();
+            }
+            if let Some(e) = cause.downcast_ref::<TransactionError>() {
+                return e.clone().into();
+            }
+            if let Some(e) = cause.downcast_ref::<AuthorizationError>() {
+                return e.clone().into();
+            }
+            if let Some(e) = cause.downcast_ref::<ValidationError>() {
+                return e.clone().into();
+            }
+        }
+
+        let message = format!("{:#}", error);
+        TransactionError::from_node_message(&message)
+            .unwrap_or(TransactionError::SubmissionFailed(message))
+            .into()
+    }
+
+    /// Classify an error reported by the L2 node
+    ///
+    /// Rejections of a transaction become [`TransactionError`]s; everything else
+    /// is an RPC failure.
+    fn from_node_error(error: impl std::fmt::Display) -> Self {
+        let message = error.to_string();
+        match TransactionError::from_node_message(&message) {
+            Some(e) => e.into(),
+            None => L2Error::RpcError(message).into(),
+        }
+    }
+
     /// Get a short error code for metrics/logging
     pub fn error_code(&self) -> &'static str {
         match self {
@@ -176,6 +300,7 @@ impl AnchorError {
             AnchorError::SequencerApi(_) => "SEQUENCER_API_ERROR",
             AnchorError::Transaction(_) => "TRANSACTION_ERROR",
             AnchorError::Authorization(_) => "AUTHORIZATION_ERROR",
+            AnchorError::Validation(_) => "VALIDATION_ERROR",
             AnchorError::Internal(_) => "INTERNAL_ERROR",
         }
     }
@@ -194,7 +319,60 @@ impl L2Error {
     }
 }
 
+impl ValidationError {
+    /// Short label for the validation metrics
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ValidationError::StateRootMismatch { .. } => "state_root_mismatch",
+            ValidationError::SequenceGap { .. } => "gap",
+            ValidationError::SequenceOverlap { .. } => "overlap",
+            ValidationError::EventsRootMismatch { .. } => "events_root_mismatch",
+            ValidationError::InvalidRoot { .. } => "invalid_root",
+            ValidationError::EmptyEventsRoot => "empty_events_root",
+            ValidationError::InvalidSequenceRange { .. } => "invalid_sequence_range",
+            ValidationError::EventCountMismatch { .. } => "event_count_mismatch",
+            ValidationError::CommittedInFuture { .. } => "committed_in_future",
+            ValidationError::DuplicateBatchId { .. } => "duplicate_batch_id",
+            ValidationError::UnreadableCommitment { .. } => "unreadable_commitment",
+        }
+    }
+
+    /// Whether the batch breaks its store's chain of state roots or sequences
+    pub fn is_sequence_anomaly(&self) -> bool {
+        matches!(
+            self,
+            ValidationError::StateRootMismatch { .. }
+                | ValidationError::SequenceGap { .. }
+                | ValidationError::SequenceOverlap { .. }
+        )
+    }
+
+    /// Compare a batch's first sequence with the one that should follow `previous_end`
+    pub fn check_sequence(previous_end: u64, sequence_start: u64) -> Result<(), Self> {
+        let expected = previous_end.saturating_add(1);
+        match sequence_start.cmp(&expected) {
+            std::cmp::Ordering::Equal => Ok(()),
+            std::cmp::Ordering::Greater => Err(ValidationError::SequenceGap {
+                expected,
+                provided: sequence_start,
+            }),
+            std::cmp::Ordering::Less => Err(ValidationError::SequenceOverlap {
+                expected,
+                provided: sequence_start,
+            }),
+        }
+    }
+}
+
 impl SequencerApiError {
+    /// Error for a non-success status; 401 and 403 become `Unauthorized`
+    pub fn from_status(status: u16, body: String) -> Self {
+        match status {
+            401 | 403 => SequencerApiError::Unauthorized { status, body },
+            _ => SequencerApiError::HttpError { status, body },
+        }
+    }
+
     fn severity(&self) -> ErrorSeverity {
         match self {
             SequencerApiError::ConnectionFailed { .. } => ErrorSeverity::Transient,
@@ -205,6 +383,13 @@ impl SequencerApiError {
                     ErrorSeverity::Warning
                 }
             }
+            // Retrying cannot fix a missing or revoked credential
+            SequencerApiError::Unauthorized { .. } => ErrorSeverity::Critical,
+            // The token endpoint refusing the client is the same misconfiguration
+            SequencerApiError::TokenRequestFailed { status, .. } => match status {
+                Some(status) if *status < 500 => ErrorSeverity::Critical,
+                _ => ErrorSeverity::Transient,
+            },
             SequencerApiError::ParseError(_) => ErrorSeverity::Warning,
             SequencerApiError::Timeout { .. } => ErrorSeverity::Transient,
             SequencerApiError::NoPendingCommitments => ErrorSeverity::Transient,
@@ -214,20 +399,101 @@ impl SequencerApiError {
 }
 
 impl TransactionError {
+    /// Recognise a node rejection from its message
+    fn from_node_message(message: &str) -> Option<Self> {
+        let lowercase = message.to_lowercase();
+        if lowercase.contains("insufficient funds") {
+            // geth: "insufficient funds for gas * price + value: address 0x.. have 1 want 2"
+            let amount_after = |marker: &str| {
+                lowercase
+                    .split_once(marker)
+                    .and_then(|(_, rest)| rest.split_whitespace().next())
+                    .unwrap_or("unknown")
+                    .to_string()
+            };
+            return Some(TransactionError::InsufficientFunds {
+                required: amount_after(" want "),
+                available: amount_after(" have "),
+            });
+        }
+        if lowercase.contains("nonce too low") || lowercase.contains("nonce too high") {
+            return Some(TransactionError::NonceError(message.to_string()));
+        }
+        if lowercase.contains("execution reverted") {
+            let decoded =
+                revert_data(message).and_then(|data| crate::client::decode_registry_error(&data));
+            return Some(decoded.unwrap_or_else(|| TransactionError::Reverted {
+                reason: message.to_string(),
+            }));
+        }
+        None
+    }
+
+    /// Whether the EVM rejected the call, as opposed to the node or the network
+    pub fn is_revert(&self) -> bool {
+        matches!(
+            self,
+            TransactionError::Reverted { .. }
+                | TransactionError::NotAuthorizedSequencer
+                | TransactionError::InvalidSequenceRange
+                | TransactionError::EmptyEventsRoot
+                | TransactionError::BatchAlreadyCommitted
+        )
+    }
+
     fn severity(&self) -> ErrorSeverity {
         match self {
             TransactionError::SubmissionFailed(_) => ErrorSeverity::Transient,
             TransactionError::Reverted { .. } => ErrorSeverity::Warning,
             TransactionError::ConfirmationTimeout => ErrorSeverity::Transient,
+            TransactionError::Stuck { .. } => ErrorSeverity::Transient,
             TransactionError::GasPriceTooHigh { .. } => ErrorSeverity::Transient,
             TransactionError::InsufficientFunds { .. } => ErrorSeverity::Critical,
             TransactionError::NonceError(_) => ErrorSeverity::Transient,
             TransactionError::EncodingError(_) => ErrorSeverity::Critical,
             TransactionError::InvalidBytes32(_) => ErrorSeverity::Critical,
+            TransactionError::NotAuthorizedSequencer => ErrorSeverity::Critical,
+            TransactionError::InvalidSequenceRange => ErrorSeverity::Warning,
+            TransactionError::EmptyEventsRoot => ErrorSeverity::Warning,
+            // The batch is on-chain; the caller recovers its anchor instead of retrying
+            TransactionError::BatchAlreadyCommitted => ErrorSeverity::Warning,
+            // Same calldata, same estimate: retrying won't fit it under the cap
+            TransactionError::GasLimitExceeded { .. } => ErrorSeverity::Warning,
+            TransactionError::DaPayloadTooLarge { .. } => ErrorSeverity::Warning,
         }
     }
 }
 
+impl From<alloy::transports::TransportError> for AnchorError {
+    fn from(error: alloy::transports::TransportError) -> Self {
+        AnchorError::from_node_error(error)
+    }
+}
+
+impl From<alloy::contract::Error> for AnchorError {
+    fn from(error: alloy::contract::Error) -> Self {
+        AnchorError::from_node_error(error)
+    }
+}
+
+impl From<alloy::providers::PendingTransactionError> for AnchorError {
+    fn from(error: alloy::providers::PendingTransactionError) -> Self {
+        AnchorError::from_node_error(error)
+    }
+}
+
+/// Revert data a node appended to its error message (`..., data: "0x..."`)
+fn revert_data(message: &str) -> Option<Vec<u8>> {
+    let (_, data) = message.rsplit_once("data: ")?;
+    let hex: String = data
+        .trim_start_matches(['"', '\\'])
+        .strip_prefix("0x")?
+        .chars()
+        .take_while(char::is_ascii_hexdigit)
+        .collect();
+    hex::decode(hex).ok()
+}
+
 /// Result type alias using AnchorError
 pub type AnchorResult<T> = std::result::Result<T, AnchorError>;
 
@@ -258,6 +524,106 @@ mod tests {
         assert!(l2_err.is_retryable());
     }
 
+    #[test]
+    fn test_from_anyhow_classifies_errors() {
+        let typed = anyhow::Error::from(AuthorizationError::InvalidPrivateKey);
+        let err = AnchorError::from_anyhow(&typed.context("submitting batch"));
+        assert!(matches!(
+            err,
+            AnchorError::Authorization(AuthorizationError::InvalidPrivateKey)
+        ));
+        assert_eq!(err.severity(), ErrorSeverity::Fatal);
+
+        let node = anyhow::anyhow!(
+            "server returned an error response: error code -32000: insufficient funds \
+             for gas * price + value: address 0xabc have 100 want 250"
+        );
+        match AnchorError::from_anyhow(&node) {
+            AnchorError::Transaction(TransactionError::InsufficientFunds {
+                required,
+                available,
+            }) => {
+                assert_eq!(required, "250");
+                assert_eq!(available, "100");
+            }
+            other => panic!("unexpected classification: {:?}", other),
+        }
+
+        let reverted = anyhow::anyhow!("execution reverted: InvalidSequence");
+        assert!(!AnchorError::from_anyhow(&reverted).is_retryable());
+
+        let unknown = anyhow::anyhow!("connection reset by peer");
+        assert!(AnchorError::from_anyhow(&unknown).is_retryable());
+    }
+
+    #[test]
+    fn test_registry_custom_errors_are_decoded() {
+        use crate::client::SetRegistry;
+        use alloy::primitives::FixedBytes;
+        use alloy::sol_types::SolError;
+
+        let node_error = |data: Vec<u8>| {
+            anyhow::anyhow!(
+                "server returned an error response: error code 3: execution reverted, \
+                 data: \"0x{}\"",
+                hex::encode(data)
+            )
+        };
+
+        let err = AnchorError::from_anyhow(&node_error(
+            SetRegistry::NotAuthorizedSequencer {}.abi_encode(),
+        ));
+        assert!(matches!(
+            err,
+            AnchorError::Transaction(TransactionError::NotAuthorizedSequencer)
+        ));
+        assert_eq!(err.severity(), ErrorSeverity::Critical);
+
+        let err =
+            AnchorError::from_anyhow(&node_error(SetRegistry::EmptyEventsRoot {}.abi_encode()));
+        assert!(matches!(
+            err,
+            AnchorError::Transaction(TransactionError::EmptyEventsRoot)
+        ));
+
+        let mismatch = SetRegistry::StateRootMismatch {
+            expected: FixedBytes::from([0x11; 32]),
+            provided: FixedBytes::ZERO,
+        };
+        match AnchorError::from_anyhow(&node_error(mismatch.abi_encode())) {
+            AnchorError::Transaction(TransactionError::Reverted { reason }) => {
+                assert!(reason.starts_with("StateRootMismatch"), "{}", reason);
+            }
+            other => panic!("unexpected classification: {:?}", other),
+        }
+
+        // Unknown selectors keep the node's message
+        let err = AnchorError::from_anyhow(&node_error(vec![0xde, 0xad, 0xbe, 0xef]));
+        match err {
+            AnchorError::Transaction(TransactionError::Reverted { reason }) => {
+                assert!(reason.contains("0xdeadbeef"), "{}", reason);
+            }
+            other => panic!("unexpected classification: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_check_sequence() {
+        assert!(ValidationError::check_sequence(10, 11).is_ok());
+        assert!(matches!(
+            ValidationError::check_sequence(10, 15),
+            Err(ValidationError::SequenceGap {
+                expected: 11,
+                provided: 15
+            })
+        ));
+        let overlap = ValidationError::check_sequence(10, 6).unwrap_err();
+        assert_eq!(overlap.kind(), "overlap");
+        let err = AnchorError::from(overlap);
+        assert_eq!(err.error_code(), "VALIDATION_ERROR");
+        assert!(!err.is_retryable());
+    }
+
     #[test]
     fn test_error_codes() {
         let err = AnchorError::Transaction(TransactionError::ConfirmationTimeout);
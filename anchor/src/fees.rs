@@ -0,0 +1,189 @@
+//! Fee strategies for anchor transactions
+//!
+//! By default transactions are priced by alloy's gas filler. Operators who need
+//! tighter control select a [`FeeStrategy`] through configuration: the registry
+//! client samples the fee market before each submission and the strategy turns
+//! it into explicit EIP-1559 or legacy fee fields.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::config::AnchorConfig;
+
+/// Wei per gwei
+const WEI_PER_GWEI: f64 = 1e9;
+
+/// Fee conditions sampled from the node before a submission
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeMarket {
+    /// Base fee of the latest block; `None` on chains without EIP-1559
+    pub base_fee_per_gas: Option<u128>,
+    /// `eth_gasPrice`, which nodes report as base fee plus a suggested tip
+    pub gas_price: u128,
+}
+
+impl FeeMarket {
+    /// Priority fee implied by the node's gas price suggestion
+    pub fn suggested_priority_fee(&self) -> u128 {
+        self.gas_price
+            .saturating_sub(self.base_fee_per_gas.unwrap_or_default())
+    }
+}
+
+/// Fee fields applied to an outgoing transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeQuote {
+    Eip1559 {
+        max_fee_per_gas: u128,
+        max_priority_fee_per_gas: u128,
+    },
+    Legacy {
+        gas_price: u128,
+    },
+}
+
+/// Turns sampled fee conditions into the fees a transaction will pay
+pub trait FeeStrategy: Debug + Send + Sync {
+    /// Price a transaction for the current market
+    fn quote(&self, market: &FeeMarket) -> FeeQuote;
+}
+
+/// EIP-1559 pricing: `maxFee = base fee * multiplier + tip`
+///
+/// Falls back to legacy pricing when the chain reports no base fee.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Eip1559FeeStrategy {
+    /// Fixed tip in wei; `None` uses the node's suggestion
+    pub priority_fee_wei: Option<u128>,
+    /// Headroom over the latest base fee
+    pub base_fee_multiplier: f64,
+}
+
+impl FeeStrategy for Eip1559FeeStrategy {
+    fn quote(&self, market: &FeeMarket) -> FeeQuote {
+        let Some(base_fee) = market.base_fee_per_gas else {
+            return LegacyFeeStrategy.quote(market);
+        };
+
+        let priority_fee = self
+            .priority_fee_wei
+            .unwrap_or_else(|| market.suggested_priority_fee());
+        let base_budget = (base_fee as f64 * self.base_fee_multiplier).ceil() as u128;
+        FeeQuote::Eip1559 {
+            max_fee_per_gas: base_budget.saturating_add(priority_fee),
+            max_priority_fee_per_gas: priority_fee,
+        }
+    }
+}
+
+/// Legacy pricing at the node's suggested gas price
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LegacyFeeStrategy;
+
+impl FeeStrategy for LegacyFeeStrategy {
+    fn quote(&self, market: &FeeMarket) -> FeeQuote {
+        FeeQuote::Legacy {
+            gas_price: market.gas_price,
+        }
+    }
+}
+
+/// Build the strategy selected by `FEE_STRATEGY`; `None` keeps provider defaults
+pub fn strategy_from_config(config: &AnchorConfig) -> Option<Arc<dyn FeeStrategy>> {
+    match config.fee_strategy.as_str() {
+        "eip1559" => Some(Arc::new(Eip1559FeeStrategy {
+            priority_fee_wei: (config.priority_fee_gwei > 0.0)
+                .then(|| (config.priority_fee_gwei * WEI_PER_GWEI).round() as u128),
+            base_fee_multiplier: config.base_fee_multiplier,
+        })),
+        "legacy" => Some(Arc::new(LegacyFeeStrategy)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GWEI: u128 = 1_000_000_000;
+
+    #[test]
+    fn test_eip1559_quote_uses_multiplier_and_fixed_tip() {
+        let strategy = Eip1559FeeStrategy {
+            priority_fee_wei: Some(GWEI / 10),
+            base_fee_multiplier: 1.5,
+        };
+        let market = FeeMarket {
+            base_fee_per_gas: Some(2 * GWEI),
+            gas_price: 3 * GWEI,
+        };
+
+        assert_eq!(
+            strategy.quote(&market),
+            FeeQuote::Eip1559 {
+                max_fee_per_gas: 3 * GWEI + GWEI / 10,
+                max_priority_fee_per_gas: GWEI / 10,
+            }
+        );
+    }
+
+    #[test]
+    fn test_eip1559_quote_defaults_tip_to_node_suggestion() {
+        let strategy = Eip1559FeeStrategy {
+            priority_fee_wei: None,
+            base_fee_multiplier: 2.0,
+        };
+        let market = FeeMarket {
+            base_fee_per_gas: Some(GWEI),
+            gas_price: GWEI + 50,
+        };
+
+        assert_eq!(
+            strategy.quote(&market),
+            FeeQuote::Eip1559 {
+                max_fee_per_gas: 2 * GWEI + 50,
+                max_priority_fee_per_gas: 50,
+            }
+        );
+    }
+
+    #[test]
+    fn test_eip1559_falls_back_to_legacy_without_base_fee() {
+        let strategy = Eip1559FeeStrategy {
+            priority_fee_wei: None,
+            base_fee_multiplier: 2.0,
+        };
+        let market = FeeMarket {
+            base_fee_per_gas: None,
+            gas_price: 7,
+        };
+
+        assert_eq!(strategy.quote(&market), FeeQuote::Legacy { gas_price: 7 });
+    }
+
+    #[test]
+    fn test_strategy_from_config() {
+        let mut config = AnchorConfig::default();
+        assert!(strategy_from_config(&config).is_none());
+
+        config.fee_strategy = "eip1559".to_string();
+        config.priority_fee_gwei = 0.25;
+        let market = FeeMarket {
+            base_fee_per_gas: Some(0),
+            gas_price: 0,
+        };
+        assert_eq!(
+            strategy_from_config(&config).unwrap().quote(&market),
+            FeeQuote::Eip1559 {
+                max_fee_per_gas: GWEI / 4,
+                max_priority_fee_per_gas: GWEI / 4,
+            }
+        );
+
+        config.fee_strategy = "legacy".to_string();
+        assert_eq!(
+            strategy_from_config(&config).unwrap().quote(&market),
+            FeeQuote::Legacy { gas_price: 0 }
+        );
+    }
+}
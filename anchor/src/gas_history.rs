@@ -0,0 +1,102 @@
+//! Rolling gas price history
+//!
+//! With `GAS_AWARE_MAX_DELAY_SECS` set, the service samples the L2 gas price
+//! every cycle and keeps the last `GAS_SAMPLE_WINDOW_SECS` of samples. Batches
+//! of latency-tolerant tenants wait while the price is above the window's
+//! median, and go out at any price once they are `GAS_AWARE_MAX_DELAY_SECS` old.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Samples needed before the median is trusted; until then nothing waits
+pub const MIN_SAMPLES: usize = 5;
+
+/// Gas prices seen over the last `window`
+#[derive(Debug, Clone)]
+pub struct GasHistory {
+    window: Duration,
+    samples: VecDeque<(Instant, u128)>,
+}
+
+impl GasHistory {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Add a sample taken at `now`, dropping those older than the window
+    pub fn record(&mut self, gas_price: u128, now: Instant) {
+        while self
+            .samples
+            .front()
+            .is_some_and(|(taken, _)| now.saturating_duration_since(*taken) > self.window)
+        {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((now, gas_price));
+    }
+
+    /// Median of the samples in the window, once there are `MIN_SAMPLES` of them
+    pub fn median(&self) -> Option<u128> {
+        if self.samples.len() < MIN_SAMPLES {
+            return None;
+        }
+        let mut prices: Vec<u128> = self.samples.iter().map(|(_, price)| *price).collect();
+        prices.sort_unstable();
+        let middle = prices.len() / 2;
+        Some(if prices.len() % 2 == 0 {
+            (prices[middle - 1] + prices[middle]) / 2
+        } else {
+            prices[middle]
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_median_needs_enough_samples() {
+        let start = Instant::now();
+        let mut history = GasHistory::new(Duration::from_secs(3600));
+        for (i, price) in [5u128, 1, 4, 2].into_iter().enumerate() {
+            history.record(price, start + Duration::from_secs(i as u64 * 60));
+        }
+        assert_eq!(history.median(), None);
+
+        history.record(3, start + Duration::from_secs(240));
+        assert_eq!(history.median(), Some(3));
+        history.record(10, start + Duration::from_secs(300));
+        // Even count: mean of the middle two
+        assert_eq!(history.median(), Some(3));
+    }
+
+    #[test]
+    fn test_old_samples_leave_the_window() {
+        let start = Instant::now();
+        let mut history = GasHistory::new(Duration::from_secs(600));
+        for i in 0..5 {
+            history.record(100, start + Duration::from_secs(i * 60));
+        }
+        assert_eq!(history.median(), Some(100));
+
+        // Twenty minutes on, only the new samples are left
+        let later = start + Duration::from_secs(1200);
+        for i in 0..5 {
+            history.record(10, later + Duration::from_secs(i * 60));
+        }
+        assert_eq!(history.len(), 5);
+        assert_eq!(history.median(), Some(10));
+    }
+}
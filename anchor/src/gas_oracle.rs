@@ -0,0 +1,214 @@
+//! External gas price oracle
+//!
+//! Some deployments price transactions from a dedicated gas station rather than
+//! the node's `eth_gasPrice`. The oracle is an HTTP endpoint returning gwei
+//! estimates for each speed tier:
+//!
+//! ```json
+//! { "fast": 2.5, "standard": 1.2, "slow": 0.8 }
+//! ```
+//!
+//! Responses are cached for a short TTL so a burst of submissions costs one
+//! request. Callers fall back to the node when the oracle is unavailable.
+
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::config::AnchorConfig;
+
+/// Wei per gwei
+const WEI_PER_GWEI: f64 = 1e9;
+
+/// Estimate tier used when pricing transactions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GasOracleSpeed {
+    Fast,
+    #[default]
+    Standard,
+    Slow,
+}
+
+impl GasOracleSpeed {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GasOracleSpeed::Fast => "fast",
+            GasOracleSpeed::Standard => "standard",
+            GasOracleSpeed::Slow => "slow",
+        }
+    }
+}
+
+impl std::str::FromStr for GasOracleSpeed {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "fast" => Ok(GasOracleSpeed::Fast),
+            "standard" => Ok(GasOracleSpeed::Standard),
+            "slow" => Ok(GasOracleSpeed::Slow),
+            other => anyhow::bail!("expected fast, standard, or slow, got: {}", other),
+        }
+    }
+}
+
+/// Gas price estimates in gwei, as returned by the oracle
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct GasEstimates {
+    pub fast: f64,
+    pub standard: f64,
+    pub slow: f64,
+}
+
+impl GasEstimates {
+    /// Estimate for `speed`, in wei
+    pub fn gas_price_wei(&self, speed: GasOracleSpeed) -> u128 {
+        let gwei = match speed {
+            GasOracleSpeed::Fast => self.fast,
+            GasOracleSpeed::Standard => self.standard,
+            GasOracleSpeed::Slow => self.slow,
+        };
+        (gwei * WEI_PER_GWEI).round() as u128
+    }
+}
+
+/// HTTP gas oracle client with a response cache
+#[derive(Debug)]
+pub struct GasOracle {
+    url: String,
+    speed: GasOracleSpeed,
+    cache_ttl: Duration,
+    client: reqwest::Client,
+    cache: Mutex<Option<(Instant, GasEstimates)>>,
+}
+
+impl GasOracle {
+    /// Create a client for `url`, pricing at `speed`
+    pub fn new(url: &str, speed: GasOracleSpeed, cache_ttl: Duration, timeout: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            url: url.to_string(),
+            speed,
+            cache_ttl,
+            client,
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Build the oracle configured by `GAS_ORACLE_URL`, if any
+    pub fn from_config(config: &AnchorConfig) -> Option<Self> {
+        config.gas_oracle_url.as_deref().map(|url| {
+            Self::new(
+                url,
+                config.gas_oracle_speed,
+                Duration::from_secs(config.gas_oracle_cache_secs),
+                Duration::from_secs(config.gas_oracle_timeout_secs),
+            )
+        })
+    }
+
+    /// Endpoint queried for estimates
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Gas price in wei for the configured speed, served from cache when fresh
+    pub async fn gas_price(&self) -> Result<u128> {
+        Ok(self.estimates().await?.gas_price_wei(self.speed))
+    }
+
+    /// Latest estimates, fetching from the oracle when the cache is stale
+    pub async fn estimates(&self) -> Result<GasEstimates> {
+        let mut cache = self.cache.lock().await;
+        if let Some((fetched_at, estimates)) = *cache {
+            if fetched_at.elapsed() < self.cache_ttl {
+                return Ok(estimates);
+            }
+        }
+
+        let estimates = self.fetch().await?;
+        *cache = Some((Instant::now(), estimates));
+        Ok(estimates)
+    }
+
+    async fn fetch(&self) -> Result<GasEstimates> {
+        let response = self.client.get(&self.url).send().await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Gas oracle returned {}", response.status());
+        }
+
+        let estimates: GasEstimates = response.json().await?;
+        for value in [estimates.fast, estimates.standard, estimates.slow] {
+            if !value.is_finite() || value < 0.0 {
+                anyhow::bail!("Gas oracle returned an invalid estimate: {}", value);
+            }
+        }
+        Ok(estimates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+    fn oracle_for(server: &MockServer, cache_ttl: Duration) -> GasOracle {
+        GasOracle::new(
+            &server.uri(),
+            GasOracleSpeed::Fast,
+            cache_ttl,
+            Duration::from_secs(2),
+        )
+    }
+
+    #[test]
+    fn test_estimates_select_speed_in_wei() {
+        let estimates = GasEstimates {
+            fast: 2.5,
+            standard: 1.0,
+            slow: 0.001,
+        };
+        assert_eq!(estimates.gas_price_wei(GasOracleSpeed::Fast), 2_500_000_000);
+        assert_eq!(
+            estimates.gas_price_wei(GasOracleSpeed::Standard),
+            1_000_000_000
+        );
+        assert_eq!(estimates.gas_price_wei(GasOracleSpeed::Slow), 1_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_gas_price_is_cached() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "fast": 3.0,
+                "standard": 2.0,
+                "slow": 1.0,
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let oracle = oracle_for(&server, Duration::from_secs(60));
+        assert_eq!(oracle.gas_price().await.unwrap(), 3_000_000_000);
+        assert_eq!(oracle.gas_price().await.unwrap(), 3_000_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_gas_price_errors_when_oracle_unavailable() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let oracle = oracle_for(&server, Duration::ZERO);
+        assert!(oracle.gas_price().await.is_err());
+    }
+}
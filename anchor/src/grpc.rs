@@ -0,0 +1,673 @@
+//! gRPC sequencer client
+//!
+//! [`SequencerGrpcClient`] implements [`SequencerApi`] against the
+//! `stateset.sequencer.v1.Sequencer` service described in
+//! `proto/sequencer.proto`, for sequencers that expose gRPC rather than (or
+//! besides) REST. New commitments arrive over the server-streaming
+//! `SubscribeCommitments` call. gRPC status codes are mapped onto the same
+//! [`SequencerApiError`]s as their HTTP equivalents, so retry and circuit
+//! breaker behavior does not depend on the protocol.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::stream::{self, BoxStream, StreamExt};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::metadata::MetadataValue;
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity};
+use tonic::{Code, Status};
+use uuid::Uuid;
+
+use crate::client::{read_pending, SequencerApi};
+use crate::config::{SequencerCredentials, SequencerTimeouts, SequencerTls};
+use crate::error::{AnchorResult, SequencerApiError, ValidationError};
+use crate::metrics::AnchorMetrics;
+use crate::oauth::OAuthTokenSource;
+use crate::rate_limit::RateLimiter;
+use crate::trace_context;
+use crate::types::{
+    AnchorCostEstimate, AnchorNotification, BatchCommitment, BatchEvent, FinalizedNotification,
+    SchemaVersion,
+};
+
+/// Fully qualified gRPC service name
+const SERVICE: &str = "stateset.sequencer.v1.Sequencer";
+
+/// Messages from `proto/sequencer.proto`
+pub mod proto {
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Commitment {
+        #[prost(string, tag = "1")]
+        pub batch_id: String,
+        #[prost(string, tag = "2")]
+        pub tenant_id: String,
+        #[prost(string, tag = "3")]
+        pub store_id: String,
+        #[prost(string, tag = "4")]
+        pub prev_state_root: String,
+        #[prost(string, tag = "5")]
+        pub new_state_root: String,
+        #[prost(string, tag = "6")]
+        pub events_root: String,
+        #[prost(uint64, tag = "7")]
+        pub sequence_start: u64,
+        #[prost(uint64, tag = "8")]
+        pub sequence_end: u64,
+        #[prost(uint32, tag = "9")]
+        pub event_count: u32,
+        #[prost(string, tag = "10")]
+        pub committed_at: String,
+        #[prost(string, optional, tag = "11")]
+        pub chain_tx_hash: Option<String>,
+        #[prost(string, optional, tag = "12")]
+        pub batch_id_bytes32: Option<String>,
+        /// `major.minor`; empty means 1.0
+        #[prost(string, tag = "13")]
+        pub schema_version: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct GetPendingCommitmentsRequest {
+        #[prost(uint32, tag = "1")]
+        pub wait_secs: u32,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct GetPendingCommitmentsResponse {
+        #[prost(message, repeated, tag = "1")]
+        pub commitments: Vec<Commitment>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct SubscribeCommitmentsRequest {}
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct GetBatchEventsRequest {
+        #[prost(string, tag = "1")]
+        pub batch_id: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct BatchEvent {
+        #[prost(uint64, tag = "1")]
+        pub sequence: u64,
+        #[prost(string, tag = "2")]
+        pub event_type: String,
+        #[prost(string, tag = "3")]
+        pub payload: String,
+        #[prost(string, tag = "4")]
+        pub metadata: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct GetBatchEventsResponse {
+        #[prost(message, repeated, tag = "1")]
+        pub events: Vec<BatchEvent>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct NotifyAnchoredRequest {
+        #[prost(string, tag = "1")]
+        pub batch_id: String,
+        #[prost(string, tag = "2")]
+        pub chain_tx_hash: String,
+        #[prost(uint64, tag = "3")]
+        pub chain_id: u64,
+        #[prost(uint64, optional, tag = "4")]
+        pub block_number: Option<u64>,
+        #[prost(uint64, optional, tag = "5")]
+        pub gas_used: Option<u64>,
+        #[prost(string, repeated, tag = "6")]
+        pub blob_versioned_hashes: Vec<String>,
+        #[prost(string, optional, tag = "7")]
+        pub ipfs_cid: Option<String>,
+        #[prost(message, optional, tag = "8")]
+        pub celestia: Option<CelestiaBlob>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct CelestiaBlob {
+        #[prost(uint64, tag = "1")]
+        pub height: u64,
+        #[prost(string, tag = "2")]
+        pub namespace: String,
+        #[prost(string, tag = "3")]
+        pub commitment: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct NotifyAnchoredResponse {}
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct NotifyFinalizedRequest {
+        #[prost(string, tag = "1")]
+        pub batch_id: String,
+        #[prost(string, tag = "2")]
+        pub chain_tx_hash: String,
+        #[prost(uint64, tag = "3")]
+        pub chain_id: u64,
+        #[prost(uint64, tag = "4")]
+        pub block_number: u64,
+        #[prost(uint64, tag = "5")]
+        pub finalized_block: u64,
+        /// "l2" or "l1"
+        #[prost(string, tag = "6")]
+        pub finality: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct NotifyFinalizedResponse {}
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ReportCostEstimateRequest {
+        #[prost(string, tag = "1")]
+        pub batch_id: String,
+        #[prost(uint32, tag = "2")]
+        pub event_count: u32,
+        #[prost(uint64, tag = "3")]
+        pub gas_estimate: u64,
+        #[prost(string, tag = "4")]
+        pub gas_price_wei: String,
+        #[prost(string, tag = "5")]
+        pub estimated_cost_wei: String,
+        #[prost(string, tag = "6")]
+        pub cost_per_event_wei: String,
+        #[prost(string, tag = "7")]
+        pub estimated_at: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ReportCostEstimateResponse {}
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct HealthRequest {}
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct HealthResponse {
+        #[prost(bool, tag = "1")]
+        pub serving: bool,
+    }
+}
+
+impl From<proto::BatchEvent> for BatchEvent {
+    fn from(event: proto::BatchEvent) -> Self {
+        BatchEvent {
+            sequence: event.sequence,
+            event_type: event.event_type,
+            payload: event.payload,
+            metadata: event.metadata,
+        }
+    }
+}
+
+impl TryFrom<proto::Commitment> for BatchCommitment {
+    type Error = SequencerApiError;
+
+    fn try_from(commitment: proto::Commitment) -> Result<Self, Self::Error> {
+        let uuid = |field: &str, value: &str| {
+            Uuid::parse_str(value)
+                .map_err(|e| SequencerApiError::ParseError(format!("{}: {}", field, e)))
+        };
+        let schema_version = if commitment.schema_version.is_empty() {
+            SchemaVersion::default()
+        } else {
+            commitment
+                .schema_version
+                .parse::<SchemaVersion>()
+                .map_err(SequencerApiError::ParseError)?
+        };
+        schema_version
+            .ensure_supported()
+            .map_err(SequencerApiError::ParseError)?;
+        Ok(BatchCommitment {
+            batch_id: uuid("batch_id", &commitment.batch_id)?,
+            tenant_id: uuid("tenant_id", &commitment.tenant_id)?,
+            store_id: uuid("store_id", &commitment.store_id)?,
+            prev_state_root: commitment.prev_state_root,
+            new_state_root: commitment.new_state_root,
+            events_root: commitment.events_root,
+            sequence_start: commitment.sequence_start,
+            sequence_end: commitment.sequence_end,
+            event_count: commitment.event_count,
+            committed_at: chrono::DateTime::parse_from_rfc3339(&commitment.committed_at)
+                .map_err(|e| SequencerApiError::ParseError(format!("committed_at: {}", e)))?
+                .with_timezone(&chrono::Utc),
+            chain_tx_hash: commitment.chain_tx_hash,
+            batch_id_bytes32: commitment.batch_id_bytes32,
+            schema_version,
+        })
+    }
+}
+
+/// gRPC client for stateset-sequencer
+#[derive(Clone)]
+pub struct SequencerGrpcClient {
+    channel: Channel,
+    url: String,
+    timeouts: SequencerTimeouts,
+    credentials: Option<SequencerCredentials>,
+    /// Access tokens, used instead of `credentials` when set
+    oauth: Option<Arc<OAuthTokenSource>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    /// Counts pending entries dropped as unreadable
+    metrics: Option<Arc<AnchorMetrics>>,
+}
+
+impl SequencerGrpcClient {
+    /// Create a client; the connection is made on first use
+    ///
+    /// `tls` adds a client certificate and extra CAs to `https://` connections.
+    pub fn new(
+        url: &str,
+        connect_timeout: Duration,
+        timeouts: SequencerTimeouts,
+        tls: Option<&SequencerTls>,
+    ) -> Result<Self, tonic::transport::Error> {
+        let mut endpoint = Endpoint::from_shared(url.to_string())?.connect_timeout(connect_timeout);
+        if url.starts_with("https://") {
+            let mut tls_config = ClientTlsConfig::new().with_native_roots();
+            if let Some((cert, key)) = tls.and_then(|tls| tls.identity.as_ref()) {
+                tls_config = tls_config.identity(Identity::from_pem(cert, key));
+            }
+            if let Some(bundle) = tls.and_then(|tls| tls.ca_bundle.as_ref()) {
+                tls_config = tls_config.ca_certificate(Certificate::from_pem(bundle));
+            }
+            endpoint = endpoint.tls_config(tls_config)?;
+        }
+        Ok(Self {
+            channel: endpoint.connect_lazy(),
+            url: url.to_string(),
+            timeouts,
+            credentials: None,
+            oauth: None,
+            rate_limiter: None,
+            metrics: None,
+        })
+    }
+
+    /// Authenticate every call with an API key or bearer token
+    pub fn with_credentials(mut self, credentials: Option<SequencerCredentials>) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// Authenticate with OAuth2 client-credentials access tokens
+    pub fn with_oauth(mut self, oauth: Option<Arc<OAuthTokenSource>>) -> Self {
+        self.oauth = oauth;
+        self
+    }
+
+    /// Wait for a token from `limiter` before each call
+    pub fn with_rate_limiter(mut self, limiter: Option<Arc<RateLimiter>>) -> Self {
+        self.rate_limiter = limiter;
+        self
+    }
+
+    /// Count unreadable pending entries in `metrics`
+    pub fn with_metrics(mut self, metrics: Arc<AnchorMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    async fn request<T>(
+        &self,
+        message: T,
+        timeout: Option<Duration>,
+    ) -> AnchorResult<tonic::Request<T>> {
+        if let Some(ref limiter) = self.rate_limiter {
+            limiter.acquire().await;
+        }
+        let mut request = tonic::Request::new(message);
+        if let Some(timeout) = timeout {
+            request.set_timeout(timeout);
+        }
+        let context = trace_context::outgoing();
+        let metadata = request.metadata_mut();
+        if let Ok(value) = MetadataValue::try_from(context.traceparent()) {
+            metadata.insert("traceparent", value);
+        }
+        if let Some(Ok(value)) = context.tracestate().map(MetadataValue::try_from) {
+            metadata.insert("tracestate", value);
+        }
+        let credential = match (&self.credentials, &self.oauth) {
+            (_, Some(oauth)) => Some(("authorization", format!("Bearer {}", oauth.token().await?))),
+            (Some(SequencerCredentials::Bearer(token)), None) => {
+                Some(("authorization", format!("Bearer {}", token)))
+            }
+            (Some(SequencerCredentials::ApiKey(key)), None) => Some(("x-api-key", key.clone())),
+            (None, None) => None,
+        };
+        if let Some((key, Ok(value))) =
+            credential.map(|(key, value)| (key, MetadataValue::try_from(value)))
+        {
+            request.metadata_mut().insert(key, value);
+        }
+        Ok(request)
+    }
+
+    /// Map a failed call, dropping a rejected access token
+    async fn call_error(&self, status: Status, timeout: Duration) -> SequencerApiError {
+        if status.code() == Code::Unauthenticated {
+            if let Some(ref oauth) = self.oauth {
+                oauth.invalidate().await;
+            }
+        }
+        status_error(&self.url, timeout, status)
+    }
+
+    async fn ready(&self) -> AnchorResult<tonic::client::Grpc<Channel>> {
+        let mut grpc = tonic::client::Grpc::new(self.channel.clone());
+        grpc.ready()
+            .await
+            .map_err(|e| SequencerApiError::ConnectionFailed {
+                url: self.url.clone(),
+                message: e.to_string(),
+            })?;
+        Ok(grpc)
+    }
+
+    async fn unary<Req, Resp>(
+        &self,
+        method: &str,
+        message: Req,
+        timeout: Duration,
+    ) -> AnchorResult<Resp>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Resp: prost::Message + Default + Send + Sync + 'static,
+    {
+        let request = self.request(message, Some(timeout)).await?;
+        let mut grpc = self.ready().await?;
+        match grpc
+            .unary(
+                request,
+                method_path(method),
+                tonic::codec::ProstCodec::default(),
+            )
+            .await
+        {
+            Ok(response) => Ok(response.into_inner()),
+            Err(status) => Err(self.call_error(status, timeout).await.into()),
+        }
+    }
+
+    async fn pending(&self, wait: Duration) -> AnchorResult<Vec<BatchCommitment>> {
+        let response: proto::GetPendingCommitmentsResponse = self
+            .unary(
+                "GetPendingCommitments",
+                proto::GetPendingCommitmentsRequest {
+                    wait_secs: u32::try_from(wait.as_secs()).unwrap_or(u32::MAX),
+                },
+                self.timeouts.fetch + wait,
+            )
+            .await?;
+        Ok(read_pending(
+            response.commitments,
+            self.metrics.as_deref(),
+            |commitment| {
+                let batch_id = commitment.batch_id.clone();
+                BatchCommitment::try_from(commitment).map_err(|e| {
+                    ValidationError::UnreadableCommitment {
+                        batch_id,
+                        reason: e.to_string(),
+                    }
+                })
+            },
+        ))
+    }
+}
+
+#[async_trait]
+impl SequencerApi for SequencerGrpcClient {
+    async fn get_pending_commitments(&self) -> AnchorResult<Vec<BatchCommitment>> {
+        self.pending(Duration::ZERO).await
+    }
+
+    async fn poll_pending_commitments(&self, wait: Duration) -> AnchorResult<Vec<BatchCommitment>> {
+        self.pending(wait).await
+    }
+
+    async fn stream_commitments(
+        &self,
+        idle_timeout: Duration,
+    ) -> AnchorResult<BoxStream<'static, AnchorResult<BatchCommitment>>> {
+        let request = self
+            .request(proto::SubscribeCommitmentsRequest {}, None)
+            .await?;
+        let mut grpc = self.ready().await?;
+        let streaming: tonic::Streaming<proto::Commitment> = match grpc
+            .server_streaming(
+                request,
+                method_path("SubscribeCommitments"),
+                tonic::codec::ProstCodec::default(),
+            )
+            .await
+        {
+            Ok(response) => response.into_inner(),
+            Err(status) => return Err(self.call_error(status, self.timeouts.fetch).await.into()),
+        };
+
+        let url = self.url.clone();
+        Ok(stream::unfold(Some(streaming), move |streaming| {
+            let url = url.clone();
+            async move {
+                let mut streaming = streaming?;
+                let item = match tokio::time::timeout(idle_timeout, streaming.message()).await {
+                    Ok(Ok(Some(commitment))) => {
+                        let item = BatchCommitment::try_from(commitment).map_err(Into::into);
+                        return Some((item, Some(streaming)));
+                    }
+                    Ok(Ok(None)) => return None,
+                    Ok(Err(status)) => status_error(&url, idle_timeout, status),
+                    Err(_) => SequencerApiError::Timeout {
+                        seconds: idle_timeout.as_secs(),
+                    },
+                };
+                // Report the failure, then end the stream
+                Some((Err(item.into()), None))
+            }
+        })
+        .boxed())
+    }
+
+    async fn get_batch_events(&self, batch_id: Uuid) -> AnchorResult<Vec<BatchEvent>> {
+        let response: proto::GetBatchEventsResponse = self
+            .unary(
+                "GetBatchEvents",
+                proto::GetBatchEventsRequest {
+                    batch_id: batch_id.to_string(),
+                },
+                self.timeouts.fetch,
+            )
+            .await?;
+        Ok(response.events.into_iter().map(Into::into).collect())
+    }
+
+    async fn notify_anchored(
+        &self,
+        batch_id: Uuid,
+        notification: &AnchorNotification,
+    ) -> AnchorResult<()> {
+        let _: proto::NotifyAnchoredResponse = self
+            .unary(
+                "NotifyAnchored",
+                proto::NotifyAnchoredRequest {
+                    batch_id: batch_id.to_string(),
+                    chain_tx_hash: notification.chain_tx_hash.clone(),
+                    chain_id: notification.chain_id,
+                    block_number: notification.block_number,
+                    gas_used: notification.gas_used,
+                    blob_versioned_hashes: notification.blob_versioned_hashes.clone(),
+                    ipfs_cid: notification.ipfs_cid.clone(),
+                    celestia: notification
+                        .celestia
+                        .as_ref()
+                        .map(|blob| proto::CelestiaBlob {
+                            height: blob.height,
+                            namespace: blob.namespace.clone(),
+                            commitment: blob.commitment.clone(),
+                        }),
+                },
+                self.timeouts.notify,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn notify_finalized(
+        &self,
+        batch_id: Uuid,
+        notification: &FinalizedNotification,
+    ) -> AnchorResult<()> {
+        let _: proto::NotifyFinalizedResponse = self
+            .unary(
+                "NotifyFinalized",
+                proto::NotifyFinalizedRequest {
+                    batch_id: batch_id.to_string(),
+                    chain_tx_hash: notification.chain_tx_hash.clone(),
+                    chain_id: notification.chain_id,
+                    block_number: notification.block_number,
+                    finalized_block: notification.finalized_block,
+                    finality: notification.finality.as_str().to_string(),
+                },
+                self.timeouts.notify,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn report_cost_estimate(
+        &self,
+        batch_id: Uuid,
+        estimate: &AnchorCostEstimate,
+    ) -> AnchorResult<()> {
+        let _: proto::ReportCostEstimateResponse = self
+            .unary(
+                "ReportCostEstimate",
+                proto::ReportCostEstimateRequest {
+                    batch_id: batch_id.to_string(),
+                    event_count: estimate.event_count,
+                    gas_estimate: estimate.gas_estimate,
+                    gas_price_wei: estimate.gas_price_wei.clone(),
+                    estimated_cost_wei: estimate.estimated_cost_wei.clone(),
+                    cost_per_event_wei: estimate.cost_per_event_wei.clone(),
+                    estimated_at: estimate.estimated_at.to_rfc3339(),
+                },
+                self.timeouts.notify,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn health(&self) -> AnchorResult<bool> {
+        let response: proto::HealthResponse = self
+            .unary("Health", proto::HealthRequest {}, self.timeouts.health)
+            .await?;
+        Ok(response.serving)
+    }
+}
+
+fn method_path(method: &str) -> PathAndQuery {
+    PathAndQuery::try_from(format!("/{}/{}", SERVICE, method))
+        .expect("gRPC method paths are valid URI paths")
+}
+
+/// Map a gRPC status onto the error its HTTP equivalent would produce
+fn status_error(url: &str, timeout: Duration, status: Status) -> SequencerApiError {
+    let http_status = match status.code() {
+        Code::DeadlineExceeded => {
+            return SequencerApiError::Timeout {
+                seconds: timeout.as_secs(),
+            }
+        }
+        Code::Unavailable => {
+            return SequencerApiError::ConnectionFailed {
+                url: url.to_string(),
+                message: status.message().to_string(),
+            }
+        }
+        Code::InvalidArgument | Code::FailedPrecondition | Code::OutOfRange => 400,
+        Code::Unauthenticated => 401,
+        Code::PermissionDenied => 403,
+        Code::NotFound => 404,
+        Code::AlreadyExists | Code::Aborted => 409,
+        Code::ResourceExhausted => 429,
+        Code::Cancelled => 499,
+        Code::Unimplemented => 501,
+        _ => 500,
+    };
+    SequencerApiError::from_status(http_status, status.message().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commitment_converts_from_proto() {
+        let batch_id = Uuid::new_v4();
+        let commitment = proto::Commitment {
+            batch_id: batch_id.to_string(),
+            tenant_id: Uuid::new_v4().to_string(),
+            store_id: Uuid::new_v4().to_string(),
+            prev_state_root: format!("0x{}", "0".repeat(64)),
+            new_state_root: format!("0x{}", "22".repeat(32)),
+            events_root: format!("0x{}", "11".repeat(32)),
+            sequence_start: 1,
+            sequence_end: 10,
+            event_count: 10,
+            committed_at: "2026-10-16T09:30:00Z".to_string(),
+            chain_tx_hash: None,
+            batch_id_bytes32: None,
+            schema_version: String::new(),
+        };
+        let converted = BatchCommitment::try_from(commitment.clone()).unwrap();
+        assert_eq!(converted.batch_id, batch_id);
+        assert_eq!(converted.event_count, 10);
+        assert_eq!(converted.schema_version, SchemaVersion::CURRENT);
+
+        let next_major = proto::Commitment {
+            schema_version: "2.0".to_string(),
+            ..commitment.clone()
+        };
+        assert!(matches!(
+            BatchCommitment::try_from(next_major),
+            Err(SequencerApiError::ParseError(_))
+        ));
+
+        let invalid = proto::Commitment {
+            committed_at: "yesterday".to_string(),
+            ..commitment
+        };
+        assert!(matches!(
+            BatchCommitment::try_from(invalid),
+            Err(SequencerApiError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_status_maps_to_http_equivalent() {
+        let timeout = Duration::from_secs(5);
+        assert!(matches!(
+            status_error(
+                "http://sequencer",
+                timeout,
+                Status::deadline_exceeded("slow")
+            ),
+            SequencerApiError::Timeout { seconds: 5 }
+        ));
+        assert!(matches!(
+            status_error("http://sequencer", timeout, Status::unavailable("down")),
+            SequencerApiError::ConnectionFailed { .. }
+        ));
+        assert!(matches!(
+            status_error("http://sequencer", timeout, Status::permission_denied("no")),
+            SequencerApiError::Unauthorized { status: 403, .. }
+        ));
+        assert!(matches!(
+            status_error("http://sequencer", timeout, Status::internal("boom")),
+            SequencerApiError::HttpError { status: 500, .. }
+        ));
+    }
+}
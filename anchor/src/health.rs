@@ -2,28 +2,62 @@
 //!
 //! Provides endpoints for Kubernetes probes and monitoring:
 //! - GET /health - Liveness probe (always returns 200 if server is running)
-//! - GET /ready - Readiness probe (checks L2 and sequencer connectivity)
-//! - GET /metrics - Prometheus-compatible metrics
+//! - GET /startup - Startup probe (provider created, chain id checked, signer authorization verified)
+//! - GET /ready - Readiness probe (healthy/degraded/unhealthy per component; 503 only when unhealthy)
+//! - GET /metrics - Prometheus metrics from the shared registry
 //! - GET /stats - JSON anchor statistics
 //! - GET /errors - Error statistics by category
-
+//! - GET /standby - Warm-standby pre-flight status
+//! - GET /pending - Batches fetched in the last cycle and why each is or isn't being anchored
+//! - GET /pause - Whether submissions are paused and why
+//! - POST /resume - Operator resume after a pause (requires `ADMIN_TOKEN`)
+//! - POST /admin/pause - Operator pause of submissions, optional `{"reason": ...}` body (requires `ADMIN_TOKEN`)
+//! - POST /admin/resume - Same as POST /resume
+//! - POST /admin/anchor-now - Start the next anchor cycle immediately (requires `ADMIN_TOKEN`)
+//! - POST /admin/batches/{batch_id}/retry - Retry a batch now, lifting any skip or backoff (requires `ADMIN_TOKEN`)
+//! - POST /admin/batches/{batch_id}/skip - Never anchor a batch, optional `{"reason": ...}` body (requires `ADMIN_TOKEN`)
+//! - GET /anchors/{batch_id}/calldata - Submitted calldata and decoded commitBatch arguments
+//! - GET /batches/{batch_id} - On-chain commitment of a batch, read from the registry
+//! - GET /history?limit=&offset= - Recent anchor operations from the journal, newest first
+//! - POST /v1/commitments - Signed commitment push from the sequencer (`COMMITMENT_SOURCE=webhook`)
+
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Instant;
 
+use alloy::primitives::FixedBytes;
+use async_trait::async_trait;
 use axum::{
-    extract::State,
-    http::StatusCode,
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Response},
-    routing::get,
+    routing::{get, post},
     Json, Router,
 };
-use serde::Serialize;
-use tokio::sync::RwLock;
-use tracing::info;
-
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Notify, RwLock};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::budget::{BudgetStatus, GasBudget};
+use crate::client::{decode_commit_batch, uuid_lookup_keys, CommittedBatch, DecodedCommitBatch};
 use crate::config::AnchorConfig;
-use crate::types::AnchorStats;
+use crate::error::AnchorResult;
+use crate::journal::{AnchorJournal, HistoryEntry, PauseStatus};
+use crate::metrics::AnchorMetrics;
+use crate::nonce::NonceSnapshot;
+use crate::signing::{self, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use crate::sla::{TimeToAnchor, WindowPercentiles};
+use crate::source::webhook::{self, WebhookCommitmentSource};
+use crate::success_window::WindowSuccessRate;
+use crate::types::{
+    AnchorRecord, AnchorStats, BatchCommitment, BatchIdScheme, CircuitBreakerState,
+    CommitmentSourceKind, CostTotals, TenantStats,
+};
 
 /// Error counts by category for monitoring
 #[derive(Debug, Default, Clone, Serialize)]
@@ -33,12 +67,215 @@ pub struct ErrorCounts {
     pub sequencer_api_errors: u64,
     pub transaction_errors: u64,
     pub authorization_errors: u64,
+    pub validation_errors: u64,
     pub internal_errors: u64,
     pub last_error_time: Option<String>,
     pub last_error_message: Option<String>,
     pub last_error_code: Option<String>,
 }
 
+/// Pre-flight status of an instance running in warm standby
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct StandbyStatus {
+    /// Whether the instance is currently in standby (not submitting)
+    pub standby: bool,
+    /// Whether the signer is still an authorized sequencer
+    pub authorized: bool,
+    /// Signer balance in wei at the last pre-flight
+    pub balance_wei: Option<String>,
+    /// Pending commitments seen at the last pre-flight
+    pub mirrored_pending: usize,
+    /// Time of the last completed pre-flight
+    pub last_preflight: Option<String>,
+    /// Whether the instance could take over submission immediately
+    pub takeover_ready: bool,
+    /// Replica holding the leader lease, when `LEADER_ELECTION_LEASE` is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lease_holder: Option<String>,
+}
+
+/// Initialization steps the startup probe waits for
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct StartupStatus {
+    /// Whether every step below has completed; stays set afterwards
+    pub started: bool,
+    /// Whether the L2 provider was created from the RPC endpoints and signer key
+    pub provider_created: bool,
+    /// Chain id read from L2, once it matched `EXPECTED_L2_CHAIN_ID` (if set)
+    pub chain_id: Option<u64>,
+    /// Whether the signer was confirmed as an authorized sequencer in the registry
+    pub authorization_verified: bool,
+    /// Time startup completed
+    pub started_at: Option<String>,
+}
+
+/// What the last cycle decided for a fetched batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PendingStatus {
+    /// Being submitted this cycle
+    Planned,
+    /// Fewer events than `MIN_EVENTS_FOR_ANCHOR`; completed without anchoring, or
+    /// held until `MAX_COMMITMENT_AGE_SECS` if set
+    BelowThreshold,
+    /// Refused by validation
+    Invalid,
+    /// Another copy of a batch id earlier in the same fetch, which is handled instead
+    Duplicate,
+    /// Already anchored; the acknowledgement is being re-sent
+    AlreadyAnchored,
+    /// Anchored; the sequencer acknowledgement is queued for retry
+    AwaitingAcknowledgement,
+    /// Its events could not be fetched to verify `events_root`
+    VerificationPending,
+    /// Its tenant/store stream is backing off after a failure
+    StreamBackoff,
+    /// Beyond `MAX_COMMITMENTS_PER_CYCLE`; waits for the next cycle
+    CycleLimit,
+    /// Waits for an earlier batch of its stream (sequence gap or overlap)
+    OrderingHold,
+    /// Skipped by an operator; completed without anchoring
+    Skipped,
+    /// Gas price above `MAX_GAS_PRICE_GWEI`
+    GasCap,
+    /// Submissions are paused pending operator resume
+    Paused,
+    /// Outside `ANCHOR_SCHEDULE`; waits for the next window
+    OutsideSchedule,
+    /// Gas is above its recent median; waits up to `GAS_AWARE_MAX_DELAY_SECS`
+    GasAboveMedian,
+    /// `MAX_ANCHORS_PER_HOUR` reached; waits for room in the hourly window
+    Throttled,
+}
+
+impl PendingStatus {
+    /// Same as the JSON representation, for stats keys and metric labels
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PendingStatus::Planned => "planned",
+            PendingStatus::BelowThreshold => "below_threshold",
+            PendingStatus::Invalid => "invalid",
+            PendingStatus::Duplicate => "duplicate",
+            PendingStatus::AlreadyAnchored => "already_anchored",
+            PendingStatus::AwaitingAcknowledgement => "awaiting_acknowledgement",
+            PendingStatus::VerificationPending => "verification_pending",
+            PendingStatus::StreamBackoff => "stream_backoff",
+            PendingStatus::CycleLimit => "cycle_limit",
+            PendingStatus::OrderingHold => "ordering_hold",
+            PendingStatus::Skipped => "skipped",
+            PendingStatus::GasCap => "gas_cap",
+            PendingStatus::Paused => "paused",
+            PendingStatus::OutsideSchedule => "outside_schedule",
+            PendingStatus::GasAboveMedian => "gas_above_median",
+            PendingStatus::Throttled => "throttled",
+        }
+    }
+}
+
+/// A batch fetched in the last cycle
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingBatch {
+    pub batch_id: Uuid,
+    pub tenant_id: Uuid,
+    pub store_id: Uuid,
+    pub sequence_start: u64,
+    pub sequence_end: u64,
+    pub event_count: u32,
+    pub committed_at: DateTime<Utc>,
+    pub status: PendingStatus,
+    /// Why the batch is not `planned`, when there is more to say than the status
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Operator-defined context of a `skipped` batch
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl PendingBatch {
+    pub fn new(
+        commitment: &BatchCommitment,
+        status: PendingStatus,
+        reason: Option<String>,
+    ) -> Self {
+        Self {
+            batch_id: commitment.batch_id,
+            tenant_id: commitment.tenant_id,
+            store_id: commitment.store_id,
+            sequence_start: commitment.sequence_start,
+            sequence_end: commitment.sequence_end,
+            event_count: commitment.event_count,
+            committed_at: commitment.committed_at,
+            status,
+            reason,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// Attach the operator's metadata
+    pub fn with_metadata(mut self, metadata: BTreeMap<String, String>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+}
+
+/// Batches fetched in the last cycle
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingQueue {
+    pub fetched_at: DateTime<Utc>,
+    pub batches: Vec<PendingBatch>,
+}
+
+/// Signer balance at the last periodic check
+#[derive(Debug, Clone, Serialize)]
+pub struct WalletStatus {
+    pub address: String,
+    pub balance_wei: String,
+    /// Configured low-balance threshold (`None` = disabled)
+    pub threshold_wei: Option<String>,
+    /// Balance is below the threshold; `/ready` reports degraded
+    pub low: bool,
+    pub checked_at: String,
+}
+
+/// Registry state at the last background re-verification
+#[derive(Debug, Clone, Serialize)]
+pub struct RegistryStatus {
+    /// Whether the signer is an authorized sequencer
+    pub authorized: bool,
+    /// Whether contract code is deployed at `SET_REGISTRY_ADDRESS`
+    pub code_present: bool,
+    /// Whether the registry enforces state-root chaining
+    pub strict_mode: bool,
+    /// Whether the registry owner has paused `commitBatch`; submissions wait
+    /// and `/ready` reports degraded until it is unpaused
+    pub paused: bool,
+    pub checked_at: String,
+}
+
+impl RegistryStatus {
+    /// The service can anchor against this registry
+    pub fn usable(&self) -> bool {
+        self.authorized && self.code_present
+    }
+}
+
+/// Latest L2 block seen by the service
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct L2Head {
+    pub block_number: u64,
+    /// Block timestamp in unix seconds
+    pub block_timestamp: u64,
+}
+
+impl L2Head {
+    /// Seconds since the block was produced
+    pub fn age_secs(&self, now: chrono::DateTime<chrono::Utc>) -> u64 {
+        u64::try_from(now.timestamp())
+            .unwrap_or_default()
+            .saturating_sub(self.block_timestamp)
+    }
+}
+
 /// Health server state shared across handlers
 pub struct HealthState {
     /// Service start time for uptime calculation
@@ -59,11 +296,75 @@ pub struct HealthState {
     /// Whether the service is ready to anchor
     pub is_ready: RwLock<bool>,
 
+    /// Set once shutdown starts; in-flight anchors are still finishing
+    pub draining: RwLock<bool>,
+
     /// Error counts by category
     pub error_counts: RwLock<ErrorCounts>,
 
     /// Recent errors (circular buffer)
     pub recent_errors: RwLock<Vec<ErrorRecord>>,
+
+    /// Recently anchored batches (circular buffer)
+    pub anchors: RwLock<Vec<AnchorRecord>>,
+
+    /// Warm-standby pre-flight status
+    pub standby: RwLock<StandbyStatus>,
+
+    /// Initialization progress behind `/startup`
+    pub startup: RwLock<StartupStatus>,
+
+    /// Batches fetched in the last cycle (`None` until the first fetch)
+    pub pending: RwLock<Option<PendingQueue>>,
+
+    /// Signer nonce state at the last reconcile
+    pub nonce: RwLock<Option<NonceSnapshot>>,
+
+    /// Submission pause, shared with the anchor service
+    pub pause: Arc<RwLock<PauseStatus>>,
+
+    /// Gas spend budget, shared with the anchor service (`None` = unlimited)
+    pub budget: Arc<RwLock<Option<GasBudget>>>,
+
+    /// Signer balance at the last check
+    pub wallet: RwLock<Option<WalletStatus>>,
+
+    /// Latest L2 block at the last check
+    pub l2_head: RwLock<Option<L2Head>>,
+
+    /// Registry state at the last re-verification, shared with the anchor service
+    pub registry: Arc<RwLock<Option<RegistryStatus>>>,
+
+    /// In-flight transactions and finished operations, shared with the anchor service
+    pub journal: Arc<AnchorJournal>,
+
+    /// Prometheus registry, shared with the anchor service and registry client
+    pub metrics: Arc<AnchorMetrics>,
+
+    /// Receiver for pushed commitments (`COMMITMENT_SOURCE=webhook`)
+    pub webhook: Option<Arc<WebhookCommitmentSource>>,
+
+    /// Wakes the anchor loop before `anchor_interval_secs` is up
+    pub wake: Arc<Notify>,
+
+    /// Batches an operator asked to retry, taken by the anchor service when next fetched
+    pub batch_retries: Arc<RwLock<HashSet<Uuid>>>,
+
+    /// Registry reads for `GET /batches/{batch_id}`, set once the service has connected
+    pub batch_lookup: RwLock<Option<Arc<dyn BatchLookup>>>,
+}
+
+/// Registry reads behind `GET /batches/{batch_id}`
+#[async_trait]
+pub trait BatchLookup: Send + Sync {
+    /// Chain the registry is deployed on
+    fn chain_id(&self) -> u64;
+
+    /// Commitment stored under a registry key, or `None` if the key is unused
+    async fn committed_batch(
+        &self,
+        chain_batch_id: FixedBytes<32>,
+    ) -> AnchorResult<Option<CommittedBatch>>;
 }
 
 /// Record of a recent error
@@ -80,7 +381,23 @@ impl HealthState {
     /// Maximum number of recent errors to keep
     const MAX_RECENT_ERRORS: usize = 100;
 
+    /// Maximum number of anchor records to keep
+    const MAX_ANCHOR_RECORDS: usize = 1000;
+
+    /// Shared state over `stats`
+    ///
+    /// `stats` backs `/stats` and the gauges on `/metrics`. The `/metrics`
+    /// counters start at zero and only move as events are recorded, so totals
+    /// already in `stats` are not reflected there.
     pub fn new(config: AnchorConfig, stats: Arc<RwLock<AnchorStats>>) -> Self {
+        let budget = GasBudget::from_config(&config);
+        let journal = Arc::new(AnchorJournal::from_config(&config));
+        let webhook = (config.commitment_source == CommitmentSourceKind::Webhook)
+            .then(|| Arc::new(WebhookCommitmentSource::from_config(&config)));
+        // Pushed commitments and operators wake the loop the same way
+        let wake = webhook
+            .as_ref()
+            .map_or_else(|| Arc::new(Notify::new()), |webhook| webhook.wake());
         Self {
             start_time: Instant::now(),
             stats,
@@ -88,8 +405,25 @@ impl HealthState {
             last_l2_check: RwLock::new(None),
             last_sequencer_check: RwLock::new(None),
             is_ready: RwLock::new(false),
+            draining: RwLock::new(false),
             error_counts: RwLock::new(ErrorCounts::default()),
             recent_errors: RwLock::new(Vec::with_capacity(Self::MAX_RECENT_ERRORS)),
+            anchors: RwLock::new(Vec::new()),
+            standby: RwLock::new(StandbyStatus::default()),
+            startup: RwLock::new(StartupStatus::default()),
+            pending: RwLock::new(None),
+            nonce: RwLock::new(None),
+            pause: Arc::new(RwLock::new(PauseStatus::default())),
+            budget: Arc::new(RwLock::new(budget)),
+            wallet: RwLock::new(None),
+            l2_head: RwLock::new(None),
+            registry: Arc::new(RwLock::new(None)),
+            journal,
+            metrics: Arc::new(AnchorMetrics::new()),
+            webhook,
+            wake,
+            batch_retries: Arc::new(RwLock::new(HashSet::new())),
+            batch_lookup: RwLock::new(None),
         }
     }
 
@@ -98,6 +432,16 @@ impl HealthState {
         *self.is_ready.write().await = ready;
     }
 
+    /// Report not ready while shutdown finishes in-flight anchors
+    pub async fn set_draining(&self) {
+        *self.draining.write().await = true;
+    }
+
+    /// Serve `GET /batches/{batch_id}` from `lookup`
+    pub async fn set_batch_lookup(&self, lookup: Arc<dyn BatchLookup>) {
+        *self.batch_lookup.write().await = Some(lookup);
+    }
+
     /// Update L2 check timestamp
     pub async fn mark_l2_healthy(&self) {
         *self.last_l2_check.write().await = Some(Instant::now());
@@ -108,6 +452,106 @@ impl HealthState {
         *self.last_sequencer_check.write().await = Some(Instant::now());
     }
 
+    /// L2 checked within the last minute and its head still advancing
+    pub async fn l2_connected(&self) -> bool {
+        checked_recently(*self.last_l2_check.read().await) && !self.l2_head_stale().await
+    }
+
+    /// Sequencer checked within the last minute
+    pub async fn sequencer_connected(&self) -> bool {
+        checked_recently(*self.last_sequencer_check.read().await)
+    }
+
+    /// Status of each component, and of the service as the worst of them
+    pub async fn health_report(&self) -> HealthReport {
+        let now = chrono::Utc::now();
+        let (sequencer_breaker, l2_breaker) = {
+            let stats = self.stats.read().await;
+            (stats.sequencer_breaker_state, stats.l2_breaker_state)
+        };
+
+        let mut service = ComponentHealth::new("service");
+        if *self.draining.read().await {
+            service.fail("shutting down; finishing in-flight anchors");
+        } else if !*self.is_ready.read().await {
+            service.fail("not ready to anchor (starting up or halted)");
+        }
+
+        let mut l2 = ComponentHealth::new("l2_rpc");
+        if !checked_recently(*self.last_l2_check.read().await) {
+            l2.fail("no successful check in the last 60s");
+        }
+        if self.l2_head_stale().await {
+            if let Some(head) = *self.l2_head.read().await {
+                l2.fail(format!(
+                    "head block {} is {}s old",
+                    head.block_number,
+                    head.age_secs(now)
+                ));
+            }
+        }
+        l2.breaker(l2_breaker);
+
+        let mut sequencer = ComponentHealth::new("sequencer_api");
+        if !checked_recently(*self.last_sequencer_check.read().await) {
+            sequencer.fail("no successful check in the last 60s");
+        }
+        sequencer.breaker(sequencer_breaker);
+
+        let mut registry = ComponentHealth::new("registry");
+        if let Some(status) = self.registry.read().await.as_ref() {
+            if !status.authorized {
+                registry.fail("signer is not an authorized sequencer");
+            }
+            if !status.code_present {
+                registry.fail("no contract code at SET_REGISTRY_ADDRESS");
+            }
+            if status.paused {
+                registry.degrade("commitBatch paused by the registry owner");
+            }
+        }
+
+        let mut budget = ComponentHealth::new("gas_budget");
+        if let Some(status) = self.budget.read().await.as_ref().map(|b| b.status(now)) {
+            if status.exhausted {
+                budget.degrade(format!(
+                    "{} budget of {} wei exhausted until {}",
+                    status.period.as_str(),
+                    status.limit_wei,
+                    status.resets_at
+                ));
+            }
+        }
+
+        let mut wallet = ComponentHealth::new("wallet");
+        if let Some(status) = self.wallet.read().await.as_ref().filter(|w| w.low) {
+            wallet.degrade(format!(
+                "balance of {} wei below {} wei",
+                status.balance_wei,
+                status.threshold_wei.as_deref().unwrap_or("0")
+            ));
+        }
+
+        let mut submissions = ComponentHealth::new("submissions");
+        let pause = self.pause.read().await;
+        if pause.paused {
+            submissions.degrade(match pause.reason.as_deref() {
+                Some(reason) => format!("paused: {}", reason),
+                None => "paused".to_string(),
+            });
+        }
+
+        HealthReport::new(vec![
+            service,
+            l2,
+            sequencer,
+            registry,
+            budget,
+            wallet,
+            submissions,
+        ])
+    }
+
     /// Record an error for tracking
     pub async fn record_error(&self, error: &crate::error::AnchorError) {
         use chrono::Utc;
@@ -121,14 +565,27 @@ impl HealthState {
         // Update counts
         {
             let mut counts = self.error_counts.write().await;
-            match error {
-                crate::error::AnchorError::Config(_) => counts.config_errors += 1,
-                crate::error::AnchorError::L2Connection(_) => counts.l2_connection_errors += 1,
-                crate::error::AnchorError::SequencerApi(_) => counts.sequencer_api_errors += 1,
-                crate::error::AnchorError::Transaction(_) => counts.transaction_errors += 1,
-                crate::error::AnchorError::Authorization(_) => counts.authorization_errors += 1,
-                crate::error::AnchorError::Internal(_) => counts.internal_errors += 1,
-            }
+            let (count, category) = match error {
+                crate::error::AnchorError::Config(_) => (&mut counts.config_errors, "config"),
+                crate::error::AnchorError::L2Connection(_) => {
+                    (&mut counts.l2_connection_errors, "l2_connection")
+                }
+                crate::error::AnchorError::SequencerApi(_) => {
+                    (&mut counts.sequencer_api_errors, "sequencer_api")
+                }
+                crate::error::AnchorError::Transaction(_) => {
+                    (&mut counts.transaction_errors, "transaction")
+                }
+                crate::error::AnchorError::Authorization(_) => {
+                    (&mut counts.authorization_errors, "authorization")
+                }
+                crate::error::AnchorError::Validation(_) => {
+                    (&mut counts.validation_errors, "validation")
+                }
+                crate::error::AnchorError::Internal(_) => (&mut counts.internal_errors, "internal"),
+            };
+            *count += 1;
+            self.metrics.record_error(category);
             counts.last_error_time = Some(timestamp.clone());
             counts.last_error_message = Some(message.clone());
             counts.last_error_code = Some(error_code.clone());
@@ -150,6 +607,101 @@ impl HealthState {
         }
     }
 
+    /// Startup step: L2 provider created
+    pub async fn mark_provider_created(&self) {
+        self.update_startup(|startup| startup.provider_created = true)
+            .await;
+    }
+
+    /// Startup step: L2 chain id read and checked
+    pub async fn mark_chain_id_verified(&self, chain_id: u64) {
+        self.update_startup(|startup| startup.chain_id = Some(chain_id))
+            .await;
+    }
+
+    /// Startup step: signer authorization verified in the registry
+    pub async fn mark_authorization_verified(&self) {
+        self.update_startup(|startup| startup.authorization_verified = true)
+            .await;
+    }
+
+    async fn update_startup(&self, step: impl FnOnce(&mut StartupStatus)) {
+        let mut startup = self.startup.write().await;
+        step(&mut startup);
+        if !startup.started
+            && startup.provider_created
+            && startup.chain_id.is_some()
+            && startup.authorization_verified
+        {
+            startup.started = true;
+            startup.started_at = Some(chrono::Utc::now().to_rfc3339());
+        }
+    }
+
+    /// Update warm-standby status
+    pub async fn set_standby_status(&self, status: StandbyStatus) {
+        *self.standby.write().await = status;
+    }
+
+    /// Record which replica holds the leader lease
+    pub async fn set_lease_holder(&self, holder: Option<String>) {
+        self.standby.write().await.lease_holder = holder;
+    }
+
+    /// Publish what the latest cycle fetched and decided
+    pub async fn set_pending_queue(&self, queue: PendingQueue) {
+        *self.pending.write().await = Some(queue);
+    }
+
+    /// Update signer balance status
+    pub async fn set_wallet_status(&self, status: WalletStatus) {
+        *self.wallet.write().await = Some(status);
+    }
+
+    /// Update the latest L2 block
+    pub async fn set_l2_head(&self, head: L2Head) {
+        *self.l2_head.write().await = Some(head);
+    }
+
+    /// Whether the latest L2 block is older than `MAX_L2_HEAD_AGE_SECS`
+    ///
+    /// A stalled node still answers RPCs, so this marks L2 unhealthy even when
+    /// connectivity checks pass.
+    pub async fn l2_head_stale(&self) -> bool {
+        let max_age = self.config.max_l2_head_age_secs;
+        max_age > 0
+            && self
+                .l2_head
+                .read()
+                .await
+                .is_some_and(|head| head.age_secs(chrono::Utc::now()) > max_age)
+    }
+
+    /// Update signer nonce state
+    pub async fn set_nonce_state(&self, snapshot: NonceSnapshot) {
+        *self.nonce.write().await = Some(snapshot);
+    }
+
+    /// Record an anchored batch, replacing any earlier record for the same batch
+    pub async fn record_anchor(&self, record: AnchorRecord) {
+        let mut anchors = self.anchors.write().await;
+        anchors.retain(|existing| existing.batch_id != record.batch_id);
+        if anchors.len() >= Self::MAX_ANCHOR_RECORDS {
+            anchors.remove(0);
+        }
+        anchors.push(record);
+    }
+
+    /// Look up an anchor record by batch id
+    pub async fn get_anchor(&self, batch_id: &Uuid) -> Option<AnchorRecord> {
+        self.anchors
+            .read()
+            .await
+            .iter()
+            .find(|record| &record.batch_id == batch_id)
+            .cloned()
+    }
+
     /// Get error counts
     pub async fn get_error_counts(&self) -> ErrorCounts {
         self.error_counts.read().await.clone()
@@ -184,11 +736,121 @@ pub struct HealthResponse {
 /// Readiness response
 #[derive(Debug, Serialize)]
 pub struct ReadyResponse {
+    /// Not unhealthy
     pub ready: bool,
+    pub status: HealthStatus,
+    pub components: Vec<ComponentHealth>,
     pub l2_connected: bool,
     pub sequencer_connected: bool,
     pub last_l2_check_secs_ago: Option<u64>,
     pub last_sequencer_check_secs_ago: Option<u64>,
+    /// Submissions stopped until the gas budget window resets
+    pub budget_exhausted: bool,
+    pub gas_budget: Option<BudgetStatus>,
+    /// Calls to a dependency with an open breaker are skipped until it half-opens
+    pub circuit_breakers: CircuitBreakersResponse,
+    /// Ready, but a component needs operator attention (e.g. low balance)
+    pub degraded: bool,
+    pub wallet: Option<WalletStatus>,
+    pub l2_head_block: Option<u64>,
+    pub l2_head_age_secs: Option<u64>,
+    /// Authorization, code presence and strict mode at the last re-verification
+    pub registry: Option<RegistryStatus>,
+}
+
+/// Circuit breaker state per dependency
+#[derive(Debug, Serialize)]
+pub struct CircuitBreakersResponse {
+    pub sequencer_api: String,
+    pub l2_rpc: String,
+}
+
+/// Whether a dependency check succeeded within the last 60 seconds
+fn checked_recently(last_check: Option<Instant>) -> bool {
+    last_check.is_some_and(|t| t.elapsed().as_secs() < 60)
+}
+
+/// Health of a component or of the whole service, best first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Healthy,
+    /// Still serving, but needs operator attention
+    Degraded,
+    /// Not able to anchor; `/ready` returns 503
+    Unhealthy,
+}
+
+impl HealthStatus {
+    /// Metric representation for Prometheus (0=healthy, 1=degraded, 2=unhealthy)
+    pub fn as_metric(&self) -> i64 {
+        match self {
+            HealthStatus::Healthy => 0,
+            HealthStatus::Degraded => 1,
+            HealthStatus::Unhealthy => 2,
+        }
+    }
+}
+
+/// Status of one component and what brought it down
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentHealth {
+    pub component: &'static str,
+    pub status: HealthStatus,
+    pub reasons: Vec<String>,
+}
+
+impl ComponentHealth {
+    fn new(component: &'static str) -> Self {
+        Self {
+            component,
+            status: HealthStatus::Healthy,
+            reasons: Vec::new(),
+        }
+    }
+
+    fn degrade(&mut self, reason: impl Into<String>) {
+        self.report(HealthStatus::Degraded, reason.into());
+    }
+
+    fn fail(&mut self, reason: impl Into<String>) {
+        self.report(HealthStatus::Unhealthy, reason.into());
+    }
+
+    /// An open breaker stops calls to the dependency; a half-open one is still probing it
+    fn breaker(&mut self, state: CircuitBreakerState) {
+        match state {
+            CircuitBreakerState::Open => self.fail("circuit breaker open"),
+            CircuitBreakerState::HalfOpen => self.degrade("circuit breaker half-open"),
+            CircuitBreakerState::Closed => {}
+        }
+    }
+
+    fn report(&mut self, status: HealthStatus, reason: String) {
+        self.status = self.status.max(status);
+        self.reasons.push(reason);
+    }
+}
+
+/// Component statuses behind `/ready`
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    /// Worst component status
+    pub status: HealthStatus,
+    pub components: Vec<ComponentHealth>,
+}
+
+impl HealthReport {
+    fn new(components: Vec<ComponentHealth>) -> Self {
+        Self {
+            status: components
+                .iter()
+                .map(|component| component.status)
+                .max()
+                .unwrap_or(HealthStatus::Healthy),
+            components,
+        }
+    }
 }
 
 /// Stats response
@@ -197,7 +859,10 @@ pub struct StatsResponse {
     pub total_anchored: u64,
     pub total_failed: u64,
     pub total_events_anchored: u64,
+    /// Over the process lifetime
     pub success_rate: f64,
+    /// Over the last 5 minutes, hour and day
+    pub success_rate_windows: Vec<WindowSuccessRate>,
     pub successful_cycles: u64,
     pub failed_cycles: u64,
     pub cycle_success_rate: f64,
@@ -215,6 +880,83 @@ pub struct StatsResponse {
     pub uptime_secs: u64,
     pub circuit_breaker_state: String,
     pub circuit_breaker_open_skips: u64,
+    pub sequencer_breaker_state: String,
+    pub l2_breaker_state: String,
+    pub fee_bumps: u64,
+    pub stuck_txs: u64,
+    pub deferred_batches: u64,
+    pub oldest_deferred_secs: u64,
+    pub deferral_overrides: u64,
+    pub gas_estimated_total: u64,
+    pub gas_used_total: u64,
+    pub last_gas_estimate: Option<u64>,
+    pub last_gas_used: Option<u64>,
+    pub gas_used_to_estimate_ratio: Option<f64>,
+    /// Gas used by every mined anchor transaction
+    pub total_gas_used: u64,
+    /// Fees paid for anchor and batch data transactions, including reverted ones
+    pub total_wei_spent: String,
+    /// Mean time from broadcast to a mined receipt
+    pub avg_confirmation_latency_ms: u64,
+    /// Fetched commitments not submitted, summed over cycles, by `/pending` status
+    pub skipped_commitments: BTreeMap<&'static str, u64>,
+    /// The same for the last cycle only
+    pub last_cycle_skips: BTreeMap<&'static str, u64>,
+    pub nonce: Option<NonceSnapshot>,
+    /// Commit-to-anchor percentiles over the last hour and day
+    pub time_to_anchor: Vec<WindowPercentiles>,
+    pub sla_target_secs: Option<u64>,
+    pub sla_violations: u64,
+    /// Latest batches anchored later than the SLA target, newest first
+    pub recent_sla_violations: Vec<TimeToAnchor>,
+    /// Tenants with the most failures first
+    pub per_tenant: Vec<TenantStatsEntry>,
+}
+
+/// Anchor outcomes and gas spend for one tenant
+#[derive(Debug, Serialize)]
+pub struct TenantStatsEntry {
+    pub tenant_id: String,
+    pub batches_anchored: u64,
+    pub events_anchored: u64,
+    pub batches_failed: u64,
+    pub success_rate: f64,
+    /// Across all the tenant's stores, including reverted attempts
+    pub wei_spent: String,
+}
+
+/// Per-tenant stats, merging outcomes with the spend attributed in `costs`
+fn per_tenant_stats(stats: &AnchorStats) -> Vec<TenantStatsEntry> {
+    let mut tenants: HashMap<Uuid, (TenantStats, u128)> = stats
+        .tenants
+        .iter()
+        .map(|(tenant_id, tenant)| (*tenant_id, (*tenant, 0)))
+        .collect();
+    for ((tenant_id, _), totals) in &stats.costs {
+        let (_, wei_spent) = tenants.entry(*tenant_id).or_default();
+        *wei_spent = wei_spent.saturating_add(totals.wei_spent);
+    }
+    let mut entries: Vec<(Uuid, TenantStats, u128)> = tenants
+        .into_iter()
+        .map(|(tenant_id, (tenant, wei_spent))| (tenant_id, tenant, wei_spent))
+        .collect();
+    entries.sort_by(|a, b| {
+        b.1.batches_failed
+            .cmp(&a.1.batches_failed)
+            .then(b.1.batches_anchored.cmp(&a.1.batches_anchored))
+            .then(a.0.cmp(&b.0))
+    });
+    entries
+        .into_iter()
+        .map(|(tenant_id, tenant, wei_spent)| TenantStatsEntry {
+            tenant_id: tenant_id.to_string(),
+            batches_anchored: tenant.batches_anchored,
+            events_anchored: tenant.events_anchored,
+            batches_failed: tenant.batches_failed,
+            success_rate: tenant.success_rate(),
+            wei_spent: wei_spent.to_string(),
+        })
+        .collect()
 }
 
 /// Errors response
@@ -224,6 +966,164 @@ pub struct ErrorsResponse {
     pub recent_errors: Vec<ErrorRecord>,
 }
 
+/// Calldata response for an anchored batch
+#[derive(Debug, Serialize)]
+pub struct CalldataResponse {
+    pub batch_id: String,
+    pub tx_hash: String,
+    pub chain_id: u64,
+    pub block_number: u64,
+    pub batch_id_scheme: BatchIdScheme,
+    pub chain_batch_id: String,
+    pub calldata: String,
+    pub decoded: Option<DecodedCommitBatch>,
+    pub decode_error: Option<String>,
+}
+
+/// On-chain commitment of a batch
+#[derive(Debug, Serialize)]
+pub struct BatchResponse {
+    pub batch_id: String,
+    pub chain_id: u64,
+    pub batch_id_scheme: BatchIdScheme,
+    pub chain_batch_id: String,
+    pub events_root: String,
+    pub new_state_root: String,
+    pub sequence_start: u64,
+    pub sequence_end: u64,
+    pub event_count: u32,
+    /// Block timestamp of the commit
+    pub committed_at: Option<DateTime<Utc>>,
+    /// Transaction and block of the `BatchCommitted` log, if it was found
+    pub tx_hash: Option<String>,
+    pub block_number: Option<u64>,
+    pub tenant_store_key: Option<String>,
+    /// Whether this instance anchored the batch since it started
+    pub anchored_by_this_service: bool,
+}
+
+/// Gas spend for one tenant/store, or the total across all of them
+#[derive(Debug, Serialize)]
+pub struct CostEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store_id: Option<String>,
+    pub batches: u64,
+    pub events: u64,
+    pub wei_spent: String,
+    pub cost_per_batch_wei: String,
+    pub cost_per_event_wei: String,
+    pub da_bytes: u64,
+    pub da_wei_spent: String,
+    pub cost_per_da_byte_wei: String,
+}
+
+impl CostEntry {
+    fn new(ids: Option<(Uuid, Uuid)>, totals: &CostTotals) -> Self {
+        Self {
+            tenant_id: ids.map(|(tenant_id, _)| tenant_id.to_string()),
+            store_id: ids.map(|(_, store_id)| store_id.to_string()),
+            batches: totals.batches,
+            events: totals.events,
+            wei_spent: totals.wei_spent.to_string(),
+            cost_per_batch_wei: totals.cost_per_batch_wei().to_string(),
+            cost_per_event_wei: totals.cost_per_event_wei().to_string(),
+            da_bytes: totals.da_bytes,
+            da_wei_spent: totals.da_wei_spent.to_string(),
+            cost_per_da_byte_wei: totals.cost_per_da_byte_wei().to_string(),
+        }
+    }
+}
+
+/// Costs response, tenants ordered by spend (highest first)
+#[derive(Debug, Serialize)]
+pub struct CostsResponse {
+    pub total: CostEntry,
+    pub tenants: Vec<CostEntry>,
+}
+
+/// Sequencer API schema versions this build can consume
+pub const SUPPORTED_SCHEMA_VERSIONS: &[&str] = &["v1"];
+
+/// Features enabled on this instance, for orchestration tooling and the sequencer
+#[derive(Debug, Serialize)]
+pub struct CapabilitiesResponse {
+    pub version: &'static str,
+    pub schema_versions: &'static [&'static str],
+    /// Several commitments per transaction via Multicall3
+    pub aggregation: bool,
+    pub max_batches_per_tx: u32,
+    pub max_concurrent_anchors: u32,
+    pub claims: bool,
+    pub webhooks: bool,
+    pub finality_tracking: bool,
+    pub batch_id_scheme: BatchIdScheme,
+    pub fee_strategy: String,
+    pub gas_oracle: bool,
+    pub max_gas_price_gwei: Option<u64>,
+    pub max_defer_secs: Option<u64>,
+    pub standby: bool,
+    pub admin_endpoints: bool,
+    pub shard_index: u32,
+    pub shard_count: u32,
+    pub anchor_schedule: Option<String>,
+}
+
+impl CapabilitiesResponse {
+    pub fn from_config(config: &AnchorConfig) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            schema_versions: SUPPORTED_SCHEMA_VERSIONS,
+            aggregation: config.max_batches_per_tx > 1 && config.multicall_address.is_some(),
+            max_batches_per_tx: config.max_batches_per_tx,
+            max_concurrent_anchors: config.max_concurrent_anchors,
+            claims: false,
+            webhooks: false,
+            finality_tracking: false,
+            batch_id_scheme: config.batch_id_scheme,
+            fee_strategy: config.fee_strategy.clone(),
+            gas_oracle: config.gas_oracle_url.is_some(),
+            max_gas_price_gwei: (config.max_gas_price_gwei > 0)
+                .then_some(config.max_gas_price_gwei),
+            max_defer_secs: (config.max_defer_secs > 0).then_some(config.max_defer_secs),
+            standby: config.standby_mode,
+            admin_endpoints: config.admin_token.is_some(),
+            shard_index: config.shard_index,
+            shard_count: config.shard_count,
+            anchor_schedule: config.anchor_schedule.clone(),
+        }
+    }
+}
+
+/// Build metadata embedded by `build.rs`
+#[derive(Debug, Serialize)]
+pub struct VersionResponse {
+    pub version: &'static str,
+    /// Commit the binary was built from, or `unknown`
+    pub git_sha: &'static str,
+    pub build_timestamp: Option<DateTime<Utc>>,
+    /// Cargo features compiled in
+    pub features: Vec<&'static str>,
+}
+
+impl VersionResponse {
+    pub fn current() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            git_sha: env!("SET_ANCHOR_GIT_SHA"),
+            build_timestamp: env!("SET_ANCHOR_BUILD_TIMESTAMP")
+                .parse()
+                .ok()
+                .and_then(|secs| DateTime::from_timestamp(secs, 0)),
+            features: env!("SET_ANCHOR_FEATURES")
+                .split(',')
+                .filter(|feature| !feature.is_empty())
+                .collect(),
+        }
+    }
+}
+
 /// Health check handler - liveness probe
 async fn health_handler(State(state): State<Arc<HealthState>>) -> Json<HealthResponse> {
     Json(HealthResponse {
@@ -233,25 +1133,61 @@ async fn health_handler(State(state): State<Arc<HealthState>>) -> Json<HealthRes
     })
 }
 
+/// Startup check handler - startup probe
+///
+/// 503 until initialization completes, then 200 for the life of the process;
+/// readiness afterwards is `/ready`'s concern.
+async fn startup_handler(State(state): State<Arc<HealthState>>) -> Response {
+    let startup = state.startup.read().await.clone();
+    let status = if startup.started {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(startup)).into_response()
+}
+
 /// Readiness check handler - readiness probe
 async fn ready_handler(State(state): State<Arc<HealthState>>) -> Response {
-    let is_ready = *state.is_ready.read().await;
-
-    let last_l2 = state.last_l2_check.read().await;
-    let last_seq = state.last_sequencer_check.read().await;
-
-    // Consider healthy if checked within last 60 seconds
-    let l2_healthy = last_l2.map(|t| t.elapsed().as_secs() < 60).unwrap_or(false);
-    let seq_healthy = last_seq
-        .map(|t| t.elapsed().as_secs() < 60)
-        .unwrap_or(false);
+    let report = state.health_report().await;
+    let last_l2 = *state.last_l2_check.read().await;
+    let last_seq = *state.last_sequencer_check.read().await;
+    let l2_head = *state.l2_head.read().await;
+
+    let gas_budget = state
+        .budget
+        .read()
+        .await
+        .as_ref()
+        .map(|budget| budget.status(chrono::Utc::now()));
+    let budget_exhausted = gas_budget.as_ref().is_some_and(|status| status.exhausted);
+
+    let (sequencer_breaker, l2_breaker) = {
+        let stats = state.stats.read().await;
+        (stats.sequencer_breaker_state, stats.l2_breaker_state)
+    };
+    let wallet = state.wallet.read().await.clone();
+    let registry = state.registry.read().await.clone();
 
     let response = ReadyResponse {
-        ready: is_ready && l2_healthy && seq_healthy,
-        l2_connected: l2_healthy,
-        sequencer_connected: seq_healthy,
+        ready: report.status != HealthStatus::Unhealthy,
+        status: report.status,
+        components: report.components,
+        l2_connected: state.l2_connected().await,
+        sequencer_connected: state.sequencer_connected().await,
         last_l2_check_secs_ago: last_l2.map(|t| t.elapsed().as_secs()),
         last_sequencer_check_secs_ago: last_seq.map(|t| t.elapsed().as_secs()),
+        budget_exhausted,
+        gas_budget,
+        circuit_breakers: CircuitBreakersResponse {
+            sequencer_api: sequencer_breaker.as_str().to_string(),
+            l2_rpc: l2_breaker.as_str().to_string(),
+        },
+        degraded: report.status == HealthStatus::Degraded,
+        wallet,
+        l2_head_block: l2_head.map(|head| head.block_number),
+        l2_head_age_secs: l2_head.map(|head| head.age_secs(chrono::Utc::now())),
+        registry,
     };
 
     if response.ready {
@@ -262,148 +1198,117 @@ async fn ready_handler(State(state): State<Arc<HealthState>>) -> Response {
 }
 
 /// Metrics handler - Prometheus format
+///
+/// Counters are advanced where their events happen (the stats recorder, the
+/// anchor loop and `HealthState::record_error`), not copied from `AnchorStats`;
+/// a scrape only samples the current state into gauges and renders the shared
+/// registry.
 async fn metrics_handler(State(state): State<Arc<HealthState>>) -> String {
     let stats = state.stats.read().await;
-    let error_counts = state.error_counts.read().await;
     let uptime = state.start_time.elapsed().as_secs();
-    let last_l2 = state.last_l2_check.read().await;
-    let last_seq = state.last_sequencer_check.read().await;
-
-    let success_rate = stats.anchor_success_rate();
-    let cycle_success_rate = stats.cycle_success_rate();
-
-    let is_ready = *state.is_ready.read().await;
-    let l2_healthy = last_l2.map(|t| t.elapsed().as_secs() < 60).unwrap_or(false);
-    let seq_healthy = last_seq
-        .map(|t| t.elapsed().as_secs() < 60)
-        .unwrap_or(false);
-    let is_ready = if is_ready && l2_healthy && seq_healthy {
-        1
-    } else {
-        0
+    let report = state.health_report().await;
+    let l2_head = *state.l2_head.read().await;
+    let l2_healthy = state.l2_connected().await;
+    let seq_healthy = state.sequencer_connected().await;
+    let now = chrono::Utc::now();
+    let (budget_exhausted, gas_spent_wei, gas_budget_wei) = match *state.budget.read().await {
+        Some(ref budget) => (
+            budget.is_exhausted(now),
+            budget.spent_wei(now),
+            budget.limit_wei(),
+        ),
+        None => (false, 0, 0),
     };
-    let l2_connected = if l2_healthy { 1 } else { 0 };
-    let sequencer_connected = if seq_healthy { 1 } else { 0 };
-
-    let total_errors = error_counts.config_errors
-        + error_counts.l2_connection_errors
-        + error_counts.sequencer_api_errors
-        + error_counts.transaction_errors
-        + error_counts.authorization_errors
-        + error_counts.internal_errors;
-    let circuit_breaker_state = stats.circuit_breaker_state.as_metric();
-
-    format!(
-        r#"# HELP set_anchor_batches_total Total number of batches processed
-# TYPE set_anchor_batches_total counter
-set_anchor_batches_total{{status="success"}} {}
-set_anchor_batches_total{{status="failed"}} {}
-
-# HELP set_anchor_events_total Total number of events anchored
-# TYPE set_anchor_events_total counter
-set_anchor_events_total {}
-
-# HELP set_anchor_gas_price_skips_total Total number of gas price skips
-# TYPE set_anchor_gas_price_skips_total counter
-set_anchor_gas_price_skips_total {}
-
-# HELP set_anchor_consecutive_failures Consecutive failed anchors
-# TYPE set_anchor_consecutive_failures gauge
-set_anchor_consecutive_failures {}
-
-# HELP set_anchor_avg_anchor_time_ms Average anchor time in milliseconds
-# TYPE set_anchor_avg_anchor_time_ms gauge
-set_anchor_avg_anchor_time_ms {}
-
-# HELP set_anchor_cycles_total Total anchor cycles completed
-# TYPE set_anchor_cycles_total counter
-set_anchor_cycles_total {}
-
-# HELP set_anchor_cycles_by_status_total Anchor cycles grouped by outcome
-# TYPE set_anchor_cycles_by_status_total counter
-set_anchor_cycles_by_status_total{{status="success"}} {}
-set_anchor_cycles_by_status_total{{status="failed"}} {}
-
-# HELP set_anchor_l2_connected Whether L2 is reachable
-# TYPE set_anchor_l2_connected gauge
-set_anchor_l2_connected {}
-
-# HELP set_anchor_sequencer_connected Whether the sequencer API is reachable
-# TYPE set_anchor_sequencer_connected gauge
-set_anchor_sequencer_connected {}
-
-# HELP set_anchor_l2_connection_failures_total Total L2 connection failures
-# TYPE set_anchor_l2_connection_failures_total counter
-set_anchor_l2_connection_failures_total {}
-
-# HELP set_anchor_sequencer_api_failures_total Total sequencer API failures
-# TYPE set_anchor_sequencer_api_failures_total counter
-set_anchor_sequencer_api_failures_total {}
-
-# HELP set_anchor_success_rate Ratio of successful anchors
-# TYPE set_anchor_success_rate gauge
-set_anchor_success_rate {}
-
-# HELP set_anchor_cycle_success_rate Ratio of successful cycles
-# TYPE set_anchor_cycle_success_rate gauge
-set_anchor_cycle_success_rate {}
-
-# HELP set_anchor_uptime_seconds Service uptime in seconds
-# TYPE set_anchor_uptime_seconds gauge
-set_anchor_uptime_seconds {}
-
-# HELP set_anchor_ready Whether the service is ready
-# TYPE set_anchor_ready gauge
-set_anchor_ready {}
-
-# HELP set_anchor_errors_total Total errors by category
-# TYPE set_anchor_errors_total counter
-set_anchor_errors_total{{category="config"}} {}
-set_anchor_errors_total{{category="l2_connection"}} {}
-set_anchor_errors_total{{category="sequencer_api"}} {}
-set_anchor_errors_total{{category="transaction"}} {}
-set_anchor_errors_total{{category="authorization"}} {}
-set_anchor_errors_total{{category="internal"}} {}
-
-# HELP set_anchor_errors_total_sum Sum of all errors
-# TYPE set_anchor_errors_total_sum counter
-set_anchor_errors_total_sum {}
-
-# HELP set_anchor_circuit_breaker_state Circuit breaker state (0=closed, 1=half-open, 2=open)
-# TYPE set_anchor_circuit_breaker_state gauge
-set_anchor_circuit_breaker_state {}
-
-# HELP set_anchor_circuit_breaker_open_skips_total Total cycles skipped due to open circuit breaker
-# TYPE set_anchor_circuit_breaker_open_skips_total counter
-set_anchor_circuit_breaker_open_skips_total {}
-"#,
-        stats.total_anchored,
-        stats.total_failed,
-        stats.total_events_anchored,
-        stats.gas_price_skips,
-        stats.consecutive_failures,
-        stats.avg_anchor_time_ms,
-        stats.total_cycles,
-        stats.successful_cycles,
-        stats.failed_cycles,
-        l2_connected,
-        sequencer_connected,
-        stats.l2_connection_failures,
-        stats.sequencer_api_failures,
-        success_rate,
-        cycle_success_rate,
-        uptime,
-        is_ready,
-        error_counts.config_errors,
-        error_counts.l2_connection_errors,
-        error_counts.sequencer_api_errors,
-        error_counts.transaction_errors,
-        error_counts.authorization_errors,
-        error_counts.internal_errors,
-        total_errors,
-        circuit_breaker_state,
-        stats.circuit_breaker_open_skips,
-    )
+
+    let standby = state.standby.read().await.clone();
+    let paused = state.pause.read().await.paused;
+    let registry_paused = state
+        .registry
+        .read()
+        .await
+        .as_ref()
+        .is_some_and(|registry| registry.paused);
+    let wallet = state.wallet.read().await.clone();
+
+    let m = &state.metrics;
+    m.consecutive_failures
+        .set(gauge_value(stats.consecutive_failures));
+    m.avg_anchor_time_ms
+        .set(gauge_value(stats.avg_anchor_time_ms));
+    m.l2_connected.set(i64::from(l2_healthy));
+    m.sequencer_connected.set(i64::from(seq_healthy));
+    m.success_rate.set(stats.anchor_success_rate());
+    for window in stats.success_windows.rates(now) {
+        m.windowed_success_rate
+            .with_label_values(&[window.window])
+            .set(window.success_rate);
+    }
+    m.cycle_success_rate.set(stats.cycle_success_rate());
+    m.uptime_seconds.set(gauge_value(uptime));
+    m.ready
+        .set(i64::from(report.status != HealthStatus::Unhealthy));
+    m.health_status.set(report.status.as_metric());
+    for component in &report.components {
+        m.component_health_status
+            .with_label_values(&[component.component])
+            .set(component.status.as_metric());
+    }
+    m.circuit_breaker_state
+        .set(gauge_value(stats.circuit_breaker_state.as_metric()));
+    m.dependency_circuit_breaker_state
+        .with_label_values(&["sequencer_api"])
+        .set(gauge_value(stats.sequencer_breaker_state.as_metric()));
+    m.dependency_circuit_breaker_state
+        .with_label_values(&["l2_rpc"])
+        .set(gauge_value(stats.l2_breaker_state.as_metric()));
+    m.deferred_batches.set(gauge_value(stats.deferred_batches));
+    m.oldest_deferred_seconds
+        .set(gauge_value(stats.oldest_deferred_secs));
+    m.wei_spent.set(stats.total_wei_spent as f64);
+    m.avg_confirmation_latency_ms
+        .set(gauge_value(stats.avg_confirmation_latency_ms()));
+    // Reasons the last cycle did not see drop out rather than keep an old count
+    m.cycle_commitments_skipped.reset();
+    for (reason, count) in &stats.last_cycle_skips {
+        m.cycle_commitments_skipped
+            .with_label_values(&[reason])
+            .set(gauge_value(*count));
+    }
+    for window in stats.time_to_anchor.percentiles(now) {
+        for (quantile, secs) in [
+            ("0.5", window.p50_secs),
+            ("0.95", window.p95_secs),
+            ("0.99", window.p99_secs),
+        ] {
+            m.time_to_anchor_quantile
+                .with_label_values(&[window.window, quantile])
+                .set(secs as f64);
+        }
+    }
+    m.standby.set(i64::from(standby.standby));
+    m.takeover_ready.set(i64::from(standby.takeover_ready));
+    m.paused.set(i64::from(paused));
+    m.registry_paused.set(i64::from(registry_paused));
+    m.gas_spent_wei.set(gas_spent_wei as f64);
+    m.gas_budget_wei.set(gas_budget_wei as f64);
+    m.budget_exhausted.set(i64::from(budget_exhausted));
+    if let Some(wallet) = wallet {
+        m.wallet_balance_wei
+            .set(wallet.balance_wei.parse().unwrap_or_default());
+        m.wallet_balance_low.set(i64::from(wallet.low));
+    }
+    if let Some(head) = l2_head {
+        m.l2_head_block.set(gauge_value(head.block_number));
+        m.l2_head_age_seconds
+            .set(gauge_value(head.age_secs(chrono::Utc::now())));
+    }
+
+    m.render()
+}
+
+/// Clamp a stats value into an integer gauge
+fn gauge_value(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
 }
 
 /// Errors handler - error statistics
@@ -417,44 +1322,613 @@ async fn errors_handler(State(state): State<Arc<HealthState>>) -> Json<ErrorsRes
     })
 }
 
-/// Stats handler - JSON statistics
-async fn stats_handler(State(state): State<Arc<HealthState>>) -> Json<StatsResponse> {
-    let stats = state.stats.read().await;
-    let uptime = state.start_time.elapsed().as_secs();
+/// Standby handler - warm-standby pre-flight status
+async fn standby_handler(State(state): State<Arc<HealthState>>) -> Json<StandbyStatus> {
+    Json(state.standby.read().await.clone())
+}
 
-    Json(StatsResponse {
-        total_anchored: stats.total_anchored,
-        total_failed: stats.total_failed,
-        total_events_anchored: stats.total_events_anchored,
-        success_rate: stats.anchor_success_rate(),
-        successful_cycles: stats.successful_cycles,
-        failed_cycles: stats.failed_cycles,
-        cycle_success_rate: stats.cycle_success_rate(),
-        last_anchor_time: stats.last_anchor_time.map(|t| t.to_rfc3339()),
-        last_batch_id: stats.last_batch_id.map(|id| id.to_string()),
-        consecutive_failures: stats.consecutive_failures,
-        l2_connection_failures: stats.l2_connection_failures,
-        sequencer_api_failures: stats.sequencer_api_failures,
-        gas_price_skips: stats.gas_price_skips,
-        avg_anchor_time_ms: stats.avg_anchor_time_ms,
-        last_l2_healthy: stats.last_l2_healthy.map(|t| t.to_rfc3339()),
-        last_sequencer_healthy: stats.last_sequencer_healthy.map(|t| t.to_rfc3339()),
-        total_cycles: stats.total_cycles,
-        service_started: stats.service_started.map(|t| t.to_rfc3339()),
-        uptime_secs: uptime,
-        circuit_breaker_state: stats.circuit_breaker_state.as_str().to_string(),
-        circuit_breaker_open_skips: stats.circuit_breaker_open_skips,
-    })
+/// A fetched batch with its age at the time of the request
+#[derive(Debug, Serialize)]
+pub struct PendingEntry {
+    #[serde(flatten)]
+    pub batch: PendingBatch,
+    /// Seconds since the sequencer committed the batch
+    pub age_secs: i64,
 }
 
-/// Create the health server router
-pub fn create_router(state: Arc<HealthState>) -> Router {
+/// Current queue as of the last cycle
+#[derive(Debug, Serialize)]
+pub struct PendingResponse {
+    pub fetched_at: Option<DateTime<Utc>>,
+    pub total: usize,
+    pub planned: usize,
+    /// Oldest first
+    pub batches: Vec<PendingEntry>,
+}
+
+/// Pending handler - what the last cycle fetched and what it did with each batch
+async fn pending_handler(State(state): State<Arc<HealthState>>) -> Json<PendingResponse> {
+    let Some(queue) = state.pending.read().await.clone() else {
+        return Json(PendingResponse {
+            fetched_at: None,
+            total: 0,
+            planned: 0,
+            batches: Vec::new(),
+        });
+    };
+
+    let now = Utc::now();
+    let mut batches: Vec<PendingEntry> = queue
+        .batches
+        .into_iter()
+        .map(|batch| PendingEntry {
+            age_secs: (now - batch.committed_at).num_seconds().max(0),
+            batch,
+        })
+        .collect();
+    batches.sort_by_key(|entry| entry.batch.committed_at);
+    Json(PendingResponse {
+        fetched_at: Some(queue.fetched_at),
+        total: batches.len(),
+        planned: batches
+            .iter()
+            .filter(|entry| entry.batch.status == PendingStatus::Planned)
+            .count(),
+        batches,
+    })
+}
+
+/// Costs handler - gas spend attributed per tenant/store
+async fn costs_handler(State(state): State<Arc<HealthState>>) -> Json<CostsResponse> {
+    let stats = state.stats.read().await;
+
+    let mut total = CostTotals::default();
+    let mut tenants: Vec<_> = stats.costs.iter().collect();
+    tenants.sort_by(|a, b| b.1.wei_spent.cmp(&a.1.wei_spent));
+    for (_, totals) in &tenants {
+        total.add(totals);
+    }
+
+    Json(CostsResponse {
+        total: CostEntry::new(None, &total),
+        tenants: tenants
+            .into_iter()
+            .map(|(ids, totals)| CostEntry::new(Some(*ids), totals))
+            .collect(),
+    })
+}
+
+/// Version handler - which build is running
+async fn version_handler() -> Json<VersionResponse> {
+    Json(VersionResponse::current())
+}
+
+/// Config handler - effective configuration, credentials redacted
+async fn config_handler(State(state): State<Arc<HealthState>>) -> Response {
+    Json(&state.config).into_response()
+}
+
+/// Capabilities handler - features enabled on this instance
+async fn capabilities_handler(State(state): State<Arc<HealthState>>) -> Json<CapabilitiesResponse> {
+    Json(CapabilitiesResponse::from_config(&state.config))
+}
+
+/// Pause handler - whether submissions are paused and why
+async fn pause_handler(State(state): State<Arc<HealthState>>) -> Json<PauseStatus> {
+    Json(state.pause.read().await.clone())
+}
+
+/// Reject a request that does not carry `ADMIN_TOKEN` as a bearer token
+fn require_admin(state: &HealthState, headers: &HeaderMap, action: &str) -> Option<Response> {
+    let Some(ref token) = state.config.admin_token else {
+        return Some(
+            (
+                StatusCode::FORBIDDEN,
+                Json(serde_json::json!({
+                    "error": format!("{} is disabled; set ADMIN_TOKEN", action)
+                })),
+            )
+                .into_response(),
+        );
+    };
+    let presented = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if !presented.is_some_and(|presented| signing::tokens_match(token, presented)) {
+        return Some(
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({ "error": "invalid admin token" })),
+            )
+                .into_response(),
+        );
+    }
+    None
+}
+
+/// Parse an optional JSON body; an empty body gives the defaults
+fn parse_admin_body<T: serde::de::DeserializeOwned + Default>(
+    body: &Bytes,
+    action: &str,
+) -> Result<T, Response> {
+    if body.is_empty() {
+        return Ok(T::default());
+    }
+    serde_json::from_slice(body).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": format!("invalid {} request: {}", action, e) })),
+        )
+            .into_response()
+    })
+}
+
+/// Body of `POST /admin/pause`
+#[derive(Debug, Default, Deserialize)]
+pub struct PauseRequest {
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// Admin pause handler - operator stops submissions until resumed
+///
+/// The service keeps polling the sequencer and sending queued notifications
+/// while paused. Pausing again keeps the original reason, time and metadata.
+async fn admin_pause_handler(
+    State(state): State<Arc<HealthState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    if let Some(rejection) = require_admin(&state, &headers, "pause") {
+        return rejection;
+    }
+    let request: PauseRequest = match parse_admin_body(&body, "pause") {
+        Ok(request) => request,
+        Err(rejection) => return rejection,
+    };
+
+    let mut pause = state.pause.write().await;
+    if !pause.paused {
+        let reason = request
+            .reason
+            .unwrap_or_else(|| "paused by operator".to_string());
+        warn!(reason = %reason, "Submissions paused by operator");
+        *pause = PauseStatus {
+            paused: true,
+            reason: Some(reason),
+            paused_at: Some(Utc::now().to_rfc3339()),
+            metadata: request.metadata,
+        };
+        // The pause holds in memory either way; only a restart would lose it
+        if let Err(e) = state.journal.record_pause(&pause).await {
+            warn!(error = %e, "Failed to persist pause");
+        }
+    }
+    Json(pause.clone()).into_response()
+}
+
+/// Anchor-now handler - operator starts the next anchor cycle without waiting
+///
+/// Refused while submissions are paused, since the cycle would be skipped.
+async fn anchor_now_handler(State(state): State<Arc<HealthState>>, headers: HeaderMap) -> Response {
+    if let Some(rejection) = require_admin(&state, &headers, "anchor-now") {
+        return rejection;
+    }
+    let pause = state.pause.read().await.clone();
+    if pause.paused {
+        return (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({
+                "error": "submissions are paused; resume first",
+                "reason": pause.reason,
+            })),
+        )
+            .into_response();
+    }
+
+    info!("Anchor cycle requested by operator");
+    state.wake.notify_one();
+    (
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({ "status": "anchor cycle starting" })),
+    )
+        .into_response()
+}
+
+/// Body of `POST /admin/batches/{batch_id}/skip`
+#[derive(Debug, Default, Deserialize)]
+pub struct SkipRequest {
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// Batch retry handler - operator asks for a batch to be tried again now
+///
+/// Lifts a skip, and the next time the batch is fetched its stream's backoff
+/// is cleared and the registry is checked again instead of the anchored cache.
+async fn batch_retry_handler(
+    State(state): State<Arc<HealthState>>,
+    Path(batch_id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Response {
+    if let Some(rejection) = require_admin(&state, &headers, "retry") {
+        return rejection;
+    }
+    let unskipped = match state.journal.unskip(&batch_id).await {
+        Ok(unskipped) => unskipped,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({ "error": format!("{:#}", e) })),
+            )
+                .into_response();
+        }
+    };
+
+    warn!(batch_id = %batch_id, unskipped, "Batch retry requested by operator");
+    state.batch_retries.write().await.insert(batch_id);
+    state.wake.notify_one();
+    (
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({
+            "batch_id": batch_id,
+            "status": "retry requested",
+            "unskipped": unskipped,
+        })),
+    )
+        .into_response()
+}
+
+/// Batch skip handler - operator stops a batch from ever being anchored
+async fn batch_skip_handler(
+    State(state): State<Arc<HealthState>>,
+    Path(batch_id): Path<Uuid>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    if let Some(rejection) = require_admin(&state, &headers, "skip") {
+        return rejection;
+    }
+    let request: SkipRequest = match parse_admin_body(&body, "skip") {
+        Ok(request) => request,
+        Err(rejection) => return rejection,
+    };
+    let reason = request
+        .reason
+        .unwrap_or_else(|| "skipped by operator".to_string());
+
+    match state.journal.skip(batch_id, reason, request.metadata).await {
+        Ok(skipped) => {
+            warn!(batch_id = %batch_id, reason = %skipped.reason, "Batch skipped by operator");
+            state.batch_retries.write().await.remove(&batch_id);
+            Json(skipped).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": format!("{:#}", e) })),
+        )
+            .into_response(),
+    }
+}
+
+/// Resume handler - operator lifts a submission pause
+async fn resume_handler(State(state): State<Arc<HealthState>>, headers: HeaderMap) -> Response {
+    if let Some(rejection) = require_admin(&state, &headers, "resume") {
+        return rejection;
+    }
+
+    let mut pause = state.pause.write().await;
+    if pause.paused {
+        warn!(reason = ?pause.reason, "Submissions resumed by operator");
+    }
+    *pause = PauseStatus::default();
+    if let Err(e) = state.journal.record_pause(&pause).await {
+        warn!(error = %e, "Failed to clear persisted pause; a restart pauses again");
+    }
+    Json(pause.clone()).into_response()
+}
+
+/// Push handler - the sequencer delivers signed commitments to the webhook source
+async fn push_commitments_handler(
+    State(state): State<Arc<HealthState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let Some(ref webhook) = state.webhook else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "error": "commitment webhook is disabled; set COMMITMENT_SOURCE=webhook"
+            })),
+        )
+            .into_response();
+    };
+
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+    if let Err(e) = webhook.verify(
+        header(TIMESTAMP_HEADER),
+        header(SIGNATURE_HEADER),
+        &body,
+        chrono::Utc::now().timestamp(),
+    ) {
+        warn!(error = %e, "Rejected pushed commitments");
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response();
+    }
+
+    let commitments = match webhook::decode(&body) {
+        Ok(commitments) => commitments,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": format!("invalid commitments: {}", e) })),
+            )
+                .into_response();
+        }
+    };
+
+    let received = commitments.len();
+    match webhook.push(commitments).await {
+        Ok(queued) => {
+            debug!(received, queued, "Commitments pushed by sequencer");
+            (
+                StatusCode::ACCEPTED,
+                Json(serde_json::json!({ "received": received, "queued": queued })),
+            )
+                .into_response()
+        }
+        Err(e) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+/// Paging for `GET /history`
+#[derive(Debug, Deserialize)]
+pub struct HistoryQuery {
+    #[serde(default = "default_history_limit")]
+    pub limit: usize,
+    #[serde(default)]
+    pub offset: usize,
+}
+
+fn default_history_limit() -> usize {
+    50
+}
+
+/// Page of recent anchor operations
+#[derive(Debug, Serialize)]
+pub struct HistoryResponse {
+    /// Operations kept in total
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    /// Newest first
+    pub entries: Vec<HistoryEntry>,
+}
+
+/// History handler - recent anchor results, newest first
+async fn history_handler(
+    State(state): State<Arc<HealthState>>,
+    Query(query): Query<HistoryQuery>,
+) -> Json<HistoryResponse> {
+    const MAX_HISTORY_LIMIT: usize = 500;
+
+    let limit = query.limit.min(MAX_HISTORY_LIMIT);
+    let (entries, total) = state.journal.history(query.offset, limit).await;
+    Json(HistoryResponse {
+        total,
+        offset: query.offset,
+        limit,
+        entries,
+    })
+}
+
+/// Calldata handler - raw and decoded commitBatch input for an anchored batch
+async fn anchor_calldata_handler(
+    State(state): State<Arc<HealthState>>,
+    Path(batch_id): Path<Uuid>,
+) -> Response {
+    let Some(record) = state.get_anchor(&batch_id).await else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "batch not anchored by this service" })),
+        )
+            .into_response();
+    };
+    let Some(calldata) = record.calldata else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "calldata not recorded for this batch" })),
+        )
+            .into_response();
+    };
+
+    let (decoded, decode_error) = match hex::decode(calldata.trim_start_matches("0x"))
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| decode_commit_batch(&bytes).map_err(|e| e.to_string()))
+    {
+        Ok(decoded) => (Some(decoded), None),
+        Err(e) => (None, Some(e)),
+    };
+
+    Json(CalldataResponse {
+        batch_id: record.batch_id.to_string(),
+        tx_hash: record.tx_hash,
+        chain_id: record.chain_id,
+        block_number: record.block_number,
+        batch_id_scheme: record.batch_id_scheme,
+        chain_batch_id: record.chain_batch_id,
+        calldata,
+        decoded,
+        decode_error,
+    })
+    .into_response()
+}
+
+/// Batch handler - look up a batch's commitment in the registry
+///
+/// Tries the key recorded when this instance anchored the batch, then every
+/// key derivable from the UUID, configured scheme first.
+async fn batch_handler(
+    State(state): State<Arc<HealthState>>,
+    Path(batch_id): Path<Uuid>,
+) -> Response {
+    let Some(lookup) = state.batch_lookup.read().await.clone() else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({ "error": "registry not connected yet" })),
+        )
+            .into_response();
+    };
+
+    let record = state.get_anchor(&batch_id).await;
+    let mut keys: Vec<(BatchIdScheme, FixedBytes<32>)> = record
+        .as_ref()
+        .and_then(|record| {
+            let key = record.chain_batch_id.parse().ok()?;
+            Some((record.batch_id_scheme, key))
+        })
+        .into_iter()
+        .collect();
+    let mut derived = uuid_lookup_keys(&batch_id).to_vec();
+    derived.sort_by_key(|(scheme, _)| *scheme != state.config.batch_id_scheme);
+    for (scheme, key) in derived {
+        if !keys.iter().any(|(_, known)| *known == key) {
+            keys.push((scheme, key));
+        }
+    }
+
+    for (scheme, key) in keys {
+        let batch = match lookup.committed_batch(key).await {
+            Ok(Some(batch)) => batch,
+            Ok(None) => continue,
+            Err(e) => {
+                return (
+                    StatusCode::BAD_GATEWAY,
+                    Json(serde_json::json!({ "error": e.to_string() })),
+                )
+                    .into_response();
+            }
+        };
+        let commitment = batch.commitment;
+        return Json(BatchResponse {
+            batch_id: batch_id.to_string(),
+            chain_id: lookup.chain_id(),
+            batch_id_scheme: scheme,
+            chain_batch_id: key.to_string(),
+            events_root: commitment.events_root.to_string(),
+            new_state_root: commitment.new_state_root.to_string(),
+            sequence_start: commitment.sequence_start,
+            sequence_end: commitment.sequence_end,
+            event_count: commitment.event_count,
+            committed_at: DateTime::from_timestamp(commitment.timestamp as i64, 0),
+            tx_hash: batch.event.as_ref().map(|event| event.tx_hash.to_string()),
+            block_number: batch.event.as_ref().map(|event| event.block_number),
+            tenant_store_key: batch
+                .event
+                .as_ref()
+                .map(|event| event.tenant_store_key.to_string()),
+            anchored_by_this_service: record.is_some(),
+        })
+        .into_response();
+    }
+
+    (
+        StatusCode::NOT_FOUND,
+        Json(serde_json::json!({ "error": "batch not found in the registry" })),
+    )
+        .into_response()
+}
+
+/// Stats handler - JSON statistics
+async fn stats_handler(State(state): State<Arc<HealthState>>) -> Json<StatsResponse> {
+    let stats = state.stats.read().await;
+    let uptime = state.start_time.elapsed().as_secs();
+
+    Json(StatsResponse {
+        total_anchored: stats.total_anchored,
+        total_failed: stats.total_failed,
+        total_events_anchored: stats.total_events_anchored,
+        success_rate: stats.anchor_success_rate(),
+        success_rate_windows: stats.success_windows.rates(Utc::now()),
+        successful_cycles: stats.successful_cycles,
+        failed_cycles: stats.failed_cycles,
+        cycle_success_rate: stats.cycle_success_rate(),
+        last_anchor_time: stats.last_anchor_time.map(|t| t.to_rfc3339()),
+        last_batch_id: stats.last_batch_id.map(|id| id.to_string()),
+        consecutive_failures: stats.consecutive_failures,
+        l2_connection_failures: stats.l2_connection_failures,
+        sequencer_api_failures: stats.sequencer_api_failures,
+        gas_price_skips: stats.gas_price_skips,
+        avg_anchor_time_ms: stats.avg_anchor_time_ms,
+        last_l2_healthy: stats.last_l2_healthy.map(|t| t.to_rfc3339()),
+        last_sequencer_healthy: stats.last_sequencer_healthy.map(|t| t.to_rfc3339()),
+        total_cycles: stats.total_cycles,
+        service_started: stats.service_started.map(|t| t.to_rfc3339()),
+        uptime_secs: uptime,
+        circuit_breaker_state: stats.circuit_breaker_state.as_str().to_string(),
+        circuit_breaker_open_skips: stats.circuit_breaker_open_skips,
+        sequencer_breaker_state: stats.sequencer_breaker_state.as_str().to_string(),
+        l2_breaker_state: stats.l2_breaker_state.as_str().to_string(),
+        fee_bumps: stats.fee_bumps,
+        stuck_txs: stats.stuck_txs,
+        deferred_batches: stats.deferred_batches,
+        oldest_deferred_secs: stats.oldest_deferred_secs,
+        deferral_overrides: stats.deferral_overrides,
+        gas_estimated_total: stats.gas_estimated_total,
+        gas_used_total: stats.gas_used_total,
+        last_gas_estimate: stats.last_gas_estimate,
+        last_gas_used: stats.last_gas_used,
+        gas_used_to_estimate_ratio: stats.gas_used_to_estimate_ratio(),
+        total_gas_used: stats.total_gas_used,
+        total_wei_spent: stats.total_wei_spent.to_string(),
+        avg_confirmation_latency_ms: stats.avg_confirmation_latency_ms(),
+        skipped_commitments: stats.skipped_commitments.clone().into_iter().collect(),
+        last_cycle_skips: stats.last_cycle_skips.clone().into_iter().collect(),
+        nonce: state.nonce.read().await.clone(),
+        time_to_anchor: stats.time_to_anchor.percentiles(Utc::now()),
+        sla_target_secs: (state.config.sla_target_secs > 0).then_some(state.config.sla_target_secs),
+        sla_violations: stats.time_to_anchor.violations(),
+        recent_sla_violations: stats.time_to_anchor.recent_violations(),
+        per_tenant: per_tenant_stats(&stats),
+    })
+}
+
+/// Create the health server router
+pub fn create_router(state: Arc<HealthState>) -> Router {
     Router::new()
         .route("/health", get(health_handler))
+        .route("/startup", get(startup_handler))
         .route("/ready", get(ready_handler))
         .route("/metrics", get(metrics_handler))
         .route("/stats", get(stats_handler))
         .route("/errors", get(errors_handler))
+        .route("/standby", get(standby_handler))
+        .route("/pending", get(pending_handler))
+        .route("/version", get(version_handler))
+        .route("/config", get(config_handler))
+        .route("/capabilities", get(capabilities_handler))
+        .route("/costs", get(costs_handler))
+        .route("/pause", get(pause_handler))
+        .route("/resume", post(resume_handler))
+        .route("/admin/pause", post(admin_pause_handler))
+        .route("/admin/resume", post(resume_handler))
+        .route("/admin/anchor-now", post(anchor_now_handler))
+        .route("/admin/batches/{batch_id}/retry", post(batch_retry_handler))
+        .route("/admin/batches/{batch_id}/skip", post(batch_skip_handler))
+        .route("/anchors/{batch_id}/calldata", get(anchor_calldata_handler))
+        .route("/batches/{batch_id}", get(batch_handler))
+        .route("/history", get(history_handler))
+        .route("/v1/commitments", post(push_commitments_handler))
         .with_state(state)
 }
 
@@ -481,15 +1955,17 @@ impl HealthServer {
         Arc::clone(&self.state)
     }
 
-    /// Run the health server
-    pub async fn run(&self) -> anyhow::Result<()> {
+    /// Run the health server until `shutdown` is cancelled
+    pub async fn run(&self, shutdown: CancellationToken) -> anyhow::Result<()> {
         let addr = SocketAddr::from(([0, 0, 0, 0], self.port));
         let router = create_router(Arc::clone(&self.state));
 
         info!(port = self.port, "Health server starting");
 
         let listener = tokio::net::TcpListener::bind(addr).await?;
-        axum::serve(listener, router).await?;
+        axum::serve(listener, router)
+            .with_graceful_shutdown(shutdown.cancelled_owned())
+            .await?;
 
         Ok(())
     }
@@ -499,6 +1975,8 @@ impl HealthServer {
 mod tests {
     use super::*;
     use crate::error::{AnchorError, L2Error};
+    use crate::recorder::{StatsEvent, StatsRecorder};
+    use crate::types::SchemaVersion;
     use axum::body::Body;
     use axum::http::Request;
     use tower::util::ServiceExt;
@@ -524,6 +2002,7 @@ mod tests {
             circuit_breaker_reset_timeout_secs: 60,
             circuit_breaker_half_open_success_threshold: 3,
             tx_confirmation_timeout_secs: 60,
+            ..AnchorConfig::default()
         }
     }
 
@@ -620,17 +2099,58 @@ mod tests {
         assert_eq!(json["sequencer_connected"], true);
     }
 
+    #[tokio::test]
+    async fn test_ready_endpoint_not_ready_while_draining() {
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let state = Arc::new(HealthState::new(test_config(), stats));
+        state.set_ready(true).await;
+        state.mark_l2_healthy().await;
+        state.mark_sequencer_healthy().await;
+        state.set_draining().await;
+
+        let router = create_router(state);
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/ready")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["ready"], false);
+        assert_eq!(json["components"][0]["component"], "service");
+        assert_eq!(
+            json["components"][0]["reasons"][0],
+            "shutting down; finishing in-flight anchors"
+        );
+    }
+
     #[tokio::test]
     async fn test_metrics_endpoint() {
-        let stats = Arc::new(RwLock::new(AnchorStats {
-            total_anchored: 10,
-            total_failed: 2,
-            total_events_anchored: 500,
-            last_anchor_time: None,
-            last_batch_id: None,
-            ..AnchorStats::default()
-        }));
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
         let state = Arc::new(HealthState::new(test_config(), stats));
+        let recorder = StatsRecorder::new(&state.stats).with_metrics(state.metrics.clone());
+        for _ in 0..10 {
+            recorder.record(StatsEvent::AnchorSucceeded {
+                batch_id: Uuid::new_v4(),
+                tenant_id: Uuid::new_v4(),
+                event_count: 50,
+                anchor_time_ms: 100,
+            });
+        }
+        for _ in 0..2 {
+            recorder.record(StatsEvent::AnchorFailed {
+                tenant_id: Uuid::new_v4(),
+            });
+        }
+        recorder.flush().await;
         let router = create_router(state);
 
         let response = router
@@ -680,55 +2200,1327 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_errors_endpoint() {
-        let stats = Arc::new(RwLock::new(AnchorStats::default()));
-        let state = Arc::new(HealthState::new(test_config(), stats));
+    async fn test_stats_report_time_to_anchor() {
+        let now = Utc::now();
+        let mut config = test_config();
+        config.sla_target_secs = 600;
+        let state = Arc::new(HealthState::new(
+            config,
+            Arc::new(RwLock::new(AnchorStats::default())),
+        ));
+        let recorder = StatsRecorder::new(&state.stats).with_metrics(state.metrics.clone());
+        for secs in [60, 120, 900] {
+            recorder.record(StatsEvent::TimeToAnchor {
+                sample: TimeToAnchor::new(
+                    Uuid::new_v4(),
+                    Uuid::new_v4(),
+                    Uuid::new_v4(),
+                    now - chrono::Duration::seconds(secs),
+                    now,
+                ),
+                sla_target_secs: 600,
+            });
+        }
+        recorder.flush().await;
+        let router = create_router(state);
 
-        // Record some errors
-        let error = AnchorError::L2Connection(L2Error::Timeout { seconds: 30 });
-        state.record_error(&error).await;
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/stats")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["sla_target_secs"], 600);
+        assert_eq!(json["sla_violations"], 1);
+        assert_eq!(json["recent_sla_violations"][0]["secs"], 900);
+        assert_eq!(json["time_to_anchor"][0]["window"], "1h");
+        assert_eq!(json["time_to_anchor"][0]["count"], 3);
+        assert_eq!(json["time_to_anchor"][0]["p50_secs"], 120);
+        assert_eq!(json["time_to_anchor"][0]["p99_secs"], 900);
+
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/metrics")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_str = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body_str.contains("set_anchor_sla_violations_total 1"));
+        assert!(body_str.contains(
+            "set_anchor_time_to_anchor_quantile_seconds{quantile=\"0.95\",window=\"24h\"} 900"
+        ));
+    }
 
+    #[tokio::test]
+    async fn test_stats_report_windowed_success_rate() {
+        let mut stats = AnchorStats::default();
+        for _ in 0..3 {
+            stats.record_success(100);
+        }
+        stats.record_anchor_failure();
+        let state = Arc::new(HealthState::new(
+            test_config(),
+            Arc::new(RwLock::new(stats)),
+        ));
         let router = create_router(state);
 
         let response = router
+            .clone()
             .oneshot(
                 Request::builder()
-                    .uri("/errors")
+                    .uri("/stats")
                     .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let windows = json["success_rate_windows"].as_array().unwrap();
+        assert_eq!(windows.len(), 3);
+        assert_eq!(windows[0]["window"], "5m");
+        assert_eq!(windows[0]["anchored"], 3);
+        assert_eq!(windows[0]["failed"], 1);
+        assert_eq!(windows[2]["success_rate"], 0.75);
 
-        assert_eq!(response.status(), StatusCode::OK);
-
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/metrics")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
         let body = axum::body::to_bytes(response.into_body(), usize::MAX)
             .await
             .unwrap();
         let body_str = String::from_utf8(body.to_vec()).unwrap();
-
-        assert!(body_str.contains("l2_connection_errors"));
+        assert!(body_str.contains("set_anchor_windowed_success_rate{window=\"1h\"} 0.75"));
     }
 
     #[tokio::test]
-    async fn test_record_error() {
-        let stats = Arc::new(RwLock::new(AnchorStats::default()));
-        let state = Arc::new(HealthState::new(test_config(), stats));
-
-        let error = AnchorError::L2Connection(L2Error::ConnectionFailed {
-            url: "http://localhost:8547".to_string(),
-            message: "Connection refused".to_string(),
+    async fn test_stats_report_gas_latency_and_skips() {
+        let stats = AnchorStats {
+            total_wei_spent: 12_345_678_901_234_567_890,
+            ..AnchorStats::default()
+        };
+        let state = Arc::new(HealthState::new(
+            test_config(),
+            Arc::new(RwLock::new(stats)),
+        ));
+        let recorder = StatsRecorder::new(&state.stats).with_metrics(state.metrics.clone());
+        recorder.record(StatsEvent::Confirmed {
+            latency_ms: 1_500,
+            gas_used: 80_000,
         });
+        recorder.record(StatsEvent::Confirmed {
+            latency_ms: 500,
+            gas_used: 70_000,
+        });
+        recorder.record(StatsEvent::Skipped(vec![("ordering_hold", 4)]));
+        recorder.flush().await;
+        let router = create_router(state);
 
-        state.record_error(&error).await;
-
-        let counts = state.get_error_counts().await;
-        assert_eq!(counts.l2_connection_errors, 1);
-        assert!(counts.last_error_code.is_some());
-        assert_eq!(counts.last_error_code.unwrap(), "L2_CONNECTION_ERROR");
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/stats")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["total_gas_used"], 150_000);
+        assert_eq!(json["total_wei_spent"], "12345678901234567890");
+        assert_eq!(json["avg_confirmation_latency_ms"], 1_000);
+        assert_eq!(json["skipped_commitments"]["ordering_hold"], 4);
+
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/metrics")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_str = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body_str.contains("set_anchor_gas_used_total 150000"));
+        assert!(body_str.contains("set_anchor_avg_confirmation_latency_ms 1000"));
+        assert!(
+            body_str.contains("set_anchor_commitments_skipped_total{reason=\"ordering_hold\"} 4")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_errors_endpoint() {
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let state = Arc::new(HealthState::new(test_config(), stats));
+
+        // Record some errors
+        let error = AnchorError::L2Connection(L2Error::Timeout { seconds: 30 });
+        state.record_error(&error).await;
+
+        let router = create_router(state);
+
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/errors")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_str = String::from_utf8(body.to_vec()).unwrap();
+
+        assert!(body_str.contains("l2_connection_errors"));
+    }
+
+    #[tokio::test]
+    async fn test_anchor_calldata_endpoint() {
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let state = Arc::new(HealthState::new(test_config(), stats));
+
+        let commitment = crate::types::BatchCommitment {
+            batch_id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            store_id: Uuid::new_v4(),
+            prev_state_root: format!("0x{}", "0".repeat(64)),
+            new_state_root: format!("0x{}", "22".repeat(32)),
+            events_root: format!("0x{}", "11".repeat(32)),
+            sequence_start: 1,
+            sequence_end: 5,
+            event_count: 5,
+            committed_at: chrono::Utc::now(),
+            chain_tx_hash: None,
+            batch_id_bytes32: None,
+            schema_version: SchemaVersion::CURRENT,
+        };
+        let calldata =
+            crate::client::encode_commit_batch(&commitment, BatchIdScheme::Padded).unwrap();
+        let decoded_batch_id =
+            crate::client::batch_id_to_bytes32(&commitment, BatchIdScheme::Padded)
+                .unwrap()
+                .to_string();
+        state
+            .record_anchor(AnchorRecord {
+                batch_id: commitment.batch_id,
+                tenant_id: commitment.tenant_id,
+                store_id: commitment.store_id,
+                tx_hash: "0xabc".to_string(),
+                chain_id: 84532001,
+                block_number: 7,
+                gas_used: 21_000,
+                anchored_at: chrono::Utc::now(),
+                calldata: Some(calldata.to_string()),
+                batch_id_scheme: BatchIdScheme::Padded,
+                chain_batch_id: decoded_batch_id.clone(),
+            })
+            .await;
+
+        let router = create_router(state);
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/anchors/{}/calldata", commitment.batch_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["decoded"]["function"], "commitBatch");
+        assert_eq!(json["batch_id_scheme"], "padded");
+        assert_eq!(json["chain_batch_id"], json["decoded"]["batch_id"]);
+        assert_eq!(json["chain_batch_id"], decoded_batch_id);
+        assert_eq!(json["decoded"]["event_count"], 5);
+        assert_eq!(json["decoded"]["events_root"], commitment.events_root);
+
+        let missing = router
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/anchors/{}/calldata", Uuid::new_v4()))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    }
+
+    /// Registry holding a single batch
+    struct SingleBatchLookup {
+        batch: CommittedBatch,
+    }
+
+    #[async_trait]
+    impl BatchLookup for SingleBatchLookup {
+        fn chain_id(&self) -> u64 {
+            84532001
+        }
+
+        async fn committed_batch(
+            &self,
+            chain_batch_id: FixedBytes<32>,
+        ) -> AnchorResult<Option<CommittedBatch>> {
+            let stored = self.batch.event.as_ref().unwrap().chain_batch_id;
+            Ok((chain_batch_id == stored).then(|| self.batch.clone()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_endpoint() {
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let state = Arc::new(HealthState::new(test_config(), stats));
+        let router = create_router(Arc::clone(&state));
+        let batch_id = Uuid::new_v4();
+        let get = |batch_id: Uuid| {
+            Request::builder()
+                .uri(format!("/batches/{}", batch_id))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = router.clone().oneshot(get(batch_id)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        // Stored under the keccak key, whatever scheme is configured
+        let (_, chain_batch_id) = crate::client::uuid_lookup_keys(&batch_id)[1];
+        let event = crate::client::CommittedBatchEvent {
+            chain_batch_id,
+            tenant_store_key: FixedBytes::repeat_byte(0x33),
+            events_root: FixedBytes::repeat_byte(0x11),
+            new_state_root: FixedBytes::repeat_byte(0x22),
+            tx_hash: FixedBytes::repeat_byte(0xab),
+            block_number: 42,
+            log_index: 0,
+            sequence_start: 1,
+            sequence_end: 5,
+            event_count: 5,
+        };
+        state
+            .set_batch_lookup(Arc::new(SingleBatchLookup {
+                batch: CommittedBatch {
+                    commitment: crate::client::OnChainCommitment {
+                        events_root: event.events_root,
+                        new_state_root: event.new_state_root,
+                        sequence_start: 1,
+                        sequence_end: 5,
+                        event_count: 5,
+                        timestamp: 1_700_000_000,
+                    },
+                    event: Some(event),
+                },
+            }))
+            .await;
+
+        let response = router.clone().oneshot(get(batch_id)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["batch_id_scheme"], "keccak");
+        assert_eq!(json["chain_batch_id"], chain_batch_id.to_string());
+        assert_eq!(json["events_root"], format!("0x{}", "11".repeat(32)));
+        assert_eq!(json["sequence_end"], 5);
+        assert_eq!(json["tx_hash"], format!("0x{}", "ab".repeat(32)));
+        assert_eq!(json["block_number"], 42);
+        assert_eq!(json["committed_at"], "2023-11-14T22:13:20Z");
+        assert_eq!(json["anchored_by_this_service"], false);
+
+        let missing = router.oneshot(get(Uuid::new_v4())).await.unwrap();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_history_endpoint() {
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let state = Arc::new(HealthState::new(test_config(), stats));
+        for n in 0..3 {
+            state
+                .journal
+                .record_history(HistoryEntry::failed(Uuid::new_v4(), format!("error {}", n)))
+                .await
+                .unwrap();
+        }
+
+        let response = create_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/history?limit=2&offset=1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["total"], 3);
+        assert_eq!(json["entries"].as_array().unwrap().len(), 2);
+        assert_eq!(json["entries"][0]["outcome"], "failed");
+        assert_eq!(json["entries"][0]["error"], "error 1");
+        assert_eq!(json["entries"][1]["error"], "error 0");
+    }
+
+    #[tokio::test]
+    async fn test_startup_endpoint_waits_for_every_step() {
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let state = Arc::new(HealthState::new(test_config(), stats));
+        let router = create_router(Arc::clone(&state));
+        let startup = || {
+            Request::builder()
+                .uri("/startup")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        state.mark_provider_created().await;
+        state.mark_chain_id_verified(84532001).await;
+        let response = router.clone().oneshot(startup()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["started"], false);
+        assert_eq!(json["provider_created"], true);
+        assert_eq!(json["chain_id"], 84532001);
+        assert_eq!(json["authorization_verified"], false);
+
+        state.mark_authorization_verified().await;
+        let response = router.clone().oneshot(startup()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let started_at = state.startup.read().await.started_at.clone();
+        assert!(started_at.is_some());
+
+        // Losing readiness later does not send the pod back into startup
+        state.set_ready(false).await;
+        let response = router.oneshot(startup()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(state.startup.read().await.started_at, started_at);
+    }
+
+    #[tokio::test]
+    async fn test_standby_endpoint() {
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let state = Arc::new(HealthState::new(test_config(), stats));
+        state
+            .set_standby_status(StandbyStatus {
+                standby: true,
+                authorized: true,
+                balance_wei: Some("1000".to_string()),
+                mirrored_pending: 3,
+                last_preflight: Some(chrono::Utc::now().to_rfc3339()),
+                takeover_ready: true,
+                lease_holder: Some("set-anchor-1".to_string()),
+            })
+            .await;
+
+        let router = create_router(state);
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/standby")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["standby"], true);
+        assert_eq!(json["takeover_ready"], true);
+        assert_eq!(json["mirrored_pending"], 3);
+        assert_eq!(json["lease_holder"], "set-anchor-1");
+    }
+
+    #[tokio::test]
+    async fn test_record_error() {
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let state = Arc::new(HealthState::new(test_config(), stats));
+
+        let error = AnchorError::L2Connection(L2Error::ConnectionFailed {
+            url: "http://localhost:8547".to_string(),
+            message: "Connection refused".to_string(),
+        });
+
+        state.record_error(&error).await;
+
+        let counts = state.get_error_counts().await;
+        assert_eq!(counts.l2_connection_errors, 1);
+        assert!(counts.last_error_code.is_some());
+        assert_eq!(counts.last_error_code.unwrap(), "L2_CONNECTION_ERROR");
 
         let recent = state.get_recent_errors(10).await;
         assert_eq!(recent.len(), 1);
         assert!(recent[0].is_retryable);
     }
+
+    #[tokio::test]
+    async fn test_resume_requires_admin_token() {
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let config = AnchorConfig {
+            admin_token: Some("secret".to_string()),
+            ..test_config()
+        };
+        let state = Arc::new(HealthState::new(config, stats));
+        *state.pause.write().await = PauseStatus {
+            paused: true,
+            reason: Some("sequencer de-authorized".to_string()),
+            paused_at: Some(chrono::Utc::now().to_rfc3339()),
+            ..PauseStatus::default()
+        };
+        let router = create_router(Arc::clone(&state));
+
+        let resume = |token: &str| {
+            Request::builder()
+                .method("POST")
+                .uri("/resume")
+                .header("authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let rejected = router.clone().oneshot(resume("wrong")).await.unwrap();
+        assert_eq!(rejected.status(), StatusCode::UNAUTHORIZED);
+        assert!(state.pause.read().await.paused);
+
+        let accepted = router.clone().oneshot(resume("secret")).await.unwrap();
+        assert_eq!(accepted.status(), StatusCode::OK);
+        assert!(!state.pause.read().await.paused);
+
+        let metrics = router
+            .oneshot(
+                Request::builder()
+                    .uri("/metrics")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(metrics.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(String::from_utf8_lossy(&body).contains("set_anchor_paused 0"));
+    }
+
+    #[tokio::test]
+    async fn test_admin_pause_and_resume() {
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let config = AnchorConfig {
+            admin_token: Some("secret".to_string()),
+            ..test_config()
+        };
+        let state = Arc::new(HealthState::new(config, stats));
+        let router = create_router(Arc::clone(&state));
+        let admin = |path: &str, token: &str, body: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(path)
+                .header("authorization", format!("Bearer {}", token))
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let rejected = router
+            .clone()
+            .oneshot(admin("/admin/pause", "wrong", ""))
+            .await
+            .unwrap();
+        assert_eq!(rejected.status(), StatusCode::UNAUTHORIZED);
+        assert!(!state.pause.read().await.paused);
+
+        let paused = router
+            .clone()
+            .oneshot(admin(
+                "/admin/pause",
+                "secret",
+                r#"{"reason": "registry upgrade", "metadata": {"ticket": "OPS-7", "owner": "dana"}}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(paused.status(), StatusCode::OK);
+        let pause = state.pause.read().await.clone();
+        assert!(pause.paused);
+        assert_eq!(pause.reason.as_deref(), Some("registry upgrade"));
+        assert_eq!(pause.metadata["ticket"], "OPS-7");
+
+        // A second pause keeps the original reason and metadata
+        router
+            .clone()
+            .oneshot(admin("/admin/pause", "secret", ""))
+            .await
+            .unwrap();
+        assert_eq!(
+            state.pause.read().await.reason.as_deref(),
+            Some("registry upgrade")
+        );
+        let status = router
+            .clone()
+            .oneshot(Request::get("/pause").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(status.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["metadata"]["owner"], "dana");
+
+        let resumed = router
+            .oneshot(admin("/admin/resume", "secret", ""))
+            .await
+            .unwrap();
+        assert_eq!(resumed.status(), StatusCode::OK);
+        assert!(!state.pause.read().await.paused);
+    }
+
+    #[tokio::test]
+    async fn test_admin_pause_persists_until_resume() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal.json");
+        let config = AnchorConfig {
+            admin_token: Some("secret".to_string()),
+            journal_path: Some(path.to_string_lossy().into_owned()),
+            ..test_config()
+        };
+        let state = Arc::new(HealthState::new(
+            config,
+            Arc::new(RwLock::new(AnchorStats::default())),
+        ));
+        let router = create_router(Arc::clone(&state));
+        let admin = |path: &str, body: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(path)
+                .header("authorization", "Bearer secret")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        router
+            .clone()
+            .oneshot(admin(
+                "/admin/pause",
+                r#"{"reason": "registry upgrade", "metadata": {"ticket": "OPS-7"}}"#,
+            ))
+            .await
+            .unwrap();
+        // A later run finds the pause and its metadata
+        let restored = AnchorJournal::new(Some(path.clone()))
+            .load_pause()
+            .await
+            .unwrap();
+        assert!(restored.paused);
+        assert_eq!(restored.reason.as_deref(), Some("registry upgrade"));
+        assert_eq!(restored.metadata["ticket"], "OPS-7");
+
+        router.oneshot(admin("/admin/resume", "")).await.unwrap();
+        let restored = AnchorJournal::new(Some(path)).load_pause().await.unwrap();
+        assert!(!restored.paused);
+    }
+
+    #[tokio::test]
+    async fn test_anchor_now_wakes_loop() {
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let config = AnchorConfig {
+            admin_token: Some("secret".to_string()),
+            ..test_config()
+        };
+        let state = Arc::new(HealthState::new(config, stats));
+        let router = create_router(Arc::clone(&state));
+        let anchor_now = |token: &str| {
+            Request::builder()
+                .method("POST")
+                .uri("/admin/anchor-now")
+                .header("authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let rejected = router.clone().oneshot(anchor_now("wrong")).await.unwrap();
+        assert_eq!(rejected.status(), StatusCode::UNAUTHORIZED);
+
+        state.pause.write().await.paused = true;
+        let paused = router.clone().oneshot(anchor_now("secret")).await.unwrap();
+        assert_eq!(paused.status(), StatusCode::CONFLICT);
+
+        *state.pause.write().await = PauseStatus::default();
+        let accepted = router.oneshot(anchor_now("secret")).await.unwrap();
+        assert_eq!(accepted.status(), StatusCode::ACCEPTED);
+        // The wake-up is kept until the loop next waits
+        tokio::time::timeout(std::time::Duration::from_secs(1), state.wake.notified())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_batch_skip_and_retry() {
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let config = AnchorConfig {
+            admin_token: Some("secret".to_string()),
+            ..test_config()
+        };
+        let state = Arc::new(HealthState::new(config, stats));
+        let router = create_router(Arc::clone(&state));
+        let batch_id = Uuid::new_v4();
+        let admin = |action: &str, body: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/admin/batches/{}/{}", batch_id, action))
+                .header("authorization", "Bearer secret")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let skipped = router
+            .clone()
+            .oneshot(admin(
+                "skip",
+                r#"{"reason": "bad events_root", "metadata": {"ticket": "OPS-9"}}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(skipped.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(skipped.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["metadata"]["ticket"], "OPS-9");
+        let skip = state.journal.skipped(&batch_id).await.unwrap();
+        assert_eq!(skip.reason, "bad events_root");
+        assert_eq!(skip.metadata["ticket"], "OPS-9");
+
+        let retried = router.clone().oneshot(admin("retry", "")).await.unwrap();
+        assert_eq!(retried.status(), StatusCode::ACCEPTED);
+        let body = axum::body::to_bytes(retried.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["unskipped"], true);
+        assert!(state.journal.skipped(&batch_id).await.is_none());
+        assert!(state.batch_retries.read().await.contains(&batch_id));
+
+        let malformed = router.oneshot(admin("skip", "not json")).await.unwrap();
+        assert_eq!(malformed.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_server_stops_when_cancelled() {
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        // Port 0 binds any free port
+        let server = HealthServer::new(test_config(), stats, 0);
+        let shutdown = CancellationToken::new();
+        let running = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { server.run(shutdown).await }
+        });
+
+        shutdown.cancel();
+        let stopped = tokio::time::timeout(std::time::Duration::from_secs(5), running)
+            .await
+            .expect("server should stop once cancelled")
+            .unwrap();
+        assert!(stopped.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_ready_reports_exhausted_gas_budget() {
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let config = AnchorConfig {
+            gas_budget_eth: 0.5,
+            ..test_config()
+        };
+        let state = Arc::new(HealthState::new(config, stats));
+        state.set_ready(true).await;
+        state.mark_l2_healthy().await;
+        state.mark_sequencer_healthy().await;
+        let router = create_router(Arc::clone(&state));
+        let ready = || {
+            Request::builder()
+                .uri("/ready")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = router.clone().oneshot(ready()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        state
+            .budget
+            .write()
+            .await
+            .as_mut()
+            .unwrap()
+            .record(500_000_000_000_000_000, chrono::Utc::now());
+
+        // Nothing is broken, submissions just wait for the next window
+        let response = router.clone().oneshot(ready()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["status"], "degraded");
+        assert_eq!(json["degraded"], true);
+        let budget = json["components"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|component| component["component"] == "gas_budget")
+            .unwrap();
+        assert_eq!(budget["status"], "degraded");
+        assert!(budget["reasons"][0]
+            .as_str()
+            .unwrap()
+            .starts_with("daily budget of 500000000000000000 wei exhausted until "));
+        assert_eq!(json["budget_exhausted"], true);
+        assert_eq!(json["gas_budget"]["period"], "daily");
+        assert_eq!(json["gas_budget"]["spent_wei"], "500000000000000000");
+    }
+
+    #[tokio::test]
+    async fn test_ready_reports_open_dependency_breaker() {
+        let stats = Arc::new(RwLock::new(AnchorStats {
+            l2_breaker_state: CircuitBreakerState::Open,
+            sequencer_breaker_state: CircuitBreakerState::HalfOpen,
+            ..AnchorStats::default()
+        }));
+        let state = Arc::new(HealthState::new(test_config(), stats));
+        state.set_ready(true).await;
+        state.mark_l2_healthy().await;
+        state.mark_sequencer_healthy().await;
+        let router = create_router(state);
+
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/ready")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["circuit_breakers"]["l2_rpc"], "open");
+        assert_eq!(json["circuit_breakers"]["sequencer_api"], "half-open");
+        assert_eq!(json["status"], "unhealthy");
+        assert_eq!(json["components"][1]["component"], "l2_rpc");
+        assert_eq!(json["components"][1]["status"], "unhealthy");
+        assert_eq!(json["components"][1]["reasons"][0], "circuit breaker open");
+        assert_eq!(json["components"][2]["status"], "degraded");
+
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/metrics")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8_lossy(&body);
+        assert!(
+            body.contains("set_anchor_dependency_circuit_breaker_state{dependency=\"l2_rpc\"} 2")
+        );
+        assert!(body.contains(
+            "set_anchor_dependency_circuit_breaker_state{dependency=\"sequencer_api\"} 1"
+        ));
+        assert!(body.contains("set_anchor_health_status 2"));
+        assert!(body.contains("set_anchor_component_health_status{component=\"l2_rpc\"} 2"));
+        assert!(body.contains("set_anchor_component_health_status{component=\"sequencer_api\"} 1"));
+    }
+
+    #[tokio::test]
+    async fn test_ready_degraded_while_sequencer_flaps() {
+        let stats = Arc::new(RwLock::new(AnchorStats {
+            sequencer_breaker_state: CircuitBreakerState::HalfOpen,
+            ..AnchorStats::default()
+        }));
+        let state = Arc::new(HealthState::new(test_config(), stats));
+        let report = state.health_report().await;
+        assert_eq!(report.status, HealthStatus::Unhealthy);
+        assert_eq!(
+            report.components[0].reasons,
+            vec!["not ready to anchor (starting up or halted)"]
+        );
+
+        state.set_ready(true).await;
+        state.mark_l2_healthy().await;
+        state.mark_sequencer_healthy().await;
+        let report = state.health_report().await;
+        assert_eq!(report.status, HealthStatus::Degraded);
+        let degraded: Vec<_> = report
+            .components
+            .iter()
+            .filter(|component| component.status != HealthStatus::Healthy)
+            .map(|component| (component.component, component.reasons.clone()))
+            .collect();
+        assert_eq!(
+            degraded,
+            vec![(
+                "sequencer_api",
+                vec!["circuit breaker half-open".to_string()]
+            )]
+        );
+
+        let response = create_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/ready")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_ready_degraded_on_low_balance() {
+        let state = Arc::new(HealthState::new(
+            test_config(),
+            Arc::new(RwLock::new(AnchorStats::default())),
+        ));
+        state.set_ready(true).await;
+        state.mark_l2_healthy().await;
+        state.mark_sequencer_healthy().await;
+        state
+            .set_wallet_status(WalletStatus {
+                address: "0x1111111111111111111111111111111111111111".to_string(),
+                balance_wei: "40000000000000000".to_string(),
+                threshold_wei: Some("50000000000000000".to_string()),
+                low: true,
+                checked_at: "2026-10-16T09:30:00+00:00".to_string(),
+            })
+            .await;
+        let router = create_router(state);
+
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/ready")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        // Still submitting, so still ready
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["degraded"], true);
+        assert_eq!(json["wallet"]["balance_wei"], "40000000000000000");
+
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/metrics")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8_lossy(&body);
+        assert!(body.contains("set_anchor_wallet_balance_wei 40000000000000000"));
+        assert!(body.contains("set_anchor_wallet_balance_low 1"));
+    }
+
+    #[tokio::test]
+    async fn test_stale_l2_head_marks_l2_unhealthy() {
+        let state = Arc::new(HealthState::new(
+            AnchorConfig {
+                max_l2_head_age_secs: 30,
+                ..test_config()
+            },
+            Arc::new(RwLock::new(AnchorStats::default())),
+        ));
+        state.set_ready(true).await;
+        state.mark_l2_healthy().await;
+        state.mark_sequencer_healthy().await;
+        let stalled_at = chrono::Utc::now().timestamp() as u64 - 300;
+        state
+            .set_l2_head(L2Head {
+                block_number: 1_234,
+                block_timestamp: stalled_at,
+            })
+            .await;
+        let router = create_router(state);
+
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/ready")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["l2_connected"], false);
+        assert_eq!(json["l2_head_block"], 1_234);
+        assert!(json["l2_head_age_secs"].as_u64().unwrap() >= 300);
+
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/metrics")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8_lossy(&body);
+        assert!(body.contains("set_anchor_l2_head_block 1234"));
+        assert!(body.contains("set_anchor_l2_connected 0"));
+    }
+
+    #[tokio::test]
+    async fn test_costs_per_tenant() {
+        let (tenant_a, tenant_b, store) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut stats = AnchorStats::default();
+        stats.costs.insert(
+            (tenant_a, store),
+            CostTotals {
+                batches: 2,
+                events: 100,
+                wei_spent: 1_000,
+                da_bytes: 40,
+                da_wei_spent: 200,
+            },
+        );
+        stats.costs.insert(
+            (tenant_b, store),
+            CostTotals {
+                batches: 1,
+                events: 10,
+                wei_spent: 3_000,
+                ..Default::default()
+            },
+        );
+        let state = Arc::new(HealthState::new(
+            test_config(),
+            Arc::new(RwLock::new(stats)),
+        ));
+
+        let response = create_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/costs")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["total"]["batches"], 3);
+        assert_eq!(json["total"]["wei_spent"], "4000");
+        assert!(json["total"].get("tenant_id").is_none());
+        assert_eq!(json["tenants"][0]["tenant_id"], tenant_b.to_string());
+        assert_eq!(json["tenants"][0]["cost_per_event_wei"], "300");
+        assert_eq!(json["tenants"][1]["cost_per_batch_wei"], "500");
+        assert_eq!(json["tenants"][1]["cost_per_event_wei"], "10");
+        assert_eq!(json["tenants"][1]["cost_per_da_byte_wei"], "5");
+        assert_eq!(json["total"]["da_bytes"], 40);
+    }
+
+    #[tokio::test]
+    async fn test_stats_per_tenant() {
+        let (healthy, failing, store) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut stats = AnchorStats::default();
+        stats.tenants.insert(
+            healthy,
+            TenantStats {
+                batches_anchored: 9,
+                events_anchored: 900,
+                batches_failed: 0,
+            },
+        );
+        stats.tenants.insert(
+            failing,
+            TenantStats {
+                batches_anchored: 1,
+                events_anchored: 50,
+                batches_failed: 3,
+            },
+        );
+        for (tenant, wei_spent) in [(healthy, 700), (failing, 2_000)] {
+            stats.costs.insert(
+                (tenant, store),
+                CostTotals {
+                    wei_spent,
+                    ..Default::default()
+                },
+            );
+        }
+        // Spend on a second store adds up under the same tenant
+        stats.costs.insert(
+            (failing, Uuid::new_v4()),
+            CostTotals {
+                wei_spent: 500,
+                ..Default::default()
+            },
+        );
+        let state = Arc::new(HealthState::new(
+            test_config(),
+            Arc::new(RwLock::new(stats)),
+        ));
+
+        let response = create_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/stats")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let tenants = json["per_tenant"].as_array().unwrap();
+        assert_eq!(tenants.len(), 2);
+        assert_eq!(tenants[0]["tenant_id"], failing.to_string());
+        assert_eq!(tenants[0]["batches_failed"], 3);
+        assert_eq!(tenants[0]["success_rate"], 0.25);
+        assert_eq!(tenants[0]["wei_spent"], "2500");
+        assert_eq!(tenants[1]["events_anchored"], 900);
+        assert_eq!(tenants[1]["success_rate"], 1.0);
+        assert_eq!(tenants[1]["wei_spent"], "700");
+    }
+
+    #[tokio::test]
+    async fn test_version_endpoint() {
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let state = Arc::new(HealthState::new(test_config(), stats));
+
+        let response = create_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/version")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+        assert!(!json["git_sha"].as_str().unwrap().is_empty());
+        assert!(json["build_timestamp"].is_string());
+        let features = json["features"].as_array().unwrap();
+        assert_eq!(
+            features.iter().any(|feature| feature == "grpc"),
+            cfg!(feature = "grpc")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_reflect_config() {
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let config = AnchorConfig {
+            max_batches_per_tx: 4,
+            multicall_address: Some("0xcA11bde05977b3631167028862bE2a173976CA11".to_string()),
+            batch_id_scheme: BatchIdScheme::Keccak,
+            max_gas_price_gwei: 50,
+            shard_index: 2,
+            shard_count: 3,
+            ..test_config()
+        };
+        let router = create_router(Arc::new(HealthState::new(config, stats)));
+
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/capabilities")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["aggregation"], true);
+        assert_eq!(json["max_batches_per_tx"], 4);
+        assert_eq!(json["batch_id_scheme"], "keccak");
+        assert_eq!(json["max_gas_price_gwei"], 50);
+        assert_eq!(json["shard_index"], 2);
+        assert_eq!(json["shard_count"], 3);
+        assert_eq!(json["schema_versions"], serde_json::json!(["v1"]));
+        assert_eq!(json["claims"], false);
+        assert_eq!(json["gas_oracle"], false);
+    }
+
+    #[tokio::test]
+    async fn test_resume_disabled_without_admin_token() {
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let state = Arc::new(HealthState::new(test_config(), stats));
+        let router = create_router(state);
+
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/resume")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_push_commitments_requires_signature() {
+        use crate::source::CommitmentSource;
+
+        let secret = "0123456789abcdef0123456789abcdef";
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let config = AnchorConfig {
+            commitment_source: CommitmentSourceKind::Webhook,
+            webhook_secret: Some(secret.to_string()),
+            ..test_config()
+        };
+        let state = Arc::new(HealthState::new(config, stats));
+        let router = create_router(Arc::clone(&state));
+
+        let batch_id = Uuid::new_v4();
+        let body = serde_json::to_vec(&serde_json::json!({
+            "batch_id": batch_id,
+            "tenant_id": Uuid::new_v4(),
+            "store_id": Uuid::new_v4(),
+            "prev_state_root": format!("0x{}", "00".repeat(32)),
+            "new_state_root": format!("0x{}", "11".repeat(32)),
+            "events_root": format!("0x{}", "22".repeat(32)),
+            "sequence_start": 1,
+            "sequence_end": 10,
+            "event_count": 10,
+            "committed_at": chrono::Utc::now(),
+            "chain_tx_hash": null,
+        }))
+        .unwrap();
+        let push = |signature: String| {
+            let now = chrono::Utc::now().timestamp();
+            Request::builder()
+                .method("POST")
+                .uri("/v1/commitments")
+                .header(TIMESTAMP_HEADER, now.to_string())
+                .header(SIGNATURE_HEADER, signature)
+                .body(Body::from(body.clone()))
+                .unwrap()
+        };
+
+        let forged = crate::signing::sign(b"not the secret", chrono::Utc::now().timestamp(), &body);
+        let rejected = router.clone().oneshot(push(forged)).await.unwrap();
+        assert_eq!(rejected.status(), StatusCode::UNAUTHORIZED);
+
+        let signed = crate::signing::sign(secret.as_bytes(), chrono::Utc::now().timestamp(), &body);
+        let accepted = router.oneshot(push(signed)).await.unwrap();
+        assert_eq!(accepted.status(), StatusCode::ACCEPTED);
+
+        let queued = state.webhook.as_ref().unwrap().fetch().await.unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].batch_id, batch_id);
+    }
+
+    #[tokio::test]
+    async fn test_push_commitments_disabled_for_other_sources() {
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let state = Arc::new(HealthState::new(test_config(), stats));
+        let router = create_router(state);
+
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/commitments")
+                    .body(Body::from("[]"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
 }
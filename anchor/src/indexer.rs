@@ -0,0 +1,358 @@
+//! Registry indexer
+//!
+//! With `INDEXER_DATABASE_URL` set (and the `indexer` feature built in), a
+//! background task scans the registry's `BatchCommitted` logs into SQLite or
+//! Postgres: batch key, tenant/store key, roots, sequence range, block,
+//! transaction and submitter. Operators can query registry state locally, and
+//! event reconciliation reads from the index instead of scanning logs over RPC.
+//!
+//! Only logs at least `INDEXER_CONFIRMATIONS` blocks deep are indexed, so a
+//! reorg short of that depth never leaves a stale row. The scan position is
+//! stored alongside the rows in the same transaction; a restart resumes where
+//! the last scan stopped.
+//!
+//! SQLite files are only created if the URL asks for it, e.g.
+//! `sqlite:///var/lib/set-anchor/index.db?mode=rwc`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy::{
+    primitives::{Address, FixedBytes},
+    providers::Provider,
+    transports::BoxTransport,
+};
+use anyhow::{Context, Result};
+use sqlx::{any::AnyPoolOptions, AnyPool, Row};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::client::{CommittedBatchEvent, RegistryClient};
+use crate::config::AnchorConfig;
+use crate::metrics::AnchorMetrics;
+
+const SCHEMA: [&str; 3] = [
+    "CREATE TABLE IF NOT EXISTS indexed_batches (
+        chain_batch_id TEXT PRIMARY KEY,
+        tenant_store_key TEXT NOT NULL,
+        events_root TEXT NOT NULL,
+        new_state_root TEXT NOT NULL,
+        sequence_start BIGINT NOT NULL,
+        sequence_end BIGINT NOT NULL,
+        event_count BIGINT NOT NULL,
+        block_number BIGINT NOT NULL,
+        log_index BIGINT NOT NULL,
+        tx_hash TEXT NOT NULL,
+        submitter TEXT
+    )",
+    "CREATE INDEX IF NOT EXISTS indexed_batches_block ON indexed_batches (block_number, log_index)",
+    "CREATE TABLE IF NOT EXISTS indexer_state (
+        id BIGINT PRIMARY KEY,
+        next_block BIGINT NOT NULL
+    )",
+];
+
+/// A `BatchCommitted` log as stored in the index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedBatch {
+    pub event: CommittedBatchEvent,
+    /// Sender of the committing transaction
+    pub submitter: Option<Address>,
+}
+
+/// Database of indexed `BatchCommitted` logs
+pub struct BatchIndex {
+    pool: AnyPool,
+}
+
+impl BatchIndex {
+    /// Connect to `url` and create the tables if they don't exist
+    pub async fn connect(url: &str) -> Result<Self> {
+        sqlx::any::install_default_drivers();
+        let pool = AnyPoolOptions::new()
+            .max_connections(4)
+            .connect(url)
+            .await
+            .context("Failed to connect to indexer database")?;
+        for statement in SCHEMA {
+            sqlx::query(statement)
+                .execute(&pool)
+                .await
+                .context("Failed to create indexer tables")?;
+        }
+        Ok(Self { pool })
+    }
+
+    /// First block the next scan covers, or `None` before the first scan
+    pub async fn next_block(&self) -> Result<Option<u64>> {
+        let row = sqlx::query("SELECT next_block FROM indexer_state WHERE id = 1")
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.map(|row| row.get::<i64, _>(0) as u64))
+    }
+
+    /// Store the batches found in a scan and move the scan position to `next_block`
+    pub async fn record(&self, batches: &[IndexedBatch], next_block: u64) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for batch in batches {
+            let event = &batch.event;
+            sqlx::query(
+                "INSERT INTO indexed_batches (chain_batch_id, tenant_store_key, events_root, \
+                 new_state_root, sequence_start, sequence_end, event_count, block_number, \
+                 log_index, tx_hash, submitter) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) \
+                 ON CONFLICT (chain_batch_id) DO UPDATE SET \
+                 tenant_store_key = excluded.tenant_store_key, \
+                 events_root = excluded.events_root, \
+                 new_state_root = excluded.new_state_root, \
+                 sequence_start = excluded.sequence_start, \
+                 sequence_end = excluded.sequence_end, \
+                 event_count = excluded.event_count, \
+                 block_number = excluded.block_number, \
+                 log_index = excluded.log_index, \
+                 tx_hash = excluded.tx_hash, \
+                 submitter = excluded.submitter",
+            )
+            .bind(event.chain_batch_id.to_string())
+            .bind(event.tenant_store_key.to_string())
+            .bind(event.events_root.to_string())
+            .bind(event.new_state_root.to_string())
+            .bind(event.sequence_start as i64)
+            .bind(event.sequence_end as i64)
+            .bind(event.event_count as i64)
+            .bind(event.block_number as i64)
+            .bind(event.log_index as i64)
+            .bind(event.tx_hash.to_string())
+            .bind(batch.submitter.map(|submitter| submitter.to_string()))
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query(
+            "INSERT INTO indexer_state (id, next_block) VALUES (1, $1) \
+             ON CONFLICT (id) DO UPDATE SET next_block = excluded.next_block",
+        )
+        .bind(next_block as i64)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// The indexed log for a registry batch key
+    pub async fn batch(&self, chain_batch_id: FixedBytes<32>) -> Result<Option<IndexedBatch>> {
+        let row = sqlx::query(&format!("{} WHERE chain_batch_id = $1", SELECT_BATCHES))
+            .bind(chain_batch_id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+        row.map(|row| decode_batch(&row)).transpose()
+    }
+
+    /// Indexed logs in `from_block..=to_block`, in chain order
+    pub async fn batches(&self, from_block: u64, to_block: u64) -> Result<Vec<IndexedBatch>> {
+        let rows = sqlx::query(&format!(
+            "{} WHERE block_number >= $1 AND block_number <= $2 \
+             ORDER BY block_number, log_index",
+            SELECT_BATCHES
+        ))
+        .bind(from_block as i64)
+        .bind(to_block as i64)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter().map(decode_batch).collect()
+    }
+}
+
+const SELECT_BATCHES: &str = "SELECT chain_batch_id, tenant_store_key, events_root, \
+    new_state_root, sequence_start, sequence_end, event_count, block_number, log_index, \
+    tx_hash, submitter FROM indexed_batches";
+
+fn decode_batch(row: &sqlx::any::AnyRow) -> Result<IndexedBatch> {
+    let hash = |index: usize| -> Result<FixedBytes<32>> {
+        let value: String = row.try_get(index)?;
+        value
+            .parse()
+            .with_context(|| format!("Malformed hash {} in indexer database", value))
+    };
+    let submitter: Option<String> = row.try_get(10)?;
+    Ok(IndexedBatch {
+        event: CommittedBatchEvent {
+            chain_batch_id: hash(0)?,
+            tenant_store_key: hash(1)?,
+            events_root: hash(2)?,
+            new_state_root: hash(3)?,
+            sequence_start: row.try_get::<i64, _>(4)? as u64,
+            sequence_end: row.try_get::<i64, _>(5)? as u64,
+            event_count: row.try_get::<i64, _>(6)? as u32,
+            block_number: row.try_get::<i64, _>(7)? as u64,
+            log_index: row.try_get::<i64, _>(8)? as u64,
+            tx_hash: hash(9)?,
+        },
+        submitter: submitter.and_then(|submitter| submitter.parse().ok()),
+    })
+}
+
+/// Scan parameters, from `INDEXER_*`
+#[derive(Debug, Clone, Copy)]
+pub struct IndexerSettings {
+    pub start_block: u64,
+    pub confirmations: u64,
+    pub max_block_range: u64,
+    pub interval: Duration,
+}
+
+impl IndexerSettings {
+    pub fn from_config(config: &AnchorConfig) -> Self {
+        Self {
+            start_block: config.indexer_start_block,
+            confirmations: config.indexer_confirmations,
+            max_block_range: config.indexer_max_block_range,
+            interval: Duration::from_secs(config.indexer_interval_secs),
+        }
+    }
+}
+
+/// Index every confirmed block not yet scanned, returning how many batches were found
+pub async fn index_once<P: Provider<BoxTransport> + Clone>(
+    index: &BatchIndex,
+    registry: &RegistryClient<P>,
+    settings: IndexerSettings,
+) -> Result<usize> {
+    let mut from_block = index.next_block().await?.unwrap_or(settings.start_block);
+    let confirmed = registry
+        .block_number()
+        .await?
+        .saturating_sub(settings.confirmations);
+
+    let mut indexed = 0;
+    while from_block <= confirmed {
+        let to_block = confirmed.min(from_block + settings.max_block_range - 1);
+        let events = registry
+            .batch_committed_events(from_block, to_block)
+            .await?;
+
+        // One lookup per transaction: aggregated anchors commit several batches at once
+        let mut senders = HashMap::new();
+        let mut batches = Vec::with_capacity(events.len());
+        for event in events {
+            let submitter = match senders.get(&event.tx_hash) {
+                Some(submitter) => *submitter,
+                None => {
+                    let submitter = registry.transaction_sender(event.tx_hash).await?;
+                    senders.insert(event.tx_hash, submitter);
+                    submitter
+                }
+            };
+            batches.push(IndexedBatch { event, submitter });
+        }
+
+        index.record(&batches, to_block + 1).await?;
+        debug!(
+            from_block,
+            to_block,
+            batches = batches.len(),
+            "Indexed BatchCommitted logs"
+        );
+        indexed += batches.len();
+        from_block = to_block + 1;
+    }
+    Ok(indexed)
+}
+
+/// Keep the index up to date until the process exits
+pub async fn run_indexer<P: Provider<BoxTransport> + Clone>(
+    index: Arc<BatchIndex>,
+    registry: RegistryClient<P>,
+    settings: IndexerSettings,
+    metrics: Arc<AnchorMetrics>,
+    shutdown: CancellationToken,
+) {
+    info!(
+        confirmations = settings.confirmations,
+        interval_secs = settings.interval.as_secs(),
+        "Indexing BatchCommitted logs"
+    );
+    let mut ticker = tokio::time::interval(settings.interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = shutdown.cancelled() => return,
+        }
+        let indexed = tokio::select! {
+            indexed = index_once(&index, &registry, settings) => indexed,
+            _ = shutdown.cancelled() => return,
+        };
+        match indexed {
+            Ok(indexed) => {
+                metrics.indexed_batches.inc_by(indexed as u64);
+                if let Ok(Some(next_block)) = index.next_block().await {
+                    metrics
+                        .indexer_block
+                        .set(next_block.saturating_sub(1) as i64);
+                }
+            }
+            Err(e) => {
+                metrics.indexer_errors.inc();
+                warn!(error = %e, "Failed to index BatchCommitted logs");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch(key: u8, block_number: u64, submitter: Option<Address>) -> IndexedBatch {
+        IndexedBatch {
+            event: CommittedBatchEvent {
+                chain_batch_id: FixedBytes::from([key; 32]),
+                tenant_store_key: FixedBytes::from([0x33; 32]),
+                events_root: FixedBytes::from([0x11; 32]),
+                new_state_root: FixedBytes::from([0x22; 32]),
+                tx_hash: FixedBytes::from([key; 32]),
+                block_number,
+                log_index: 0,
+                sequence_start: 1,
+                sequence_end: 10,
+                event_count: 10,
+            },
+            submitter,
+        }
+    }
+
+    async fn open(dir: &tempfile::TempDir) -> BatchIndex {
+        let url = format!(
+            "sqlite://{}?mode=rwc",
+            dir.path().join("index.db").display()
+        );
+        BatchIndex::connect(&url).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_index_round_trips_and_keeps_position() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = open(&dir).await;
+        assert_eq!(index.next_block().await.unwrap(), None);
+
+        let submitter = Address::from([0xaa; 20]);
+        index
+            .record(&[batch(1, 20, Some(submitter)), batch(2, 25, None)], 31)
+            .await
+            .unwrap();
+        // A rescan of the same range replaces rows rather than duplicating them
+        index.record(&[batch(2, 25, None)], 31).await.unwrap();
+
+        // Reopened, as after a restart
+        let index = open(&dir).await;
+        assert_eq!(index.next_block().await.unwrap(), Some(31));
+        assert_eq!(
+            index.batch(FixedBytes::from([1; 32])).await.unwrap(),
+            Some(batch(1, 20, Some(submitter)))
+        );
+        let in_range = index.batches(21, 30).await.unwrap();
+        assert_eq!(in_range, vec![batch(2, 25, None)]);
+        assert!(index.batch(FixedBytes::ZERO).await.unwrap().is_none());
+    }
+}
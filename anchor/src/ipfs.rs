@@ -0,0 +1,147 @@
+//! IPFS pinning of batch payloads
+//!
+//! With `IPFS_API_URL` set, each batch's events (the same JSON payload as
+//! [`da::encode_events`](crate::da::encode_events)) are added to IPFS and
+//! pinned before the batch is anchored. The returned CID is journaled and sent
+//! to the sequencer with the anchor notification, so an auditor holding an
+//! on-chain `events_root` can fetch the events from any gateway and recompute
+//! it.
+//!
+//! Uploads use the Kubo RPC `add` endpoint, which Kubo nodes and most pinning
+//! services (Infura, Filebase, 4EVERLAND) expose:
+//!
+//! ```text
+//! POST {IPFS_API_URL}/api/v0/add?pin=true&cid-version=1
+//! ```
+//!
+//! Pinning is best effort: a batch whose payload cannot be pinned is still
+//! anchored, without a CID.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::config::AnchorConfig;
+
+/// Response of `/api/v0/add`
+#[derive(Debug, Deserialize)]
+struct AddResponse {
+    #[serde(rename = "Hash")]
+    hash: String,
+}
+
+/// Client for a Kubo-compatible IPFS pinning API
+#[derive(Debug)]
+pub struct IpfsPinner {
+    url: String,
+    token: Option<String>,
+    client: reqwest::Client,
+}
+
+impl IpfsPinner {
+    /// Create a client for the API at `url`, authenticating with a bearer `token` if given
+    pub fn new(url: &str, token: Option<String>, timeout: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            token,
+            client,
+        }
+    }
+
+    /// Build the pinner configured by `IPFS_API_URL`, if any
+    pub fn from_config(config: &AnchorConfig) -> Option<Self> {
+        config.ipfs_api_url.as_deref().map(|url| {
+            Self::new(
+                url,
+                config.ipfs_api_token.clone(),
+                Duration::from_secs(config.ipfs_timeout_secs),
+            )
+        })
+    }
+
+    /// API the payloads are uploaded to
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Add and pin a batch's payload, returning its CID (v1)
+    pub async fn pin(&self, batch_id: Uuid, payload: Vec<u8>) -> Result<String> {
+        let part = Part::bytes(payload)
+            .file_name(format!("{}.json", batch_id))
+            .mime_str("application/json")?;
+        let mut request = self
+            .client
+            .post(format!("{}/api/v0/add", self.url))
+            .query(&[("pin", "true"), ("cid-version", "1")])
+            .multipart(Form::new().part("file", part));
+        if let Some(ref token) = self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            anyhow::bail!("IPFS API returned {}", response.status());
+        }
+        let added: AddResponse = response.json().await?;
+        if added.hash.is_empty() {
+            anyhow::bail!("IPFS API returned an empty CID");
+        }
+        Ok(added.hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::{
+        matchers::{header, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    const CID: &str = "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku";
+
+    #[tokio::test]
+    async fn test_pin_returns_cid() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v0/add"))
+            .and(query_param("pin", "true"))
+            .and(query_param("cid-version", "1"))
+            .and(header("authorization", "Bearer secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Name": "batch.json",
+                "Hash": CID,
+                "Size": "12",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let pinner = IpfsPinner::new(
+            &format!("{}/", server.uri()),
+            Some("secret".to_string()),
+            Duration::from_secs(2),
+        );
+        let cid = pinner.pin(Uuid::new_v4(), b"[]".to_vec()).await.unwrap();
+        assert_eq!(cid, CID);
+    }
+
+    #[tokio::test]
+    async fn test_pin_errors_when_api_unavailable() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let pinner = IpfsPinner::new(&server.uri(), None, Duration::from_secs(2));
+        assert!(pinner.pin(Uuid::new_v4(), b"[]".to_vec()).await.is_err());
+    }
+}
@@ -0,0 +1,514 @@
+//! Persistent journal of in-flight anchor transactions
+//!
+//! Transaction hashes are written to disk right after broadcast and before
+//! waiting for a receipt. If the process dies in between, the transaction may
+//! still land on-chain; on startup the service resolves every journaled entry
+//! and replays the sequencer notification so no anchor goes unreported.
+//!
+//! Entries are removed once the sequencer has acknowledged the anchor.
+//!
+//! The journal also keeps the last `HISTORY_SIZE` finished operations (anchored,
+//! failed or deferred) for `GET /history`, persisted next to the journal as
+//! `<name>.history.json`, the batches an operator told the service to skip
+//! in `<name>.skipped.json`, and a submission pause with its metadata in
+//! `<name>.pause.json` so it holds across restarts until an operator resumes.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::config::AnchorConfig;
+use crate::types::{AnchorNotification, CelestiaBlobRef};
+
+/// A submitted transaction that has not yet been acknowledged by the sequencer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub batch_id: Uuid,
+    pub tx_hash: String,
+    pub chain_id: u64,
+    pub submitted_at: DateTime<Utc>,
+    /// Set once the receipt is known; `None` means the receipt is still unresolved
+    pub notification: Option<AnchorNotification>,
+    /// Versioned hashes of the blobs carrying the batch's events (`DA_MODE=blob`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blob_versioned_hashes: Vec<String>,
+    /// CID of the batch's events pinned to IPFS
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ipfs_cid: Option<String>,
+    /// Celestia blob carrying the batch's events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub celestia: Option<CelestiaBlobRef>,
+}
+
+/// How an anchor operation ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryOutcome {
+    Anchored,
+    /// Found already committed on chain without the transaction that committed it
+    AlreadyAnchored,
+    Failed,
+    /// Left for a later cycle because gas was too expensive
+    Deferred,
+    /// Skipped for good by an operator
+    Skipped,
+}
+
+/// A finished anchor operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub batch_id: Uuid,
+    pub outcome: HistoryOutcome,
+    pub recorded_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_used: Option<u64>,
+    /// Why the operation failed or was deferred
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl HistoryEntry {
+    /// Entry for the anchor `notification` reports
+    pub fn anchored(batch_id: Uuid, notification: &AnchorNotification) -> Self {
+        Self {
+            tx_hash: Some(notification.chain_tx_hash.clone()),
+            chain_id: Some(notification.chain_id),
+            block_number: notification.block_number,
+            gas_used: notification.gas_used,
+            ..Self::unanchored(batch_id, HistoryOutcome::Anchored, None)
+        }
+    }
+
+    /// Entry for a batch the registry already holds, reported by `notification`
+    pub fn already_anchored(batch_id: Uuid, notification: &AnchorNotification) -> Self {
+        Self {
+            chain_id: Some(notification.chain_id),
+            ..Self::unanchored(batch_id, HistoryOutcome::AlreadyAnchored, None)
+        }
+    }
+
+    /// Entry for a batch abandoned this cycle
+    pub fn failed(batch_id: Uuid, error: String) -> Self {
+        Self::unanchored(batch_id, HistoryOutcome::Failed, Some(error))
+    }
+
+    /// Entry for a batch left for a later cycle
+    pub fn deferred(batch_id: Uuid, reason: String) -> Self {
+        Self::unanchored(batch_id, HistoryOutcome::Deferred, Some(reason))
+    }
+
+    /// Entry for a batch an operator skipped
+    pub fn skipped(batch_id: Uuid, reason: String) -> Self {
+        Self::unanchored(batch_id, HistoryOutcome::Skipped, Some(reason))
+    }
+
+    fn unanchored(batch_id: Uuid, outcome: HistoryOutcome, error: Option<String>) -> Self {
+        Self {
+            batch_id,
+            outcome,
+            recorded_at: Utc::now(),
+            tx_hash: None,
+            chain_id: None,
+            block_number: None,
+            gas_used: None,
+            error,
+        }
+    }
+}
+
+/// A batch the service completes without anchoring
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedBatch {
+    pub batch_id: Uuid,
+    pub reason: String,
+    pub skipped_at: DateTime<Utc>,
+    /// Operator-defined context such as a ticket id, owner or expiry
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// Submission pause that only an operator can lift
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PauseStatus {
+    /// Whether submissions are paused
+    pub paused: bool,
+    /// Why submissions were paused
+    pub reason: Option<String>,
+    /// Time the pause began
+    pub paused_at: Option<String>,
+    /// Operator-defined context such as a ticket id, owner or expiry
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// File-backed journal of in-flight transactions
+pub struct AnchorJournal {
+    path: Option<PathBuf>,
+    entries: Mutex<HashMap<Uuid, JournalEntry>>,
+    /// Finished operations, oldest first
+    history: Mutex<VecDeque<HistoryEntry>>,
+    history_size: usize,
+    skipped: Mutex<HashMap<Uuid, SkippedBatch>>,
+}
+
+impl AnchorJournal {
+    /// History entries kept when no size is configured
+    pub const DEFAULT_HISTORY_SIZE: usize = 1000;
+
+    /// Create a journal persisted at `path`, or kept in memory only when `None`
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            entries: Mutex::new(HashMap::new()),
+            history: Mutex::new(VecDeque::new()),
+            history_size: Self::DEFAULT_HISTORY_SIZE,
+            skipped: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Journal configured by `JOURNAL_PATH` and `HISTORY_SIZE`
+    pub fn from_config(config: &AnchorConfig) -> Self {
+        Self::new(config.journal_path.as_ref().map(PathBuf::from))
+            .with_history_size(config.history_size as usize)
+    }
+
+    /// Keep the last `size` finished operations (0 = no history)
+    pub fn with_history_size(mut self, size: usize) -> Self {
+        self.history_size = size;
+        self
+    }
+
+    /// Whether entries survive a restart
+    pub fn is_persistent(&self) -> bool {
+        self.path.is_some()
+    }
+
+    /// Load entries and history from disk, replacing anything held in memory
+    ///
+    /// Returns the number of in-flight entries.
+    pub async fn load(&self) -> Result<usize> {
+        let Some(ref path) = self.path else {
+            return Ok(0);
+        };
+        self.load_history(path).await?;
+        self.load_skipped(path).await?;
+
+        let loaded: HashMap<Uuid, JournalEntry> = match tokio::fs::read(path).await {
+            Ok(bytes) if bytes.is_empty() => HashMap::new(),
+            Ok(bytes) => serde_json::from_slice::<Vec<JournalEntry>>(&bytes)
+                .with_context(|| format!("Failed to parse journal at {}", path.display()))?
+                .into_iter()
+                .map(|entry| (entry.batch_id, entry))
+                .collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read journal at {}", path.display()))
+            }
+        };
+
+        let count = loaded.len();
+        *self.entries.lock().await = loaded;
+        Ok(count)
+    }
+
+    /// Record a broadcast transaction before waiting for its receipt
+    ///
+    /// Data availability references already recorded for the batch are kept: a
+    /// fee-bump replacement carries the same payload.
+    pub async fn record_submitted(
+        &self,
+        batch_id: Uuid,
+        tx_hash: &str,
+        chain_id: u64,
+    ) -> Result<()> {
+        let mut entries = self.entries.lock().await;
+        let (blob_versioned_hashes, ipfs_cid, celestia) = entries
+            .remove(&batch_id)
+            .map(|entry| (entry.blob_versioned_hashes, entry.ipfs_cid, entry.celestia))
+            .unwrap_or_default();
+        entries.insert(
+            batch_id,
+            JournalEntry {
+                batch_id,
+                tx_hash: tx_hash.to_string(),
+                chain_id,
+                submitted_at: Utc::now(),
+                notification: None,
+                blob_versioned_hashes,
+                ipfs_cid,
+                celestia,
+            },
+        );
+        self.persist(&entries).await
+    }
+
+    /// Record the versioned hashes of the blobs posted with a batch's transaction
+    pub async fn record_blobs(&self, batch_id: Uuid, versioned_hashes: &[String]) -> Result<()> {
+        let mut entries = self.entries.lock().await;
+        let Some(entry) = entries.get_mut(&batch_id) else {
+            return Ok(());
+        };
+        entry.blob_versioned_hashes = versioned_hashes.to_vec();
+        self.persist(&entries).await
+    }
+
+    /// Record the CID a batch's events were pinned under
+    pub async fn record_ipfs_cid(&self, batch_id: Uuid, cid: &str) -> Result<()> {
+        let mut entries = self.entries.lock().await;
+        let Some(entry) = entries.get_mut(&batch_id) else {
+            return Ok(());
+        };
+        entry.ipfs_cid = Some(cid.to_string());
+        self.persist(&entries).await
+    }
+
+    /// Record the Celestia blob carrying a batch's events
+    pub async fn record_celestia(&self, batch_id: Uuid, blob: &CelestiaBlobRef) -> Result<()> {
+        let mut entries = self.entries.lock().await;
+        let Some(entry) = entries.get_mut(&batch_id) else {
+            return Ok(());
+        };
+        entry.celestia = Some(blob.clone());
+        self.persist(&entries).await
+    }
+
+    /// Attach the resolved notification to an entry once its receipt is known
+    pub async fn record_confirmed(
+        &self,
+        batch_id: Uuid,
+        notification: &AnchorNotification,
+    ) -> Result<()> {
+        let mut entries = self.entries.lock().await;
+        let entry = entries.entry(batch_id).or_insert_with(|| JournalEntry {
+            batch_id,
+            tx_hash: notification.chain_tx_hash.clone(),
+            chain_id: notification.chain_id,
+            submitted_at: Utc::now(),
+            notification: None,
+            blob_versioned_hashes: notification.blob_versioned_hashes.clone(),
+            ipfs_cid: notification.ipfs_cid.clone(),
+            celestia: notification.celestia.clone(),
+        });
+        entry.tx_hash = notification.chain_tx_hash.clone();
+        entry.notification = Some(notification.clone());
+        self.persist(&entries).await
+    }
+
+    /// Drop an entry once the sequencer has acknowledged it (or it is known to be void)
+    pub async fn remove(&self, batch_id: &Uuid) -> Result<()> {
+        let mut entries = self.entries.lock().await;
+        if entries.remove(batch_id).is_none() {
+            return Ok(());
+        }
+        self.persist(&entries).await
+    }
+
+    /// Snapshot of all entries, oldest submission first
+    pub async fn entries(&self) -> Vec<JournalEntry> {
+        let mut entries: Vec<JournalEntry> = self.entries.lock().await.values().cloned().collect();
+        entries.sort_by_key(|entry| entry.submitted_at);
+        entries
+    }
+
+    /// Number of entries awaiting acknowledgement
+    pub async fn len(&self) -> usize {
+        self.entries.lock().await.len()
+    }
+
+    /// Whether the journal holds no entries
+    pub async fn is_empty(&self) -> bool {
+        self.entries.lock().await.is_empty()
+    }
+
+    /// Record a finished operation, dropping the oldest beyond `HISTORY_SIZE`
+    pub async fn record_history(&self, entry: HistoryEntry) -> Result<()> {
+        if self.history_size == 0 {
+            return Ok(());
+        }
+        let mut history = self.history.lock().await;
+        history.push_back(entry);
+        while history.len() > self.history_size {
+            history.pop_front();
+        }
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        write_atomically(&history_path(path), &serde_json::to_vec(&*history)?).await
+    }
+
+    /// Page of finished operations, newest first, and the number kept in total
+    pub async fn history(&self, offset: usize, limit: usize) -> (Vec<HistoryEntry>, usize) {
+        let history = self.history.lock().await;
+        let page = history.iter().rev().skip(offset).take(limit).cloned();
+        (page.collect(), history.len())
+    }
+
+    /// Skip a batch from now on, keeping the original reason and metadata if already skipped
+    pub async fn skip(
+        &self,
+        batch_id: Uuid,
+        reason: String,
+        metadata: BTreeMap<String, String>,
+    ) -> Result<SkippedBatch> {
+        let mut skipped = self.skipped.lock().await;
+        if let Some(existing) = skipped.get(&batch_id) {
+            return Ok(existing.clone());
+        }
+        let batch = SkippedBatch {
+            batch_id,
+            reason: reason.clone(),
+            skipped_at: Utc::now(),
+            metadata,
+        };
+        skipped.insert(batch_id, batch.clone());
+        self.persist_skipped(&skipped).await?;
+        drop(skipped);
+        self.record_history(HistoryEntry::skipped(batch_id, reason))
+            .await?;
+        Ok(batch)
+    }
+
+    /// Stop skipping a batch; `false` if it was not skipped
+    pub async fn unskip(&self, batch_id: &Uuid) -> Result<bool> {
+        let mut skipped = self.skipped.lock().await;
+        if skipped.remove(batch_id).is_none() {
+            return Ok(false);
+        }
+        self.persist_skipped(&skipped).await?;
+        Ok(true)
+    }
+
+    /// The skip recorded for a batch, if any
+    pub async fn skipped(&self, batch_id: &Uuid) -> Option<SkippedBatch> {
+        self.skipped.lock().await.get(batch_id).cloned()
+    }
+
+    async fn persist_skipped(&self, skipped: &HashMap<Uuid, SkippedBatch>) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        let mut snapshot: Vec<&SkippedBatch> = skipped.values().collect();
+        snapshot.sort_by_key(|batch| batch.skipped_at);
+        write_atomically(&skipped_path(path), &serde_json::to_vec_pretty(&snapshot)?).await
+    }
+
+    async fn load_skipped(&self, path: &Path) -> Result<()> {
+        let path = skipped_path(path);
+        let loaded: Vec<SkippedBatch> = match tokio::fs::read(&path).await {
+            Ok(bytes) if bytes.is_empty() => Vec::new(),
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .with_context(|| format!("Failed to parse skip list at {}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read skip list at {}", path.display()))
+            }
+        };
+        *self.skipped.lock().await = loaded
+            .into_iter()
+            .map(|batch| (batch.batch_id, batch))
+            .collect();
+        Ok(())
+    }
+
+    /// Persist a pause, or clear the persisted one once it is lifted
+    pub async fn record_pause(&self, pause: &PauseStatus) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        let path = pause_path(path);
+        if pause.paused {
+            return write_atomically(&path, &serde_json::to_vec_pretty(pause)?).await;
+        }
+        match tokio::fs::remove_file(&path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to clear pause at {}", path.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The pause persisted by an earlier run; not paused if there is none
+    pub async fn load_pause(&self) -> Result<PauseStatus> {
+        let Some(ref path) = self.path else {
+            return Ok(PauseStatus::default());
+        };
+        let path = pause_path(path);
+        match tokio::fs::read(&path).await {
+            Ok(bytes) if bytes.is_empty() => Ok(PauseStatus::default()),
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .with_context(|| format!("Failed to parse pause at {}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(PauseStatus::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read pause at {}", path.display())),
+        }
+    }
+
+    async fn load_history(&self, path: &Path) -> Result<()> {
+        let path = history_path(path);
+        let mut loaded: VecDeque<HistoryEntry> = match tokio::fs::read(&path).await {
+            Ok(bytes) if bytes.is_empty() => VecDeque::new(),
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .with_context(|| format!("Failed to parse history at {}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => VecDeque::new(),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read history at {}", path.display()))
+            }
+        };
+        while loaded.len() > self.history_size {
+            loaded.pop_front();
+        }
+        *self.history.lock().await = loaded;
+        Ok(())
+    }
+
+    async fn persist(&self, entries: &HashMap<Uuid, JournalEntry>) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+
+        let mut snapshot: Vec<&JournalEntry> = entries.values().collect();
+        snapshot.sort_by_key(|entry| entry.submitted_at);
+        let bytes = serde_json::to_vec_pretty(&snapshot)?;
+        write_atomically(path, &bytes).await
+    }
+}
+
+/// `journal.json` keeps its history in `journal.history.json`
+fn history_path(path: &Path) -> PathBuf {
+    path.with_extension("history.json")
+}
+
+/// `journal.json` keeps its skip list in `journal.skipped.json`
+fn skipped_path(path: &Path) -> PathBuf {
+    path.with_extension("skipped.json")
+}
+
+/// `journal.json` keeps an operator pause in `journal.pause.json`
+fn pause_path(path: &Path) -> PathBuf {
+    path.with_extension("pause.json")
+}
+
+/// Write to a sibling file and rename so a crash never leaves a torn file
+async fn write_atomically(path: &Path, bytes: &[u8]) -> Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    tokio::fs::write(&tmp_path, bytes)
+        .await
+        .with_context(|| format!("Failed to write journal at {}", tmp_path.display()))?;
+    tokio::fs::rename(&tmp_path, path)
+        .await
+        .with_context(|| format!("Failed to replace journal at {}", path.display()))?;
+    Ok(())
+}
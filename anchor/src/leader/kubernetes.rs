@@ -0,0 +1,537 @@
+//! Kubernetes Lease leader election
+//!
+//! With `LEADER_ELECTION_LEASE` set, replicas compete for a
+//! `coordination.k8s.io/v1` Lease, and a standby takes over once the holder
+//! stops renewing it for `LEADER_ELECTION_LEASE_SECS`.
+//!
+//! A lease counts as expired once this replica has seen it unchanged for its
+//! duration, rather than by comparing its `renewTime` with the local clock, so
+//! clock skew between nodes can't cut a holder's lease short. Every write
+//! carries the `resourceVersion` that was read, so of two replicas racing for
+//! an expired lease only one wins.
+//!
+//! The pod's service account needs `get`, `create` and `update` on `leases`
+//! in the `coordination.k8s.io` API group.
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use super::{identity_from_config, LeaderElection, LeaderRole};
+use crate::config::AnchorConfig;
+
+/// Where Kubernetes mounts the pod's service account credentials
+const SERVICE_ACCOUNT_DIR: &str = "/var/run/secrets/kubernetes.io/serviceaccount";
+
+/// Connection and timing settings for one lease
+#[derive(Debug, Clone)]
+pub struct LeaseSettings {
+    /// Kubernetes API server, e.g. `https://10.0.0.1:443`
+    pub api_url: String,
+    /// Bearer token file, re-read on every request since kubelet rotates it
+    pub token_path: Option<PathBuf>,
+    /// PEM bundle of the API server's CA
+    pub ca_certificate: Option<Vec<u8>>,
+    pub namespace: String,
+    pub name: String,
+    /// Written as `holderIdentity`; unique per replica
+    pub identity: String,
+    pub lease_duration: Duration,
+    pub renew_interval: Duration,
+}
+
+impl LeaseSettings {
+    /// In-cluster settings for `LEADER_ELECTION_LEASE`, if set
+    pub fn from_config(config: &AnchorConfig) -> Result<Option<Self>> {
+        let Some(ref name) = config.leader_election_lease else {
+            return Ok(None);
+        };
+        let service_account = PathBuf::from(SERVICE_ACCOUNT_DIR);
+
+        let host = std::env::var("KUBERNETES_SERVICE_HOST")
+            .context("LEADER_ELECTION_LEASE requires running in Kubernetes (KUBERNETES_SERVICE_HOST is unset)")?;
+        let port = std::env::var("KUBERNETES_SERVICE_PORT").unwrap_or_else(|_| "443".to_string());
+        // IPv6 service addresses need brackets in a URL
+        let api_url = if host.contains(':') {
+            format!("https://[{}]:{}", host, port)
+        } else {
+            format!("https://{}:{}", host, port)
+        };
+
+        let namespace = match config.leader_election_namespace {
+            Some(ref namespace) => namespace.clone(),
+            None => std::fs::read_to_string(service_account.join("namespace"))
+                .context("Cannot read the pod namespace; set LEADER_ELECTION_NAMESPACE")?
+                .trim()
+                .to_string(),
+        };
+        let identity = identity_from_config(config);
+        let ca_certificate = std::fs::read(service_account.join("ca.crt")).ok();
+
+        Ok(Some(Self {
+            api_url,
+            token_path: Some(service_account.join("token")),
+            ca_certificate,
+            namespace,
+            name: name.clone(),
+            identity,
+            lease_duration: Duration::from_secs(config.leader_election_lease_secs),
+            renew_interval: Duration::from_secs(config.leader_election_renew_secs),
+        }))
+    }
+}
+
+/// A `coordination.k8s.io/v1` Lease, reduced to the fields used here
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Lease {
+    #[serde(default = "lease_api_version")]
+    api_version: String,
+    #[serde(default = "lease_kind")]
+    kind: String,
+    metadata: LeaseMetadata,
+    #[serde(default)]
+    spec: LeaseSpec,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LeaseMetadata {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    resource_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LeaseSpec {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    holder_identity: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    lease_duration_seconds: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    acquire_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    renew_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    lease_transitions: Option<u64>,
+}
+
+fn lease_api_version() -> String {
+    "coordination.k8s.io/v1".to_string()
+}
+
+fn lease_kind() -> String {
+    "Lease".to_string()
+}
+
+/// Kubernetes `MicroTime`
+fn micro_time() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+/// Competes for one lease on behalf of this replica
+#[derive(Debug)]
+pub struct LeaseElector {
+    settings: LeaseSettings,
+    client: reqwest::Client,
+    /// Last lease spec seen, and when it was first seen
+    observed: Option<(LeaseSpec, Instant)>,
+    /// Last time this replica acquired or renewed the lease
+    renewed_at: Option<Instant>,
+}
+
+impl LeaseElector {
+    pub fn new(settings: LeaseSettings) -> Result<Self> {
+        let mut builder = reqwest::Client::builder().timeout(settings.renew_interval);
+        if let Some(ref bundle) = settings.ca_certificate {
+            for certificate in reqwest::Certificate::from_pem_bundle(bundle)
+                .context("Invalid Kubernetes CA certificate")?
+            {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+        Ok(Self {
+            client: builder.build()?,
+            settings,
+            observed: None,
+            renewed_at: None,
+        })
+    }
+}
+
+#[async_trait]
+impl LeaderElection for LeaseElector {
+    fn identity(&self) -> &str {
+        &self.settings.identity
+    }
+
+    fn renew_interval(&self) -> Duration {
+        self.settings.renew_interval
+    }
+
+    /// Whether the last successful renewal is recent enough to keep submitting
+    ///
+    /// Leaves one renewal interval of margin before another replica could see
+    /// the lease expire, so a leader that can't reach the API server stops
+    /// before a successor starts.
+    fn holds_role(&self) -> bool {
+        let deadline = self
+            .settings
+            .lease_duration
+            .saturating_sub(self.settings.renew_interval);
+        self.renewed_at
+            .is_some_and(|renewed_at| renewed_at.elapsed() < deadline)
+    }
+
+    /// Renew the lease if held, take it if free or expired, otherwise report the holder
+    async fn try_acquire_or_renew(&mut self) -> Result<LeaderRole> {
+        let Some(lease) = self.get().await? else {
+            return self.create().await;
+        };
+        self.observe(&lease.spec);
+
+        let holder = lease
+            .spec
+            .holder_identity
+            .clone()
+            .filter(|holder| !holder.is_empty());
+        let now = micro_time();
+        let spec = match holder {
+            Some(ref holder) if *holder == self.settings.identity => LeaseSpec {
+                renew_time: Some(now),
+                lease_duration_seconds: Some(self.settings.lease_duration.as_secs()),
+                ..lease.spec.clone()
+            },
+            Some(_) if !self.expired(&lease.spec) => {
+                self.renewed_at = None;
+                return Ok(LeaderRole::Follower { holder });
+            }
+            _ => LeaseSpec {
+                holder_identity: Some(self.settings.identity.clone()),
+                lease_duration_seconds: Some(self.settings.lease_duration.as_secs()),
+                acquire_time: Some(now.clone()),
+                renew_time: Some(now),
+                lease_transitions: Some(
+                    lease.spec.lease_transitions.unwrap_or(0) + u64::from(holder.is_some()),
+                ),
+            },
+        };
+
+        if self.update(lease.metadata, spec.clone()).await? {
+            self.observe(&spec);
+            self.renewed_at = Some(Instant::now());
+            Ok(LeaderRole::Leader)
+        } else {
+            // Someone else wrote the lease since it was read
+            self.renewed_at = None;
+            Ok(LeaderRole::Follower { holder })
+        }
+    }
+
+    /// Give the lease up so a standby can take over without waiting for it to expire
+    async fn release(&mut self) -> Result<()> {
+        if self.renewed_at.take().is_none() {
+            return Ok(());
+        }
+        let Some(lease) = self.get().await? else {
+            return Ok(());
+        };
+        if lease.spec.holder_identity.as_deref() != Some(self.settings.identity.as_str()) {
+            return Ok(());
+        }
+        let spec = LeaseSpec {
+            holder_identity: None,
+            lease_duration_seconds: Some(1),
+            renew_time: Some(micro_time()),
+            ..lease.spec
+        };
+        self.update(lease.metadata, spec).await?;
+        Ok(())
+    }
+}
+
+impl LeaseElector {
+    fn observe(&mut self, spec: &LeaseSpec) {
+        if self.observed.as_ref().is_none_or(|(seen, _)| seen != spec) {
+            self.observed = Some((spec.clone(), Instant::now()));
+        }
+    }
+
+    fn expired(&self, spec: &LeaseSpec) -> bool {
+        let duration = spec
+            .lease_duration_seconds
+            .map(Duration::from_secs)
+            .unwrap_or(self.settings.lease_duration);
+        self.observed
+            .as_ref()
+            .is_none_or(|(_, seen_at)| seen_at.elapsed() >= duration)
+    }
+
+    fn leases_url(&self) -> String {
+        format!(
+            "{}/apis/coordination.k8s.io/v1/namespaces/{}/leases",
+            self.settings.api_url.trim_end_matches('/'),
+            self.settings.namespace
+        )
+    }
+
+    fn lease_url(&self) -> String {
+        format!("{}/{}", self.leases_url(), self.settings.name)
+    }
+
+    fn authorize(&self, request: reqwest::RequestBuilder) -> Result<reqwest::RequestBuilder> {
+        match self.settings.token_path {
+            Some(ref path) => {
+                let token = std::fs::read_to_string(path)
+                    .with_context(|| format!("Cannot read {}", path.display()))?;
+                Ok(request.bearer_auth(token.trim()))
+            }
+            None => Ok(request),
+        }
+    }
+
+    async fn get(&self) -> Result<Option<Lease>> {
+        let response = self
+            .authorize(self.client.get(self.lease_url()))?
+            .send()
+            .await?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => Ok(Some(response.json().await?)),
+            status => anyhow::bail!("Reading lease returned {}", status),
+        }
+    }
+
+    async fn create(&mut self) -> Result<LeaderRole> {
+        let now = micro_time();
+        let lease = Lease {
+            api_version: lease_api_version(),
+            kind: lease_kind(),
+            metadata: LeaseMetadata {
+                name: self.settings.name.clone(),
+                resource_version: None,
+            },
+            spec: LeaseSpec {
+                holder_identity: Some(self.settings.identity.clone()),
+                lease_duration_seconds: Some(self.settings.lease_duration.as_secs()),
+                acquire_time: Some(now.clone()),
+                renew_time: Some(now),
+                lease_transitions: Some(0),
+            },
+        };
+        let response = self
+            .authorize(self.client.post(self.leases_url()))?
+            .json(&lease)
+            .send()
+            .await?;
+        match response.status() {
+            // Another replica created it first; read it next round
+            StatusCode::CONFLICT => Ok(LeaderRole::Follower { holder: None }),
+            status if status.is_success() => {
+                self.observe(&lease.spec);
+                self.renewed_at = Some(Instant::now());
+                Ok(LeaderRole::Leader)
+            }
+            status => anyhow::bail!("Creating lease returned {}", status),
+        }
+    }
+
+    /// Write `spec`, returning false if the lease changed since `metadata` was read
+    async fn update(&self, metadata: LeaseMetadata, spec: LeaseSpec) -> Result<bool> {
+        let lease = Lease {
+            api_version: lease_api_version(),
+            kind: lease_kind(),
+            metadata,
+            spec,
+        };
+        let response = self
+            .authorize(self.client.put(self.lease_url()))?
+            .json(&lease)
+            .send()
+            .await?;
+        match response.status() {
+            StatusCode::CONFLICT => Ok(false),
+            status if status.is_success() => Ok(true),
+            status => anyhow::bail!("Updating lease returned {}", status),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::{
+        matchers::{body_partial_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    const LEASE_PATH: &str = "/apis/coordination.k8s.io/v1/namespaces/anchoring/leases/set-anchor";
+
+    fn elector(server: &MockServer, lease_duration: Duration) -> LeaseElector {
+        LeaseElector::new(LeaseSettings {
+            api_url: server.uri(),
+            token_path: None,
+            ca_certificate: None,
+            namespace: "anchoring".to_string(),
+            name: "set-anchor".to_string(),
+            identity: "set-anchor-0".to_string(),
+            lease_duration,
+            renew_interval: Duration::from_secs(1),
+        })
+        .unwrap()
+    }
+
+    fn lease(holder: &str, duration_secs: u64) -> serde_json::Value {
+        serde_json::json!({
+            "apiVersion": "coordination.k8s.io/v1",
+            "kind": "Lease",
+            "metadata": {"name": "set-anchor", "resourceVersion": "42"},
+            "spec": {
+                "holderIdentity": holder,
+                "leaseDurationSeconds": duration_secs,
+                "renewTime": "2026-01-01T00:00:00.000000Z",
+                "leaseTransitions": 3,
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn test_creates_missing_lease() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(LEASE_PATH))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(
+                "/apis/coordination.k8s.io/v1/namespaces/anchoring/leases",
+            ))
+            .and(body_partial_json(serde_json::json!({
+                "metadata": {"name": "set-anchor"},
+                "spec": {"holderIdentity": "set-anchor-0", "leaseDurationSeconds": 15},
+            })))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut elector = elector(&server, Duration::from_secs(15));
+        assert_eq!(
+            elector.try_acquire_or_renew().await.unwrap(),
+            LeaderRole::Leader
+        );
+        assert!(elector.holds_role());
+    }
+
+    #[tokio::test]
+    async fn test_takes_over_only_after_lease_expires() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(LEASE_PATH))
+            .respond_with(ResponseTemplate::new(200).set_body_json(lease("set-anchor-1", 1)))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path(LEASE_PATH))
+            .and(body_partial_json(serde_json::json!({
+                "metadata": {"resourceVersion": "42"},
+                "spec": {"holderIdentity": "set-anchor-0", "leaseTransitions": 4},
+            })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut elector = elector(&server, Duration::from_secs(15));
+        // The holder's renewTime is long past, but this replica only just saw it
+        assert_eq!(
+            elector.try_acquire_or_renew().await.unwrap(),
+            LeaderRole::Follower {
+                holder: Some("set-anchor-1".to_string())
+            }
+        );
+        assert!(!elector.holds_role());
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        assert_eq!(
+            elector.try_acquire_or_renew().await.unwrap(),
+            LeaderRole::Leader
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lost_race_stays_follower() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(LEASE_PATH))
+            .respond_with(ResponseTemplate::new(200).set_body_json(lease("", 15)))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path(LEASE_PATH))
+            .respond_with(ResponseTemplate::new(409))
+            .mount(&server)
+            .await;
+
+        let mut elector = elector(&server, Duration::from_secs(15));
+        assert_eq!(
+            elector.try_acquire_or_renew().await.unwrap(),
+            LeaderRole::Follower { holder: None }
+        );
+        assert!(!elector.holds_role());
+    }
+
+    #[tokio::test]
+    async fn test_release_clears_holder() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(LEASE_PATH))
+            .respond_with(ResponseTemplate::new(200).set_body_json(lease("set-anchor-0", 15)))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path(LEASE_PATH))
+            .and(body_partial_json(serde_json::json!({
+                "spec": {"holderIdentity": "set-anchor-0"},
+            })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut elector = elector(&server, Duration::from_secs(15));
+        assert_eq!(
+            elector.try_acquire_or_renew().await.unwrap(),
+            LeaderRole::Leader
+        );
+
+        server.reset().await;
+        Mock::given(method("GET"))
+            .and(path(LEASE_PATH))
+            .respond_with(ResponseTemplate::new(200).set_body_json(lease("set-anchor-0", 15)))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path(LEASE_PATH))
+            .and(body_partial_json(serde_json::json!({
+                "spec": {"leaseDurationSeconds": 1},
+            })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        elector.release().await.unwrap();
+        assert!(!elector.holds_role());
+
+        let released = &server.received_requests().await.unwrap()[1];
+        let body: serde_json::Value = serde_json::from_slice(&released.body).unwrap();
+        assert!(body["spec"].get("holderIdentity").is_none());
+    }
+}
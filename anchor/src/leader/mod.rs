@@ -0,0 +1,73 @@
+//! Single-submitter coordination
+//!
+//! Two anchor instances submitting at once would commit every batch twice, so
+//! replicas can elect one submitter. The holder anchors; the others stay in
+//! warm standby (as with `STANDBY_MODE`), running their pre-flight checks, and
+//! one of them takes over once the holder goes away.
+//!
+//! - [`kubernetes`]: a `coordination.k8s.io/v1` Lease, with `LEADER_ELECTION_LEASE`
+//! - [`postgres`]: a Postgres advisory lock, with `LEADER_ELECTION_DATABASE_URL`
+//!   (needs the `pg-lock` feature)
+
+pub mod kubernetes;
+#[cfg(feature = "pg-lock")]
+pub mod postgres;
+
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::config::AnchorConfig;
+
+/// Who holds the submitter role after an election round
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LeaderRole {
+    Leader,
+    Follower { holder: Option<String> },
+}
+
+/// One way of agreeing which replica submits
+#[async_trait]
+pub trait LeaderElection: Send {
+    /// Name this replica holds the role under
+    fn identity(&self) -> &str;
+
+    /// How often `try_acquire_or_renew` should run
+    fn renew_interval(&self) -> Duration;
+
+    /// Keep the role if held, take it if free, otherwise report the holder
+    async fn try_acquire_or_renew(&mut self) -> Result<LeaderRole>;
+
+    /// Whether the role is still safely held, for rounds that failed
+    fn holds_role(&self) -> bool;
+
+    /// Give the role up so a standby can take over without waiting
+    async fn release(&mut self) -> Result<()>;
+}
+
+/// The election configured by `LEADER_ELECTION_LEASE` or `LEADER_ELECTION_DATABASE_URL`, if any
+pub fn from_config(config: &AnchorConfig) -> Result<Option<Box<dyn LeaderElection>>> {
+    if let Some(settings) = kubernetes::LeaseSettings::from_config(config)? {
+        return Ok(Some(Box::new(kubernetes::LeaseElector::new(settings)?)));
+    }
+    #[cfg(feature = "pg-lock")]
+    if let Some(election) = postgres::AdvisoryLockElection::from_config(config) {
+        return Ok(Some(Box::new(election)));
+    }
+    #[cfg(not(feature = "pg-lock"))]
+    if config.leader_election_database_url.is_some() {
+        anyhow::bail!("LEADER_ELECTION_DATABASE_URL requires a build with the `pg-lock` feature");
+    }
+    Ok(None)
+}
+
+/// `LEADER_ELECTION_IDENTITY`, else `HOSTNAME` (the pod name under Kubernetes),
+/// else the process id
+pub(crate) fn identity_from_config(config: &AnchorConfig) -> String {
+    config
+        .leader_election_identity
+        .clone()
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .unwrap_or_else(|| format!("set-anchor-{}", std::process::id()))
+}
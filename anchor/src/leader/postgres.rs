@@ -0,0 +1,212 @@
+//! Postgres advisory lock election
+//!
+//! For deployments outside Kubernetes, `LEADER_ELECTION_DATABASE_URL` points
+//! every replica at one Postgres database, where they compete for the
+//! session-level advisory lock `LEADER_ELECTION_LOCK_KEY`. The holder keeps it
+//! for as long as its connection lives and checks that connection every
+//! `LEADER_ELECTION_RENEW_SECS`. If the check fails it stands down at once:
+//! Postgres frees the lock with the dead session, so another replica may
+//! already hold it.
+//!
+//! Each acquisition also bumps `epoch` in the `anchor_leader` table, a fencing
+//! token that orders holders. The registry can't check it, but it is logged
+//! with every takeover, and the table shows who holds the role now.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use sqlx::{Connection, PgConnection};
+use tracing::{info, warn};
+
+use super::{identity_from_config, LeaderElection, LeaderRole};
+use crate::config::AnchorConfig;
+
+const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS anchor_leader (
+    lock_key BIGINT PRIMARY KEY,
+    epoch BIGINT NOT NULL,
+    holder TEXT NOT NULL,
+    acquired_at TIMESTAMPTZ NOT NULL DEFAULT now()
+)";
+
+/// Competes for one advisory lock on behalf of this replica
+pub struct AdvisoryLockElection {
+    url: String,
+    key: i64,
+    identity: String,
+    renew_interval: Duration,
+    /// Open session, kept between rounds; it owns the lock while leading
+    session: Option<PgConnection>,
+    /// Epoch of the current hold, while leading
+    epoch: Option<i64>,
+}
+
+impl AdvisoryLockElection {
+    pub fn new(url: &str, key: i64, identity: String, renew_interval: Duration) -> Self {
+        Self {
+            url: url.to_string(),
+            key,
+            identity,
+            renew_interval,
+            session: None,
+            epoch: None,
+        }
+    }
+
+    /// Election configured by `LEADER_ELECTION_DATABASE_URL`, if any
+    pub fn from_config(config: &AnchorConfig) -> Option<Self> {
+        config.leader_election_database_url.as_deref().map(|url| {
+            Self::new(
+                url,
+                config.leader_election_lock_key,
+                identity_from_config(config),
+                Duration::from_secs(config.leader_election_renew_secs),
+            )
+        })
+    }
+
+    /// Epoch of the current hold, while this replica is the submitter
+    pub fn epoch(&self) -> Option<i64> {
+        self.epoch
+    }
+
+    async fn round(&mut self) -> Result<LeaderRole> {
+        if self.session.is_none() {
+            let mut session = PgConnection::connect(&self.url)
+                .await
+                .context("Failed to connect to leader election database")?;
+            sqlx::query(SCHEMA)
+                .execute(&mut session)
+                .await
+                .context("Failed to create anchor_leader table")?;
+            self.session = Some(session);
+        }
+        let session = self.session.as_mut().expect("session was just opened");
+
+        if self.epoch.is_some() {
+            // The lock lives exactly as long as this session
+            sqlx::query("SELECT 1").execute(&mut *session).await?;
+            return Ok(LeaderRole::Leader);
+        }
+
+        let acquired: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+            .bind(self.key)
+            .fetch_one(&mut *session)
+            .await?;
+        if !acquired {
+            let holder: Option<String> =
+                sqlx::query_scalar("SELECT holder FROM anchor_leader WHERE lock_key = $1")
+                    .bind(self.key)
+                    .fetch_optional(&mut *session)
+                    .await?;
+            return Ok(LeaderRole::Follower { holder });
+        }
+
+        let epoch: i64 = sqlx::query_scalar(
+            "INSERT INTO anchor_leader (lock_key, epoch, holder) VALUES ($1, 1, $2)
+             ON CONFLICT (lock_key) DO UPDATE
+             SET epoch = anchor_leader.epoch + 1, holder = EXCLUDED.holder, acquired_at = now()
+             RETURNING epoch",
+        )
+        .bind(self.key)
+        .bind(&self.identity)
+        .fetch_one(&mut *session)
+        .await?;
+        info!(epoch, lock_key = self.key, "Acquired leader advisory lock");
+        self.epoch = Some(epoch);
+        Ok(LeaderRole::Leader)
+    }
+}
+
+#[async_trait]
+impl LeaderElection for AdvisoryLockElection {
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn renew_interval(&self) -> Duration {
+        self.renew_interval
+    }
+
+    /// Check the lock-holding session if leading, otherwise try to take the lock
+    async fn try_acquire_or_renew(&mut self) -> Result<LeaderRole> {
+        let round = tokio::time::timeout(self.renew_interval, self.round())
+            .await
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("Leader election database timed out")));
+        if round.is_err() {
+            // Reconnect next round; a lock held by this session is gone with it
+            if let Some(epoch) = self.epoch.take() {
+                warn!(epoch, "Lost the session holding the leader advisory lock");
+            }
+            self.session = None;
+        }
+        round
+    }
+
+    fn holds_role(&self) -> bool {
+        self.epoch.is_some()
+    }
+
+    async fn release(&mut self) -> Result<()> {
+        let Some(mut session) = self.session.take() else {
+            return Ok(());
+        };
+        if self.epoch.take().is_some() {
+            sqlx::query("SELECT pg_advisory_unlock($1)")
+                .bind(self.key)
+                .execute(&mut session)
+                .await?;
+        }
+        session.close().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn election(url: &str, identity: &str) -> AdvisoryLockElection {
+        AdvisoryLockElection::new(
+            url,
+            0x5e7_a11c,
+            identity.to_string(),
+            Duration::from_secs(5),
+        )
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Postgres - run with LEADER_ELECTION_TEST_DATABASE_URL set and --ignored"]
+    async fn test_one_holder_at_a_time() {
+        let url = std::env::var("LEADER_ELECTION_TEST_DATABASE_URL").unwrap();
+        let mut first = election(&url, "anchor-a");
+        let mut second = election(&url, "anchor-b");
+
+        assert_eq!(
+            first.try_acquire_or_renew().await.unwrap(),
+            LeaderRole::Leader
+        );
+        let epoch = first.epoch().unwrap();
+        assert_eq!(
+            second.try_acquire_or_renew().await.unwrap(),
+            LeaderRole::Follower {
+                holder: Some("anchor-a".to_string())
+            }
+        );
+        // Renewing only checks the session
+        assert_eq!(
+            first.try_acquire_or_renew().await.unwrap(),
+            LeaderRole::Leader
+        );
+        assert_eq!(first.epoch(), Some(epoch));
+
+        first.release().await.unwrap();
+        assert!(!first.holds_role());
+        assert_eq!(
+            second.try_acquire_or_renew().await.unwrap(),
+            LeaderRole::Leader
+        );
+        assert_eq!(second.epoch(), Some(epoch + 1));
+        second.release().await.unwrap();
+    }
+}
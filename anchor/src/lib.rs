@@ -3,12 +3,47 @@
 //! Bridges stateset-sequencer batch commitments to on-chain SetRegistry.
 //! Provides cryptographic anchoring of commerce events on Set Chain L2.
 
+pub mod alert;
+pub mod archive;
+pub mod audit;
+pub mod budget;
+pub mod celestia;
 pub mod client;
 pub mod config;
+pub mod da;
+pub mod dedup;
 pub mod error;
+pub mod fees;
+pub mod gas_history;
+pub mod gas_oracle;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 pub mod health;
+#[cfg(feature = "indexer")]
+pub mod indexer;
+pub mod ipfs;
+pub mod journal;
+pub mod leader;
+pub mod merkle;
+pub mod metrics;
+pub mod nonce;
+pub mod oauth;
+pub mod private_relay;
+pub mod rate_limit;
+pub mod reconcile;
+pub mod recorder;
+pub mod reporting;
+pub mod rpc_pool;
+pub mod schedule;
 pub mod service;
+pub mod signing;
+pub mod sla;
+pub mod source;
+pub mod success_window;
+pub mod throttle;
+pub mod trace_context;
 pub mod types;
+pub mod validate;
 
 #[cfg(test)]
 mod tests;
@@ -16,6 +51,7 @@ mod tests;
 pub use config::AnchorConfig;
 pub use error::{AnchorError, ErrorSeverity};
 pub use health::{HealthServer, HealthState};
+pub use journal::AnchorJournal;
 pub use service::AnchorService;
 pub use types::{
     AnchorNotification, AnchorResult, AnchorStats, BatchCommitment, CircuitBreaker,
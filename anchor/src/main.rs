@@ -3,19 +3,29 @@
 //! Bridges stateset-sequencer batch commitments to on-chain SetRegistry.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use tokio::sync::RwLock;
-use tracing::{error, info};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
 use tracing_subscriber::{fmt, EnvFilter};
 
-use set_anchor::{AnchorConfig, AnchorService, AnchorStats, HealthServer, HealthState};
+use set_anchor::{
+    audit, reporting, source, AnchorConfig, AnchorService, AnchorStats, HealthServer, HealthState,
+};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Load .env file if present
     dotenvy::dotenv().ok();
 
+    // `set-anchor audit <verify|export> [PATH]` checks the audit log instead of running
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("audit") {
+        return audit::run_command(&args[1..]).await;
+    }
+
     // Initialize logging
     let filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new("info,set_anchor=debug"));
@@ -29,6 +39,7 @@ async fn main() -> Result<()> {
 
     info!(
         version = env!("CARGO_PKG_VERSION"),
+        git_sha = env!("SET_ANCHOR_GIT_SHA"),
         "Set Chain Anchor Service starting"
     );
 
@@ -36,8 +47,12 @@ async fn main() -> Result<()> {
     let config = AnchorConfig::from_env()?;
     config.validate()?;
 
+    // Report critical and fatal errors to Sentry when SENTRY_DSN is set
+    let _sentry = reporting::init(&config);
+
     info!(
         l2_rpc = %config.l2_rpc_url,
+        l2_rpc_fallbacks = config.l2_rpc_fallback_urls.len(),
         registry = %config.set_registry_address,
         sequencer_api = %config.sequencer_api_url,
         interval = config.anchor_interval_secs,
@@ -48,6 +63,24 @@ async fn main() -> Result<()> {
         sequencer_timeout_secs = config.sequencer_request_timeout_secs,
         sequencer_connect_timeout_secs = config.sequencer_connect_timeout_secs,
         circuit_breaker_threshold = config.circuit_breaker_failure_threshold,
+        journal_path = ?config.journal_path,
+        max_batches_per_tx = config.max_batches_per_tx,
+        standby_mode = config.standby_mode,
+        leader_election_lease = ?config.leader_election_lease,
+        report_cost_estimates = config.report_cost_estimates,
+        max_concurrent_anchors = config.max_concurrent_anchors,
+        shard = %format!("{}/{}", config.shard_index, config.shard_count),
+        sequencer_timeouts = ?config.sequencer_timeouts(),
+        watch_registry_ownership = config.watch_registry_ownership,
+        fee_strategy = %config.fee_strategy,
+        sentry = config.sentry_dsn.is_some(),
+        sequencer_protocol = config.sequencer_protocol.as_str(),
+        sequencer_auth = config
+            .sequencer_api_token
+            .as_ref()
+            .map(|_| config.sequencer_auth_scheme.as_str()),
+        commitment_source = config.commitment_source.as_str(),
+        shutdown_timeout_secs = config.shutdown_timeout_secs,
         "Configuration loaded"
     );
 
@@ -58,30 +91,66 @@ async fn main() -> Result<()> {
     let health_state = Arc::new(HealthState::new(config.clone(), Arc::clone(&stats)));
 
     // Create anchor service with health state
-    let service = AnchorService::with_health_state(config.clone(), Arc::clone(&health_state));
+    let mut service = AnchorService::with_health_state(config.clone(), Arc::clone(&health_state));
+    if let Some(source) = source::connect(&config).await? {
+        service = service.with_commitment_source(source);
+    }
 
     // Create health server
     let health_server = HealthServer::with_state(Arc::clone(&health_state), config.health_port);
 
-    // Run both services concurrently
+    // Run both services concurrently; the health server outlives the anchor
+    // loop so probes and scrapes keep working while shutdown drains
+    let shutdown = CancellationToken::new();
+    let server_shutdown = CancellationToken::new();
+    let mut health_task = tokio::spawn({
+        let server_shutdown = server_shutdown.clone();
+        async move { health_server.run(server_shutdown).await }
+    });
+    let anchor_loop = service.run(shutdown.clone());
+    tokio::pin!(anchor_loop);
     tokio::select! {
-        result = service.run() => {
+        result = &mut anchor_loop => {
             if let Err(e) = result {
                 error!(error = %e, "Anchor service failed");
-                return Err(e);
+                return Err(e.into());
             }
         }
-        result = health_server.run() => {
-            if let Err(e) = result {
-                error!(error = %e, "Health server failed");
-                return Err(e);
-            }
+        result = &mut health_task => {
+            // The server is only told to stop after this select, so any exit is a failure
+            let e = match result {
+                Ok(Ok(())) => anyhow::anyhow!("health server stopped unexpectedly"),
+                Ok(Err(e)) => e,
+                Err(e) => e.into(),
+            };
+            error!(error = %e, "Health server failed");
+            return Err(e);
         }
-        _ = tokio::signal::ctrl_c() => {
-            info!("Received shutdown signal, shutting down gracefully");
+        _ = shutdown_signal() => {
+            info!(
+                timeout_secs = config.shutdown_timeout_secs,
+                "Received shutdown signal, finishing in-flight anchors"
+            );
+            health_state.set_draining().await;
+            shutdown.cancel();
+            let timeout = Duration::from_secs(config.shutdown_timeout_secs);
+            match tokio::time::timeout(timeout, &mut anchor_loop).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => error!(error = %e, "Anchor service failed while shutting down"),
+                Err(_) => warn!(
+                    "Shutdown timed out with anchors in flight; they will be recovered from the journal on restart"
+                ),
+            }
         }
     }
 
+    server_shutdown.cancel();
+    match health_task.await {
+        Ok(Err(e)) => error!(error = %e, "Health server failed while shutting down"),
+        Err(e) => error!(error = %e, "Health server task failed"),
+        Ok(Ok(())) => {}
+    }
+
     // Log final stats before exit
     let final_stats = stats.read().await;
     info!(
@@ -94,3 +163,23 @@ async fn main() -> Result<()> {
     );
     Ok(())
 }
+
+/// Resolve on SIGINT, or SIGTERM where the platform has it
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+                return;
+            }
+            Err(e) => warn!(error = %e, "Cannot listen for SIGTERM; only SIGINT stops the service"),
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
+}
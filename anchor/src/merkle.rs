@@ -0,0 +1,114 @@
+//! Events Merkle tree
+//!
+//! Rebuilds a batch's `events_root` from its raw events the same way
+//! `SetRegistry.verifyInclusion` walks a proof: leaves are
+//! `keccak256(abi.encodePacked(eventType, payload, metadata))` in sequence
+//! order, and each parent is `keccak256(left ++ right)` by position. A level
+//! with an odd number of nodes pairs its last node with itself.
+
+use alloy::primitives::{keccak256, B256};
+
+use crate::error::{AnchorResult, SequencerApiError};
+use crate::types::BatchEvent;
+
+/// Leaf hash of one event, as the SDK's `computeEventLeaf` builds it
+pub fn event_leaf(event: &BatchEvent) -> AnchorResult<B256> {
+    let bytes = |field: &str, value: &str| {
+        hex::decode(value.strip_prefix("0x").unwrap_or(value)).map_err(|e| {
+            SequencerApiError::ParseError(format!("event {} {}: {}", event.sequence, field, e))
+        })
+    };
+    let mut packed = event.event_type.as_bytes().to_vec();
+    packed.extend(bytes("payload", &event.payload)?);
+    packed.extend(bytes("metadata", &event.metadata)?);
+    Ok(keccak256(packed))
+}
+
+/// Root over `leaves` (zero for an empty batch)
+pub fn merkle_root(leaves: &[B256]) -> B256 {
+    if leaves.is_empty() {
+        return B256::ZERO;
+    }
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let right = pair.get(1).unwrap_or(&pair[0]);
+                keccak256([pair[0].as_slice(), right.as_slice()].concat())
+            })
+            .collect();
+    }
+    level[0]
+}
+
+/// Root over a batch's events, sorted by sequence number
+pub fn events_root(events: &[BatchEvent]) -> AnchorResult<B256> {
+    let mut ordered: Vec<&BatchEvent> = events.iter().collect();
+    ordered.sort_by_key(|event| event.sequence);
+    let leaves = ordered
+        .into_iter()
+        .map(event_leaf)
+        .collect::<AnchorResult<Vec<_>>>()?;
+    Ok(merkle_root(&leaves))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(sequence: u64, event_type: &str) -> BatchEvent {
+        BatchEvent {
+            sequence,
+            event_type: event_type.to_string(),
+            payload: format!("0x{}", hex::encode(sequence.to_be_bytes())),
+            metadata: String::new(),
+        }
+    }
+
+    /// Walk a proof exactly like `SetRegistry._computeMerkleRoot`
+    fn root_from_proof(leaf: B256, proof: &[B256], mut index: usize) -> B256 {
+        proof.iter().fold(leaf, |hash, sibling| {
+            let parent = if index % 2 == 0 {
+                keccak256([hash.as_slice(), sibling.as_slice()].concat())
+            } else {
+                keccak256([sibling.as_slice(), hash.as_slice()].concat())
+            };
+            index /= 2;
+            parent
+        })
+    }
+
+    #[test]
+    fn test_root_matches_registry_proof_walk() {
+        let leaves: Vec<B256> = (0..3)
+            .map(|i| event_leaf(&event(i, "OrderCreated")).unwrap())
+            .collect();
+        let root = merkle_root(&leaves);
+
+        let pair01 = keccak256([leaves[0].as_slice(), leaves[1].as_slice()].concat());
+        let pair22 = keccak256([leaves[2].as_slice(), leaves[2].as_slice()].concat());
+        assert_eq!(root_from_proof(leaves[1], &[leaves[0], pair22], 1), root);
+        assert_eq!(root_from_proof(leaves[2], &[leaves[2], pair01], 2), root);
+
+        assert_eq!(merkle_root(&leaves[..1]), leaves[0]);
+        assert_eq!(merkle_root(&[]), B256::ZERO);
+    }
+
+    #[test]
+    fn test_events_root_ignores_delivery_order() {
+        let events = vec![event(2, "OrderShipped"), event(1, "OrderCreated")];
+        let sorted = vec![event(1, "OrderCreated"), event(2, "OrderShipped")];
+        assert_eq!(events_root(&events).unwrap(), events_root(&sorted).unwrap());
+
+        let mut tampered = sorted.clone();
+        tampered[1].payload = "0xff".to_string();
+        assert_ne!(
+            events_root(&tampered).unwrap(),
+            events_root(&sorted).unwrap()
+        );
+
+        tampered[1].payload = "0xzz".to_string();
+        assert!(events_root(&tampered).is_err());
+    }
+}
@@ -0,0 +1,887 @@
+//! Prometheus metrics registry
+//!
+//! One [`AnchorMetrics`] is shared by the health server, the anchor service and
+//! the registry client. Totals the service already aggregates in
+//! [`AnchorStats`](crate::types::AnchorStats) are copied in when `/metrics` is
+//! scraped; histograms and per-call counters are recorded where they happen.
+
+use std::time::Duration;
+
+use prometheus::{
+    Encoder, Gauge, GaugeVec, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec,
+    IntGauge, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+
+/// Buckets for anchor cycle duration, in seconds
+const CYCLE_DURATION_BUCKETS: &[f64] = &[0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0];
+
+/// Buckets for gas used per anchor transaction
+const GAS_USED_BUCKETS: &[f64] = &[
+    50_000.0,
+    75_000.0,
+    100_000.0,
+    150_000.0,
+    200_000.0,
+    300_000.0,
+    500_000.0,
+    1_000_000.0,
+    2_000_000.0,
+    5_000_000.0,
+];
+
+/// Buckets for effective gas price, in gwei (L2 prices are usually well below 1)
+const GAS_PRICE_GWEI_BUCKETS: &[f64] = &[
+    0.001, 0.01, 0.05, 0.1, 0.5, 1.0, 2.0, 5.0, 10.0, 25.0, 50.0, 100.0,
+];
+
+/// Buckets for hourly-cap delays: from one cycle up to the full window
+const THROTTLE_DELAY_BUCKETS: &[f64] = &[
+    30.0, 60.0, 120.0, 300.0, 600.0, 900.0, 1800.0, 2700.0, 3600.0,
+];
+
+/// Buckets for commit-to-anchor time, in seconds
+const TIME_TO_ANCHOR_BUCKETS: &[f64] = &[
+    30.0, 60.0, 120.0, 300.0, 600.0, 900.0, 1800.0, 3600.0, 7200.0, 21_600.0, 86_400.0,
+];
+
+/// Buckets for L2 RPC request latency, in seconds
+const RPC_LATENCY_BUCKETS: &[f64] = &[0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Wei per gwei
+const WEI_PER_GWEI: f64 = 1e9;
+
+/// Metric handles registered in a shared registry
+pub struct AnchorMetrics {
+    registry: Registry,
+    pub batches: IntCounterVec,
+    pub events: IntCounter,
+    pub gas_price_skips: IntCounter,
+    pub consecutive_failures: IntGauge,
+    pub avg_anchor_time_ms: IntGauge,
+    pub avg_confirmation_latency_ms: IntGauge,
+    pub total_gas_used: IntCounter,
+    pub wei_spent: Gauge,
+    pub commitments_skipped: IntCounterVec,
+    pub cycle_commitments_skipped: IntGaugeVec,
+    pub cycles: IntCounter,
+    pub cycles_by_status: IntCounterVec,
+    pub l2_connected: IntGauge,
+    pub sequencer_connected: IntGauge,
+    pub l2_connection_failures: IntCounter,
+    pub sequencer_api_failures: IntCounter,
+    pub success_rate: Gauge,
+    pub windowed_success_rate: GaugeVec,
+    pub cycle_success_rate: Gauge,
+    pub uptime_seconds: IntGauge,
+    pub ready: IntGauge,
+    pub health_status: IntGauge,
+    pub component_health_status: IntGaugeVec,
+    pub errors: IntCounterVec,
+    pub errors_sum: IntCounter,
+    pub circuit_breaker_state: IntGauge,
+    pub dependency_circuit_breaker_state: IntGaugeVec,
+    pub circuit_breaker_open_skips: IntCounter,
+    pub fee_bumps: IntCounter,
+    pub stuck_txs: IntCounter,
+    pub deferred_batches: IntGauge,
+    pub oldest_deferred_seconds: IntGauge,
+    pub deferral_overrides: IntCounter,
+    pub time_to_anchor: Histogram,
+    pub time_to_anchor_quantile: GaugeVec,
+    pub sla_violations: IntCounter,
+    pub standby: IntGauge,
+    pub takeover_ready: IntGauge,
+    pub leadership_transitions: IntCounterVec,
+    pub paused: IntGauge,
+    pub registry_paused: IntGauge,
+    pub gas_spent_wei: Gauge,
+    pub gas_budget_wei: Gauge,
+    pub budget_exhausted: IntGauge,
+    pub cycle_duration: Histogram,
+    pub transactions_sent: IntCounterVec,
+    pub gas_used: Histogram,
+    pub effective_gas_price_gwei: Histogram,
+    pub wallet_balance_wei: Gauge,
+    pub gas_price_median_wei: Gauge,
+    pub throttled_batches: IntGauge,
+    pub throttle_delay: Histogram,
+    pub wallet_balance_low: IntGauge,
+    pub l2_head_block: IntGauge,
+    pub l2_head_age_seconds: IntGauge,
+    pub rpc_requests: IntCounterVec,
+    pub rpc_latency: HistogramVec,
+    pub rpc_error_rate: GaugeVec,
+    pub rpc_endpoint_score: GaugeVec,
+    pub sequence_anomalies: IntCounterVec,
+    pub validation_failures: IntCounterVec,
+    pub duplicate_deliveries: IntCounter,
+    pub awaiting_finality: IntGauge,
+    pub reconciliation_discrepancies: IntCounterVec,
+    pub simulation_reverts: IntCounter,
+    pub access_lists: IntCounterVec,
+    pub access_list_gas_saved: IntCounter,
+    pub private_submissions: IntCounterVec,
+    pub blobs_posted: IntCounter,
+    pub da_calldata_bytes: IntCounter,
+    pub ipfs_pins: IntCounterVec,
+    pub celestia_submissions: IntCounterVec,
+    pub archive_uploads: IntCounterVec,
+    pub audit_records: IntCounterVec,
+    pub indexed_batches: IntCounter,
+    pub indexer_block: IntGauge,
+    pub indexer_errors: IntCounter,
+}
+
+impl AnchorMetrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let metrics = Self {
+            batches: register(
+                &registry,
+                IntCounterVec::new(
+                    Opts::new(
+                        "set_anchor_batches_total",
+                        "Total number of batches processed",
+                    ),
+                    &["status"],
+                ),
+            ),
+            events: register(
+                &registry,
+                IntCounter::new("set_anchor_events_total", "Total number of events anchored"),
+            ),
+            gas_price_skips: register(
+                &registry,
+                IntCounter::new(
+                    "set_anchor_gas_price_skips_total",
+                    "Total number of gas price skips",
+                ),
+            ),
+            consecutive_failures: register(
+                &registry,
+                IntGauge::new(
+                    "set_anchor_consecutive_failures",
+                    "Consecutive failed anchors",
+                ),
+            ),
+            avg_anchor_time_ms: register(
+                &registry,
+                IntGauge::new(
+                    "set_anchor_avg_anchor_time_ms",
+                    "Average anchor time in milliseconds",
+                ),
+            ),
+            avg_confirmation_latency_ms: register(
+                &registry,
+                IntGauge::new(
+                    "set_anchor_avg_confirmation_latency_ms",
+                    "Average time from broadcast to a mined receipt, in milliseconds",
+                ),
+            ),
+            total_gas_used: register(
+                &registry,
+                IntCounter::new(
+                    "set_anchor_gas_used_total",
+                    "Gas used by mined anchor transactions",
+                ),
+            ),
+            wei_spent: register(
+                &registry,
+                Gauge::new(
+                    "set_anchor_wei_spent",
+                    "Fees paid for anchor transactions since startup, including reverted ones",
+                ),
+            ),
+            commitments_skipped: register(
+                &registry,
+                IntCounterVec::new(
+                    Opts::new(
+                        "set_anchor_commitments_skipped_total",
+                        "Fetched commitments not submitted in a cycle, by /pending status",
+                    ),
+                    &["reason"],
+                ),
+            ),
+            cycle_commitments_skipped: register(
+                &registry,
+                IntGaugeVec::new(
+                    Opts::new(
+                        "set_anchor_cycle_commitments_skipped",
+                        "Fetched commitments the last cycle did not submit, by /pending status",
+                    ),
+                    &["reason"],
+                ),
+            ),
+            cycles: register(
+                &registry,
+                IntCounter::new("set_anchor_cycles_total", "Total anchor cycles completed"),
+            ),
+            cycles_by_status: register(
+                &registry,
+                IntCounterVec::new(
+                    Opts::new(
+                        "set_anchor_cycles_by_status_total",
+                        "Anchor cycles grouped by outcome",
+                    ),
+                    &["status"],
+                ),
+            ),
+            l2_connected: register(
+                &registry,
+                IntGauge::new("set_anchor_l2_connected", "Whether L2 is reachable"),
+            ),
+            sequencer_connected: register(
+                &registry,
+                IntGauge::new(
+                    "set_anchor_sequencer_connected",
+                    "Whether the sequencer API is reachable",
+                ),
+            ),
+            l2_connection_failures: register(
+                &registry,
+                IntCounter::new(
+                    "set_anchor_l2_connection_failures_total",
+                    "Total L2 connection failures",
+                ),
+            ),
+            sequencer_api_failures: register(
+                &registry,
+                IntCounter::new(
+                    "set_anchor_sequencer_api_failures_total",
+                    "Total sequencer API failures",
+                ),
+            ),
+            success_rate: register(
+                &registry,
+                Gauge::new("set_anchor_success_rate", "Ratio of successful anchors"),
+            ),
+            windowed_success_rate: register(
+                &registry,
+                GaugeVec::new(
+                    Opts::new(
+                        "set_anchor_windowed_success_rate",
+                        "Ratio of successful anchors over a rolling window (1 with no attempts)",
+                    ),
+                    &["window"],
+                ),
+            ),
+            cycle_success_rate: register(
+                &registry,
+                Gauge::new(
+                    "set_anchor_cycle_success_rate",
+                    "Ratio of successful cycles",
+                ),
+            ),
+            uptime_seconds: register(
+                &registry,
+                IntGauge::new("set_anchor_uptime_seconds", "Service uptime in seconds"),
+            ),
+            ready: register(
+                &registry,
+                IntGauge::new("set_anchor_ready", "Whether the service is ready"),
+            ),
+            health_status: register(
+                &registry,
+                IntGauge::new(
+                    "set_anchor_health_status",
+                    "Service health (0=healthy, 1=degraded, 2=unhealthy)",
+                ),
+            ),
+            component_health_status: register(
+                &registry,
+                IntGaugeVec::new(
+                    Opts::new(
+                        "set_anchor_component_health_status",
+                        "Health per component (0=healthy, 1=degraded, 2=unhealthy)",
+                    ),
+                    &["component"],
+                ),
+            ),
+            errors: register(
+                &registry,
+                IntCounterVec::new(
+                    Opts::new("set_anchor_errors_total", "Total errors by category"),
+                    &["category"],
+                ),
+            ),
+            errors_sum: register(
+                &registry,
+                IntCounter::new("set_anchor_errors_total_sum", "Sum of all errors"),
+            ),
+            circuit_breaker_state: register(
+                &registry,
+                IntGauge::new(
+                    "set_anchor_circuit_breaker_state",
+                    "Circuit breaker state (0=closed, 1=half-open, 2=open)",
+                ),
+            ),
+            dependency_circuit_breaker_state: register(
+                &registry,
+                IntGaugeVec::new(
+                    Opts::new(
+                        "set_anchor_dependency_circuit_breaker_state",
+                        "Circuit breaker state per dependency (0=closed, 1=half-open, 2=open)",
+                    ),
+                    &["dependency"],
+                ),
+            ),
+            circuit_breaker_open_skips: register(
+                &registry,
+                IntCounter::new(
+                    "set_anchor_circuit_breaker_open_skips_total",
+                    "Total cycles skipped due to open circuit breaker",
+                ),
+            ),
+            fee_bumps: register(
+                &registry,
+                IntCounter::new(
+                    "set_anchor_fee_bumps_total",
+                    "Total fee-bump replacements of stuck transactions",
+                ),
+            ),
+            stuck_txs: register(
+                &registry,
+                IntCounter::new(
+                    "set_anchor_stuck_txs_total",
+                    "Total transactions detected stuck in the mempool",
+                ),
+            ),
+            deferred_batches: register(
+                &registry,
+                IntGauge::new(
+                    "set_anchor_deferred_batches",
+                    "Batches waiting for gas to drop below the cap",
+                ),
+            ),
+            oldest_deferred_seconds: register(
+                &registry,
+                IntGauge::new(
+                    "set_anchor_oldest_deferred_seconds",
+                    "Age of the oldest deferred batch",
+                ),
+            ),
+            deferral_overrides: register(
+                &registry,
+                IntCounter::new(
+                    "set_anchor_deferral_overrides_total",
+                    "Deferred batches anchored above the cap after MAX_DEFER_SECS",
+                ),
+            ),
+            time_to_anchor: register(
+                &registry,
+                Histogram::with_opts(
+                    HistogramOpts::new(
+                        "set_anchor_time_to_anchor_seconds",
+                        "Time from the sequencer committing a batch to its anchor",
+                    )
+                    .buckets(TIME_TO_ANCHOR_BUCKETS.to_vec()),
+                ),
+            ),
+            time_to_anchor_quantile: register(
+                &registry,
+                GaugeVec::new(
+                    Opts::new(
+                        "set_anchor_time_to_anchor_quantile_seconds",
+                        "Commit-to-anchor time percentiles over a rolling window",
+                    ),
+                    &["window", "quantile"],
+                ),
+            ),
+            sla_violations: register(
+                &registry,
+                IntCounter::new(
+                    "set_anchor_sla_violations_total",
+                    "Batches anchored later than SLA_TARGET_SECS",
+                ),
+            ),
+            standby: register(
+                &registry,
+                IntGauge::new(
+                    "set_anchor_standby",
+                    "Whether the instance is in warm standby (not submitting)",
+                ),
+            ),
+            takeover_ready: register(
+                &registry,
+                IntGauge::new(
+                    "set_anchor_takeover_ready",
+                    "Whether a standby instance could take over immediately",
+                ),
+            ),
+            leadership_transitions: register(
+                &registry,
+                IntCounterVec::new(
+                    Opts::new(
+                        "set_anchor_leadership_transitions_total",
+                        "Submitter role changes from leader election",
+                    ),
+                    &["direction"],
+                ),
+            ),
+            paused: register(
+                &registry,
+                IntGauge::new(
+                    "set_anchor_paused",
+                    "Whether submissions are paused pending operator resume",
+                ),
+            ),
+            registry_paused: register(
+                &registry,
+                IntGauge::new(
+                    "set_anchor_registry_paused",
+                    "Whether the registry owner has paused commitBatch",
+                ),
+            ),
+            gas_spent_wei: register(
+                &registry,
+                Gauge::new(
+                    "set_anchor_gas_spent_wei",
+                    "Gas spent in the current budget window",
+                ),
+            ),
+            gas_budget_wei: register(
+                &registry,
+                Gauge::new(
+                    "set_anchor_gas_budget_wei",
+                    "Gas budget per window (0 = unlimited)",
+                ),
+            ),
+            budget_exhausted: register(
+                &registry,
+                IntGauge::new(
+                    "set_anchor_budget_exhausted",
+                    "Whether submissions are stopped until the budget window resets",
+                ),
+            ),
+            cycle_duration: register(
+                &registry,
+                Histogram::with_opts(
+                    HistogramOpts::new(
+                        "set_anchor_cycle_duration_seconds",
+                        "Time spent in each anchor cycle",
+                    )
+                    .buckets(CYCLE_DURATION_BUCKETS.to_vec()),
+                ),
+            ),
+            transactions_sent: register(
+                &registry,
+                IntCounterVec::new(
+                    Opts::new(
+                        "set_anchor_transactions_sent_total",
+                        "Transactions broadcast by kind",
+                    ),
+                    &["kind"],
+                ),
+            ),
+            gas_used: register(
+                &registry,
+                Histogram::with_opts(
+                    HistogramOpts::new("set_anchor_gas_used", "Gas used per anchor transaction")
+                        .buckets(GAS_USED_BUCKETS.to_vec()),
+                ),
+            ),
+            effective_gas_price_gwei: register(
+                &registry,
+                Histogram::with_opts(
+                    HistogramOpts::new(
+                        "set_anchor_effective_gas_price_gwei",
+                        "Effective gas price paid per anchor transaction, in gwei",
+                    )
+                    .buckets(GAS_PRICE_GWEI_BUCKETS.to_vec()),
+                ),
+            ),
+            gas_price_median_wei: register(
+                &registry,
+                Gauge::new(
+                    "set_anchor_gas_price_median_wei",
+                    "Median L2 gas price over GAS_SAMPLE_WINDOW_SECS (0 until enough samples)",
+                ),
+            ),
+            throttled_batches: register(
+                &registry,
+                IntGauge::new(
+                    "set_anchor_throttled_batches",
+                    "Batches held back by MAX_ANCHORS_PER_HOUR",
+                ),
+            ),
+            throttle_delay: register(
+                &registry,
+                Histogram::with_opts(
+                    HistogramOpts::new(
+                        "set_anchor_throttle_delay_seconds",
+                        "Time batches held back by MAX_ANCHORS_PER_HOUR waited before anchoring",
+                    )
+                    .buckets(THROTTLE_DELAY_BUCKETS.to_vec()),
+                ),
+            ),
+            wallet_balance_wei: register(
+                &registry,
+                Gauge::new(
+                    "set_anchor_wallet_balance_wei",
+                    "Signer balance at the last check",
+                ),
+            ),
+            wallet_balance_low: register(
+                &registry,
+                IntGauge::new(
+                    "set_anchor_wallet_balance_low",
+                    "Whether the signer balance is below LOW_BALANCE_THRESHOLD_ETH",
+                ),
+            ),
+            l2_head_block: register(
+                &registry,
+                IntGauge::new("set_anchor_l2_head_block", "Latest L2 block number"),
+            ),
+            l2_head_age_seconds: register(
+                &registry,
+                IntGauge::new(
+                    "set_anchor_l2_head_age_seconds",
+                    "Seconds since the latest L2 block was produced",
+                ),
+            ),
+            rpc_requests: register(
+                &registry,
+                IntCounterVec::new(
+                    Opts::new(
+                        "set_anchor_rpc_requests_total",
+                        "L2 RPC requests by endpoint and outcome",
+                    ),
+                    &["endpoint", "outcome"],
+                ),
+            ),
+            rpc_latency: register(
+                &registry,
+                HistogramVec::new(
+                    HistogramOpts::new(
+                        "set_anchor_rpc_latency_seconds",
+                        "L2 RPC request latency by endpoint",
+                    )
+                    .buckets(RPC_LATENCY_BUCKETS.to_vec()),
+                    &["endpoint"],
+                ),
+            ),
+            rpc_error_rate: register(
+                &registry,
+                GaugeVec::new(
+                    Opts::new(
+                        "set_anchor_rpc_error_rate",
+                        "Recent L2 RPC error rate by endpoint (0-1)",
+                    ),
+                    &["endpoint"],
+                ),
+            ),
+            rpc_endpoint_score: register(
+                &registry,
+                GaugeVec::new(
+                    Opts::new(
+                        "set_anchor_rpc_endpoint_score",
+                        "L2 RPC endpoint routing score (lower is preferred)",
+                    ),
+                    &["endpoint"],
+                ),
+            ),
+            sequence_anomalies: register(
+                &registry,
+                IntCounterVec::new(
+                    Opts::new(
+                        "set_anchor_sequence_anomalies_total",
+                        "Batches held back for breaking their store's sequence, by kind",
+                    ),
+                    &["kind"],
+                ),
+            ),
+            validation_failures: register(
+                &registry,
+                IntCounterVec::new(
+                    Opts::new(
+                        "set_anchor_validation_failures_total",
+                        "Commitments rejected before submission, by reason",
+                    ),
+                    &["reason"],
+                ),
+            ),
+            duplicate_deliveries: register(
+                &registry,
+                IntCounter::new(
+                    "set_anchor_duplicate_deliveries_total",
+                    "Re-delivered commitments recognised as already anchored without an RPC call",
+                ),
+            ),
+            awaiting_finality: register(
+                &registry,
+                IntGauge::new(
+                    "set_anchor_awaiting_finality",
+                    "Anchored batches not yet reported to the sequencer as finalized",
+                ),
+            ),
+            reconciliation_discrepancies: register(
+                &registry,
+                IntCounterVec::new(
+                    Opts::new(
+                        "set_anchor_reconciliation_discrepancies_total",
+                        "BatchCommitted logs that disagree with local anchors, by kind",
+                    ),
+                    &["kind"],
+                ),
+            ),
+            simulation_reverts: register(
+                &registry,
+                IntCounter::new(
+                    "set_anchor_simulation_reverts_total",
+                    "Transactions dropped because their eth_call simulation reverted",
+                ),
+            ),
+            access_lists: register(
+                &registry,
+                IntCounterVec::new(
+                    Opts::new(
+                        "set_anchor_access_lists_total",
+                        "Access list lookups for anchor transactions, by outcome",
+                    ),
+                    &["outcome"],
+                ),
+            ),
+            access_list_gas_saved: register(
+                &registry,
+                IntCounter::new(
+                    "set_anchor_access_list_gas_saved_total",
+                    "Estimated gas saved by attaching access lists to anchor transactions",
+                ),
+            ),
+            private_submissions: register(
+                &registry,
+                IntCounterVec::new(
+                    Opts::new(
+                        "set_anchor_private_submissions_total",
+                        "Transactions sent through the private relay, by outcome",
+                    ),
+                    &["outcome"],
+                ),
+            ),
+            blobs_posted: register(
+                &registry,
+                IntCounter::new(
+                    "set_anchor_blobs_posted_total",
+                    "EIP-4844 blobs carrying batch events posted with commitBatch (DA_MODE=blob)",
+                ),
+            ),
+            da_calldata_bytes: register(
+                &registry,
+                IntCounter::new(
+                    "set_anchor_da_calldata_bytes_total",
+                    "Compressed batch event bytes posted as calldata (DA_MODE=calldata)",
+                ),
+            ),
+            ipfs_pins: register(
+                &registry,
+                IntCounterVec::new(
+                    Opts::new(
+                        "set_anchor_ipfs_pins_total",
+                        "Batch payload uploads to IPFS, by outcome",
+                    ),
+                    &["outcome"],
+                ),
+            ),
+            celestia_submissions: register(
+                &registry,
+                IntCounterVec::new(
+                    Opts::new(
+                        "set_anchor_celestia_submissions_total",
+                        "Batch payload blob submissions to Celestia, by outcome",
+                    ),
+                    &["outcome"],
+                ),
+            ),
+            archive_uploads: register(
+                &registry,
+                IntCounterVec::new(
+                    Opts::new(
+                        "set_anchor_archive_uploads_total",
+                        "Anchored batch artifacts written to object storage, by outcome",
+                    ),
+                    &["outcome"],
+                ),
+            ),
+            audit_records: register(
+                &registry,
+                IntCounterVec::new(
+                    Opts::new(
+                        "set_anchor_audit_records_total",
+                        "Records appended to the audit log, by outcome",
+                    ),
+                    &["outcome"],
+                ),
+            ),
+            indexed_batches: register(
+                &registry,
+                IntCounter::new(
+                    "set_anchor_indexed_batches_total",
+                    "BatchCommitted logs written to the indexer database",
+                ),
+            ),
+            indexer_block: register(
+                &registry,
+                IntGauge::new(
+                    "set_anchor_indexer_block",
+                    "Last block scanned into the indexer database",
+                ),
+            ),
+            indexer_errors: register(
+                &registry,
+                IntCounter::new(
+                    "set_anchor_indexer_errors_total",
+                    "Indexer scans that failed and will be retried",
+                ),
+            ),
+            registry,
+        };
+
+        // Export the fixed outcomes and error categories at zero before the first one happens
+        for status in ["success", "failed"] {
+            metrics.batches.with_label_values(&[status]);
+            metrics.cycles_by_status.with_label_values(&[status]);
+        }
+        for category in [
+            "config",
+            "l2_connection",
+            "sequencer_api",
+            "transaction",
+            "authorization",
+            "validation",
+            "internal",
+        ] {
+            metrics.errors.with_label_values(&[category]);
+        }
+        metrics
+    }
+
+    /// Record how long an anchor cycle took
+    pub fn observe_cycle(&self, duration: Duration) {
+        self.cycle_duration.observe(duration.as_secs_f64());
+    }
+
+    /// Count a broadcast transaction (`commit`, `aggregate`, `replacement` or `cancel`)
+    pub fn record_transaction_sent(&self, kind: &str) {
+        self.transactions_sent.with_label_values(&[kind]).inc();
+    }
+
+    /// Record the gas used and price paid by a mined anchor transaction
+    pub fn observe_receipt(&self, gas_used: u64, effective_gas_price_wei: u128) {
+        self.gas_used.observe(gas_used as f64);
+        self.effective_gas_price_gwei
+            .observe(effective_gas_price_wei as f64 / WEI_PER_GWEI);
+    }
+
+    /// Record one L2 RPC request and the endpoint's updated health
+    pub fn observe_rpc_request(
+        &self,
+        endpoint: &str,
+        latency: Duration,
+        failed: bool,
+        error_rate: f64,
+        score: f64,
+    ) {
+        let outcome = if failed { "error" } else { "ok" };
+        self.rpc_requests
+            .with_label_values(&[endpoint, outcome])
+            .inc();
+        self.rpc_latency
+            .with_label_values(&[endpoint])
+            .observe(latency.as_secs_f64());
+        self.rpc_error_rate
+            .with_label_values(&[endpoint])
+            .set(error_rate);
+        self.rpc_endpoint_score
+            .with_label_values(&[endpoint])
+            .set(score);
+    }
+
+    /// Count a commitment rejected before submission
+    ///
+    /// Breaks in a store's state-root or sequence chain are also counted under
+    /// `set_anchor_sequence_anomalies_total`.
+    pub fn record_validation_failure(&self, reason: &str, sequence_anomaly: bool) {
+        self.validation_failures.with_label_values(&[reason]).inc();
+        if sequence_anomaly {
+            self.sequence_anomalies.with_label_values(&[reason]).inc();
+        }
+    }
+
+    /// Count a cycle skipped because a circuit breaker is open; it also counts as failed
+    pub fn record_open_circuit_skip(&self) {
+        self.cycles_by_status.with_label_values(&["failed"]).inc();
+        self.circuit_breaker_open_skips.inc();
+    }
+
+    /// Count an error reported to `HealthState::record_error` under its category
+    pub fn record_error(&self, category: &str) {
+        self.errors.with_label_values(&[category]).inc();
+        self.errors_sum.inc();
+    }
+
+    /// Prometheus text exposition of every registered metric
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        // Encoding into a Vec only fails on malformed metric families
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            tracing::warn!(error = %e, "Failed to encode metrics");
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+impl Default for AnchorMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for AnchorMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnchorMetrics").finish_non_exhaustive()
+    }
+}
+
+fn register<M>(registry: &Registry, metric: prometheus::Result<M>) -> M
+where
+    M: prometheus::core::Collector + Clone + 'static,
+{
+    let metric = metric.expect("metric options are valid");
+    registry
+        .register(Box::new(metric.clone()))
+        .expect("metric names are unique");
+    metric
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_includes_histogram_and_labels() {
+        let metrics = AnchorMetrics::new();
+        metrics.observe_cycle(Duration::from_millis(700));
+        metrics.record_transaction_sent("commit");
+        metrics.events.inc_by(5);
+
+        let rendered = metrics.render();
+        assert!(rendered.contains("set_anchor_cycle_duration_seconds_bucket{le=\"1\"} 1"));
+        assert!(rendered.contains("set_anchor_cycle_duration_seconds_count 1"));
+        assert!(rendered.contains("set_anchor_transactions_sent_total{kind=\"commit\"} 1"));
+        assert!(rendered.contains("set_anchor_events_total 5"));
+    }
+
+    #[test]
+    fn test_observe_receipt_converts_price_to_gwei() {
+        let metrics = AnchorMetrics::new();
+        metrics.observe_receipt(120_000, 50_000_000);
+
+        let rendered = metrics.render();
+        assert!(rendered.contains("set_anchor_gas_used_bucket{le=\"100000\"} 0"));
+        assert!(rendered.contains("set_anchor_gas_used_bucket{le=\"150000\"} 1"));
+        assert!(rendered.contains("set_anchor_gas_used_sum 120000"));
+        assert!(rendered.contains("set_anchor_effective_gas_price_gwei_bucket{le=\"0.01\"} 0"));
+        assert!(rendered.contains("set_anchor_effective_gas_price_gwei_bucket{le=\"0.05\"} 1"));
+    }
+}
@@ -0,0 +1,176 @@
+//! Nonce manager for the anchor signer
+//!
+//! Alloy's default nonce filler asks the node for the pending transaction count
+//! on every send, so two transactions broadcast concurrently from the same
+//! signer can be handed the same nonce. This manager hands out nonces from a
+//! local counter instead, seeded from the node on first use.
+//!
+//! The local counter can drift from the chain: a broadcast can fail after its
+//! nonce was allocated, a transaction can be dropped from the mempool, or
+//! another process can send from the same key. [`SharedNonceManager::reconcile`]
+//! compares the counter against `eth_getTransactionCount` and heals both
+//! directions, so a gap never stalls every later transaction.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use alloy::{
+    network::Network,
+    primitives::Address,
+    providers::{fillers::NonceManager, Provider},
+    transports::{Transport, TransportResult},
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+/// Locally tracked nonce state for one signer
+#[derive(Debug, Clone, Default)]
+struct AccountNonce {
+    /// Next nonce to hand out; `None` until seeded from the node
+    next: Option<u64>,
+    chain_latest: Option<u64>,
+    chain_pending: Option<u64>,
+    /// Times the local counter was behind the node (another sender used the key)
+    desyncs: u64,
+    /// Times the local counter was ahead of the node (allocated nonces never landed)
+    gaps_healed: u64,
+    last_reconciled: Option<DateTime<Utc>>,
+}
+
+/// Point-in-time view of a signer's nonce state, served on `/stats`
+#[derive(Debug, Clone, Serialize)]
+pub struct NonceSnapshot {
+    pub address: String,
+    /// Next nonce the service will use
+    pub local_next: Option<u64>,
+    /// Transactions mined from this signer at the last reconcile
+    pub chain_latest: Option<u64>,
+    /// Transactions mined or in the node's mempool at the last reconcile
+    pub chain_pending: Option<u64>,
+    pub desyncs: u64,
+    pub gaps_healed: u64,
+    pub last_reconciled: Option<String>,
+}
+
+/// Nonce manager shared between the provider's nonce filler and the service
+#[derive(Clone, Debug, Default)]
+pub struct SharedNonceManager {
+    accounts: Arc<Mutex<HashMap<Address, AccountNonce>>>,
+}
+
+impl SharedNonceManager {
+    /// Create an empty manager; nonces are fetched from the node on first use
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget cached nonces so the next transaction re-reads the pending count
+    pub async fn resync(&self) {
+        for account in self.accounts.lock().await.values_mut() {
+            account.next = None;
+        }
+    }
+
+    /// Next nonce that would be handed out for `address`, if cached
+    pub async fn peek(&self, address: Address) -> Option<u64> {
+        self.accounts
+            .lock()
+            .await
+            .get(&address)
+            .and_then(|account| account.next)
+    }
+
+    /// Compare the local counter with the node and heal any drift
+    ///
+    /// Must be called while no submission from `address` is in flight; a nonce
+    /// allocated but not yet broadcast would otherwise look like a gap.
+    pub async fn reconcile<P, T, N>(
+        &self,
+        provider: &P,
+        address: Address,
+    ) -> TransportResult<NonceSnapshot>
+    where
+        P: Provider<T, N>,
+        N: Network,
+        T: Transport + Clone,
+    {
+        // Hold the lock so nothing is allocated between the fetch and the fix-up
+        let mut accounts = self.accounts.lock().await;
+        let latest = provider.get_transaction_count(address).latest().await?;
+        let pending = provider.get_transaction_count(address).pending().await?;
+
+        let account = accounts.entry(address).or_default();
+        match account.next {
+            Some(next) if next < pending => {
+                warn!(
+                    address = %address,
+                    local_next = next,
+                    chain_pending = pending,
+                    "Local nonce behind node; another sender may be using this key"
+                );
+                account.desyncs += 1;
+                account.next = Some(pending);
+            }
+            Some(next) if next > pending => {
+                warn!(
+                    address = %address,
+                    local_next = next,
+                    chain_pending = pending,
+                    "Nonce gap detected; rewinding to the node's pending count"
+                );
+                account.gaps_healed += 1;
+                account.next = Some(pending);
+            }
+            _ => {}
+        }
+        account.chain_latest = Some(latest);
+        account.chain_pending = Some(pending);
+        account.last_reconciled = Some(Utc::now());
+
+        Ok(snapshot(address, account))
+    }
+
+    /// Current state for `address` without contacting the node
+    pub async fn snapshot(&self, address: Address) -> NonceSnapshot {
+        let accounts = self.accounts.lock().await;
+        match accounts.get(&address) {
+            Some(account) => snapshot(address, account),
+            None => snapshot(address, &AccountNonce::default()),
+        }
+    }
+}
+
+fn snapshot(address: Address, account: &AccountNonce) -> NonceSnapshot {
+    NonceSnapshot {
+        address: address.to_string(),
+        local_next: account.next,
+        chain_latest: account.chain_latest,
+        chain_pending: account.chain_pending,
+        desyncs: account.desyncs,
+        gaps_healed: account.gaps_healed,
+        last_reconciled: account.last_reconciled.map(|t| t.to_rfc3339()),
+    }
+}
+
+#[async_trait]
+impl NonceManager for SharedNonceManager {
+    async fn get_next_nonce<P, T, N>(&self, provider: &P, address: Address) -> TransportResult<u64>
+    where
+        P: Provider<T, N>,
+        N: Network,
+        T: Transport + Clone,
+    {
+        // Hold the lock across the fetch so concurrent first uses cannot both seed
+        let mut accounts = self.accounts.lock().await;
+        let account = accounts.entry(address).or_default();
+        let nonce = match account.next {
+            Some(nonce) => nonce,
+            None => provider.get_transaction_count(address).pending().await?,
+        };
+        account.next = Some(nonce + 1);
+        Ok(nonce)
+    }
+}
@@ -0,0 +1,281 @@
+//! Private transaction submission
+//!
+//! With `PRIVATE_RELAY_URL` set, signed anchor transactions are sent to a
+//! private relay (Flashbots Protect, or a builder's private mempool endpoint)
+//! instead of the public mempool, so they cannot be front-run or sniped while
+//! pending. Only `eth_sendRawTransaction` goes to the relay; every other
+//! request still reaches the L2 node.
+//!
+//! A transaction the relay rejects, or cannot be reached for, is broadcast
+//! publicly straight away. One the relay accepts but that has no receipt after
+//! `PRIVATE_RELAY_TIMEOUT_SECS` is broadcast publicly with the same signed
+//! bytes, so a stalled relay only delays the anchor. Until then the node has
+//! never seen the transaction, which fee-bump replacements treat as nothing to
+//! replace.
+
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use alloy::{
+    primitives::{keccak256, Bytes, FixedBytes},
+    rpc::{
+        client::ClientBuilder,
+        json_rpc::{RequestPacket, ResponsePacket, SerializedRequest},
+    },
+    transports::{BoxTransport, TransportError, TransportFut},
+};
+use tower::Service;
+use tracing::{debug, info, warn};
+
+use crate::metrics::AnchorMetrics;
+
+/// JSON-RPC method routed to the relay
+const SEND_RAW_TRANSACTION: &str = "eth_sendRawTransaction";
+
+/// Transport that sends raw transactions to a private relay, falling back to `inner`
+#[derive(Clone)]
+pub struct PrivateRelayTransport {
+    inner: BoxTransport,
+    relay: BoxTransport,
+    /// Relay host for logs
+    label: String,
+    /// How long a relayed transaction may go without a receipt before it is made public
+    fallback_after: Duration,
+    metrics: Option<Arc<AnchorMetrics>>,
+}
+
+impl PrivateRelayTransport {
+    /// Route raw transactions to `relay`, and everything else to `inner`
+    pub fn new(
+        inner: BoxTransport,
+        relay: BoxTransport,
+        label: String,
+        fallback_after: Duration,
+    ) -> Self {
+        Self {
+            inner,
+            relay,
+            label,
+            fallback_after,
+            metrics: None,
+        }
+    }
+
+    /// Count submissions by outcome in `metrics`
+    pub fn with_metrics(mut self, metrics: Arc<AnchorMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    fn record(&self, outcome: &str) {
+        if let Some(ref metrics) = self.metrics {
+            metrics
+                .private_submissions
+                .with_label_values(&[outcome])
+                .inc();
+        }
+    }
+
+    async fn submit(
+        mut self,
+        request: SerializedRequest,
+    ) -> Result<ResponsePacket, TransportError> {
+        let raw = match request
+            .params()
+            .map(|params| serde_json::from_str::<(Bytes,)>(params.get()))
+        {
+            Some(Ok((raw,))) => raw,
+            _ => return self.inner.call(RequestPacket::Single(request)).await,
+        };
+        let tx_hash = keccak256(&raw);
+
+        match self
+            .relay
+            .call(RequestPacket::Single(request.clone()))
+            .await
+        {
+            Ok(response) if response.is_success() => {
+                self.record("relayed");
+                debug!(tx_hash = %tx_hash, relay = %self.label, "Sent transaction to private relay");
+                tokio::spawn(self.clone().fallback_if_unmined(tx_hash, raw));
+                Ok(response)
+            }
+            Ok(response) => {
+                self.record("rejected");
+                warn!(
+                    tx_hash = %tx_hash,
+                    relay = %self.label,
+                    error = ?response.as_error(),
+                    "Private relay rejected transaction; broadcasting publicly"
+                );
+                self.inner.call(RequestPacket::Single(request)).await
+            }
+            Err(e) => {
+                self.record("relay_error");
+                warn!(
+                    tx_hash = %tx_hash,
+                    relay = %self.label,
+                    error = %e,
+                    "Private relay unreachable; broadcasting publicly"
+                );
+                self.inner.call(RequestPacket::Single(request)).await
+            }
+        }
+    }
+
+    /// Broadcast `raw` publicly if it is still unmined after `fallback_after`
+    async fn fallback_if_unmined(self, tx_hash: FixedBytes<32>, raw: Bytes) {
+        tokio::time::sleep(self.fallback_after).await;
+        let client = ClientBuilder::default().transport(self.inner.clone(), false);
+        match client
+            .request::<_, Option<serde_json::Value>>("eth_getTransactionReceipt", (tx_hash,))
+            .await
+        {
+            Ok(Some(_)) => return,
+            Ok(None) => {}
+            Err(e) => {
+                debug!(tx_hash = %tx_hash, error = %e, "Failed to check relayed transaction; broadcasting publicly");
+            }
+        }
+
+        self.record("public_fallback");
+        info!(
+            tx_hash = %tx_hash,
+            relay = %self.label,
+            waited_secs = self.fallback_after.as_secs(),
+            "Relayed transaction not mined; broadcasting publicly"
+        );
+        if let Err(e) = client
+            .request::<_, FixedBytes<32>>(SEND_RAW_TRANSACTION, (raw,))
+            .await
+        {
+            // Typically "already known" or "nonce too low": it was mined or replaced meanwhile
+            debug!(tx_hash = %tx_hash, error = %e, "Public fallback broadcast failed");
+        }
+    }
+}
+
+impl Service<RequestPacket> for PrivateRelayTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        match request {
+            RequestPacket::Single(request) if request.method() == SEND_RAW_TRANSACTION => {
+                Box::pin(self.clone().submit(request))
+            }
+            request => {
+                let mut inner = self.inner.clone();
+                Box::pin(async move { inner.call(request).await })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::providers::{Provider, ProviderBuilder};
+    use alloy::transports::Transport;
+    use std::sync::Mutex;
+
+    fn http(url: &str) -> BoxTransport {
+        ClientBuilder::default()
+            .http(url.parse().unwrap())
+            .transport()
+            .clone()
+            .boxed()
+    }
+
+    type Calls = Arc<Mutex<Vec<String>>>;
+
+    async fn node(calls: Calls) -> wiremock::MockServer {
+        crate::tests::rpc_mock::start(move |method, params| {
+            calls.lock().unwrap().push(method.to_string());
+            match method {
+                "eth_blockNumber" => Ok(serde_json::json!("0x2a")),
+                "eth_getTransactionReceipt" => Ok(serde_json::Value::Null),
+                "eth_sendRawTransaction" => {
+                    let raw: Bytes = serde_json::from_value(params[0].clone()).unwrap();
+                    Ok(serde_json::json!(keccak256(&raw)))
+                }
+                other => Err(format!("unexpected method {}", other)),
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_raw_transactions_go_to_relay_then_fall_back() {
+        let node_calls = Calls::default();
+        let relay_calls = Calls::default();
+        let public = node(node_calls.clone()).await;
+        let relay = node(relay_calls.clone()).await;
+
+        let metrics = Arc::new(AnchorMetrics::new());
+        let transport = PrivateRelayTransport::new(
+            http(&public.uri()),
+            http(&relay.uri()),
+            "relay".to_string(),
+            Duration::from_millis(50),
+        )
+        .with_metrics(metrics.clone());
+        let provider =
+            ProviderBuilder::new().on_client(ClientBuilder::default().transport(transport, false));
+
+        assert_eq!(provider.get_block_number().await.unwrap(), 42);
+        let raw = [0x02, 0xc0];
+        let pending = provider.send_raw_transaction(&raw).await.unwrap();
+        assert_eq!(*pending.tx_hash(), keccak256(raw));
+        assert_eq!(*relay_calls.lock().unwrap(), vec!["eth_sendRawTransaction"]);
+        assert_eq!(*node_calls.lock().unwrap(), vec!["eth_blockNumber"]);
+
+        // Unmined after the timeout: the same bytes go to the public node
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(
+            *node_calls.lock().unwrap(),
+            vec![
+                "eth_blockNumber",
+                "eth_getTransactionReceipt",
+                "eth_sendRawTransaction"
+            ]
+        );
+        let outcomes = &metrics.private_submissions;
+        assert_eq!(outcomes.with_label_values(&["relayed"]).get(), 1);
+        assert_eq!(outcomes.with_label_values(&["public_fallback"]).get(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_relay_broadcasts_publicly() {
+        let node_calls = Calls::default();
+        let public = node(node_calls.clone()).await;
+
+        let metrics = Arc::new(AnchorMetrics::new());
+        let transport = PrivateRelayTransport::new(
+            http(&public.uri()),
+            http("http://127.0.0.1:1"),
+            "relay".to_string(),
+            Duration::from_secs(60),
+        )
+        .with_metrics(metrics.clone());
+        let provider =
+            ProviderBuilder::new().on_client(ClientBuilder::default().transport(transport, false));
+
+        let pending = provider.send_raw_transaction(&[0x02, 0xc0]).await.unwrap();
+        assert_eq!(*pending.tx_hash(), keccak256([0x02, 0xc0]));
+        assert_eq!(*node_calls.lock().unwrap(), vec!["eth_sendRawTransaction"]);
+        assert_eq!(
+            metrics
+                .private_submissions
+                .with_label_values(&["relay_error"])
+                .get(),
+            1
+        );
+    }
+}
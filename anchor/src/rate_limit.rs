@@ -0,0 +1,137 @@
+//! Client-side rate limiting for outgoing calls
+//!
+//! A token bucket holding up to one second's worth of requests. Callers wait
+//! for a token instead of failing, so a large backlog is spread out rather
+//! than tripping the provider's own limit and coming back as a burst of 429s.
+//! Used by the L2 RPC transport (`L2_RPC_RATE_LIMIT`) and the sequencer
+//! clients (`SEQUENCER_RATE_LIMIT`).
+
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use alloy::{
+    rpc::json_rpc::{RequestPacket, ResponsePacket},
+    transports::{BoxTransport, TransportError, TransportFut},
+};
+use tokio::time::Instant;
+use tower::Service;
+use tracing::debug;
+
+/// Token bucket shared by every caller of one dependency
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// Tokens added per second
+    rate: f64,
+    /// Bucket capacity
+    burst: f64,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled: Instant,
+}
+
+impl RateLimiter {
+    /// Allow `per_second` requests per second, with bursts of up to the same number
+    pub fn new(per_second: f64) -> Self {
+        let burst = per_second.max(1.0);
+        Self {
+            rate: per_second,
+            burst,
+            bucket: Mutex::new(Bucket {
+                tokens: burst,
+                refilled: Instant::now(),
+            }),
+        }
+    }
+
+    /// Limiter for a configured rate (`None` when the rate is 0 = unlimited)
+    pub fn from_rate(per_second: f64) -> Option<Arc<Self>> {
+        (per_second > 0.0).then(|| Arc::new(Self::new(per_second)))
+    }
+
+    /// Wait until a request may be sent
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().unwrap_or_else(|p| p.into_inner());
+                let now = Instant::now();
+                let elapsed = now.saturating_duration_since(bucket.refilled);
+                bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * self.rate).min(self.burst);
+                bucket.refilled = now;
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate)
+            };
+            debug!(
+                wait_ms = wait.as_millis() as u64,
+                "Rate limit reached; waiting"
+            );
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Transport that takes a token from `limiter` before each request
+#[derive(Clone)]
+pub struct RateLimitedTransport {
+    inner: BoxTransport,
+    limiter: Arc<RateLimiter>,
+}
+
+impl RateLimitedTransport {
+    pub fn new(inner: BoxTransport, limiter: Arc<RateLimiter>) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+impl Service<RequestPacket> for RateLimitedTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let mut inner = self.inner.clone();
+        let limiter = Arc::clone(&self.limiter);
+        Box::pin(async move {
+            limiter.acquire().await;
+            inner.call(request).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_burst_then_steady_rate() {
+        let limiter = RateLimiter::new(10.0);
+
+        let started = Instant::now();
+        for _ in 0..10 {
+            limiter.acquire().await;
+        }
+        assert!(started.elapsed() < Duration::from_millis(100));
+
+        for _ in 0..5 {
+            limiter.acquire().await;
+        }
+        assert!(started.elapsed() >= Duration::from_millis(400));
+    }
+
+    #[test]
+    fn test_zero_rate_is_unlimited() {
+        assert!(RateLimiter::from_rate(0.0).is_none());
+        assert!(RateLimiter::from_rate(2.5).is_some());
+    }
+}
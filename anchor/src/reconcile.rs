@@ -0,0 +1,249 @@
+//! Cross-checks `BatchCommitted` logs against what this service anchored
+//!
+//! Every anchor the service records is expected to show up as a registry
+//! event from the same transaction. Events nobody here submitted point at
+//! another writer (a second instance, a manual call); anchors with no event
+//! once their block has been scanned point at a reorg or a bad receipt.
+
+use std::collections::HashMap;
+
+use alloy::primitives::FixedBytes;
+use uuid::Uuid;
+
+use crate::client::CommittedBatchEvent;
+
+/// An anchor recorded by this service, waiting for its event
+#[derive(Debug, Clone)]
+struct ExpectedAnchor {
+    batch_id: Uuid,
+    tx_hash: FixedBytes<32>,
+    block_number: u64,
+}
+
+/// Disagreement between the registry's event log and local anchors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Discrepancy {
+    /// A batch committed by a transaction this service didn't send
+    External {
+        chain_batch_id: FixedBytes<32>,
+        tx_hash: FixedBytes<32>,
+        block_number: u64,
+    },
+    /// A local anchor whose block was scanned without finding its event
+    Missing {
+        batch_id: Uuid,
+        tx_hash: FixedBytes<32>,
+        block_number: u64,
+    },
+    /// The batch was committed, but by a different transaction than recorded
+    TxMismatch {
+        batch_id: Uuid,
+        expected: FixedBytes<32>,
+        actual: FixedBytes<32>,
+    },
+}
+
+impl Discrepancy {
+    /// Label for `set_anchor_reconciliation_discrepancies_total`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Discrepancy::External { .. } => "external",
+            Discrepancy::Missing { .. } => "missing",
+            Discrepancy::TxMismatch { .. } => "tx_mismatch",
+        }
+    }
+}
+
+/// Scan position and the anchors not yet seen on-chain
+#[derive(Debug)]
+pub struct BatchReconciler {
+    next_block: u64,
+    expected: HashMap<FixedBytes<32>, ExpectedAnchor>,
+}
+
+impl BatchReconciler {
+    /// Start scanning at `next_block`
+    pub fn new(next_block: u64) -> Self {
+        Self {
+            next_block,
+            expected: HashMap::new(),
+        }
+    }
+
+    /// First block the next scan covers
+    pub fn next_block(&self) -> u64 {
+        self.next_block
+    }
+
+    /// Anchors recorded but not yet matched to an event
+    pub fn pending(&self) -> usize {
+        self.expected.len()
+    }
+
+    /// Expect an event for an anchor this service recorded
+    ///
+    /// Anchors in blocks that were already scanned (recovered from event
+    /// history) are not tracked.
+    pub fn expect(
+        &mut self,
+        chain_batch_id: FixedBytes<32>,
+        batch_id: Uuid,
+        tx_hash: FixedBytes<32>,
+        block_number: u64,
+    ) {
+        if block_number < self.next_block {
+            return;
+        }
+        self.expected.insert(
+            chain_batch_id,
+            ExpectedAnchor {
+                batch_id,
+                tx_hash,
+                block_number,
+            },
+        );
+    }
+
+    /// Match the events of `next_block..=scanned_to` and advance past them
+    pub fn reconcile(
+        &mut self,
+        events: &[CommittedBatchEvent],
+        scanned_to: u64,
+    ) -> Vec<Discrepancy> {
+        let mut discrepancies = Vec::new();
+
+        for event in events {
+            match self.expected.remove(&event.chain_batch_id) {
+                Some(expected) if expected.tx_hash == event.tx_hash => {}
+                Some(expected) => discrepancies.push(Discrepancy::TxMismatch {
+                    batch_id: expected.batch_id,
+                    expected: expected.tx_hash,
+                    actual: event.tx_hash,
+                }),
+                None => discrepancies.push(Discrepancy::External {
+                    chain_batch_id: event.chain_batch_id,
+                    tx_hash: event.tx_hash,
+                    block_number: event.block_number,
+                }),
+            }
+        }
+
+        let mut missing: Vec<_> = self
+            .expected
+            .iter()
+            .filter(|(_, expected)| expected.block_number <= scanned_to)
+            .map(|(chain_batch_id, _)| *chain_batch_id)
+            .collect();
+        missing.sort_by_key(|chain_batch_id| self.expected[chain_batch_id].block_number);
+        for chain_batch_id in missing {
+            if let Some(expected) = self.expected.remove(&chain_batch_id) {
+                discrepancies.push(Discrepancy::Missing {
+                    batch_id: expected.batch_id,
+                    tx_hash: expected.tx_hash,
+                    block_number: expected.block_number,
+                });
+            }
+        }
+
+        self.next_block = scanned_to + 1;
+        discrepancies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(key: u8, tx: u8, block_number: u64) -> CommittedBatchEvent {
+        CommittedBatchEvent {
+            chain_batch_id: FixedBytes::from([key; 32]),
+            tenant_store_key: FixedBytes::ZERO,
+            events_root: FixedBytes::ZERO,
+            new_state_root: FixedBytes::ZERO,
+            tx_hash: FixedBytes::from([tx; 32]),
+            block_number,
+            log_index: 0,
+            sequence_start: 1,
+            sequence_end: 10,
+            event_count: 10,
+        }
+    }
+
+    #[test]
+    fn test_reconcile_classifies_discrepancies() {
+        let mut reconciler = BatchReconciler::new(100);
+        let (matched, mismatched, lost, later) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        reconciler.expect(
+            FixedBytes::from([1; 32]),
+            matched,
+            FixedBytes::from([0xa1; 32]),
+            101,
+        );
+        reconciler.expect(
+            FixedBytes::from([2; 32]),
+            mismatched,
+            FixedBytes::from([0xa2; 32]),
+            102,
+        );
+        reconciler.expect(
+            FixedBytes::from([3; 32]),
+            lost,
+            FixedBytes::from([0xa3; 32]),
+            103,
+        );
+        reconciler.expect(
+            FixedBytes::from([4; 32]),
+            later,
+            FixedBytes::from([0xa4; 32]),
+            120,
+        );
+        // Recovered from history before the scan started; never flagged
+        reconciler.expect(
+            FixedBytes::from([5; 32]),
+            Uuid::new_v4(),
+            FixedBytes::ZERO,
+            50,
+        );
+        assert_eq!(reconciler.pending(), 4);
+
+        let discrepancies = reconciler.reconcile(
+            &[
+                event(1, 0xa1, 101),
+                event(2, 0xb2, 102),
+                event(9, 0xb9, 104),
+            ],
+            110,
+        );
+
+        assert_eq!(
+            discrepancies,
+            vec![
+                Discrepancy::TxMismatch {
+                    batch_id: mismatched,
+                    expected: FixedBytes::from([0xa2; 32]),
+                    actual: FixedBytes::from([0xb2; 32]),
+                },
+                Discrepancy::External {
+                    chain_batch_id: FixedBytes::from([9; 32]),
+                    tx_hash: FixedBytes::from([0xb9; 32]),
+                    block_number: 104,
+                },
+                Discrepancy::Missing {
+                    batch_id: lost,
+                    tx_hash: FixedBytes::from([0xa3; 32]),
+                    block_number: 103,
+                },
+            ]
+        );
+        assert_eq!(reconciler.next_block(), 111);
+        assert_eq!(reconciler.pending(), 1);
+
+        assert!(reconciler.reconcile(&[event(4, 0xa4, 120)], 130).is_empty());
+        assert_eq!(reconciler.pending(), 0);
+    }
+}
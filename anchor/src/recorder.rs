@@ -0,0 +1,248 @@
+//! Off-path aggregation of anchor statistics
+//!
+//! The submit path reports results by pushing [`StatsEvent`]s onto an unbounded
+//! channel, which never waits. A dedicated task drains the channel and applies
+//! every queued event under a single write lock, so a slow `/metrics` scrape or
+//! a burst of concurrent anchors cannot stall submission on the stats lock.
+//! Counters in the Prometheus registry are advanced as each event is applied.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+use tokio::sync::{mpsc, oneshot, RwLock};
+use uuid::Uuid;
+
+use crate::metrics::AnchorMetrics;
+use crate::sla::TimeToAnchor;
+use crate::types::{AnchorStats, CostTotals};
+
+/// A stats update produced on the submit path
+#[derive(Debug)]
+pub enum StatsEvent {
+    AnchorSucceeded {
+        batch_id: Uuid,
+        tenant_id: Uuid,
+        event_count: u32,
+        anchor_time_ms: u64,
+    },
+    AnchorFailed {
+        tenant_id: Uuid,
+    },
+    NotificationFailed,
+    GasPriceSkip,
+    /// A stuck transaction was replaced with higher fees
+    FeeBump,
+    /// A transaction stayed pending past the stuck-block threshold
+    StuckTx,
+    /// Current size and age of the gas-price deferral queue
+    DeferralQueue {
+        depth: u64,
+        oldest_secs: u64,
+    },
+    /// A batch deferred past `max_defer_secs` was anchored above the gas cap
+    DeferralOverride,
+    /// A confirmed transaction's gas estimate against the gas it used
+    GasEstimate {
+        estimated: u64,
+        used: u64,
+    },
+    /// An anchor transaction was mined after `latency_ms`
+    Confirmed {
+        latency_ms: u64,
+        gas_used: u64,
+    },
+    /// Commitments a cycle fetched but did not submit, by `/pending` status;
+    /// sent every cycle, so an empty list clears the last cycle's counts
+    Skipped(Vec<(&'static str, u64)>),
+    /// Gas spend attributed to a tenant/store
+    AnchorCost {
+        tenant_id: Uuid,
+        store_id: Uuid,
+        cost: CostTotals,
+    },
+    /// Commit-to-anchor duration of an anchored batch, checked against `sla_target_secs`
+    TimeToAnchor {
+        sample: TimeToAnchor,
+        sla_target_secs: u64,
+    },
+    L2Healthy,
+    SequencerHealthy,
+    /// Acknowledged once every earlier event has been applied
+    Flush(oneshot::Sender<()>),
+}
+
+/// Non-blocking handle for recording stats from the hot path
+pub struct StatsRecorder {
+    tx: mpsc::UnboundedSender<StatsEvent>,
+    rx: Mutex<Option<mpsc::UnboundedReceiver<StatsEvent>>>,
+    started: AtomicBool,
+    /// Weak so the recorder does not keep the stats alive on its own
+    stats: Weak<RwLock<AnchorStats>>,
+    metrics: Option<Arc<AnchorMetrics>>,
+}
+
+impl StatsRecorder {
+    /// Create a recorder applying events to `stats`
+    ///
+    /// The aggregator task is spawned on first use, so the recorder can be built
+    /// outside a runtime.
+    pub fn new(stats: &Arc<RwLock<AnchorStats>>) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        Self {
+            tx,
+            rx: Mutex::new(Some(rx)),
+            started: AtomicBool::new(false),
+            stats: Arc::downgrade(stats),
+            metrics: None,
+        }
+    }
+
+    /// Count applied events in `metrics`
+    pub fn with_metrics(mut self, metrics: Arc<AnchorMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Queue an event without waiting
+    pub fn record(&self, event: StatsEvent) {
+        self.ensure_started();
+        // Sending only fails once the stats themselves are gone
+        let _ = self.tx.send(event);
+    }
+
+    /// Wait until every event recorded so far has been applied
+    pub async fn flush(&self) {
+        let (ack, done) = oneshot::channel();
+        self.record(StatsEvent::Flush(ack));
+        let _ = done.await;
+    }
+
+    fn ensure_started(&self) {
+        if self.started.load(Ordering::Acquire) {
+            return;
+        }
+        let Some(rx) = self.rx.lock().unwrap_or_else(|e| e.into_inner()).take() else {
+            return;
+        };
+        tokio::spawn(aggregate(rx, self.stats.clone(), self.metrics.clone()));
+        self.started.store(true, Ordering::Release);
+    }
+}
+
+async fn aggregate(
+    mut rx: mpsc::UnboundedReceiver<StatsEvent>,
+    stats: Weak<RwLock<AnchorStats>>,
+    metrics: Option<Arc<AnchorMetrics>>,
+) {
+    let mut acks = Vec::new();
+    while let Some(first) = rx.recv().await {
+        let Some(shared) = stats.upgrade() else {
+            return;
+        };
+        let mut stats = shared.write().await;
+        let mut next = Some(first);
+        while let Some(event) = next {
+            if let Some(ref metrics) = metrics {
+                count_event(metrics, &event);
+            }
+            match event {
+                StatsEvent::AnchorSucceeded {
+                    batch_id,
+                    tenant_id,
+                    event_count,
+                    anchor_time_ms,
+                } => {
+                    stats.record_success(anchor_time_ms);
+                    stats.total_events_anchored += event_count as u64;
+                    stats.last_batch_id = Some(batch_id);
+                    let tenant = stats.tenants.entry(tenant_id).or_default();
+                    tenant.batches_anchored += 1;
+                    tenant.events_anchored += event_count as u64;
+                }
+                StatsEvent::AnchorFailed { tenant_id } => {
+                    stats.record_anchor_failure();
+                    stats.tenants.entry(tenant_id).or_default().batches_failed += 1;
+                }
+                StatsEvent::NotificationFailed => stats.sequencer_api_failures += 1,
+                StatsEvent::GasPriceSkip => stats.record_gas_skip(),
+                StatsEvent::FeeBump => stats.fee_bumps += 1,
+                StatsEvent::StuckTx => stats.stuck_txs += 1,
+                StatsEvent::DeferralQueue { depth, oldest_secs } => {
+                    stats.deferred_batches = depth;
+                    stats.oldest_deferred_secs = oldest_secs;
+                }
+                StatsEvent::DeferralOverride => stats.deferral_overrides += 1,
+                StatsEvent::GasEstimate { estimated, used } => {
+                    stats.record_gas_estimate(estimated, used)
+                }
+                StatsEvent::Confirmed {
+                    latency_ms,
+                    gas_used,
+                } => stats.record_confirmation(latency_ms, gas_used),
+                StatsEvent::Skipped(counts) => {
+                    for (reason, count) in &counts {
+                        *stats.skipped_commitments.entry(reason).or_default() += count;
+                    }
+                    stats.last_cycle_skips = counts.into_iter().collect();
+                }
+                StatsEvent::AnchorCost {
+                    tenant_id,
+                    store_id,
+                    cost,
+                } => {
+                    stats.total_wei_spent = stats.total_wei_spent.saturating_add(cost.wei_spent);
+                    stats
+                        .costs
+                        .entry((tenant_id, store_id))
+                        .or_default()
+                        .add(&cost)
+                }
+                StatsEvent::TimeToAnchor {
+                    sample,
+                    sla_target_secs,
+                } => {
+                    let violated = stats.time_to_anchor.record(sample, sla_target_secs);
+                    if let Some(metrics) = metrics.as_deref().filter(|_| violated) {
+                        metrics.sla_violations.inc();
+                    }
+                }
+                StatsEvent::L2Healthy => stats.mark_l2_healthy(),
+                StatsEvent::SequencerHealthy => stats.mark_sequencer_healthy(),
+                StatsEvent::Flush(ack) => acks.push(ack),
+            }
+            next = rx.try_recv().ok();
+        }
+        drop(stats);
+
+        for ack in acks.drain(..) {
+            let _ = ack.send(());
+        }
+    }
+}
+
+/// Advance the registry counters an event feeds; SLA violations are counted
+/// once the sample has been checked against its target
+fn count_event(metrics: &AnchorMetrics, event: &StatsEvent) {
+    match event {
+        StatsEvent::AnchorSucceeded { event_count, .. } => {
+            metrics.batches.with_label_values(&["success"]).inc();
+            metrics.events.inc_by(u64::from(*event_count));
+        }
+        StatsEvent::AnchorFailed { .. } => metrics.batches.with_label_values(&["failed"]).inc(),
+        StatsEvent::NotificationFailed => metrics.sequencer_api_failures.inc(),
+        StatsEvent::GasPriceSkip => metrics.gas_price_skips.inc(),
+        StatsEvent::FeeBump => metrics.fee_bumps.inc(),
+        StatsEvent::StuckTx => metrics.stuck_txs.inc(),
+        StatsEvent::DeferralOverride => metrics.deferral_overrides.inc(),
+        StatsEvent::Confirmed { gas_used, .. } => metrics.total_gas_used.inc_by(*gas_used),
+        StatsEvent::Skipped(counts) => {
+            for (reason, count) in counts {
+                metrics
+                    .commitments_skipped
+                    .with_label_values(&[reason])
+                    .inc_by(*count);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,121 @@
+//! Error reporting to Sentry
+//!
+//! When `SENTRY_DSN` is set, critical and fatal [`AnchorError`]s are captured
+//! with their error code, retryability and (where known) the batch being
+//! anchored, tagged with the service release and `SENTRY_ENVIRONMENT`.
+//! Transient and warning-level errors stay in logs and `/errors` only. Without
+//! a DSN no client is bound and reporting is a no-op.
+
+use sentry::{ClientInitGuard, Level};
+use uuid::Uuid;
+
+use crate::config::AnchorConfig;
+use crate::error::{AnchorError, ErrorSeverity};
+use crate::types::BatchCommitment;
+
+/// Batch an error occurred while anchoring
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ErrorContext {
+    pub batch_id: Option<Uuid>,
+    pub tenant_id: Option<Uuid>,
+    pub store_id: Option<Uuid>,
+}
+
+impl From<&BatchCommitment> for ErrorContext {
+    fn from(commitment: &BatchCommitment) -> Self {
+        Self {
+            batch_id: Some(commitment.batch_id),
+            tenant_id: Some(commitment.tenant_id),
+            store_id: Some(commitment.store_id),
+        }
+    }
+}
+
+/// Start the Sentry client configured by `SENTRY_DSN`
+///
+/// Events are flushed when the returned guard is dropped, so hold it for the
+/// life of the process.
+pub fn init(config: &AnchorConfig) -> Option<ClientInitGuard> {
+    let dsn = config.sentry_dsn.as_deref()?;
+    let guard = sentry::init((
+        dsn,
+        sentry::ClientOptions {
+            release: sentry::release_name!(),
+            environment: config.sentry_environment.clone().map(Into::into),
+            ..Default::default()
+        },
+    ));
+    Some(guard)
+}
+
+/// Whether `error` is severe enough to report
+pub fn should_report(error: &AnchorError) -> bool {
+    matches!(
+        error.severity(),
+        ErrorSeverity::Critical | ErrorSeverity::Fatal
+    )
+}
+
+/// Capture `error` if it is critical or fatal
+pub fn report(error: &AnchorError, context: ErrorContext) {
+    if !should_report(error) {
+        return;
+    }
+
+    let level = match error.severity() {
+        ErrorSeverity::Fatal => Level::Fatal,
+        _ => Level::Error,
+    };
+    sentry::with_scope(
+        |scope| {
+            scope.set_level(Some(level));
+            scope.set_tag("error_code", error.error_code());
+            scope.set_tag("retryable", error.is_retryable());
+            if let Some(batch_id) = context.batch_id {
+                scope.set_tag("batch_id", batch_id);
+            }
+            if let Some(tenant_id) = context.tenant_id {
+                scope.set_tag("tenant_id", tenant_id);
+            }
+            if let Some(store_id) = context.store_id {
+                scope.set_tag("store_id", store_id);
+            }
+        },
+        || sentry::capture_error(error),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{AuthorizationError, L2Error};
+
+    #[test]
+    fn test_reports_only_critical_and_fatal_errors() {
+        let batch_id = Uuid::new_v4();
+        let events = sentry::test::with_captured_events(|| {
+            report(
+                &AnchorError::L2Connection(L2Error::RpcError("timeout".to_string())),
+                ErrorContext::default(),
+            );
+            report(
+                &AnchorError::Authorization(AuthorizationError::NotAuthorized {
+                    address: "0x1111111111111111111111111111111111111111".to_string(),
+                }),
+                ErrorContext {
+                    batch_id: Some(batch_id),
+                    ..ErrorContext::default()
+                },
+            );
+        });
+
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.level, Level::Fatal);
+        assert_eq!(
+            event.tags.get("batch_id").map(String::as_str),
+            Some(batch_id.to_string().as_str())
+        );
+        assert!(event.tags.contains_key("error_code"));
+    }
+}
@@ -0,0 +1,273 @@
+//! Health-scored routing across several L2 RPC endpoints
+//!
+//! With `L2_RPC_FALLBACK_URLS` set, every JSON-RPC request (reads and
+//! transaction broadcasts alike) goes to the endpoint with the best score:
+//! its recent latency plus a penalty for its recent error rate. A request that fails
+//! at the transport level is retried on the next best endpoint, so one node
+//! going down costs at most one slow call. Error rates decay over time, which
+//! lets a recovered endpoint win traffic back without explicit probing.
+//!
+//! JSON-RPC error responses (reverts, nonce errors) mean the node answered and
+//! count as successes. Subscriptions are not routed, so a pooled client always
+//! polls for receipts.
+
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use alloy::{
+    rpc::json_rpc::{RequestPacket, ResponsePacket},
+    transports::{BoxTransport, TransportError, TransportFut},
+};
+use tokio::time::Instant;
+use tower::Service;
+use tracing::{debug, warn};
+
+use crate::metrics::AnchorMetrics;
+
+/// Weight of the newest sample in the latency and error averages
+const SMOOTHING: f64 = 0.2;
+
+/// Score added at a 100% error rate, in milliseconds of latency
+const ERROR_PENALTY_MS: f64 = 5_000.0;
+
+/// Time for an idle endpoint's error rate to halve
+const ERROR_HALF_LIFE: Duration = Duration::from_secs(60);
+
+/// Rolling health of one endpoint
+#[derive(Debug, Clone, Copy)]
+struct EndpointHealth {
+    /// Average latency of successful requests, in milliseconds
+    latency_ms: f64,
+    /// Average failure rate between 0 and 1, as of `updated`
+    error_rate: f64,
+    updated: Instant,
+}
+
+impl EndpointHealth {
+    fn new(now: Instant) -> Self {
+        Self {
+            latency_ms: 0.0,
+            error_rate: 0.0,
+            updated: now,
+        }
+    }
+
+    fn error_rate_at(&self, now: Instant) -> f64 {
+        let idle = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.error_rate * 0.5f64.powf(idle / ERROR_HALF_LIFE.as_secs_f64())
+    }
+
+    /// Lower is better; unmeasured endpoints score best so they get tried
+    fn score_at(&self, now: Instant) -> f64 {
+        self.latency_ms + ERROR_PENALTY_MS * self.error_rate_at(now)
+    }
+
+    fn record(&mut self, latency: Duration, failed: bool, now: Instant) {
+        let error_rate = self.error_rate_at(now);
+        self.error_rate = error_rate + SMOOTHING * (f64::from(u8::from(failed)) - error_rate);
+        if !failed {
+            let latency_ms = latency.as_secs_f64() * 1000.0;
+            self.latency_ms = if self.latency_ms == 0.0 {
+                latency_ms
+            } else {
+                self.latency_ms + SMOOTHING * (latency_ms - self.latency_ms)
+            };
+        }
+        self.updated = now;
+    }
+}
+
+struct Endpoint {
+    /// Host (or socket path) used in logs and metric labels
+    label: String,
+    transport: BoxTransport,
+    health: Mutex<EndpointHealth>,
+}
+
+/// Transport that spreads requests over several endpoints by health score
+#[derive(Clone)]
+pub struct RpcEndpointPool {
+    endpoints: Arc<Vec<Endpoint>>,
+    metrics: Option<Arc<AnchorMetrics>>,
+}
+
+impl RpcEndpointPool {
+    /// Pool over `(label, transport)` pairs, in configuration order
+    pub fn new(endpoints: Vec<(String, BoxTransport)>) -> Self {
+        let now = Instant::now();
+        Self {
+            endpoints: Arc::new(
+                endpoints
+                    .into_iter()
+                    .map(|(label, transport)| Endpoint {
+                        label,
+                        transport,
+                        health: Mutex::new(EndpointHealth::new(now)),
+                    })
+                    .collect(),
+            ),
+            metrics: None,
+        }
+    }
+
+    /// Export per-endpoint request counts, latency and scores to `metrics`
+    pub fn with_metrics(mut self, metrics: Arc<AnchorMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Endpoint indices from healthiest to least healthy
+    ///
+    /// Ties keep configuration order, so the primary URL wins while all
+    /// endpoints are equally healthy.
+    fn ranked(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let scores: Vec<f64> = self
+            .endpoints
+            .iter()
+            .map(|endpoint| lock(&endpoint.health).score_at(now))
+            .collect();
+        let mut order: Vec<usize> = (0..self.endpoints.len()).collect();
+        order.sort_by(|a, b| scores[*a].total_cmp(&scores[*b]));
+        order
+    }
+
+    /// Label and current score of every endpoint, in configuration order
+    pub fn scores(&self) -> Vec<(String, f64)> {
+        let now = Instant::now();
+        self.endpoints
+            .iter()
+            .map(|endpoint| (endpoint.label.clone(), lock(&endpoint.health).score_at(now)))
+            .collect()
+    }
+
+    fn record(&self, endpoint: &Endpoint, latency: Duration, failed: bool) {
+        let health = {
+            let mut health = lock(&endpoint.health);
+            health.record(latency, failed, Instant::now());
+            *health
+        };
+        if let Some(ref metrics) = self.metrics {
+            metrics.observe_rpc_request(
+                &endpoint.label,
+                latency,
+                failed,
+                health.error_rate,
+                health.score_at(health.updated),
+            );
+        }
+    }
+
+    async fn dispatch(self, request: RequestPacket) -> Result<ResponsePacket, TransportError> {
+        let mut last_error = None;
+        for index in self.ranked() {
+            let endpoint = &self.endpoints[index];
+            let started = Instant::now();
+            match endpoint.transport.clone().call(request.clone()).await {
+                Ok(response) => {
+                    self.record(endpoint, started.elapsed(), false);
+                    return Ok(response);
+                }
+                Err(e) => {
+                    self.record(endpoint, started.elapsed(), true);
+                    warn!(
+                        endpoint = %endpoint.label,
+                        error = %e,
+                        "L2 RPC request failed; trying the next endpoint"
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+        debug!("Every L2 RPC endpoint failed the request");
+        Err(last_error.expect("pool has at least one endpoint"))
+    }
+}
+
+impl Service<RequestPacket> for RpcEndpointPool {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        Box::pin(self.clone().dispatch(request))
+    }
+}
+
+fn lock(health: &Mutex<EndpointHealth>) -> std::sync::MutexGuard<'_, EndpointHealth> {
+    // The guarded data is plain numbers, so a poisoned lock is still usable
+    health
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errors_outweigh_latency_and_decay() {
+        let now = Instant::now();
+        let mut fast = EndpointHealth::new(now);
+        let mut slow = EndpointHealth::new(now);
+        fast.record(Duration::from_millis(20), false, now);
+        slow.record(Duration::from_millis(200), false, now);
+        assert!(fast.score_at(now) < slow.score_at(now));
+
+        for _ in 0..3 {
+            fast.record(Duration::from_millis(20), true, now);
+        }
+        assert!(fast.score_at(now) > slow.score_at(now));
+
+        // Ten minutes without traffic and the failures are mostly forgotten
+        let later = now + Duration::from_secs(600);
+        assert!(fast.score_at(later) < slow.score_at(later));
+    }
+
+    #[tokio::test]
+    async fn test_failed_requests_move_to_the_next_endpoint() {
+        use alloy::providers::{Provider, ProviderBuilder};
+        use alloy::rpc::client::ClientBuilder;
+        use alloy::transports::Transport;
+
+        let healthy = crate::tests::rpc_mock::start(|method, _| match method {
+            "eth_blockNumber" => Ok(serde_json::json!("0x2a")),
+            other => Err(format!("unexpected method {}", other)),
+        })
+        .await;
+        let http = |url: String| {
+            ClientBuilder::default()
+                .http(url.parse().unwrap())
+                .transport()
+                .clone()
+                .boxed()
+        };
+
+        let metrics = Arc::new(AnchorMetrics::new());
+        let pool = RpcEndpointPool::new(vec![
+            ("down".to_string(), http("http://127.0.0.1:1".to_string())),
+            ("healthy".to_string(), http(healthy.uri())),
+        ])
+        .with_metrics(metrics.clone());
+        let provider = ProviderBuilder::new()
+            .on_client(ClientBuilder::default().transport(pool.clone(), false));
+
+        assert_eq!(provider.get_block_number().await.unwrap(), 42);
+        assert_eq!(provider.get_block_number().await.unwrap(), 42);
+
+        let scores = pool.scores();
+        assert_eq!(scores[0].0, "down");
+        assert!(scores[0].1 > scores[1].1);
+
+        let rendered = metrics.render();
+        assert!(rendered
+            .contains("set_anchor_rpc_requests_total{endpoint=\"down\",outcome=\"error\"} 1"));
+        assert!(rendered
+            .contains("set_anchor_rpc_requests_total{endpoint=\"healthy\",outcome=\"ok\"} 2"));
+    }
+}
@@ -0,0 +1,242 @@
+//! Cron-style anchoring schedule
+//!
+//! `ANCHOR_SCHEDULE` takes a five-field cron expression (minute, hour, day of
+//! month, month, day of week), evaluated in UTC. Submissions only happen during
+//! minutes it matches: `* 0-5 * * *` anchors between 00:00 and 06:00 UTC,
+//! `*/15 * * * *` in the first minute of every quarter hour. Fields accept `*`,
+//! single values, ranges `a-b`, steps `*/n` or `a-b/n`, and comma-separated
+//! lists. As in cron, a day matches if either the day of month or the day of
+//! week does when both are restricted; day of week 0 and 7 are both Sunday.
+
+use chrono::{DateTime, Datelike, Duration, DurationRound, NaiveDate, Timelike, Utc};
+
+/// Furthest ahead `next_window` looks; every valid expression matches within it
+const SEARCH_DAYS: i64 = 8 * 366;
+
+/// Parsed `ANCHOR_SCHEDULE`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnchorSchedule {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// Day of month was `*`
+    any_day_of_month: bool,
+    /// Day of week was `*`
+    any_day_of_week: bool,
+}
+
+impl AnchorSchedule {
+    /// Whether submissions are allowed during the minute containing `at`
+    pub fn allows(&self, at: DateTime<Utc>) -> bool {
+        self.day_matches(at.date_naive())
+            && bit(self.hours, at.hour())
+            && bit(self.minutes, at.minute())
+    }
+
+    /// Start of the first allowed minute after `at`
+    pub fn next_window(&self, at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = at.duration_trunc(Duration::minutes(1)).ok()? + Duration::minutes(1);
+        let limit = start + Duration::days(SEARCH_DAYS);
+        let mut candidate = start;
+        while candidate < limit {
+            if !self.day_matches(candidate.date_naive()) {
+                candidate = candidate.duration_trunc(Duration::days(1)).ok()? + Duration::days(1);
+            } else if !bit(self.hours, candidate.hour()) {
+                candidate = candidate.duration_trunc(Duration::hours(1)).ok()? + Duration::hours(1);
+            } else if !bit(self.minutes, candidate.minute()) {
+                candidate += Duration::minutes(1);
+            } else {
+                return Some(candidate);
+            }
+        }
+        None
+    }
+
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        if !bit(self.months, date.month()) {
+            return false;
+        }
+        let day_of_month = bit(self.days_of_month, date.day());
+        let day_of_week = bit(self.days_of_week, date.weekday().num_days_from_sunday());
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (true, true) => true,
+            (false, true) => day_of_month,
+            (true, false) => day_of_week,
+            (false, false) => day_of_month || day_of_week,
+        }
+    }
+}
+
+impl std::fmt::Display for AnchorSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+impl std::str::FromStr for AnchorSchedule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            anyhow::bail!(
+                "expected 5 fields (minute hour day-of-month month day-of-week), got {}",
+                fields.len()
+            );
+        };
+        let mut days_of_week = parse_field(day_of_week, "day of week", 0, 7)?;
+        // 7 is Sunday as well
+        if bit(days_of_week, 7) {
+            days_of_week |= 1;
+        }
+        let schedule = Self {
+            expression: fields.join(" "),
+            minutes: parse_field(minute, "minute", 0, 59)?,
+            hours: parse_field(hour, "hour", 0, 23)?,
+            days_of_month: parse_field(day_of_month, "day of month", 1, 31)?,
+            months: parse_field(month, "month", 1, 12)?,
+            days_of_week,
+            any_day_of_month: day_of_month == "*",
+            any_day_of_week: day_of_week == "*",
+        };
+        // Only impossible dates (such as 30 February) never come around
+        if schedule.next_window(DateTime::UNIX_EPOCH).is_none() {
+            anyhow::bail!("{} never matches", schedule.expression);
+        }
+        Ok(schedule)
+    }
+}
+
+fn bit(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+/// Bit mask of the values one field allows
+fn parse_field(field: &str, name: &str, min: u32, max: u32) -> anyhow::Result<u64> {
+    let number = |value: &str| -> anyhow::Result<u32> {
+        let parsed: u32 = value
+            .parse()
+            .map_err(|_| anyhow::anyhow!("invalid {} value: {}", name, value))?;
+        if !(min..=max).contains(&parsed) {
+            anyhow::bail!("{} {} is outside {}-{}", name, parsed, min, max);
+        }
+        Ok(parsed)
+    };
+
+    let mut mask = 0;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("invalid {} step: {}", name, step))?;
+                if step == 0 {
+                    anyhow::bail!("{} step must be > 0", name);
+                }
+                (range, Some(step))
+            }
+            None => (item, None),
+        };
+        let (first, last) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((first, last)) => (number(first)?, number(last)?),
+                // `a/n` runs from a to the end of the field
+                None if step.is_some() => (number(range)?, max),
+                None => {
+                    let value = number(range)?;
+                    (value, value)
+                }
+            },
+        };
+        if first > last {
+            anyhow::bail!("{} range {} runs backwards", name, range);
+        }
+        for value in (first..=last).step_by(step.unwrap_or(1) as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        // October 2026; the 18th is a Sunday
+        Utc.with_ymd_and_hms(2026, 10, day, hour, minute, 30)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_nightly_window() {
+        let schedule: AnchorSchedule = "* 0-5 * * *".parse().unwrap();
+        assert!(schedule.allows(at(15, 0, 0)));
+        assert!(schedule.allows(at(15, 5, 59)));
+        assert!(!schedule.allows(at(15, 6, 0)));
+        assert_eq!(
+            schedule.next_window(at(15, 13, 45)),
+            Some(Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap())
+        );
+        // Inside the window the next allowed minute is simply the following one
+        assert_eq!(
+            schedule.next_window(at(15, 2, 10)),
+            Some(Utc.with_ymd_and_hms(2026, 10, 15, 2, 11, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_steps_lists_and_weekdays() {
+        let quarter_hours: AnchorSchedule = "*/15 * * * *".parse().unwrap();
+        assert!(quarter_hours.allows(at(15, 9, 30)));
+        assert!(!quarter_hours.allows(at(15, 9, 31)));
+        assert_eq!(
+            quarter_hours.next_window(at(15, 9, 31)),
+            Some(Utc.with_ymd_and_hms(2026, 10, 15, 9, 45, 0).unwrap())
+        );
+
+        let weekends: AnchorSchedule = "0,30 12 * * 6,7".parse().unwrap();
+        assert!(weekends.allows(at(18, 12, 30)));
+        assert!(!weekends.allows(at(15, 12, 30)));
+        assert_eq!(
+            weekends.next_window(at(15, 8, 0)),
+            Some(Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap())
+        );
+
+        // Either day field matches when both are restricted
+        let first_or_monday: AnchorSchedule = "0 0 1 * 1".parse().unwrap();
+        assert!(first_or_monday.allows(at(1, 0, 0)));
+        assert!(first_or_monday.allows(at(19, 0, 0)));
+        assert!(!first_or_monday.allows(at(20, 0, 0)));
+    }
+
+    #[test]
+    fn test_rejects_invalid_expressions() {
+        for expression in [
+            "* * * *",
+            "60 * * * *",
+            "* 6-2 * * *",
+            "*/0 * * * *",
+            "* * 30 2 *",
+            "x * * * *",
+        ] {
+            assert!(
+                expression.parse::<AnchorSchedule>().is_err(),
+                "{} should be rejected",
+                expression
+            );
+        }
+        assert_eq!(
+            "  * 0-5  * * * "
+                .parse::<AnchorSchedule>()
+                .unwrap()
+                .to_string(),
+            "* 0-5 * * *"
+        );
+    }
+}
@@ -1,34 +1,91 @@
 //! Main anchor service implementation
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
 use alloy::{
-    primitives::{Address, U256},
+    primitives::{Address, Bytes, FixedBytes, U256},
     providers::Provider,
-    transports::http::Http,
+    transports::BoxTransport,
 };
 use anyhow::Result;
-use chrono::Utc;
-use tokio::sync::RwLock;
-use tracing::{debug, error, info, warn};
+use chrono::{DateTime, Utc};
+use futures::stream::{self, BoxStream, StreamExt};
+use tokio::sync::{Notify, RwLock};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, info_span, warn, Instrument};
 use uuid::Uuid;
 
+#[cfg(feature = "indexer")]
+use crate::indexer::{run_indexer, BatchIndex, IndexerSettings};
 use crate::{
-    client::{create_provider, AnchoredBatchMetadata, RegistryClient, SequencerApiClient},
+    alert::{Alert, AlertHook},
+    archive::{AnchorArchive, ArchiveOutcome, ObjectArchive},
+    audit::{AuditEvent, AuditLog},
+    budget::GasBudget,
+    celestia::CelestiaClient,
+    client::{
+        batch_id_to_bytes32, create_provider_with_endpoints, encode_commit_batch,
+        AnchoredBatchMetadata, CommittedBatch, CommittedBatchEvent, GasLimitPolicy, L2Transport,
+        ReceiptOutcome, RegistryClient, RegistryControlEvent, SequencerApi, SequencerApiClient,
+    },
     config::AnchorConfig,
+    da::{self, DaAttachment},
+    dedup::AnchoredCache,
     error::{
         AnchorError, AuthorizationError, ConfigError, L2Error, SequencerApiError, TransactionError,
+        ValidationError,
+    },
+    fees,
+    gas_history::GasHistory,
+    gas_oracle::GasOracle,
+    health::{
+        BatchLookup, HealthState, L2Head, PendingBatch, PendingQueue, PendingStatus,
+        RegistryStatus, StandbyStatus, WalletStatus,
     },
-    health::HealthState,
+    ipfs::IpfsPinner,
+    journal::{AnchorJournal, HistoryEntry, PauseStatus},
+    leader::{self, LeaderElection, LeaderRole},
+    merkle,
+    metrics::AnchorMetrics,
+    nonce::SharedNonceManager,
+    oauth::OAuthTokenSource,
+    rate_limit::RateLimiter,
+    reconcile::{BatchReconciler, Discrepancy},
+    recorder::{StatsEvent, StatsRecorder},
+    reporting::{self, ErrorContext},
+    schedule::AnchorSchedule,
+    sla::TimeToAnchor,
+    source::{CommitmentSource, SequencerCommitmentSource},
+    throttle::AnchorThrottle,
+    trace_context::TraceContext,
     types::{
-        AnchorNotification, AnchorResult, AnchorStats, BatchCommitment, CircuitBreaker,
-        CircuitBreakerState, ErrorType,
+        AnchorCostEstimate, AnchorNotification, AnchorRecord, AnchorResult, AnchorStats,
+        BatchCommitment, BatchIdScheme, CelestiaBlobRef, CircuitBreaker, CircuitBreakerState,
+        CostTotals, DaMode, Dependency, DependencyBreaker, ErrorType, FinalityMode,
+        FinalizedNotification,
     },
+    validate,
 };
+#[cfg(feature = "grpc")]
+use crate::{grpc::SequencerGrpcClient, types::SequencerProtocol};
+
+type RpcTransport = BoxTransport;
+
+/// How often block height is checked while watching for stuck transactions
+const STUCK_CHECK_INTERVAL: Duration = Duration::from_secs(2);
 
-type HttpTransport = Http<reqwest::Client>;
+/// Pause between fetches that waited for work and found none
+const IDLE_REFETCH_DELAY: Duration = Duration::from_secs(1);
+
+/// Registry control state we expect while watching control events
+struct RegistryGuard {
+    /// Expected owner, when `watch_registry_ownership` is set
+    owner: Option<Address>,
+    next_block: u64,
+}
 
 enum AnchorCycleOutcome {
     Healthy(Vec<AnchorResult>),
@@ -38,68 +95,446 @@ enum AnchorCycleOutcome {
 /// Anchor service that bridges sequencer to on-chain registry
 pub struct AnchorService {
     config: AnchorConfig,
-    sequencer_client: SequencerApiClient,
+    sequencer_client: Arc<dyn SequencerApi>,
+    source: Arc<dyn CommitmentSource>,
     stats: Arc<RwLock<AnchorStats>>,
+    recorder: StatsRecorder,
     health_state: Option<Arc<HealthState>>,
     circuit_breaker: Arc<RwLock<CircuitBreaker>>,
+    sequencer_breaker: Arc<RwLock<DependencyBreaker>>,
+    l2_breaker: Arc<RwLock<DependencyBreaker>>,
     pending_notifications: Arc<RwLock<HashMap<Uuid, AnchorNotification>>>,
+    journal: Arc<AnchorJournal>,
+    /// Shared with the leader election task, which flips it as the lease moves
+    standby: Arc<AtomicBool>,
+    nonce_manager: SharedNonceManager,
+    pause: Arc<RwLock<PauseStatus>>,
+    deferred: Arc<RwLock<HashMap<Uuid, DeferredBatch>>>,
+    /// Tenant/store streams backing off after a failed cycle
+    stream_backoff: Arc<RwLock<HashMap<(Uuid, Uuid), StreamBackoff>>>,
+    /// Batches an operator asked to retry (`POST /admin/batches/{batch_id}/retry`)
+    batch_retries: Arc<RwLock<HashSet<Uuid>>>,
+    /// Batches known to be anchored, so re-deliveries skip the registry lookup
+    anchored: Arc<RwLock<AnchoredCache>>,
+    /// Anchored batches waiting for `notify_finalized`
+    awaiting_finality: Arc<RwLock<HashMap<Uuid, AnchorNotification>>>,
+    /// `BatchCommitted` cross-check, when enabled
+    reconciler: RwLock<Option<BatchReconciler>>,
+    /// Database of `BatchCommitted` logs, when `INDEXER_DATABASE_URL` is set
+    #[cfg(feature = "indexer")]
+    batch_index: RwLock<Option<Arc<BatchIndex>>>,
+    budget: Arc<RwLock<Option<GasBudget>>>,
+    /// Minutes submissions may happen in, when `ANCHOR_SCHEDULE` is set
+    schedule: Option<AnchorSchedule>,
+    /// Per-cycle gas prices, when `GAS_AWARE_MAX_DELAY_SECS` is set
+    gas_history: RwLock<GasHistory>,
+    /// Anchors over the last hour, when `MAX_ANCHORS_PER_HOUR` is set
+    throttle: Option<RwLock<AnchorThrottle>>,
+    metrics: Arc<AnchorMetrics>,
+    alert_hook: Option<AlertHook>,
+    /// Pins batch payloads to IPFS, when `IPFS_API_URL` is set
+    ipfs: Option<IpfsPinner>,
+    /// Archives anchored batches to object storage, when `ARCHIVE_BUCKET` is set
+    archive: Option<Arc<ObjectArchive>>,
+    /// Hash-chained record of attempts, results and notifications, when `AUDIT_LOG_PATH` is set
+    audit_log: Option<AuditLog>,
+    /// Submits batch payloads to Celestia, when `CELESTIA_RPC_URL` is set
+    celestia: Option<CelestiaClient>,
+    /// Celestia blobs of batches not yet anchored, so a retry doesn't pay for them again
+    celestia_blobs: RwLock<HashMap<Uuid, CelestiaBlobRef>>,
+    last_balance_check: RwLock<Option<std::time::Instant>>,
+    /// Registry state published by the background re-verification task
+    registry_status: Arc<RwLock<Option<RegistryStatus>>>,
+    /// Set while the registry has revoked our signer
+    deauthorized: AtomicBool,
+    /// Set while no contract code is found at the registry address
+    registry_code_missing: AtomicBool,
+    /// Set while the registry owner has paused `commitBatch`
+    registry_paused: AtomicBool,
+    balance_low: AtomicBool,
+    /// Cuts the wait between cycles short when the commitment stream reports work
+    wake: Arc<Notify>,
+    /// Last fetch waited for work and came back empty
+    source_idle: AtomicBool,
+    /// Registry enforced state-root chaining when last checked this cycle
+    strict_registry: AtomicBool,
+    /// Cancelled once `run`'s token is; the loop stops starting new anchors
+    /// and background tasks run on child tokens
+    shutdown: CancellationToken,
+}
+
+/// A batch held back because gas was above `max_gas_price_gwei`
+#[derive(Debug, Clone, Copy)]
+struct DeferredBatch {
+    first_deferred: std::time::Instant,
+    /// Cycles or submissions that deferred this batch
+    deferrals: u32,
+}
+
+/// Retry state of one tenant/store stream, kept apart from every other stream
+#[derive(Debug, Clone, Copy)]
+struct StreamBackoff {
+    /// Consecutive cycles in which the stream failed
+    failures: u32,
+    retry_at: std::time::Instant,
 }
 
 impl AnchorService {
     /// Create a new anchor service
     pub fn new(config: AnchorConfig) -> Self {
-        let sequencer_client = SequencerApiClient::new_with_timeouts(
-            &config.sequencer_api_url,
-            Duration::from_secs(config.sequencer_request_timeout_secs),
-            Duration::from_secs(config.sequencer_connect_timeout_secs),
-        );
-        let mut circuit_breaker = CircuitBreaker::new(
-            config.circuit_breaker_failure_threshold,
-            config.circuit_breaker_reset_timeout_secs,
-        );
-        circuit_breaker.half_open_success_threshold =
-            config.circuit_breaker_half_open_success_threshold;
+        let metrics = Arc::new(AnchorMetrics::new());
+        let sequencer_client = sequencer_client_from_config(&config, &metrics);
+        let source = Arc::new(SequencerCommitmentSource::from_config(
+            &config,
+            Arc::clone(&sequencer_client),
+        ));
+        let circuit_breaker = circuit_breaker_from_config(&config);
+        let journal = Arc::new(AnchorJournal::from_config(&config));
+        let anchored = AnchoredCache::new(config.anchored_cache_size as usize);
+        // Under leader election every replica stands by until it holds the lease
+        let standby = Arc::new(AtomicBool::new(
+            config.standby_mode || config.leader_election_enabled(),
+        ));
+        let alert_hook = config.low_balance_alert_url.as_deref().map(AlertHook::new);
+        let ipfs = IpfsPinner::from_config(&config);
+        let celestia = CelestiaClient::from_config(&config);
+        let archive = ObjectArchive::from_config(&config).map(Arc::new);
+        let audit_log = AuditLog::from_config(&config);
+        let schedule = config.anchor_schedule().ok().flatten();
+        let gas_sample_window_secs = config.gas_sample_window_secs;
+        let throttle = (config.max_anchors_per_hour > 0)
+            .then(|| RwLock::new(AnchorThrottle::new(config.max_anchors_per_hour)));
+        let stats = Arc::new(RwLock::new(AnchorStats::default()));
+        let budget = GasBudget::from_config(&config);
 
         Self {
             config,
             sequencer_client,
-            stats: Arc::new(RwLock::new(AnchorStats::default())),
+            source,
+            recorder: StatsRecorder::new(&stats).with_metrics(Arc::clone(&metrics)),
+            stats,
             health_state: None,
-            circuit_breaker: Arc::new(RwLock::new(circuit_breaker)),
+            circuit_breaker: Arc::new(RwLock::new(circuit_breaker.clone())),
+            sequencer_breaker: Arc::new(RwLock::new(DependencyBreaker::new(
+                circuit_breaker.clone(),
+            ))),
+            l2_breaker: Arc::new(RwLock::new(DependencyBreaker::new(circuit_breaker))),
             pending_notifications: Arc::new(RwLock::new(HashMap::new())),
+            journal,
+            standby,
+            nonce_manager: SharedNonceManager::new(),
+            pause: Arc::new(RwLock::new(PauseStatus::default())),
+            deferred: Arc::new(RwLock::new(HashMap::new())),
+            stream_backoff: Arc::new(RwLock::new(HashMap::new())),
+            batch_retries: Arc::new(RwLock::new(HashSet::new())),
+            anchored: Arc::new(RwLock::new(anchored)),
+            awaiting_finality: Arc::new(RwLock::new(HashMap::new())),
+            reconciler: RwLock::new(None),
+            #[cfg(feature = "indexer")]
+            batch_index: RwLock::new(None),
+            budget: Arc::new(RwLock::new(budget)),
+            metrics,
+            alert_hook,
+            ipfs,
+            celestia,
+            celestia_blobs: RwLock::new(HashMap::new()),
+            archive,
+            audit_log,
+            schedule,
+            gas_history: RwLock::new(GasHistory::new(Duration::from_secs(gas_sample_window_secs))),
+            throttle,
+            last_balance_check: RwLock::new(None),
+            registry_status: Arc::new(RwLock::new(None)),
+            deauthorized: AtomicBool::new(false),
+            registry_code_missing: AtomicBool::new(false),
+            registry_paused: AtomicBool::new(false),
+            balance_low: AtomicBool::new(false),
+            wake: Arc::new(Notify::new()),
+            source_idle: AtomicBool::new(false),
+            strict_registry: AtomicBool::new(false),
+            shutdown: CancellationToken::new(),
         }
     }
 
     /// Create anchor service with health state for monitoring
     pub fn with_health_state(config: AnchorConfig, health_state: Arc<HealthState>) -> Self {
-        let sequencer_client = SequencerApiClient::new_with_timeouts(
-            &config.sequencer_api_url,
-            Duration::from_secs(config.sequencer_request_timeout_secs),
-            Duration::from_secs(config.sequencer_connect_timeout_secs),
-        );
-        let mut circuit_breaker = CircuitBreaker::new(
-            config.circuit_breaker_failure_threshold,
-            config.circuit_breaker_reset_timeout_secs,
-        );
-        circuit_breaker.half_open_success_threshold =
-            config.circuit_breaker_half_open_success_threshold;
+        let sequencer_client = sequencer_client_from_config(&config, &health_state.metrics);
+        // Pushed commitments and `POST /admin/anchor-now` wake the loop the same
+        // way the commitment stream does
+        let wake = Arc::clone(&health_state.wake);
+        let source: Arc<dyn CommitmentSource> = match health_state.webhook {
+            Some(ref webhook) => Arc::clone(webhook) as _,
+            None => Arc::new(SequencerCommitmentSource::from_config(
+                &config,
+                Arc::clone(&sequencer_client),
+            )),
+        };
+        let circuit_breaker = circuit_breaker_from_config(&config);
+        let journal = Arc::clone(&health_state.journal);
+        let anchored = AnchoredCache::new(config.anchored_cache_size as usize);
+        // Under leader election every replica stands by until it holds the lease
+        let standby = Arc::new(AtomicBool::new(
+            config.standby_mode || config.leader_election_enabled(),
+        ));
+        let alert_hook = config.low_balance_alert_url.as_deref().map(AlertHook::new);
+        let ipfs = IpfsPinner::from_config(&config);
+        let celestia = CelestiaClient::from_config(&config);
+        let archive = ObjectArchive::from_config(&config).map(Arc::new);
+        let audit_log = AuditLog::from_config(&config);
+        let schedule = config.anchor_schedule().ok().flatten();
+        let gas_sample_window_secs = config.gas_sample_window_secs;
+        let throttle = (config.max_anchors_per_hour > 0)
+            .then(|| RwLock::new(AnchorThrottle::new(config.max_anchors_per_hour)));
 
         Self {
             config,
             sequencer_client,
+            source,
             stats: health_state.stats.clone(),
+            recorder: StatsRecorder::new(&health_state.stats)
+                .with_metrics(Arc::clone(&health_state.metrics)),
+            pause: health_state.pause.clone(),
+            budget: health_state.budget.clone(),
+            metrics: health_state.metrics.clone(),
+            alert_hook,
+            ipfs,
+            celestia,
+            celestia_blobs: RwLock::new(HashMap::new()),
+            archive,
+            audit_log,
+            schedule,
+            gas_history: RwLock::new(GasHistory::new(Duration::from_secs(gas_sample_window_secs))),
+            throttle,
+            last_balance_check: RwLock::new(None),
+            registry_status: health_state.registry.clone(),
+            batch_retries: health_state.batch_retries.clone(),
+            deauthorized: AtomicBool::new(false),
+            registry_code_missing: AtomicBool::new(false),
+            registry_paused: AtomicBool::new(false),
+            balance_low: AtomicBool::new(false),
+            wake,
+            source_idle: AtomicBool::new(false),
+            strict_registry: AtomicBool::new(false),
+            shutdown: CancellationToken::new(),
             health_state: Some(health_state),
-            circuit_breaker: Arc::new(RwLock::new(circuit_breaker)),
+            circuit_breaker: Arc::new(RwLock::new(circuit_breaker.clone())),
+            sequencer_breaker: Arc::new(RwLock::new(DependencyBreaker::new(
+                circuit_breaker.clone(),
+            ))),
+            l2_breaker: Arc::new(RwLock::new(DependencyBreaker::new(circuit_breaker))),
             pending_notifications: Arc::new(RwLock::new(HashMap::new())),
+            journal,
+            standby,
+            nonce_manager: SharedNonceManager::new(),
+            deferred: Arc::new(RwLock::new(HashMap::new())),
+            stream_backoff: Arc::new(RwLock::new(HashMap::new())),
+            anchored: Arc::new(RwLock::new(anchored)),
+            awaiting_finality: Arc::new(RwLock::new(HashMap::new())),
+            reconciler: RwLock::new(None),
+            #[cfg(feature = "indexer")]
+            batch_index: RwLock::new(None),
         }
     }
 
+    /// Take pending commitments from `source` instead of the sequencer API
+    pub fn with_commitment_source(mut self, source: Arc<dyn CommitmentSource>) -> Self {
+        self.source = source;
+        self
+    }
+
     /// Get shared stats reference (for health server)
     pub fn stats_ref(&self) -> Arc<RwLock<AnchorStats>> {
         Arc::clone(&self.stats)
     }
 
+    /// Whether the service is in warm standby (validating but not submitting)
+    pub fn is_standby(&self) -> bool {
+        self.standby.load(Ordering::SeqCst)
+    }
+
+    /// Whether submissions are paused pending operator resume
+    pub async fn is_paused(&self) -> bool {
+        self.pause.read().await.paused
+    }
+
+    /// Whether the registry owner has paused `commitBatch`
+    pub fn is_registry_paused(&self) -> bool {
+        self.registry_paused.load(Ordering::SeqCst)
+    }
+
+    /// Whether the current gas budget window is spent
+    pub async fn is_budget_exhausted(&self) -> bool {
+        self.budget
+            .read()
+            .await
+            .as_ref()
+            .is_some_and(|budget| budget.is_exhausted(Utc::now()))
+    }
+
+    /// Record how a confirmed transaction's gas estimate compared to its usage
+    fn record_gas_estimate(&self, estimate: Option<u64>, gas_used: u64) {
+        if let Some(estimated) = estimate {
+            self.recorder.record(StatsEvent::GasEstimate {
+                estimated,
+                used: gas_used,
+            });
+        }
+    }
+
+    /// Attribute a mined transaction's fee to its tenants and charge the gas budget
+    ///
+    /// The fee is split evenly across `commitments`; a reverted transaction is
+    /// charged without counting its batches as anchored. Batch data carried by
+    /// the transaction is charged per byte as well.
+    async fn record_gas_spend<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        tx_hash: FixedBytes<32>,
+        commitments: &[BatchCommitment],
+        anchored: bool,
+        da: Option<DaCharge<'_>>,
+    ) {
+        let (cost, gas_price) = match registry.transaction_cost(tx_hash).await {
+            Ok(cost) => cost,
+            Err(e) => {
+                warn!(tx_hash = %tx_hash, error = %e, "Failed to read transaction cost");
+                return;
+            }
+        };
+
+        let (da_bytes, da_cost) = match da {
+            Some(DaCharge::Posted(data)) => (data.len() as u64, cost),
+            Some(DaCharge::Appended(data)) => (
+                data.len() as u64,
+                (da::calldata_gas(data) as u128 * gas_price).min(cost),
+            ),
+            None => (0, 0),
+        };
+        let divisor = commitments.len().max(1) as u128;
+        let share = cost / divisor;
+        for commitment in commitments {
+            self.recorder.record(StatsEvent::AnchorCost {
+                tenant_id: commitment.tenant_id,
+                store_id: commitment.store_id,
+                cost: CostTotals {
+                    batches: u64::from(anchored),
+                    events: if anchored {
+                        commitment.event_count as u64
+                    } else {
+                        0
+                    },
+                    wei_spent: share,
+                    da_bytes: da_bytes / divisor as u64,
+                    da_wei_spent: da_cost / divisor,
+                },
+            });
+        }
+
+        let mut budget = self.budget.write().await;
+        let Some(budget) = budget.as_mut() else {
+            return;
+        };
+        let now = Utc::now();
+        budget.record(cost, now);
+        if budget.is_exhausted(now) {
+            warn!(
+                spent_wei = %budget.spent_wei(now),
+                limit_wei = %budget.limit_wei(),
+                "Gas budget exhausted; submissions stop until the window resets"
+            );
+        }
+    }
+
+    /// Keep reporting while submissions are held back (paused, outside `ANCHOR_SCHEDULE`)
+    ///
+    /// Queued acknowledgements and finality notifications still go out, and the
+    /// sequencer is polled so `GET /pending` lists what is waiting, every batch
+    /// with `status` and `reason`.
+    async fn poll_while_held<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        status: PendingStatus,
+        reason: Option<String>,
+    ) {
+        self.flush_pending_notifications().await;
+        self.notify_finalized_anchors(registry).await;
+
+        let Some(fetched) = self.fetch_pending().await else {
+            return;
+        };
+        match fetched {
+            Ok(commitments) => {
+                self.record_dependency_call(Dependency::SequencerApi, true)
+                    .await;
+                self.mark_sequencer_healthy().await;
+                self.publish_pending_queue(
+                    commitments
+                        .iter()
+                        .map(|commitment| PendingBatch::new(commitment, status, reason.clone()))
+                        .collect(),
+                )
+                .await;
+            }
+            Err(e) => {
+                self.record_dependency_call(Dependency::SequencerApi, false)
+                    .await;
+                debug!(error = %e, "Failed to fetch pending commitments while held");
+            }
+        }
+    }
+
+    /// Pause submissions until an operator resumes them via `POST /resume`
+    async fn pause_submissions(&self, reason: String) {
+        let mut pause = self.pause.write().await;
+        if pause.paused {
+            return;
+        }
+        error!(reason = %reason, "Pausing anchor submissions until operator resume");
+        *pause = PauseStatus {
+            paused: true,
+            reason: Some(reason),
+            paused_at: Some(Utc::now().to_rfc3339()),
+            ..PauseStatus::default()
+        };
+        if let Err(e) = self.journal.record_pause(&pause).await {
+            warn!(error = %e, "Failed to persist pause");
+        }
+    }
+
+    /// Hold submissions again if an earlier run stopped while paused
+    async fn restore_pause(&self) -> Result<()> {
+        let restored = self.journal.load_pause().await?;
+        if restored.paused {
+            warn!(
+                reason = ?restored.reason,
+                paused_at = ?restored.paused_at,
+                "Submissions still paused from before the restart; resume to continue"
+            );
+            *self.pause.write().await = restored;
+        }
+        Ok(())
+    }
+
+    /// Enter or leave warm standby; takes effect at the next cycle
+    pub fn set_standby(&self, standby: bool) {
+        if self.standby.swap(standby, Ordering::SeqCst) != standby {
+            info!(standby = standby, "Anchor submission role changed");
+        }
+    }
+
+    /// Get shared journal reference
+    pub fn journal_ref(&self) -> Arc<AnchorJournal> {
+        Arc::clone(&self.journal)
+    }
+
     async fn record_error(&self, error: AnchorError) {
+        self.record_error_with_context(error, ErrorContext::default())
+            .await;
+    }
+
+    /// Record an error raised while anchoring a specific batch
+    async fn record_error_with_context(&self, error: AnchorError, context: ErrorContext) {
+        reporting::report(&error, context);
         if let Some(ref health) = self.health_state {
             health.record_error(&error).await;
         }
@@ -110,23 +545,229 @@ impl AnchorService {
         stats.circuit_breaker_state = state;
     }
 
-    async fn record_anchor_success(&self, commitment: &BatchCommitment, anchor_time_ms: u64) {
-        let mut stats = self.stats.write().await;
-        stats.record_success(anchor_time_ms);
-        stats.total_events_anchored += commitment.event_count as u64;
-        stats.last_batch_id = Some(commitment.batch_id);
+    fn dependency_breaker(&self, dependency: Dependency) -> &RwLock<DependencyBreaker> {
+        match dependency {
+            Dependency::SequencerApi => &self.sequencer_breaker,
+            Dependency::L2Rpc => &self.l2_breaker,
+        }
     }
 
-    async fn record_anchor_failure(&self) {
+    async fn publish_dependency_state(&self, dependency: Dependency, state: CircuitBreakerState) {
         let mut stats = self.stats.write().await;
-        stats.record_anchor_failure();
+        match dependency {
+            Dependency::SequencerApi => stats.sequencer_breaker_state = state,
+            Dependency::L2Rpc => stats.l2_breaker_state = state,
+        }
     }
 
-    async fn record_notification_failure(&self, batch_id: Uuid, error_message: String) {
-        {
-            let mut stats = self.stats.write().await;
-            stats.sequencer_api_failures += 1;
+    /// Whether calls to `dependency` are allowed; an open breaker half-opens after its reset timeout
+    async fn dependency_allowed(&self, dependency: Dependency) -> bool {
+        let (allowed, state) = {
+            let mut breaker = self.dependency_breaker(dependency).write().await;
+            (breaker.allow_request(), breaker.state())
+        };
+        self.publish_dependency_state(dependency, state).await;
+        allowed
+    }
+
+    /// Feed the outcome of a call to `dependency` into its breaker
+    async fn record_dependency_call(&self, dependency: Dependency, succeeded: bool) {
+        let (previous, state) = {
+            let mut breaker = self.dependency_breaker(dependency).write().await;
+            let previous = breaker.state();
+            if succeeded {
+                breaker.record_success();
+            } else {
+                breaker.record_failure();
+            }
+            (previous, breaker.state())
+        };
+
+        if state != previous {
+            if state == CircuitBreakerState::Open {
+                warn!(
+                    dependency = dependency.as_str(),
+                    "Dependency circuit breaker opened"
+                );
+            } else {
+                info!(
+                    dependency = dependency.as_str(),
+                    state = state.as_str(),
+                    "Dependency circuit breaker state changed"
+                );
+            }
+        }
+        self.publish_dependency_state(dependency, state).await;
+    }
+
+    async fn record_anchor_success(&self, commitment: &BatchCommitment, anchor_time_ms: u64) {
+        self.recorder.record(StatsEvent::AnchorSucceeded {
+            batch_id: commitment.batch_id,
+            tenant_id: commitment.tenant_id,
+            event_count: commitment.event_count,
+            anchor_time_ms,
+        });
+        self.record_time_to_anchor(commitment);
+        self.deferred.write().await.remove(&commitment.batch_id);
+    }
+
+    /// Track how long the batch took from commit to anchor, flagging SLA violations
+    fn record_time_to_anchor(&self, commitment: &BatchCommitment) {
+        let sample = TimeToAnchor::new(
+            commitment.batch_id,
+            commitment.tenant_id,
+            commitment.store_id,
+            commitment.committed_at,
+            Utc::now(),
+        );
+        self.metrics.time_to_anchor.observe(sample.secs as f64);
+        let target = self.config.sla_target_secs;
+        if target > 0 && sample.secs > target {
+            warn!(
+                batch_id = %commitment.batch_id,
+                tenant_id = %commitment.tenant_id,
+                store_id = %commitment.store_id,
+                time_to_anchor_secs = sample.secs,
+                sla_target_secs = target,
+                "Batch anchored later than SLA_TARGET_SECS"
+            );
+        }
+        self.recorder.record(StatsEvent::TimeToAnchor {
+            sample,
+            sla_target_secs: target,
+        });
+    }
+
+    async fn record_anchor<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        commitment: &BatchCommitment,
+        result: &AnchorResult,
+        scheme: BatchIdScheme,
+        calldata: Option<String>,
+        notification: AnchorNotification,
+    ) {
+        let chain_id = registry.chain_id();
+        if let Some(ref mut reconciler) = *self.reconciler.write().await {
+            if let (Ok(chain_batch_id), Ok(tx_hash)) = (
+                batch_id_to_bytes32(commitment, scheme),
+                result.tx_hash.parse::<FixedBytes<32>>(),
+            ) {
+                reconciler.expect(
+                    chain_batch_id,
+                    commitment.batch_id,
+                    tx_hash,
+                    result.block_number,
+                );
+            }
+        }
+        if let Some(ref health) = self.health_state {
+            let chain_batch_id = batch_id_to_bytes32(commitment, scheme)
+                .map(|id| id.to_string())
+                .unwrap_or_default();
+            health
+                .record_anchor(AnchorRecord {
+                    batch_id: commitment.batch_id,
+                    tenant_id: commitment.tenant_id,
+                    store_id: commitment.store_id,
+                    tx_hash: result.tx_hash.clone(),
+                    chain_id,
+                    block_number: result.block_number,
+                    gas_used: result.gas_used,
+                    anchored_at: Utc::now(),
+                    calldata: calldata.clone(),
+                    batch_id_scheme: scheme,
+                    chain_batch_id,
+                })
+                .await;
+        }
+        if let Some(ref archive) = self.archive {
+            self.archive_anchor(
+                registry,
+                archive,
+                commitment,
+                result,
+                scheme,
+                calldata,
+                notification,
+            )
+            .await;
         }
+    }
+
+    /// Write an anchored batch's artifact to object storage in the background
+    #[allow(clippy::too_many_arguments)]
+    async fn archive_anchor<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        archive: &Arc<ObjectArchive>,
+        commitment: &BatchCommitment,
+        result: &AnchorResult,
+        scheme: BatchIdScheme,
+        calldata: Option<String>,
+        notification: AnchorNotification,
+    ) {
+        let receipt = match result.tx_hash.parse::<FixedBytes<32>>() {
+            Ok(tx_hash) => registry
+                .transaction_receipt_json(tx_hash)
+                .await
+                .unwrap_or_else(|e| {
+                    debug!(tx_hash = %tx_hash, error = %e, "Failed to fetch receipt for archive");
+                    None
+                }),
+            Err(_) => None,
+        };
+        let artifact = AnchorArchive {
+            batch_id: commitment.batch_id,
+            commitment: commitment.clone(),
+            chain_id: registry.chain_id(),
+            tx_hash: result.tx_hash.clone(),
+            block_number: result.block_number,
+            gas_used: result.gas_used,
+            batch_id_scheme: scheme,
+            chain_batch_id: batch_id_to_bytes32(commitment, scheme)
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            calldata,
+            receipt,
+            notification,
+            archived_at: Utc::now(),
+        };
+
+        // Uploads never hold up the anchor loop
+        let archive = Arc::clone(archive);
+        let metrics = Arc::clone(&self.metrics);
+        tokio::spawn(async move {
+            match archive.put(&artifact).await {
+                Ok((key, outcome)) => {
+                    let label = match outcome {
+                        ArchiveOutcome::Archived => "archived",
+                        ArchiveOutcome::AlreadyArchived => "already_archived",
+                    };
+                    metrics.archive_uploads.with_label_values(&[label]).inc();
+                    debug!(batch_id = %artifact.batch_id, key = %key, "Archived anchored batch");
+                }
+                Err(e) => {
+                    metrics.archive_uploads.with_label_values(&["failed"]).inc();
+                    warn!(
+                        batch_id = %artifact.batch_id,
+                        bucket = %archive.bucket(),
+                        error = %e,
+                        "Failed to archive anchored batch"
+                    );
+                }
+            }
+        });
+    }
+
+    async fn record_anchor_failure(&self, commitment: &BatchCommitment) {
+        self.recorder.record(StatsEvent::AnchorFailed {
+            tenant_id: commitment.tenant_id,
+        });
+    }
+
+    async fn record_notification_failure(&self, batch_id: Uuid, error_message: String) {
+        self.recorder.record(StatsEvent::NotificationFailed);
 
         self.record_error(AnchorError::SequencerApi(
             SequencerApiError::NotificationFailed(error_message.clone()),
@@ -140,18 +781,33 @@ impl AnchorService {
     }
 
     async fn record_cycle_success(&self) {
+        self.recorder.flush().await;
         let state = {
             let mut breaker = self.circuit_breaker.write().await;
             breaker.record_success();
             breaker.state
         };
 
+        self.metrics
+            .cycles_by_status
+            .with_label_values(&["success"])
+            .inc();
         let mut stats = self.stats.write().await;
         stats.record_cycle_success();
         stats.circuit_breaker_state = state;
     }
 
     async fn record_cycle_failure(&self, error_type: ErrorType) {
+        self.recorder.flush().await;
+        self.metrics
+            .cycles_by_status
+            .with_label_values(&["failed"])
+            .inc();
+        match error_type {
+            ErrorType::L2Connection => self.metrics.l2_connection_failures.inc(),
+            ErrorType::SequencerApi => self.metrics.sequencer_api_failures.inc(),
+            ErrorType::Transaction | ErrorType::Other => {}
+        }
         let consecutive_failures = {
             let mut stats = self.stats.write().await;
             stats.record_cycle_failure(error_type);
@@ -192,10 +848,21 @@ impl AnchorService {
                 .await
             {
                 Ok(()) => {
+                    self.record_dependency_call(Dependency::SequencerApi, true)
+                        .await;
                     self.pending_notifications.write().await.remove(&batch_id);
+                    self.forget_journal_entry(&batch_id).await;
+                    self.audit(AuditEvent::Notification {
+                        batch_id,
+                        notification,
+                        acknowledged: true,
+                    })
+                    .await;
                     info!(batch_id = %batch_id, "Flushed queued anchor notification");
                 }
                 Err(e) => {
+                    self.record_dependency_call(Dependency::SequencerApi, false)
+                        .await;
                     self.record_notification_failure(batch_id, e.to_string())
                         .await;
                 }
@@ -203,34 +870,225 @@ impl AnchorService {
         }
     }
 
+    async fn forget_journal_entry(&self, batch_id: &Uuid) {
+        if let Err(e) = self.journal.remove(batch_id).await {
+            warn!(batch_id = %batch_id, error = %e, "Failed to remove journal entry");
+        }
+    }
+
+    /// Keep a finished operation for `GET /history`
+    async fn record_history(&self, entry: HistoryEntry) {
+        if let Err(e) = self.journal.record_history(entry).await {
+            warn!(error = %e, "Failed to record anchor history");
+        }
+    }
+
+    /// Append to the audit log; a failed write is logged and never blocks anchoring
+    async fn audit(&self, event: AuditEvent) {
+        let Some(ref audit_log) = self.audit_log else {
+            return;
+        };
+        match audit_log.append(event).await {
+            Ok(_) => self
+                .metrics
+                .audit_records
+                .with_label_values(&["appended"])
+                .inc(),
+            Err(e) => {
+                self.metrics
+                    .audit_records
+                    .with_label_values(&["failed"])
+                    .inc();
+                warn!(
+                    path = %audit_log.path().display(),
+                    error = %e,
+                    "Failed to append to audit log"
+                );
+            }
+        }
+    }
+
     async fn notify_sequencer_or_queue(&self, batch_id: Uuid, notification: AnchorNotification) {
-        if let Err(e) = self
-            .sequencer_client
-            .notify_anchored(batch_id, &notification)
+        self.anchored
+            .write()
             .await
-        {
+            .insert(batch_id, notification.clone());
+        if self.config.finality_tracking != FinalityMode::Off {
+            let mut awaiting = self.awaiting_finality.write().await;
+            awaiting.insert(batch_id, notification.clone());
+            self.metrics.awaiting_finality.set(awaiting.len() as i64);
+        }
+        if let Err(e) = self.journal.record_confirmed(batch_id, &notification).await {
+            warn!(batch_id = %batch_id, error = %e, "Failed to journal anchor notification");
+        }
+
+        if !self.dependency_allowed(Dependency::SequencerApi).await {
+            self.audit(AuditEvent::Notification {
+                batch_id,
+                notification: notification.clone(),
+                acknowledged: false,
+            })
+            .await;
             self.queue_notification(batch_id, notification).await;
-            self.record_notification_failure(batch_id, e.to_string())
-                .await;
             warn!(
                 batch_id = %batch_id,
-                "Queued anchor notification for retry after sequencer acknowledgement failure"
+                "Sequencer API circuit open; queued anchor notification for retry"
             );
+            return;
+        }
+
+        let result = self
+            .sequencer_client
+            .notify_anchored(batch_id, &notification)
+            .await;
+        self.audit(AuditEvent::Notification {
+            batch_id,
+            notification: notification.clone(),
+            acknowledged: result.is_ok(),
+        })
+        .await;
+        match result {
+            Ok(()) => {
+                self.record_dependency_call(Dependency::SequencerApi, true)
+                    .await;
+                self.forget_journal_entry(&batch_id).await;
+            }
+            Err(e) => {
+                self.record_dependency_call(Dependency::SequencerApi, false)
+                    .await;
+                self.queue_notification(batch_id, notification).await;
+                self.record_notification_failure(batch_id, e.to_string())
+                    .await;
+                warn!(
+                    batch_id = %batch_id,
+                    "Queued anchor notification for retry after sequencer acknowledgement failure"
+                );
+            }
+        }
+    }
+
+    /// Send `notify_finalized` for anchors whose block has reached the
+    /// configured finality, re-reading each receipt in case it was reorged
+    async fn notify_finalized_anchors<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+    ) {
+        let mode = self.config.finality_tracking;
+        let awaiting = self.awaiting_finality.read().await.clone();
+        if awaiting.is_empty() || !self.dependency_allowed(Dependency::SequencerApi).await {
+            return;
+        }
+        let finalized_block = match registry.finalized_head(mode).await {
+            Ok(Some(block)) => block,
+            Ok(None) => return,
+            Err(e) => {
+                debug!(error = %e, finality = mode.as_str(), "Failed to fetch finalized head");
+                return;
+            }
+        };
+
+        for (batch_id, anchored) in awaiting {
+            // The anchored acknowledgement goes first
+            if self.has_pending_notification(&batch_id).await {
+                continue;
+            }
+            if anchored
+                .block_number
+                .is_some_and(|block| block > finalized_block)
+            {
+                continue;
+            }
+            let Ok(tx_hash) = anchored.chain_tx_hash.parse::<FixedBytes<32>>() else {
+                self.stop_tracking_finality(&batch_id).await;
+                continue;
+            };
+            let block_number = match registry.receipt_outcome(tx_hash).await {
+                Ok(ReceiptOutcome::Confirmed(metadata)) => metadata.block_number,
+                Ok(ReceiptOutcome::Reverted) => {
+                    warn!(
+                        batch_id = %batch_id,
+                        tx_hash = %anchored.chain_tx_hash,
+                        "Anchor transaction reverted after a reorg; batch will not be finalized"
+                    );
+                    self.stop_tracking_finality(&batch_id).await;
+                    continue;
+                }
+                Ok(ReceiptOutcome::Unknown) => {
+                    warn!(
+                        batch_id = %batch_id,
+                        tx_hash = %anchored.chain_tx_hash,
+                        "Anchor transaction has no receipt; still waiting for finality"
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    debug!(batch_id = %batch_id, error = %e, "Failed to re-read anchor receipt");
+                    continue;
+                }
+            };
+            if block_number > finalized_block {
+                // Reorged into a later block
+                self.awaiting_finality
+                    .write()
+                    .await
+                    .entry(batch_id)
+                    .and_modify(|entry| entry.block_number = Some(block_number));
+                continue;
+            }
+
+            let notification = FinalizedNotification {
+                chain_tx_hash: anchored.chain_tx_hash.clone(),
+                chain_id: anchored.chain_id,
+                block_number,
+                finalized_block,
+                finality: mode,
+            };
+            match self
+                .sequencer_client
+                .notify_finalized(batch_id, &notification)
+                .await
+            {
+                Ok(()) => {
+                    self.record_dependency_call(Dependency::SequencerApi, true)
+                        .await;
+                    self.stop_tracking_finality(&batch_id).await;
+                    info!(
+                        batch_id = %batch_id,
+                        block_number,
+                        finalized_block,
+                        finality = mode.as_str(),
+                        "Notified sequencer of finalized anchor"
+                    );
+                }
+                Err(e) => {
+                    self.record_dependency_call(Dependency::SequencerApi, false)
+                        .await;
+                    self.record_notification_failure(batch_id, e.to_string())
+                        .await;
+                }
+            }
         }
     }
 
-    async fn recover_already_anchored<P: Provider<HttpTransport> + Clone>(
+    async fn stop_tracking_finality(&self, batch_id: &Uuid) {
+        let mut awaiting = self.awaiting_finality.write().await;
+        awaiting.remove(batch_id);
+        self.metrics.awaiting_finality.set(awaiting.len() as i64);
+    }
+
+    async fn recover_already_anchored<P: Provider<RpcTransport> + Clone>(
         &self,
         registry: &RegistryClient<P>,
         commitment: &BatchCommitment,
     ) -> Result<Option<AnchorResult>> {
-        let Some(AnchoredBatchMetadata {
-            tx_hash,
-            block_number,
-            gas_used,
-        }) = registry
-            .find_anchored_batch_metadata(&commitment.batch_id)
-            .await?
+        let Some((
+            scheme,
+            AnchoredBatchMetadata {
+                tx_hash,
+                block_number,
+                gas_used,
+            },
+        )) = registry.find_anchored_batch_metadata(commitment).await?
         else {
             return Ok(None);
         };
@@ -241,8 +1099,15 @@ impl AnchorService {
             chain_id: registry.chain_id(),
             block_number: Some(block_number),
             gas_used: Some(gas_used),
+            blob_versioned_hashes: Vec::new(),
+            ipfs_cid: None,
+            celestia: None,
         };
-        self.notify_sequencer_or_queue(commitment.batch_id, notification)
+        self.audit(AuditEvent::anchored(commitment.batch_id, &notification))
+            .await;
+        self.record_history(HistoryEntry::anchored(commitment.batch_id, &notification))
+            .await;
+        self.notify_sequencer_or_queue(commitment.batch_id, notification.clone())
             .await;
 
         info!(
@@ -252,22 +1117,249 @@ impl AnchorService {
             "Recovered already-anchored commitment from on-chain event history"
         );
 
-        Ok(Some(AnchorResult {
+        let result = AnchorResult {
             batch_id: commitment.batch_id,
             tx_hash: tx_hash_hex,
             block_number,
             gas_used,
             success: true,
             error: None,
-        }))
-    }
-
-    /// Run the anchor service loop
-    pub async fn run(&self) -> Result<()> {
-        info!(
-            l2_rpc = %self.config.l2_rpc_url,
-            registry = %self.config.set_registry_address,
-            interval = self.config.anchor_interval_secs,
+            deferred: false,
+        };
+        let calldata = match registry.transaction_input(tx_hash).await {
+            Ok(input) => input.map(|input| input.to_string()),
+            Err(e) => {
+                debug!(error = %e, "Failed to fetch input of recovered transaction");
+                None
+            }
+        };
+        self.record_anchor(
+            registry,
+            commitment,
+            &result,
+            scheme,
+            calldata,
+            notification,
+        )
+        .await;
+
+        Ok(Some(result))
+    }
+
+    /// Resolve transactions that were in flight when the process last stopped
+    /// and replay their sequencer notifications.
+    async fn recover_in_flight<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+    ) -> Result<()> {
+        let loaded = self.journal.load().await?;
+        if loaded == 0 {
+            return Ok(());
+        }
+
+        info!(
+            entries = loaded,
+            "Recovering in-flight transactions from journal"
+        );
+
+        for entry in self.journal.entries().await {
+            if entry.chain_id != registry.chain_id() {
+                warn!(
+                    batch_id = %entry.batch_id,
+                    journal_chain_id = entry.chain_id,
+                    chain_id = registry.chain_id(),
+                    "Ignoring journal entry recorded for a different chain"
+                );
+                continue;
+            }
+
+            let notification = match entry.notification {
+                Some(notification) => notification,
+                None => {
+                    let tx_hash = match entry.tx_hash.parse() {
+                        Ok(tx_hash) => tx_hash,
+                        Err(e) => {
+                            warn!(
+                                batch_id = %entry.batch_id,
+                                tx_hash = %entry.tx_hash,
+                                error = %e,
+                                "Dropping journal entry with malformed transaction hash"
+                            );
+                            self.forget_journal_entry(&entry.batch_id).await;
+                            continue;
+                        }
+                    };
+
+                    match registry.receipt_outcome(tx_hash).await {
+                        Ok(ReceiptOutcome::Confirmed(metadata)) => AnchorNotification {
+                            chain_tx_hash: entry.tx_hash.clone(),
+                            chain_id: entry.chain_id,
+                            block_number: Some(metadata.block_number),
+                            gas_used: Some(metadata.gas_used),
+                            blob_versioned_hashes: entry.blob_versioned_hashes.clone(),
+                            ipfs_cid: entry.ipfs_cid.clone(),
+                            celestia: entry.celestia.clone(),
+                        },
+                        Ok(ReceiptOutcome::Reverted) => {
+                            info!(
+                                batch_id = %entry.batch_id,
+                                tx_hash = %entry.tx_hash,
+                                "In-flight transaction reverted; batch will be retried"
+                            );
+                            self.forget_journal_entry(&entry.batch_id).await;
+                            continue;
+                        }
+                        Ok(ReceiptOutcome::Unknown) => {
+                            // If it lands later, the retry reverts and the event-history
+                            // recovery path reports the original transaction.
+                            warn!(
+                                batch_id = %entry.batch_id,
+                                tx_hash = %entry.tx_hash,
+                                "No receipt for in-flight transaction; batch will be retried"
+                            );
+                            self.forget_journal_entry(&entry.batch_id).await;
+                            continue;
+                        }
+                        Err(e) => {
+                            warn!(
+                                batch_id = %entry.batch_id,
+                                tx_hash = %entry.tx_hash,
+                                error = %e,
+                                "Failed to resolve in-flight transaction; keeping journal entry"
+                            );
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            info!(
+                batch_id = %entry.batch_id,
+                tx_hash = %notification.chain_tx_hash,
+                "Replaying anchor notification for recovered transaction"
+            );
+            self.notify_sequencer_or_queue(entry.batch_id, notification)
+                .await;
+        }
+
+        Ok(())
+    }
+
+    fn is_shutting_down(&self) -> bool {
+        self.shutdown.is_cancelled()
+    }
+
+    /// Whether another submission may start: not shutting down and still leader
+    fn may_start_anchor(&self) -> bool {
+        !self.is_shutting_down() && !self.is_standby()
+    }
+
+    /// Sleep between skipped cycles, returning early on shutdown
+    async fn idle(&self) {
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(self.config.anchor_interval_secs)) => {}
+            _ = self.shutdown.cancelled() => {}
+        }
+    }
+
+    /// Like `idle`, but waking by `until` if that comes first
+    async fn idle_until(&self, until: DateTime<Utc>) {
+        let delay = (until - Utc::now())
+            .to_std()
+            .unwrap_or_default()
+            .min(Duration::from_secs(self.config.anchor_interval_secs));
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = self.shutdown.cancelled() => {}
+        }
+    }
+
+    /// Hold batches back while `ANCHOR_SCHEDULE` doesn't allow submitting
+    ///
+    /// Returns when the next window opens, after listing what is waiting under
+    /// `GET /pending`; `None` means the cycle may go ahead.
+    async fn hold_outside_schedule<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+    ) -> Option<DateTime<Utc>> {
+        let schedule = self.schedule.as_ref()?;
+        let now = Utc::now();
+        if schedule.allows(now) {
+            return None;
+        }
+        // Parsing already refused schedules that never match
+        let opens_at = schedule
+            .next_window(now)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        debug!(
+            schedule = %schedule,
+            opens_at = %opens_at,
+            "Outside ANCHOR_SCHEDULE; holding batches until the next window"
+        );
+        self.poll_while_held(
+            registry,
+            PendingStatus::OutsideSchedule,
+            Some(format!("next window opens at {}", opens_at.to_rfc3339())),
+        )
+        .await;
+        Some(opens_at)
+    }
+
+    /// Fetch pending commitments, giving up on a long poll at shutdown
+    ///
+    /// `None` means shutdown was requested before the source answered; sources
+    /// deliver again whatever they handed out but never saw completed.
+    ///
+    /// With `SHARD_COUNT` > 1, batches of tenants owned by other shards are
+    /// dropped here, untouched, for their own instance to anchor.
+    async fn fetch_pending(&self) -> Option<crate::error::AnchorResult<Vec<BatchCommitment>>> {
+        let fetched = tokio::select! {
+            // An answer that is already there is still worth handling
+            biased;
+            fetched = self.source.fetch() => fetched,
+            _ = self.shutdown.cancelled() => return None,
+        };
+        Some(fetched.map(|mut commitments| {
+            let fetched = commitments.len();
+            commitments.retain(|commitment| self.config.owns_tenant(&commitment.tenant_id));
+            if commitments.len() < fetched {
+                debug!(
+                    other_shards = fetched - commitments.len(),
+                    shard = self.config.shard_index,
+                    "Leaving batches of other shards' tenants"
+                );
+            }
+            commitments
+        }))
+    }
+
+    /// Run the anchor service loop until `shutdown` is cancelled
+    ///
+    /// Cancelling stops new anchors and background tasks; `run` returns after
+    /// the current cycle's submissions are confirmed and queued notifications
+    /// are sent. Dropping the future instead stops it at once, leaving anything
+    /// in flight to journal recovery.
+    pub async fn run(&self, shutdown: CancellationToken) -> crate::error::AnchorResult<()> {
+        let forward = {
+            let inner = self.shutdown.clone();
+            tokio::spawn(async move {
+                shutdown.cancelled().await;
+                inner.cancel();
+            })
+        };
+        let result = self.run_until_cancelled().await;
+        forward.abort();
+        result
+    }
+
+    async fn run_until_cancelled(&self) -> crate::error::AnchorResult<()> {
+        info!(
+            l2_rpc = %self.config.l2_rpc_url,
+            transport = L2Transport::from_url(&self.config.l2_rpc_url)
+                .map(|t| t.kind())
+                .unwrap_or("invalid"),
+            registry = %self.config.set_registry_address,
+            interval = self.config.anchor_interval_secs,
             "Starting Set Chain anchor service"
         );
 
@@ -279,13 +1371,22 @@ impl AnchorService {
         }
 
         // Create provider and registry client
-        let provider = match create_provider(
-            &self.config.l2_rpc_url,
+        let provider = match create_provider_with_endpoints(
+            &self.config.l2_rpc_urls(),
             &self.config.sequencer_private_key,
+            self.nonce_manager.clone(),
+            Some(self.metrics.clone()),
+            RateLimiter::from_rate(self.config.l2_rpc_rate_limit),
+            self.config.private_relay(),
         )
         .await
         {
-            Ok(provider) => provider,
+            Ok(provider) => {
+                if let Some(ref health) = self.health_state {
+                    health.mark_provider_created().await;
+                }
+                provider
+            }
             Err(e) => {
                 self.record_error(AnchorError::Config(ConfigError::InvalidValue {
                     field: "provider".to_string(),
@@ -299,33 +1400,85 @@ impl AnchorService {
         let chain_id = match provider.get_chain_id().await {
             Ok(chain_id) => chain_id,
             Err(e) => {
-                self.record_error(AnchorError::L2Connection(L2Error::RpcError(e.to_string())))
-                    .await;
+                let error = AnchorError::L2Connection(L2Error::RpcError(e.to_string()));
+                self.record_error(error.clone()).await;
                 self.record_cycle_failure(ErrorType::L2Connection).await;
-                return Err(e.into());
+                return Err(error);
             }
         };
 
         if self.config.expected_l2_chain_id > 0 && chain_id != self.config.expected_l2_chain_id {
-            self.record_error(AnchorError::L2Connection(L2Error::ChainIdMismatch {
+            let error = AnchorError::L2Connection(L2Error::ChainIdMismatch {
                 expected: self.config.expected_l2_chain_id,
                 actual: chain_id,
-            }))
-            .await;
+            });
+            self.record_error(error.clone()).await;
             self.record_cycle_failure(ErrorType::L2Connection).await;
-            anyhow::bail!(
-                "L2 chain ID mismatch: expected {}, got {}",
-                self.config.expected_l2_chain_id,
-                chain_id
-            );
+            return Err(error);
         }
         info!(chain_id = chain_id, "Connected to Set Chain");
+        if let Some(ref health) = self.health_state {
+            health.mark_chain_id_verified(chain_id).await;
+        }
 
-        let registry_address: Address = self.config.set_registry_address.parse()?;
-        let registry = RegistryClient::new(registry_address, provider, chain_id);
+        let registry_address: Address = parse_address(&self.config.set_registry_address)?;
+        let verifier = RegistryClient::new(registry_address, provider.clone(), chain_id);
+        let indexer_client = self
+            .config
+            .indexer_database_url
+            .as_ref()
+            .map(|_| RegistryClient::new(registry_address, provider.clone(), chain_id));
+        let lookup_client = self
+            .health_state
+            .as_ref()
+            .map(|_| RegistryClient::new(registry_address, provider.clone(), chain_id));
+        let mut registry = RegistryClient::new(registry_address, provider, chain_id)
+            .with_nonce_manager(self.nonce_manager.clone())
+            .with_metrics(self.metrics.clone());
+        if let Some(strategy) = fees::strategy_from_config(&self.config) {
+            info!(strategy = ?strategy, "Using configured fee strategy");
+            registry = registry.with_fee_strategy(strategy);
+        }
+        if self.config.max_gas_price_gwei > 0 {
+            registry = registry.with_max_gas_price_gwei(self.config.max_gas_price_gwei);
+        }
+        if let Some(oracle) = GasOracle::from_config(&self.config) {
+            info!(
+                url = oracle.url(),
+                speed = self.config.gas_oracle_speed.as_str(),
+                "Using gas price oracle"
+            );
+            registry = registry.with_gas_oracle(Arc::new(oracle));
+        }
+        if self.config.batch_id_scheme != BatchIdScheme::default() {
+            info!(
+                scheme = self.config.batch_id_scheme.as_str(),
+                "Using batch id scheme"
+            );
+            registry = registry.with_batch_id_scheme(self.config.batch_id_scheme);
+        }
+        if self.config.max_batches_per_tx > 1 {
+            if let Some(ref multicall) = self.config.multicall_address {
+                registry = registry.with_multicall(parse_address(multicall)?);
+            }
+        }
 
         // Verify sequencer authorization
-        let signer_address = self.get_signer_address()?;
+        let signer_address = self
+            .get_signer_address()
+            .map_err(|_| ConfigError::InvalidPrivateKey)?;
+        registry = registry
+            .with_signer(signer_address)
+            .with_gas_limit(GasLimitPolicy {
+                multiplier: self.config.gas_limit_multiplier,
+                max_gas_limit: (self.config.max_gas_limit > 0).then_some(self.config.max_gas_limit),
+            });
+        if self.config.simulate_commits {
+            registry = registry.with_simulation();
+        }
+        if self.config.use_access_list {
+            registry = registry.with_access_list();
+        }
         let is_authorized = match registry.is_authorized(signer_address).await {
             Ok(is_authorized) => is_authorized,
             Err(e) => {
@@ -338,40 +1491,189 @@ impl AnchorService {
         };
 
         if !is_authorized {
-            self.record_error(AnchorError::Authorization(
-                AuthorizationError::NotAuthorized {
-                    address: format!("{:?}", signer_address),
-                },
-            ))
-            .await;
+            let error = AnchorError::Authorization(AuthorizationError::NotAuthorized {
+                address: format!("{:?}", signer_address),
+            });
+            self.record_error(error.clone()).await;
             error!(
                 address = %signer_address,
                 "Sequencer address not authorized in SetRegistry"
             );
-            anyhow::bail!("Sequencer not authorized");
+            return Err(error);
         }
 
         info!(
             address = %signer_address,
             "Sequencer authorization verified"
         );
+        if let Some(ref health) = self.health_state {
+            health.mark_authorization_verified().await;
+        }
+
+        // Before the registry guard, so a pause it raised last run keeps its reason
+        self.restore_pause()
+            .await
+            .map_err(|e| AnchorError::from_anyhow(&e))?;
+        let mut guard = self
+            .init_registry_guard(&registry)
+            .await
+            .map_err(|e| AnchorError::from_anyhow(&e))?;
+        if self.config.reconcile_batch_events {
+            let next_block = registry.block_number().await? + 1;
+            info!(from_block = next_block, "Reconciling BatchCommitted events");
+            *self.reconciler.write().await = Some(BatchReconciler::new(next_block));
+        }
+        if let (Some(url), Some(client)) = (&self.config.indexer_database_url, indexer_client) {
+            self.start_indexer(client, url, self.shutdown.child_token())
+                .await?;
+        }
+        if let (Some(health), Some(registry)) = (&self.health_state, lookup_client) {
+            health
+                .set_batch_lookup(Arc::new(RegistryBatchLookup {
+                    registry,
+                    #[cfg(feature = "indexer")]
+                    index: self.batch_index.read().await.clone(),
+                }))
+                .await;
+        }
+        if let Some(events) = registry.subscribe_control_events().await {
+            info!("Subscribed to registry control events");
+            tokio::spawn(watch_registry_events(
+                events,
+                Arc::clone(&self.wake),
+                self.shutdown.child_token(),
+            ));
+        }
+        if self.config.registry_check_interval_secs > 0 {
+            tokio::spawn(verify_registry_periodically(
+                verifier,
+                signer_address,
+                Duration::from_secs(self.config.registry_check_interval_secs),
+                Arc::clone(&self.registry_status),
+                Arc::clone(&self.wake),
+                self.shutdown.child_token(),
+            ));
+        }
+
+        let mut recovered = false;
+        if !self.is_standby() {
+            self.recover_in_flight(&registry)
+                .await
+                .map_err(|e| AnchorError::from_anyhow(&e))?;
+            recovered = true;
+        }
 
         // Mark as ready and L2 healthy
         if let Some(ref health) = self.health_state {
             health.set_ready(true).await;
-            health.mark_l2_healthy().await;
         }
-        {
-            let mut stats = self.stats.write().await;
-            stats.mark_l2_healthy();
+        self.mark_l2_healthy().await;
+
+        if self.config.sequencer_stream {
+            tokio::spawn(watch_commitment_stream(
+                Arc::clone(&self.sequencer_client),
+                Arc::clone(&self.wake),
+                Duration::from_secs(self.config.sequencer_stream_idle_timeout_secs),
+                Duration::from_secs(self.config.anchor_interval_secs),
+                self.shutdown.child_token(),
+            ));
         }
 
+        // Kept running through the shutdown drain, so a successor can't start
+        // while anchors are still in flight; dropping `run` stops it as well
+        let election = match leader::from_config(&self.config) {
+            Ok(Some(elector)) => {
+                info!(
+                    identity = elector.identity(),
+                    "Standing by until elected submitter"
+                );
+                let stop = CancellationToken::new();
+                let task = tokio::spawn(elect_leader(
+                    elector,
+                    Arc::clone(&self.standby),
+                    self.health_state.clone(),
+                    Arc::clone(&self.metrics),
+                    Arc::clone(&self.wake),
+                    stop.clone(),
+                ));
+                Some((task, stop.drop_guard()))
+            }
+            Ok(None) => None,
+            Err(e) => {
+                return Err(AnchorError::Config(ConfigError::InvalidValue {
+                    field: "LEADER_ELECTION".to_string(),
+                    message: format!("{:#}", e),
+                }));
+            }
+        };
+
         // Main loop
-        loop {
+        while !self.is_shutting_down() {
+            self.check_registry_guard(&registry, signer_address, &mut guard)
+                .await;
+            self.apply_registry_status(signer_address).await;
+            self.reconcile_batch_events(&registry).await;
+
+            self.check_wallet_balance(&registry, signer_address).await;
+            self.check_l2_head(&registry).await;
+
+            if self.is_standby() {
+                self.standby_preflight(&registry, signer_address).await;
+                // Whoever held the lease meanwhile may have left anchors in flight
+                recovered = false;
+                self.idle().await;
+                continue;
+            }
+
+            if self.is_paused().await {
+                warn!("Submissions paused pending operator resume; skipping anchor cycle");
+                let reason = self.pause.read().await.reason.clone();
+                self.poll_while_held(&registry, PendingStatus::Paused, reason)
+                    .await;
+                self.idle().await;
+                continue;
+            }
+
+            if self.is_registry_paused() {
+                warn!("Registry is paused; skipping anchor cycle until it is unpaused");
+                self.idle().await;
+                continue;
+            }
+
+            if self.is_budget_exhausted().await {
+                warn!("Gas budget exhausted; skipping anchor cycle until the window resets");
+                self.idle().await;
+                continue;
+            }
+
+            if let Some(opens_at) = self.hold_outside_schedule(&registry).await {
+                self.idle_until(opens_at).await;
+                continue;
+            }
+
+            if !recovered {
+                // Promoted from standby: pick up whatever the previous leader left in flight
+                if let Err(e) = self.recover_in_flight(&registry).await {
+                    self.record_error(AnchorError::Internal(format!(
+                        "In-flight recovery after promotion failed: {}",
+                        e
+                    )))
+                    .await;
+                    error!(error = %e, "In-flight recovery after promotion failed");
+                }
+                recovered = true;
+                if let Some(ref health) = self.health_state {
+                    let mut status = health.standby.read().await.clone();
+                    status.standby = false;
+                    health.set_standby_status(status).await;
+                }
+            }
+
             {
                 let mut stats = self.stats.write().await;
                 stats.total_cycles += 1;
             }
+            self.metrics.cycles.inc();
 
             let (allow_request, breaker_state) = {
                 let mut breaker = self.circuit_breaker.write().await;
@@ -385,20 +1687,43 @@ impl AnchorService {
                     stats.circuit_breaker_state = breaker_state;
                     stats.record_open_circuit_skip();
                 }
+                self.metrics.record_open_circuit_skip();
                 warn!(
                     state = breaker_state.as_str(),
                     "Circuit breaker open; skipping anchor cycle"
                 );
-                tokio::time::sleep(Duration::from_secs(self.config.anchor_interval_secs)).await;
+                self.idle().await;
                 continue;
             }
 
             self.update_circuit_breaker_state(breaker_state).await;
 
-            match self.anchor_pending(&registry).await {
+            if let Some(dependency) = self.open_dependency().await {
+                self.stats.write().await.record_open_circuit_skip();
+                self.metrics.record_open_circuit_skip();
+                warn!(
+                    dependency = dependency.as_str(),
+                    "Dependency circuit breaker open; skipping anchor cycle"
+                );
+                self.idle().await;
+                continue;
+            }
+
+            self.reconcile_nonces(&registry, signer_address).await;
+
+            let cycle_started = std::time::Instant::now();
+            let trace = TraceContext::new_root();
+            let span = info_span!("anchor_cycle", trace_id = %trace.trace_id_hex());
+            let outcome = trace
+                .scope(self.anchor_pending(&registry).instrument(span))
+                .await;
+            self.metrics.observe_cycle(cycle_started.elapsed());
+
+            match outcome {
                 Ok(AnchorCycleOutcome::Healthy(results)) => {
                     let successful = results.iter().filter(|r| r.success).count();
-                    let failed = results.iter().filter(|r| !r.success).count();
+                    let deferred = results.iter().filter(|r| r.deferred).count();
+                    let failed = results.len() - successful - deferred;
 
                     if failed > 0 {
                         self.record_cycle_failure(ErrorType::Transaction).await;
@@ -410,6 +1735,7 @@ impl AnchorService {
                         info!(
                             successful = successful,
                             failed = failed,
+                            deferred = deferred,
                             "Anchor cycle complete"
                         );
                     }
@@ -425,140 +1751,1842 @@ impl AnchorService {
                 }
             }
 
-            tokio::time::sleep(Duration::from_secs(self.config.anchor_interval_secs)).await;
+            self.wait_for_next_cycle().await;
+        }
+
+        self.drain_on_shutdown(&registry).await;
+        if let Some((task, stop)) = election {
+            drop(stop);
+            let _ = task.await;
         }
+        Ok(())
     }
 
-    /// Anchor all pending commitments
-    async fn anchor_pending<P: Provider<HttpTransport> + Clone>(
+    /// Send what the sequencer is still owed before exiting
+    ///
+    /// Every submitted transaction and undelivered notification is already in
+    /// the journal, so whatever doesn't get through here is recovered on restart.
+    async fn drain_on_shutdown<P: Provider<RpcTransport> + Clone>(
         &self,
         registry: &RegistryClient<P>,
-    ) -> Result<AnchorCycleOutcome> {
-        let gas_price = match registry.gas_price().await {
-            Ok(gas_price) => {
-                if let Some(ref health) = self.health_state {
-                    health.mark_l2_healthy().await;
-                }
-                {
-                    let mut stats = self.stats.write().await;
-                    stats.mark_l2_healthy();
-                }
-                gas_price
-            }
-            Err(e) => {
-                self.record_error(AnchorError::L2Connection(L2Error::GasPriceError(
-                    e.to_string(),
-                )))
-                .await;
-                warn!(error = %e, "Failed to fetch gas price");
-                return Ok(AnchorCycleOutcome::Failed(ErrorType::L2Connection));
-            }
-        };
-
-        if self.config.max_gas_price_gwei > 0 {
-            let max_gas_price =
-                U256::from(self.config.max_gas_price_gwei) * U256::from(1_000_000_000u64);
-
-            if gas_price > max_gas_price {
-                {
-                    let mut stats = self.stats.write().await;
-                    stats.record_gas_skip();
-                }
-                warn!(
-                    gas_price = %gas_price,
-                    max_gas_price = %max_gas_price,
-                    "Skipping anchor cycle: gas price above configured maximum"
-                );
-                return Ok(AnchorCycleOutcome::Healthy(vec![]));
-            }
-        }
-
+    ) {
+        info!("Shutdown requested; flushing queued anchor notifications");
         self.flush_pending_notifications().await;
+        self.notify_finalized_anchors(registry).await;
 
-        // Fetch pending commitments from sequencer
-        let mut commitments = match self.sequencer_client.get_pending_commitments().await {
-            Ok(c) => {
-                // Mark sequencer as healthy on successful fetch
-                if let Some(ref health) = self.health_state {
-                    health.mark_sequencer_healthy().await;
-                }
-                {
-                    let mut stats = self.stats.write().await;
-                    stats.mark_sequencer_healthy();
-                }
-                c
-            }
-            Err(e) => {
-                self.record_error(AnchorError::SequencerApi(
-                    SequencerApiError::ConnectionFailed {
-                        url: self.config.sequencer_api_url.clone(),
-                        message: e.to_string(),
-                    },
-                ))
-                .await;
-                debug!(error = %e, "Failed to fetch pending commitments");
-                return Ok(AnchorCycleOutcome::Failed(ErrorType::SequencerApi));
-            }
-        };
-
-        if commitments.is_empty() {
-            debug!("No pending commitments to anchor");
-            return Ok(AnchorCycleOutcome::Healthy(vec![]));
+        let queued = self.pending_notifications.read().await.len();
+        let in_flight = self.journal.entries().await.len();
+        if queued > 0 || in_flight > 0 {
+            warn!(
+                queued_notifications = queued,
+                journal_entries = in_flight,
+                "Stopping with unfinished anchors; they will be recovered from the journal on restart"
+            );
+        } else {
+            info!("All in-flight anchors finished");
         }
+    }
 
-        info!(count = commitments.len(), "Found pending commitments");
-
-        if self.config.max_commitments_per_cycle > 0 {
-            let limit = self.config.max_commitments_per_cycle as usize;
-            if commitments.len() > limit {
-                info!(
-                    limit = limit,
-                    total = commitments.len(),
-                    "Limiting commitments to max per cycle"
-                );
-                commitments.truncate(limit);
+    /// Sleep until the next poll, or until the commitment stream, webhook or an
+    /// operator asks for a cycle
+    ///
+    /// A source that waits for work (such as a long poll) has already waited
+    /// when it comes back empty, so the next fetch follows after
+    /// `IDLE_REFETCH_DELAY` instead of the full interval.
+    async fn wait_for_next_cycle(&self) {
+        let delay = if self.source_idle.load(Ordering::SeqCst) {
+            IDLE_REFETCH_DELAY.min(Duration::from_secs(self.config.anchor_interval_secs))
+        } else {
+            Duration::from_secs(self.config.anchor_interval_secs)
+        };
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = self.wake.notified() => {
+                debug!("Woken early; starting anchor cycle");
             }
+            _ = self.shutdown.cancelled() => {}
         }
+    }
 
-        let mut results = Vec::new();
-
-        for commitment in commitments {
-            // Check minimum event threshold
-            if commitment.event_count < self.config.min_events_for_anchor {
-                debug!(
-                    batch_id = %commitment.batch_id,
-                    event_count = commitment.event_count,
-                    min_required = self.config.min_events_for_anchor,
-                    "Skipping batch: below minimum event threshold"
-                );
-                continue;
+    /// First dependency whose breaker is refusing calls this cycle
+    async fn open_dependency(&self) -> Option<Dependency> {
+        for dependency in [Dependency::L2Rpc, Dependency::SequencerApi] {
+            if !self.dependency_allowed(dependency).await {
+                return Some(dependency);
             }
+        }
+        None
+    }
 
-            if self.has_pending_notification(&commitment.batch_id).await {
-                debug!(
-                    batch_id = %commitment.batch_id,
-                    "Skipping batch: awaiting sequencer acknowledgement retry"
-                );
-                continue;
-            }
+    /// Capture the block height to watch control events from, and the registry
+    /// owner when watching ownership
+    ///
+    /// Pauses immediately if the owner differs from `expected_registry_owner`.
+    async fn init_registry_guard<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+    ) -> Result<RegistryGuard> {
+        let next_block = registry.block_number().await? + 1;
+        if !self.config.watch_registry_ownership {
+            return Ok(RegistryGuard {
+                owner: None,
+                next_block,
+            });
+        }
+        let owner = registry.owner().await?;
 
-            // Anchor with retries
-            let result = self.anchor_with_retry(registry, &commitment).await;
-            results.push(result);
+        let expected = self
+            .config
+            .expected_registry_owner
+            .as_deref()
+            .ok_or_else(|| {
+                anyhow::anyhow!("WATCH_REGISTRY_OWNERSHIP=true requires EXPECTED_REGISTRY_OWNER")
+            })?
+            .parse::<Address>()?;
+        if owner != expected {
+            let error = AuthorizationError::OwnershipChanged {
+                expected: expected.to_string(),
+                actual: owner.to_string(),
+            };
+            self.pause_submissions(error.to_string()).await;
+            self.record_error(AnchorError::Authorization(error)).await;
         }
 
-        Ok(AnchorCycleOutcome::Healthy(results))
+        info!(owner = %owner, from_block = next_block, "Watching registry ownership");
+        Ok(RegistryGuard {
+            owner: Some(expected),
+            next_block,
+        })
     }
 
-    /// Anchor a single commitment with retries
-    async fn anchor_with_retry<P: Provider<HttpTransport> + Clone>(
+    /// Pause submissions on unexpected ownership transfers and halt on
+    /// de-authorization of our signer
+    async fn check_registry_guard<P: Provider<RpcTransport> + Clone>(
         &self,
         registry: &RegistryClient<P>,
-        commitment: &BatchCommitment,
-    ) -> AnchorResult {
-        let mut last_error = None;
-
+        signer_address: Address,
+        guard: &mut RegistryGuard,
+    ) {
+        let latest = match registry.block_number().await {
+            Ok(latest) => latest,
+            Err(e) => {
+                warn!(error = %e, "Failed to read block height for registry watch");
+                return;
+            }
+        };
+        if latest < guard.next_block {
+            return;
+        }
+
+        let events = match registry
+            .control_events(signer_address, guard.next_block, latest)
+            .await
+        {
+            Ok(events) => events,
+            Err(e) => {
+                warn!(error = %e, "Failed to fetch registry control events");
+                return;
+            }
+        };
+        guard.next_block = latest + 1;
+
+        for event in events {
+            match event {
+                RegistryControlEvent::OwnershipTransferred {
+                    new_owner,
+                    block_number,
+                    ..
+                } if guard.owner.is_some_and(|owner| owner != new_owner) => {
+                    let error = AuthorizationError::OwnershipChanged {
+                        expected: guard.owner.unwrap_or_default().to_string(),
+                        actual: new_owner.to_string(),
+                    };
+                    self.pause_submissions(format!("{} at block {}", error, block_number))
+                        .await;
+                    self.record_error(AnchorError::Authorization(error)).await;
+                    guard.owner = Some(new_owner);
+                }
+                RegistryControlEvent::SequencerAuthorized {
+                    authorized,
+                    block_number,
+                    ..
+                } => {
+                    let since = format!("since block {}", block_number);
+                    if authorized {
+                        self.restore_authorization(signer_address, &since).await;
+                    } else {
+                        self.halt_deauthorized(signer_address, &since).await;
+                    }
+                }
+                RegistryControlEvent::PauseChanged {
+                    paused,
+                    block_number,
+                } => {
+                    self.set_registry_paused(paused, &format!("at block {}", block_number))
+                        .await;
+                }
+                RegistryControlEvent::StrictModeUpdated {
+                    enabled,
+                    block_number,
+                } => {
+                    info!(enabled, block_number, "Registry strict mode changed");
+                    if let Some(ref mut status) = *self.registry_status.write().await {
+                        status.strict_mode = enabled;
+                    }
+                    self.strict_registry.store(
+                        enabled && self.config.preflight_state_root_check,
+                        Ordering::SeqCst,
+                    );
+                }
+                other => {
+                    debug!(event = ?other, "Registry control event");
+                }
+            }
+        }
+    }
+
+    /// `BatchCommitted` logs from `from_block` on, and the last block they cover
+    ///
+    /// Read from the indexer database when one is running, which trails the
+    /// head by `INDEXER_CONFIRMATIONS`; otherwise scanned over RPC up to the head.
+    async fn batch_events_since<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        from_block: u64,
+    ) -> Option<(Vec<CommittedBatchEvent>, u64)> {
+        #[cfg(feature = "indexer")]
+        if let Some(ref index) = *self.batch_index.read().await {
+            let indexed_through = match index.next_block().await {
+                Ok(next_block) => next_block?.checked_sub(1)?,
+                Err(e) => {
+                    warn!(error = %e, "Failed to read indexer position for event reconciliation");
+                    return None;
+                }
+            };
+            if indexed_through < from_block {
+                return None;
+            }
+            return match index.batches(from_block, indexed_through).await {
+                Ok(batches) => Some((
+                    batches.into_iter().map(|batch| batch.event).collect(),
+                    indexed_through,
+                )),
+                Err(e) => {
+                    warn!(error = %e, "Failed to read indexed BatchCommitted events");
+                    None
+                }
+            };
+        }
+
+        let latest = match registry.block_number().await {
+            Ok(latest) => latest,
+            Err(e) => {
+                warn!(error = %e, "Failed to read block height for event reconciliation");
+                return None;
+            }
+        };
+        if latest < from_block {
+            return None;
+        }
+        match registry.batch_committed_events(from_block, latest).await {
+            Ok(events) => Some((events, latest)),
+            Err(e) => {
+                warn!(error = %e, "Failed to fetch BatchCommitted events");
+                None
+            }
+        }
+    }
+
+    /// Connect to `INDEXER_DATABASE_URL` and keep it updated in the background
+    #[cfg(feature = "indexer")]
+    async fn start_indexer<P: Provider<RpcTransport> + Clone + 'static>(
+        &self,
+        registry: RegistryClient<P>,
+        url: &str,
+        shutdown: CancellationToken,
+    ) -> crate::error::AnchorResult<()> {
+        let index = BatchIndex::connect(url).await.map_err(|e| {
+            AnchorError::Config(ConfigError::InvalidValue {
+                field: "INDEXER_DATABASE_URL".to_string(),
+                message: format!("{:#}", e),
+            })
+        })?;
+        let index = Arc::new(index);
+        *self.batch_index.write().await = Some(Arc::clone(&index));
+        tokio::spawn(run_indexer(
+            index,
+            registry,
+            IndexerSettings::from_config(&self.config),
+            Arc::clone(&self.metrics),
+            shutdown,
+        ));
+        Ok(())
+    }
+
+    #[cfg(not(feature = "indexer"))]
+    async fn start_indexer<P: Provider<RpcTransport> + Clone + 'static>(
+        &self,
+        _registry: RegistryClient<P>,
+        _url: &str,
+        _shutdown: CancellationToken,
+    ) -> crate::error::AnchorResult<()> {
+        Err(AnchorError::Config(ConfigError::InvalidValue {
+            field: "INDEXER_DATABASE_URL".to_string(),
+            message: "requires a build with the `indexer` feature".to_string(),
+        }))
+    }
+
+    /// Match new `BatchCommitted` logs against the anchors recorded since the last scan
+    async fn reconcile_batch_events<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+    ) {
+        let mut reconciler = self.reconciler.write().await;
+        let Some(ref mut reconciler) = *reconciler else {
+            return;
+        };
+        let Some((events, latest)) = self
+            .batch_events_since(registry, reconciler.next_block())
+            .await
+        else {
+            return;
+        };
+
+        for discrepancy in reconciler.reconcile(&events, latest) {
+            self.metrics
+                .reconciliation_discrepancies
+                .with_label_values(&[discrepancy.kind()])
+                .inc();
+            match discrepancy {
+                Discrepancy::External {
+                    chain_batch_id,
+                    tx_hash,
+                    block_number,
+                } => warn!(
+                    chain_batch_id = %chain_batch_id,
+                    tx_hash = %tx_hash,
+                    block_number,
+                    "Batch committed by a transaction this instance did not send"
+                ),
+                Discrepancy::Missing {
+                    batch_id,
+                    tx_hash,
+                    block_number,
+                } => warn!(
+                    batch_id = %batch_id,
+                    tx_hash = %tx_hash,
+                    block_number,
+                    "Anchored batch has no BatchCommitted event; the block may have been reorged"
+                ),
+                Discrepancy::TxMismatch {
+                    batch_id,
+                    expected,
+                    actual,
+                } => warn!(
+                    batch_id = %batch_id,
+                    expected = %expected,
+                    actual = %actual,
+                    "Batch committed by a different transaction than the one recorded"
+                ),
+            }
+        }
+    }
+
+    /// Act on the registry state last published by `verify_registry_periodically`
+    ///
+    /// Backs up the event watch in case a log was missed (pruned node, RPC gap),
+    /// and pauses submissions while the registry address has no contract code.
+    async fn apply_registry_status(&self, signer_address: Address) {
+        let Some(status) = self.registry_status.read().await.clone() else {
+            return;
+        };
+
+        if !status.code_present {
+            let reason = format!(
+                "No contract code at SetRegistry address {}",
+                self.config.set_registry_address
+            );
+            self.pause_submissions(reason.clone()).await;
+            if !self.registry_code_missing.swap(true, Ordering::SeqCst) {
+                self.record_error(AnchorError::Config(ConfigError::InvalidValue {
+                    field: "set_registry_address".to_string(),
+                    message: reason,
+                }))
+                .await;
+            }
+            return;
+        }
+        if self.registry_code_missing.swap(false, Ordering::SeqCst) {
+            info!(
+                registry = %self.config.set_registry_address,
+                "Registry contract code found again; resume submissions with POST /resume"
+            );
+        }
+        self.set_registry_paused(status.paused, "on re-check").await;
+
+        if status.authorized {
+            self.restore_authorization(signer_address, "on re-check")
+                .await;
+        } else {
+            self.halt_deauthorized(signer_address, "on re-check").await;
+        }
+    }
+
+    /// Stop submitting, report not-ready and alert once our signer is revoked
+    ///
+    /// Stays halted until the signer is re-authorized; an operator resume
+    /// while still revoked is undone by the next check.
+    async fn halt_deauthorized(&self, signer_address: Address, detail: &str) {
+        let error = AuthorizationError::NotAuthorized {
+            address: signer_address.to_string(),
+        };
+        let reason = format!("{} {}", error, detail);
+        self.set_authorized_status(false).await;
+        self.pause_submissions(reason.clone()).await;
+        if let Some(ref health) = self.health_state {
+            health.set_ready(false).await;
+        }
+        if self.deauthorized.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        error!(signer = %signer_address, "Sequencer de-authorized in SetRegistry; halting");
+        self.record_error(AnchorError::Authorization(error)).await;
+        if let Some(ref hook) = self.alert_hook {
+            let alert = Alert {
+                alert: "sequencer_deauthorized",
+                message: reason,
+                signer: signer_address.to_string(),
+                balance_wei: None,
+                threshold_wei: None,
+                timestamp: Utc::now().to_rfc3339(),
+            };
+            if let Err(e) = hook.send(&alert).await {
+                warn!(url = hook.url(), error = %e, "Failed to send de-authorization alert");
+            }
+        }
+    }
+
+    /// Report ready again once a revoked signer is re-authorized
+    ///
+    /// Submissions stay paused until an operator resumes them.
+    async fn restore_authorization(&self, signer_address: Address, detail: &str) {
+        self.set_authorized_status(true).await;
+        if !self.deauthorized.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        info!(
+            signer = %signer_address,
+            detail,
+            "Sequencer re-authorized; resume submissions with POST /resume"
+        );
+        if let Some(ref health) = self.health_state {
+            health.set_ready(true).await;
+        }
+    }
+
+    /// Hold or release submissions while the registry owner pauses `commitBatch`
+    ///
+    /// Unlike an operator pause this lifts itself: the registry refuses every
+    /// commit while paused, so there is nothing to review once it unpauses.
+    async fn set_registry_paused(&self, paused: bool, detail: &str) {
+        if let Some(ref mut status) = *self.registry_status.write().await {
+            status.paused = paused;
+        }
+        if self.registry_paused.swap(paused, Ordering::SeqCst) == paused {
+            return;
+        }
+        self.metrics.registry_paused.set(i64::from(paused));
+        if paused {
+            warn!(
+                detail,
+                "Registry paused; holding submissions until it is unpaused"
+            );
+        } else {
+            info!(detail, "Registry unpaused; resuming submissions");
+            self.wake.notify_one();
+        }
+    }
+
+    /// Keep the published registry state in line with authorization events
+    async fn set_authorized_status(&self, authorized: bool) {
+        if let Some(ref mut status) = *self.registry_status.write().await {
+            status.authorized = authorized;
+        }
+    }
+
+    /// Heal nonce drift before submitting; runs between cycles when nothing is in flight
+    async fn reconcile_nonces<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        signer_address: Address,
+    ) {
+        match registry.reconcile_nonces(signer_address).await {
+            Ok(Some(snapshot)) => {
+                debug!(
+                    local_next = ?snapshot.local_next,
+                    chain_pending = ?snapshot.chain_pending,
+                    "Nonce state reconciled"
+                );
+                if let Some(ref health) = self.health_state {
+                    health.set_nonce_state(snapshot).await;
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                warn!(error = %e, "Failed to reconcile signer nonce");
+            }
+        }
+    }
+
+    /// Query the signer balance when due and alert when it drops below the threshold
+    async fn check_wallet_balance<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        signer_address: Address,
+    ) {
+        {
+            let mut last_check = self.last_balance_check.write().await;
+            let interval = Duration::from_secs(self.config.balance_check_interval_secs);
+            if last_check.is_some_and(|checked| checked.elapsed() < interval) {
+                return;
+            }
+            *last_check = Some(std::time::Instant::now());
+        }
+
+        let balance = match registry.balance_of(signer_address).await {
+            Ok(balance) => balance,
+            Err(e) => {
+                warn!(error = %e, "Failed to query signer balance");
+                return;
+            }
+        };
+
+        let threshold = low_balance_threshold_wei(&self.config);
+        let low = threshold.is_some_and(|threshold| balance < threshold);
+        let was_low = self.balance_low.swap(low, Ordering::SeqCst);
+
+        if let Some(ref health) = self.health_state {
+            health
+                .set_wallet_status(WalletStatus {
+                    address: signer_address.to_string(),
+                    balance_wei: balance.to_string(),
+                    threshold_wei: threshold.map(|threshold| threshold.to_string()),
+                    low,
+                    checked_at: Utc::now().to_rfc3339(),
+                })
+                .await;
+        }
+
+        match (low, was_low) {
+            (true, false) => {
+                error!(
+                    signer = %signer_address,
+                    balance_wei = %balance,
+                    threshold_wei = ?threshold,
+                    "Signer balance below threshold; top up before submissions fail"
+                );
+                if let (Some(hook), Some(threshold)) = (&self.alert_hook, threshold) {
+                    let alert = Alert {
+                        alert: "low_balance",
+                        message: "Signer balance below threshold".to_string(),
+                        signer: signer_address.to_string(),
+                        balance_wei: Some(balance.to_string()),
+                        threshold_wei: Some(threshold.to_string()),
+                        timestamp: Utc::now().to_rfc3339(),
+                    };
+                    if let Err(e) = hook.send(&alert).await {
+                        warn!(url = hook.url(), error = %e, "Failed to send low-balance alert");
+                    }
+                }
+            }
+            (false, true) => {
+                info!(balance_wei = %balance, "Signer balance back above threshold");
+            }
+            _ => {}
+        }
+    }
+
+    /// Record the latest L2 block so a stalled node is reported unhealthy
+    async fn check_l2_head<P: Provider<RpcTransport> + Clone>(&self, registry: &RegistryClient<P>) {
+        let (block_number, block_timestamp) = match registry.latest_block().await {
+            Ok(head) => head,
+            Err(e) => {
+                warn!(error = %e, "Failed to fetch latest L2 block");
+                return;
+            }
+        };
+        let head = L2Head {
+            block_number,
+            block_timestamp,
+        };
+
+        let max_age = self.config.max_l2_head_age_secs;
+        let age = head.age_secs(Utc::now());
+        if max_age > 0 && age > max_age {
+            warn!(
+                block_number = block_number,
+                age_secs = age,
+                max_age_secs = max_age,
+                "L2 head is stale; node may be stalled"
+            );
+        }
+
+        if let Some(ref health) = self.health_state {
+            health.set_l2_head(head).await;
+        }
+    }
+
+    /// Standby cycle: keep connections warm and verify we could take over, without submitting
+    async fn standby_preflight<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        signer_address: Address,
+    ) {
+        let authorized = match registry.is_authorized(signer_address).await {
+            Ok(authorized) => {
+                self.mark_l2_healthy().await;
+                authorized
+            }
+            Err(e) => {
+                self.record_error(AnchorError::Authorization(AuthorizationError::CheckFailed(
+                    e.to_string(),
+                )))
+                .await;
+                false
+            }
+        };
+
+        let balance = match registry.balance_of(signer_address).await {
+            Ok(balance) => Some(balance),
+            Err(e) => {
+                self.record_error(AnchorError::L2Connection(L2Error::RpcError(e.to_string())))
+                    .await;
+                None
+            }
+        };
+
+        let mirrored_pending = match self.sequencer_client.get_pending_commitments().await {
+            Ok(commitments) => {
+                self.mark_sequencer_healthy().await;
+                Some(commitments.len())
+            }
+            Err(e) => {
+                self.record_error(AnchorError::SequencerApi(
+                    SequencerApiError::ConnectionFailed {
+                        url: self.config.sequencer_api_url.clone(),
+                        message: e.to_string(),
+                    },
+                ))
+                .await;
+                None
+            }
+        };
+
+        let takeover_ready = authorized
+            && balance.is_some_and(|balance| balance > U256::ZERO)
+            && mirrored_pending.is_some();
+
+        debug!(
+            authorized = authorized,
+            balance = ?balance,
+            pending = ?mirrored_pending,
+            takeover_ready = takeover_ready,
+            "Standby pre-flight complete"
+        );
+
+        if let Some(ref health) = self.health_state {
+            let lease_holder = health.standby.read().await.lease_holder.clone();
+            health
+                .set_standby_status(StandbyStatus {
+                    standby: true,
+                    authorized,
+                    balance_wei: balance.map(|balance| balance.to_string()),
+                    mirrored_pending: mirrored_pending.unwrap_or(0),
+                    last_preflight: Some(Utc::now().to_rfc3339()),
+                    takeover_ready,
+                    lease_holder,
+                })
+                .await;
+        }
+    }
+
+    async fn mark_l2_healthy(&self) {
+        if let Some(ref health) = self.health_state {
+            health.mark_l2_healthy().await;
+        }
+        self.recorder.record(StatsEvent::L2Healthy);
+    }
+
+    async fn mark_sequencer_healthy(&self) {
+        if let Some(ref health) = self.health_state {
+            health.mark_sequencer_healthy().await;
+        }
+        self.recorder.record(StatsEvent::SequencerHealthy);
+    }
+
+    /// Anchor all pending commitments
+    async fn anchor_pending<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+    ) -> Result<AnchorCycleOutcome> {
+        let gas_price = match registry.gas_price().await {
+            Ok(gas_price) => {
+                self.record_dependency_call(Dependency::L2Rpc, true).await;
+                self.mark_l2_healthy().await;
+                gas_price
+            }
+            Err(e) => {
+                self.record_dependency_call(Dependency::L2Rpc, false).await;
+                self.record_error(AnchorError::L2Connection(L2Error::GasPriceError(
+                    e.to_string(),
+                )))
+                .await;
+                warn!(error = %e, "Failed to fetch gas price");
+                return Ok(AnchorCycleOutcome::Failed(ErrorType::L2Connection));
+            }
+        };
+
+        let max_gas_price =
+            U256::from(self.config.max_gas_price_gwei) * U256::from(1_000_000_000u64);
+        let gas_above_cap = self.config.max_gas_price_gwei > 0 && gas_price > max_gas_price;
+        if gas_above_cap {
+            self.recorder.record(StatsEvent::GasPriceSkip);
+            warn!(
+                gas_price = %gas_price,
+                max_gas_price = %max_gas_price,
+                "Gas price above configured maximum; deferring pending batches"
+            );
+        }
+
+        let gas_median = self.sample_gas_price(gas_price).await;
+
+        self.flush_pending_notifications().await;
+        self.notify_finalized_anchors(registry).await;
+
+        // Fetch pending commitments from sequencer
+        let Some(fetched) = self.fetch_pending().await else {
+            return Ok(AnchorCycleOutcome::Healthy(Vec::new()));
+        };
+        self.source_idle.store(
+            self.source.waits_for_work() && matches!(fetched, Ok(ref c) if c.is_empty()),
+            Ordering::SeqCst,
+        );
+        let commitments = match fetched {
+            Ok(c) => {
+                // Mark sequencer as healthy on successful fetch
+                self.record_dependency_call(Dependency::SequencerApi, true)
+                    .await;
+                self.mark_sequencer_healthy().await;
+                c
+            }
+            Err(e) => {
+                self.record_dependency_call(Dependency::SequencerApi, false)
+                    .await;
+                self.record_error(AnchorError::SequencerApi(
+                    SequencerApiError::ConnectionFailed {
+                        url: self.config.sequencer_api_url.clone(),
+                        message: e.to_string(),
+                    },
+                ))
+                .await;
+                debug!(error = %e, "Failed to fetch pending commitments");
+                return Ok(AnchorCycleOutcome::Failed(ErrorType::SequencerApi));
+            }
+        };
+
+        self.prune_deferrals(&commitments).await;
+
+        if commitments.is_empty() {
+            debug!("No pending commitments to anchor");
+            self.publish_deferral_queue().await;
+            self.publish_pending_queue(Vec::new()).await;
+            return Ok(AnchorCycleOutcome::Healthy(vec![]));
+        }
+
+        info!(count = commitments.len(), "Found pending commitments");
+
+        // What happens to each fetched batch, for GET /pending
+        let mut queue = Vec::with_capacity(commitments.len());
+        let mut unskipped = Vec::with_capacity(commitments.len());
+        for commitment in commitments {
+            match self.journal.skipped(&commitment.batch_id).await {
+                Some(skipped) => {
+                    debug!(
+                        batch_id = %commitment.batch_id,
+                        reason = %skipped.reason,
+                        "Skipping batch: skipped by operator"
+                    );
+                    queue.push(
+                        PendingBatch::new(
+                            &commitment,
+                            PendingStatus::Skipped,
+                            Some(skipped.reason),
+                        )
+                        .with_metadata(skipped.metadata),
+                    );
+                    self.complete_batch(commitment.batch_id).await;
+                }
+                None => unskipped.push(commitment),
+            }
+        }
+
+        let (commitments, rejected) = validate::partition(unskipped, Utc::now());
+        for (commitment, error) in rejected {
+            // The first copy of a duplicated batch is still in play
+            let duplicate = matches!(error, ValidationError::DuplicateBatchId { .. });
+            queue.push(PendingBatch::new(
+                &commitment,
+                if duplicate {
+                    PendingStatus::Duplicate
+                } else {
+                    PendingStatus::Invalid
+                },
+                Some(error.to_string()),
+            ));
+            self.report_validation_error(&commitment, error).await;
+            if !duplicate {
+                self.fail_batch(commitment.batch_id).await;
+            }
+        }
+
+        let mut eligible = Vec::new();
+
+        for commitment in commitments {
+            if self
+                .batch_retries
+                .write()
+                .await
+                .remove(&commitment.batch_id)
+            {
+                info!(batch_id = %commitment.batch_id, "Retrying batch at operator request");
+                self.stream_backoff
+                    .write()
+                    .await
+                    .remove(&stream_key(&commitment));
+                self.anchored.write().await.remove(&commitment.batch_id);
+            }
+
+            // Check minimum event threshold
+            if commitment.event_count < self.config.min_events_for_anchor {
+                let max_age = self.config.max_commitment_age_secs;
+                let age = commitment_age_secs(&commitment);
+                if max_age > 0 && age >= max_age {
+                    info!(
+                        batch_id = %commitment.batch_id,
+                        event_count = commitment.event_count,
+                        age_secs = age,
+                        "Anchoring batch below minimum event threshold: older than MAX_COMMITMENT_AGE_SECS"
+                    );
+                } else {
+                    debug!(
+                        batch_id = %commitment.batch_id,
+                        event_count = commitment.event_count,
+                        min_required = self.config.min_events_for_anchor,
+                        "Skipping batch: below minimum event threshold"
+                    );
+                    let mut reason = format!(
+                        "{} events, minimum {}",
+                        commitment.event_count, self.config.min_events_for_anchor
+                    );
+                    if max_age > 0 {
+                        reason.push_str(&format!("; anchored anyway in {}s", max_age - age));
+                    }
+                    queue.push(PendingBatch::new(
+                        &commitment,
+                        PendingStatus::BelowThreshold,
+                        Some(reason),
+                    ));
+                    // Held for the age override; without one it is done with
+                    if max_age == 0 {
+                        self.complete_batch(commitment.batch_id).await;
+                    }
+                    continue;
+                }
+            }
+
+            if self.has_pending_notification(&commitment.batch_id).await {
+                debug!(
+                    batch_id = %commitment.batch_id,
+                    "Skipping batch: awaiting sequencer acknowledgement retry"
+                );
+                queue.push(PendingBatch::new(
+                    &commitment,
+                    PendingStatus::AwaitingAcknowledgement,
+                    None,
+                ));
+                continue;
+            }
+
+            let cached = self.anchored.write().await.get(&commitment.batch_id);
+            if let Some(notification) = cached {
+                debug!(
+                    batch_id = %commitment.batch_id,
+                    tx_hash = %notification.chain_tx_hash,
+                    "Skipping batch: already anchored; re-sending acknowledgement"
+                );
+                self.metrics.duplicate_deliveries.inc();
+                queue.push(PendingBatch::new(
+                    &commitment,
+                    PendingStatus::AlreadyAnchored,
+                    Some(format!("anchored in {}", notification.chain_tx_hash)),
+                ));
+                self.notify_sequencer_or_queue(commitment.batch_id, notification)
+                    .await;
+                continue;
+            }
+
+            if self.config.verify_events_root {
+                match self.verify_events_root(&commitment).await {
+                    Ok(()) => {}
+                    Err(AnchorError::Validation(error)) => {
+                        queue.push(PendingBatch::new(
+                            &commitment,
+                            PendingStatus::Invalid,
+                            Some(error.to_string()),
+                        ));
+                        self.report_validation_error(&commitment, error).await;
+                        self.fail_batch(commitment.batch_id).await;
+                        continue;
+                    }
+                    Err(e) => {
+                        warn!(
+                            batch_id = %commitment.batch_id,
+                            error = %e,
+                            "Skipping batch: could not fetch its events to verify events_root"
+                        );
+                        queue.push(PendingBatch::new(
+                            &commitment,
+                            PendingStatus::VerificationPending,
+                            Some(e.to_string()),
+                        ));
+                        continue;
+                    }
+                }
+            }
+
+            if let Some(median) = gas_median.filter(|median| gas_price > *median) {
+                let age = commitment_age_secs(&commitment);
+                if self.config.gas_aware_tenant(&commitment.tenant_id)
+                    && age < self.config.gas_aware_max_delay_secs
+                {
+                    debug!(
+                        batch_id = %commitment.batch_id,
+                        gas_price = %gas_price,
+                        median = %median,
+                        "Holding batch: gas price above its recent median"
+                    );
+                    queue.push(PendingBatch::new(
+                        &commitment,
+                        PendingStatus::GasAboveMedian,
+                        Some(format!(
+                            "gas price {} wei above median {} wei; anchored anyway in {}s",
+                            gas_price,
+                            median,
+                            self.config.gas_aware_max_delay_secs - age
+                        )),
+                    ));
+                    continue;
+                }
+            }
+
+            if let Some(retry_in) = self.stream_backing_off(&commitment).await {
+                debug!(
+                    batch_id = %commitment.batch_id,
+                    store_id = %commitment.store_id,
+                    retry_in_secs = retry_in.as_secs(),
+                    "Skipping batch: its stream is backing off after a failure"
+                );
+                queue.push(PendingBatch::new(
+                    &commitment,
+                    PendingStatus::StreamBackoff,
+                    Some(format!("retry in {}s", retry_in.as_secs())),
+                ));
+                continue;
+            }
+
+            eligible.push(commitment);
+        }
+
+        let candidates = eligible.clone();
+        limit_cycle(
+            &mut eligible,
+            self.config.max_commitments_per_cycle,
+            &self.config.enterprise_tenants,
+        );
+        let limited: HashSet<Uuid> = eligible
+            .iter()
+            .map(|commitment| commitment.batch_id)
+            .collect();
+        let (throttled, throttle_reason) = self.apply_hourly_cap(&mut eligible).await;
+        order_within_streams(&mut eligible);
+        let (eligible, anomalies) = split_sequence_anomalies(eligible);
+        let mut held: HashMap<Uuid, String> = HashMap::new();
+        for (commitment, error) in anomalies {
+            held.insert(commitment.batch_id, error.to_string());
+            self.report_validation_error(&commitment, error).await;
+        }
+        let gas_cap_reason = format!(
+            "Gas price {} wei exceeds maximum {} wei",
+            gas_price, max_gas_price
+        );
+        let planned: HashSet<Uuid> = eligible
+            .iter()
+            .map(|commitment| commitment.batch_id)
+            .collect();
+        for commitment in &candidates {
+            let (status, reason) = if !limited.contains(&commitment.batch_id) {
+                (PendingStatus::CycleLimit, None)
+            } else if throttled.contains(&commitment.batch_id) {
+                (PendingStatus::Throttled, Some(throttle_reason.clone()))
+            } else if let Some(error) = held.remove(&commitment.batch_id) {
+                (PendingStatus::OrderingHold, Some(error))
+            } else if !planned.contains(&commitment.batch_id) {
+                (
+                    PendingStatus::OrderingHold,
+                    Some("an earlier batch of its stream is out of sequence".to_string()),
+                )
+            } else if gas_above_cap && !self.is_overdue(&commitment.batch_id).await {
+                (PendingStatus::GasCap, Some(gas_cap_reason.clone()))
+            } else {
+                (PendingStatus::Planned, None)
+            };
+            queue.push(PendingBatch::new(commitment, status, reason));
+        }
+        self.publish_pending_queue(queue).await;
+        if !eligible.is_empty() {
+            self.refresh_strict_mode(registry).await;
+        }
+        let streams: HashMap<Uuid, (Uuid, Uuid)> = eligible
+            .iter()
+            .map(|commitment| (commitment.batch_id, stream_key(commitment)))
+            .collect();
+
+        if self.config.report_cost_estimates {
+            self.report_cost_estimates(registry, &eligible, gas_price)
+                .await;
+        }
+
+        let mut results = Vec::new();
+
+        if gas_above_cap {
+            // Only batches deferred past max_defer_secs go out at the current price
+            let mut overdue = Vec::new();
+            for commitment in eligible {
+                if self.is_overdue(&commitment.batch_id).await {
+                    overdue.push(commitment);
+                } else {
+                    self.defer_batch(commitment.batch_id).await;
+                    results.push(deferred_result(&commitment, gas_cap_reason.clone()));
+                }
+            }
+            if !overdue.is_empty() {
+                warn!(
+                    count = overdue.len(),
+                    max_defer_secs = self.config.max_defer_secs,
+                    "Anchoring batches deferred past MAX_DEFER_SECS regardless of gas price"
+                );
+            }
+            results.extend(self.anchor_in_order(registry, &overdue).await);
+        } else if registry.supports_aggregation() && self.config.max_batches_per_tx > 1 {
+            let mut blocked = HashSet::new();
+            for chunk in eligible.chunks(self.config.max_batches_per_tx as usize) {
+                let chunk: Vec<BatchCommitment> = chunk
+                    .iter()
+                    .filter(|commitment| !blocked.contains(&stream_key(commitment)))
+                    .cloned()
+                    .collect();
+                if chunk.is_empty() {
+                    continue;
+                }
+                if !self.may_start_anchor() {
+                    info!("Submissions stopping; leaving remaining batches with the source");
+                    break;
+                }
+                let chunk_results = self.anchor_aggregated(registry, &chunk).await;
+                for result in chunk_results.iter().filter(|result| !result.success) {
+                    if let Some(commitment) = chunk.iter().find(|c| c.batch_id == result.batch_id) {
+                        blocked.insert(stream_key(commitment));
+                    }
+                }
+                results.extend(chunk_results);
+            }
+        } else if self.config.max_concurrent_anchors > 1 {
+            results = self.anchor_concurrently(registry, eligible).await;
+        } else {
+            results = self.anchor_in_order(registry, &eligible).await;
+        }
+
+        for result in &results {
+            if result.success {
+                self.complete_batch(result.batch_id).await;
+            } else if !result.deferred {
+                self.fail_batch(result.batch_id).await;
+            }
+        }
+        self.count_hourly_anchors(&results).await;
+        self.update_stream_backoff(&results, &streams).await;
+
+        self.publish_deferral_queue().await;
+        Ok(AnchorCycleOutcome::Healthy(results))
+    }
+
+    /// Record whether submissions this cycle need the state-root pre-flight check
+    async fn refresh_strict_mode<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+    ) {
+        let strict = if self.config.preflight_state_root_check {
+            registry.strict_mode_enabled().await.unwrap_or_else(|e| {
+                debug!(error = %e, "Failed to read registry strict mode; skipping state-root pre-flight");
+                false
+            })
+        } else {
+            false
+        };
+        self.strict_registry.store(strict, Ordering::SeqCst);
+    }
+
+    /// Check a batch continues its store's on-chain state root and sequence
+    async fn check_continuity<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        commitment: &BatchCommitment,
+    ) -> Result<(), AnchorError> {
+        let checked = match registry.check_state_root_continuity(commitment).await {
+            Ok(()) => registry.check_sequence_continuity(commitment).await,
+            Err(e) => Err(e),
+        };
+        if let Err(AnchorError::Validation(ref error)) = checked {
+            self.metrics
+                .record_validation_failure(error.kind(), error.is_sequence_anomaly());
+        }
+        checked
+    }
+
+    /// Recompute a batch's events root from the sequencer's raw events
+    async fn verify_events_root(&self, commitment: &BatchCommitment) -> Result<(), AnchorError> {
+        let events = self
+            .sequencer_client
+            .get_batch_events(commitment.batch_id)
+            .await?;
+        let computed = merkle::events_root(&events)?;
+        if commitment.events_root.parse::<FixedBytes<32>>().ok() == Some(computed) {
+            return Ok(());
+        }
+        Err(ValidationError::EventsRootMismatch {
+            expected: commitment.events_root.clone(),
+            computed: computed.to_string(),
+        }
+        .into())
+    }
+
+    /// Fetch a batch's raw events as the payload published for data availability
+    async fn batch_payload(&self, commitment: &BatchCommitment) -> Result<Vec<u8>, AnchorError> {
+        let events = self
+            .sequencer_client
+            .get_batch_events(commitment.batch_id)
+            .await?;
+        da::encode_events(&events)
+    }
+
+    /// zstd-compress a batch payload for DA_MODE=calldata
+    fn compress_payload(
+        &self,
+        commitment: &BatchCommitment,
+        payload: &[u8],
+    ) -> Result<Bytes, AnchorError> {
+        let compressed = da::compress(payload, self.config.max_da_payload_bytes as usize)?;
+        debug!(
+            batch_id = %commitment.batch_id,
+            raw_bytes = payload.len(),
+            compressed_bytes = compressed.len(),
+            "Compressed batch events"
+        );
+        Ok(compressed)
+    }
+
+    /// Submit a batch payload to Celestia before anchoring it
+    ///
+    /// A blob already submitted for the batch by an earlier attempt is reused.
+    async fn submit_to_celestia(
+        &self,
+        celestia: &CelestiaClient,
+        commitment: &BatchCommitment,
+        payload: &[u8],
+    ) -> Result<CelestiaBlobRef> {
+        if let Some(blob) = self.celestia_blobs.read().await.get(&commitment.batch_id) {
+            return Ok(blob.clone());
+        }
+
+        let blob = match celestia.submit(payload).await {
+            Ok(blob) => blob,
+            Err(e) => {
+                self.metrics
+                    .celestia_submissions
+                    .with_label_values(&["failed"])
+                    .inc();
+                return Err(e.context(format!(
+                    "Failed to submit batch {} to Celestia at {}",
+                    commitment.batch_id,
+                    celestia.url()
+                )));
+            }
+        };
+        self.metrics
+            .celestia_submissions
+            .with_label_values(&["submitted"])
+            .inc();
+        info!(
+            batch_id = %commitment.batch_id,
+            height = blob.height,
+            commitment = %blob.commitment,
+            "Submitted batch events to Celestia"
+        );
+        self.celestia_blobs
+            .write()
+            .await
+            .insert(commitment.batch_id, blob.clone());
+        Ok(blob)
+    }
+
+    /// Record a batch's Celestia blob in its journal entry
+    async fn journal_celestia(&self, batch_id: Uuid, blob: &CelestiaBlobRef) {
+        if let Err(e) = self.journal.record_celestia(batch_id, blob).await {
+            warn!(
+                batch_id = %batch_id,
+                error = %e,
+                "Failed to journal Celestia blob"
+            );
+        }
+    }
+
+    /// Pin a batch payload to IPFS and journal its CID; failures are logged, not fatal
+    async fn pin_payload(
+        &self,
+        ipfs: &IpfsPinner,
+        commitment: &BatchCommitment,
+        payload: Vec<u8>,
+    ) -> Option<String> {
+        match ipfs.pin(commitment.batch_id, payload).await {
+            Ok(cid) => {
+                self.metrics.ipfs_pins.with_label_values(&["pinned"]).inc();
+                debug!(batch_id = %commitment.batch_id, cid = %cid, "Pinned batch events to IPFS");
+                if let Err(e) = self
+                    .journal
+                    .record_ipfs_cid(commitment.batch_id, &cid)
+                    .await
+                {
+                    warn!(
+                        batch_id = %commitment.batch_id,
+                        error = %e,
+                        "Failed to journal IPFS CID"
+                    );
+                }
+                Some(cid)
+            }
+            Err(e) => {
+                self.metrics.ipfs_pins.with_label_values(&["failed"]).inc();
+                warn!(
+                    batch_id = %commitment.batch_id,
+                    ipfs_api = %ipfs.url(),
+                    error = %e,
+                    "Failed to pin batch events to IPFS; anchoring without a CID"
+                );
+                None
+            }
+        }
+    }
+
+    /// Wait for a batch data transaction and charge its fee to the batch's tenant
+    async fn confirm_batch_data<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        commitment: &BatchCommitment,
+        tx_hash: FixedBytes<32>,
+        data: &[u8],
+    ) {
+        match registry
+            .wait_for_receipt(tx_hash, self.config.tx_confirmation_timeout_secs)
+            .await
+        {
+            Ok(_) => {
+                debug!(
+                    batch_id = %commitment.batch_id,
+                    tx_hash = %tx_hash,
+                    bytes = data.len(),
+                    "Batch data posted"
+                );
+            }
+            Err(e) => {
+                error!(
+                    batch_id = %commitment.batch_id,
+                    tx_hash = %tx_hash,
+                    error = %e,
+                    "Batch data transaction did not confirm"
+                );
+                if !matches!(
+                    registry.receipt_outcome(tx_hash).await,
+                    Ok(ReceiptOutcome::Reverted)
+                ) {
+                    return;
+                }
+            }
+        }
+        self.record_gas_spend(
+            registry,
+            tx_hash,
+            std::slice::from_ref(commitment),
+            false,
+            Some(DaCharge::Posted(data)),
+        )
+        .await;
+    }
+
+    /// Log, count and record a batch refused by validation
+    async fn report_validation_error(&self, commitment: &BatchCommitment, error: ValidationError) {
+        warn!(
+            batch_id = %commitment.batch_id,
+            store_id = %commitment.store_id,
+            sequence_range = ?(commitment.sequence_start, commitment.sequence_end),
+            error = %error,
+            "Batch failed validation; not anchoring it"
+        );
+        self.metrics
+            .record_validation_failure(error.kind(), error.is_sequence_anomaly());
+        self.record_error_with_context(
+            AnchorError::Validation(error),
+            ErrorContext::from(commitment),
+        )
+        .await;
+    }
+
+    /// Time left before a stream that failed recently may be retried
+    async fn stream_backing_off(&self, commitment: &BatchCommitment) -> Option<Duration> {
+        let backoff = self.stream_backoff.read().await;
+        let retry_at = backoff.get(&stream_key(commitment))?.retry_at;
+        retry_at.checked_duration_since(std::time::Instant::now())
+    }
+
+    /// Reset streams that anchored this cycle and back off the ones that failed
+    ///
+    /// Each stream counts its own consecutive failures, so one store stuck on a
+    /// bad batch doesn't slow down retries for any other store.
+    async fn update_stream_backoff(
+        &self,
+        results: &[AnchorResult],
+        streams: &HashMap<Uuid, (Uuid, Uuid)>,
+    ) {
+        let failed: HashSet<(Uuid, Uuid)> = results
+            .iter()
+            .filter(|result| !result.success && !result.deferred)
+            .filter_map(|result| streams.get(&result.batch_id).copied())
+            .collect();
+        let mut backoff = self.stream_backoff.write().await;
+        for result in results.iter().filter(|result| result.success) {
+            if let Some(key) = streams.get(&result.batch_id) {
+                if !failed.contains(key) {
+                    backoff.remove(key);
+                }
+            }
+        }
+        let now = std::time::Instant::now();
+        for key in failed {
+            let failures = backoff.get(&key).map_or(0, |stream| stream.failures) + 1;
+            let delay = self.config.stream_retry_backoff(failures);
+            warn!(
+                tenant_id = %key.0,
+                store_id = %key.1,
+                failures,
+                retry_in_secs = delay.as_secs(),
+                "Backing off store stream after a failed cycle"
+            );
+            backoff.insert(
+                key,
+                StreamBackoff {
+                    failures,
+                    retry_at: now + delay,
+                },
+            );
+        }
+    }
+
+    /// Tell the commitment source anchoring a batch failed
+    async fn fail_batch(&self, batch_id: Uuid) {
+        if let Err(e) = self.source.fail(batch_id).await {
+            warn!(
+                batch_id = %batch_id,
+                source = self.source.name(),
+                error = %e,
+                "Failed to report batch failure to commitment source"
+            );
+        }
+    }
+
+    /// Tell the commitment source a batch needs no further delivery
+    async fn complete_batch(&self, batch_id: Uuid) {
+        if let Err(e) = self.source.complete(batch_id).await {
+            warn!(
+                batch_id = %batch_id,
+                source = self.source.name(),
+                error = %e,
+                "Failed to complete batch with commitment source"
+            );
+        }
+    }
+
+    /// Anchor commitments one at a time in the given order
+    ///
+    /// A failure holds back the rest of that batch's tenant/store stream until
+    /// the next cycle; other streams carry on. A deferral stops the cycle, since
+    /// later batches would hit the same gas cap.
+    async fn anchor_in_order<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        commitments: &[BatchCommitment],
+    ) -> Vec<AnchorResult> {
+        let mut results = Vec::with_capacity(commitments.len());
+        let mut blocked = HashSet::new();
+        for commitment in commitments {
+            let key = stream_key(commitment);
+            if blocked.contains(&key) {
+                debug!(
+                    batch_id = %commitment.batch_id,
+                    store_id = %commitment.store_id,
+                    sequence_start = commitment.sequence_start,
+                    "Holding batch until its store's earlier batch is anchored"
+                );
+                continue;
+            }
+            if !self.may_start_anchor() {
+                info!(
+                    batch_id = %commitment.batch_id,
+                    "Submissions stopping; leaving remaining batches with the source"
+                );
+                break;
+            }
+            let result = self.anchor_with_retry(registry, commitment).await;
+            let deferred = result.deferred;
+            let failed = !result.success;
+            results.push(result);
+            if deferred {
+                break;
+            }
+            if failed {
+                warn!(
+                    batch_id = %commitment.batch_id,
+                    store_id = %commitment.store_id,
+                    "Deferring remaining batches for store after failure"
+                );
+                blocked.insert(key);
+            }
+        }
+        results
+    }
+
+    /// Anchor independent tenant/store streams in parallel
+    ///
+    /// Commitments for the same store stay sequential and in sequencer order; a
+    /// failure stops that store's remaining batches for this cycle, since they
+    /// would only fail the registry's state-root chain check.
+    async fn anchor_concurrently<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        commitments: Vec<BatchCommitment>,
+    ) -> Vec<AnchorResult> {
+        let streams = group_by_store(commitments);
+        info!(
+            streams = streams.len(),
+            max_concurrent = self.config.max_concurrent_anchors,
+            "Anchoring store streams concurrently"
+        );
+
+        stream::iter(streams)
+            .map(|commitments| async move {
+                let mut results = Vec::with_capacity(commitments.len());
+                for commitment in &commitments {
+                    if !self.may_start_anchor() {
+                        break;
+                    }
+                    let result = self.anchor_with_retry(registry, commitment).await;
+                    let failed = !result.success;
+                    results.push(result);
+                    if failed {
+                        warn!(
+                            batch_id = %commitment.batch_id,
+                            store_id = %commitment.store_id,
+                            "Deferring remaining batches for store after failure"
+                        );
+                        break;
+                    }
+                }
+                results
+            })
+            .buffer_unordered(self.config.max_concurrent_anchors as usize)
+            .concat()
+            .await
+    }
+
+    /// Estimate per-batch anchoring cost and report it to the sequencer (best effort)
+    ///
+    /// Lets upstream decide to merge small batches instead of paying per-batch overhead.
+    async fn report_cost_estimates<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        commitments: &[BatchCommitment],
+        gas_price: U256,
+    ) {
+        let signer_address = match self.get_signer_address() {
+            Ok(address) => address,
+            Err(e) => {
+                warn!(error = %e, "Cannot estimate anchor cost without a signer address");
+                return;
+            }
+        };
+
+        for commitment in commitments {
+            let gas_estimate = match registry
+                .estimate_commit_gas(commitment, signer_address)
+                .await
+            {
+                Ok(gas) => gas,
+                Err(e) => {
+                    debug!(
+                        batch_id = %commitment.batch_id,
+                        error = %e,
+                        "Failed to estimate anchor gas"
+                    );
+                    continue;
+                }
+            };
+
+            let estimate = cost_estimate(commitment, gas_estimate, gas_price);
+            debug!(
+                batch_id = %commitment.batch_id,
+                gas_estimate = gas_estimate,
+                estimated_cost_wei = %estimate.estimated_cost_wei,
+                "Estimated anchor cost"
+            );
+
+            if let Err(e) = self
+                .sequencer_client
+                .report_cost_estimate(commitment.batch_id, &estimate)
+                .await
+            {
+                debug!(
+                    batch_id = %commitment.batch_id,
+                    error = %e,
+                    "Failed to report anchor cost estimate"
+                );
+            }
+        }
+    }
+
+    /// Anchor a group of commitments in a single multicall transaction,
+    /// falling back to individual submission if the aggregate fails.
+    async fn anchor_aggregated<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        chunk: &[BatchCommitment],
+    ) -> Vec<AnchorResult> {
+        let mut results = Vec::new();
+        let mut to_submit = Vec::new();
+        let mut blocked = HashSet::new();
+
+        // Already-committed batches would revert the whole aggregate, so resolve them first
+        for commitment in chunk {
+            let key = stream_key(commitment);
+            if blocked.contains(&key) {
+                debug!(
+                    batch_id = %commitment.batch_id,
+                    store_id = %commitment.store_id,
+                    sequence_start = commitment.sequence_start,
+                    "Holding batch until its store's earlier batch is anchored"
+                );
+                continue;
+            }
+            match registry.batch_commitment(commitment).await {
+                Ok(None) => to_submit.push(commitment.clone()),
+                Ok(Some(_)) | Err(_) => {
+                    let result = self.anchor_with_retry(registry, commitment).await;
+                    if !result.success {
+                        blocked.insert(key);
+                    }
+                    results.push(result);
+                }
+            }
+        }
+
+        // So would a batch that doesn't continue its store in strict mode
+        if self.strict_registry.load(Ordering::SeqCst) {
+            to_submit = self
+                .retain_continuous(registry, to_submit, &mut results)
+                .await;
+        }
+
+        if to_submit.len() < 2 {
+            results.extend(self.anchor_in_order(registry, &to_submit).await);
+            return results;
+        }
+
+        let start = std::time::Instant::now();
+        match self.submit_aggregate(registry, &to_submit).await {
+            Ok(aggregated) => {
+                let anchor_time_ms = start.elapsed().as_millis() as u64;
+                for (commitment, result) in to_submit.iter().zip(aggregated) {
+                    self.record_anchor_success(commitment, anchor_time_ms).await;
+                    results.push(result);
+                }
+            }
+            Err(e) if is_gas_price_too_high(&e) => {
+                for commitment in &to_submit {
+                    results.push(self.defer_for_gas_price(commitment, &e).await);
+                }
+            }
+            Err(e) => {
+                warn!(
+                    batches = to_submit.len(),
+                    error = %e,
+                    "Aggregated anchor failed; falling back to individual submission"
+                );
+                results.extend(self.anchor_in_order(registry, &to_submit).await);
+            }
+        }
+
+        results
+    }
+
+    /// Drop batches that don't continue their store from an aggregate
+    ///
+    /// The first batch of each store is checked against the registry like
+    /// [`Self::check_continuity`]; later ones against the batch before them in
+    /// the aggregate, which is not on chain yet. A failing batch is abandoned
+    /// for this cycle as on the single-batch path, and its store's later
+    /// batches are held.
+    async fn retain_continuous<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        commitments: Vec<BatchCommitment>,
+        results: &mut Vec<AnchorResult>,
+    ) -> Vec<BatchCommitment> {
+        let mut previous: HashMap<(Uuid, Uuid), BatchCommitment> = HashMap::new();
+        let mut blocked = HashSet::new();
+        let mut continuous = Vec::with_capacity(commitments.len());
+        for commitment in commitments {
+            let key = stream_key(&commitment);
+            if blocked.contains(&key) {
+                debug!(
+                    batch_id = %commitment.batch_id,
+                    store_id = %commitment.store_id,
+                    sequence_start = commitment.sequence_start,
+                    "Holding batch until its store's earlier batch is anchored"
+                );
+                continue;
+            }
+            let checked = match previous.get(&key) {
+                Some(previous) => {
+                    let checked = continues_batch(previous, &commitment);
+                    if let Err(ref error) = checked {
+                        self.metrics
+                            .record_validation_failure(error.kind(), error.is_sequence_anomaly());
+                    }
+                    checked.map_err(AnchorError::from)
+                }
+                None => self.check_continuity(registry, &commitment).await,
+            };
+            match checked {
+                Ok(()) => {
+                    previous.insert(key, commitment.clone());
+                    continuous.push(commitment);
+                }
+                Err(error @ AnchorError::Validation(_)) => {
+                    warn!(
+                        batch_id = %commitment.batch_id,
+                        store_id = %commitment.store_id,
+                        error = %error,
+                        "Batch does not continue its store; leaving it out of the aggregate"
+                    );
+                    results.push(
+                        self.abandon_batch(&commitment, error.to_string(), error)
+                            .await,
+                    );
+                    blocked.insert(key);
+                }
+                Err(error) => {
+                    debug!(
+                        batch_id = %commitment.batch_id,
+                        error = %error,
+                        "Continuity check failed; anchoring the batch on its own"
+                    );
+                    let result = self.anchor_with_retry(registry, &commitment).await;
+                    if result.success {
+                        previous.insert(key, commitment);
+                    } else {
+                        blocked.insert(key);
+                    }
+                    results.push(result);
+                }
+            }
+        }
+        continuous
+    }
+
+    async fn submit_aggregate<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        commitments: &[BatchCommitment],
+    ) -> Result<Vec<AnchorResult>> {
+        info!(
+            batches = commitments.len(),
+            events = commitments
+                .iter()
+                .map(|c| c.event_count as u64)
+                .sum::<u64>(),
+            "Anchoring commitments in one aggregated transaction"
+        );
+
+        // Each batch's events go to Celestia and IPFS as on the single-batch path
+        let mut payloads = Vec::with_capacity(commitments.len());
+        let mut celestia_blobs = Vec::with_capacity(commitments.len());
+        for commitment in commitments {
+            let payload = if self.ipfs.is_some() || self.celestia.is_some() {
+                Some(self.batch_payload(commitment).await?)
+            } else {
+                None
+            };
+            celestia_blobs.push(match (&self.celestia, payload.as_deref()) {
+                (Some(celestia), Some(payload)) => Some(
+                    self.submit_to_celestia(celestia, commitment, payload)
+                        .await?,
+                ),
+                _ => None,
+            });
+            payloads.push(payload);
+        }
+
+        for commitment in commitments {
+            self.audit(AuditEvent::attempt(commitment, 1)).await;
+        }
+        let tx_hash = registry.send_commit_batches(commitments).await?;
+        let tx_hash_hex = format!("0x{}", hex::encode(tx_hash.as_slice()));
+        let mut ipfs_cids = Vec::with_capacity(commitments.len());
+        for ((commitment, payload), blob) in commitments.iter().zip(payloads).zip(&celestia_blobs) {
+            if let Err(e) = self
+                .journal
+                .record_submitted(commitment.batch_id, &tx_hash_hex, registry.chain_id())
+                .await
+            {
+                warn!(
+                    batch_id = %commitment.batch_id,
+                    error = %e,
+                    "Failed to journal submitted transaction"
+                );
+            }
+            if let Some(blob) = blob {
+                self.journal_celestia(commitment.batch_id, blob).await;
+            }
+            ipfs_cids.push(match (&self.ipfs, payload) {
+                (Some(ipfs), Some(payload)) => self.pin_payload(ipfs, commitment, payload).await,
+                _ => None,
+            });
+        }
+
+        let batch_ids: Vec<Uuid> = commitments.iter().map(|c| c.batch_id).collect();
+        // A fee-bump replacement keeps the original gas limit
+        let gas_estimate = registry.take_gas_estimate(&tx_hash);
+        let (mined_hash, block_number, gas_used) =
+            match self.await_confirmation(registry, tx_hash, &batch_ids).await {
+                Ok(receipt) => receipt,
+                Err(e) => {
+                    if let Ok(ReceiptOutcome::Reverted) = registry.receipt_outcome(tx_hash).await {
+                        self.record_gas_spend(registry, tx_hash, commitments, false, None)
+                            .await;
+                        for commitment in commitments {
+                            self.forget_journal_entry(&commitment.batch_id).await;
+                        }
+                    }
+                    return Err(e);
+                }
+            };
+        self.record_gas_spend(registry, mined_hash, commitments, true, None)
+            .await;
+        self.record_gas_estimate(gas_estimate, gas_used);
+
+        // A fee-bump replacement may be the one that was mined
+        let tx_hash_hex = format!("0x{}", hex::encode(mined_hash.as_slice()));
+
+        // Gas is shared by the whole transaction; attribute it evenly per batch
+        let gas_per_batch = gas_used / commitments.len() as u64;
+        let mut results = Vec::with_capacity(commitments.len());
+
+        for ((commitment, ipfs_cid), celestia) in
+            commitments.iter().zip(ipfs_cids).zip(celestia_blobs)
+        {
+            let notification = AnchorNotification {
+                chain_tx_hash: tx_hash_hex.clone(),
+                chain_id: registry.chain_id(),
+                block_number: Some(block_number),
+                gas_used: Some(gas_per_batch),
+                blob_versioned_hashes: Vec::new(),
+                ipfs_cid,
+                celestia,
+            };
+            self.celestia_blobs
+                .write()
+                .await
+                .remove(&commitment.batch_id);
+            self.audit(AuditEvent::anchored(commitment.batch_id, &notification))
+                .await;
+            self.record_history(HistoryEntry::anchored(commitment.batch_id, &notification))
+                .await;
+            self.notify_sequencer_or_queue(commitment.batch_id, notification.clone())
+                .await;
+
+            let result = AnchorResult {
+                batch_id: commitment.batch_id,
+                tx_hash: tx_hash_hex.clone(),
+                block_number,
+                gas_used: gas_per_batch,
+                success: true,
+                error: None,
+                deferred: false,
+            };
+            let calldata = encode_commit_batch(commitment, registry.batch_id_scheme())
+                .ok()
+                .map(|calldata| calldata.to_string());
+            self.record_anchor(
+                registry,
+                commitment,
+                &result,
+                registry.batch_id_scheme(),
+                calldata,
+                notification,
+            )
+            .await;
+            results.push(result);
+        }
+
+        info!(
+            batches = commitments.len(),
+            tx_hash = %tx_hash_hex,
+            block_number = block_number,
+            gas_used = gas_used,
+            "Aggregated commitments anchored successfully"
+        );
+
+        Ok(results)
+    }
+
+    /// Anchor a single commitment with retries
+    async fn anchor_with_retry<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        commitment: &BatchCommitment,
+    ) -> AnchorResult {
+        let mut last_error = None;
+
         for attempt in 1..=self.config.max_retries {
+            self.audit(AuditEvent::attempt(commitment, attempt)).await;
             let start = std::time::Instant::now();
             match self.anchor_commitment(registry, commitment).await {
                 Ok(result) => {
@@ -567,6 +3595,9 @@ impl AnchorService {
 
                     return result;
                 }
+                Err(e) if is_gas_price_too_high(&e) => {
+                    return self.defer_for_gas_price(commitment, &e).await;
+                }
                 Err(e) => {
                     match self.recover_already_anchored(registry, commitment).await {
                         Ok(Some(result)) => {
@@ -587,14 +3618,37 @@ impl AnchorService {
                         }
                     }
 
+                    let error = AnchorError::from_anyhow(&e);
+                    match error {
+                        AnchorError::Transaction(TransactionError::BatchAlreadyCommitted) => {
+                            return self.settle_already_committed(registry, commitment).await;
+                        }
+                        AnchorError::Transaction(TransactionError::NotAuthorizedSequencer) => {
+                            if let Ok(signer_address) = self.get_signer_address() {
+                                self.halt_deauthorized(signer_address, "on commitBatch revert")
+                                    .await;
+                            }
+                        }
+                        _ => {}
+                    }
                     warn!(
                         batch_id = %commitment.batch_id,
                         attempt = attempt,
                         max_retries = self.config.max_retries,
+                        severity = ?error.severity(),
                         error = %e,
                         "Anchor attempt failed"
                     );
-                    last_error = Some(e.to_string());
+                    let retryable = error.is_retryable();
+                    last_error = Some((e.to_string(), error));
+
+                    if !retryable {
+                        warn!(
+                            batch_id = %commitment.batch_id,
+                            "Error is not retryable; abandoning batch for this cycle"
+                        );
+                        break;
+                    }
 
                     if attempt < self.config.max_retries {
                         tokio::time::sleep(Duration::from_secs(
@@ -605,32 +3659,501 @@ impl AnchorService {
                 }
             }
         }
-
-        // All retries failed
-        self.record_anchor_failure().await;
-
-        let error_message = last_error.unwrap_or_else(|| "unknown error".to_string());
-        self.record_error(AnchorError::Transaction(
-            TransactionError::SubmissionFailed(error_message.clone()),
-        ))
-        .await;
-
-        AnchorResult {
-            batch_id: commitment.batch_id,
-            tx_hash: String::new(),
-            block_number: 0,
-            gas_used: 0,
-            success: false,
-            error: Some(error_message),
-        }
+
+        // All retries failed, or the error was not retryable
+        let (error_message, error) = last_error.unwrap_or_else(|| {
+            let message = "unknown error".to_string();
+            let error =
+                AnchorError::Transaction(TransactionError::SubmissionFailed(message.clone()));
+            (message, error)
+        });
+        self.abandon_batch(commitment, error_message, error).await
+    }
+
+    /// Report a batch the registry already holds but whose transaction wasn't found
+    ///
+    /// The sequencer is told the batch is anchored without a transaction hash,
+    /// and it is counted as already anchored rather than as a fresh anchor.
+    async fn settle_already_committed<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        commitment: &BatchCommitment,
+    ) -> AnchorResult {
+        match registry.batch_commitment(commitment).await {
+            Ok(Some(stored)) => {
+                if commitment.events_root.parse::<FixedBytes<32>>().ok() != Some(stored.events_root)
+                {
+                    warn!(
+                        batch_id = %commitment.batch_id,
+                        events_root = %commitment.events_root,
+                        stored_events_root = %stored.events_root,
+                        "Registry holds a different commitment under this batch id"
+                    );
+                }
+                warn!(
+                    batch_id = %commitment.batch_id,
+                    committed_at = stored.timestamp,
+                    sequence_range = ?(stored.sequence_start, stored.sequence_end),
+                    "Registry reports the batch already committed but its event was not found; \
+                     reporting it as already anchored"
+                );
+            }
+            Ok(None) => {
+                warn!(
+                    batch_id = %commitment.batch_id,
+                    "Registry reports the batch already committed but it can't be read back; \
+                     reporting it as already anchored"
+                );
+            }
+            Err(e) => {
+                warn!(
+                    batch_id = %commitment.batch_id,
+                    error = %e,
+                    "Registry reports the batch already committed and reading it back failed; \
+                     reporting it as already anchored"
+                );
+            }
+        }
+
+        let notification = AnchorNotification {
+            chain_tx_hash: String::new(),
+            chain_id: registry.chain_id(),
+            block_number: None,
+            gas_used: None,
+            blob_versioned_hashes: Vec::new(),
+            ipfs_cid: None,
+            celestia: None,
+        };
+        self.metrics
+            .batches
+            .with_label_values(&["already_anchored"])
+            .inc();
+        self.deferred.write().await.remove(&commitment.batch_id);
+        self.audit(AuditEvent::anchored(commitment.batch_id, &notification))
+            .await;
+        self.record_history(HistoryEntry::already_anchored(
+            commitment.batch_id,
+            &notification,
+        ))
+        .await;
+        // Journals the notification until the sequencer acknowledges it
+        self.notify_sequencer_or_queue(commitment.batch_id, notification)
+            .await;
+
+        AnchorResult {
+            batch_id: commitment.batch_id,
+            tx_hash: String::new(),
+            block_number: 0,
+            gas_used: 0,
+            success: true,
+            error: None,
+            deferred: false,
+        }
+    }
+
+    /// Count, record and report a batch that won't be anchored this cycle
+    async fn abandon_batch(
+        &self,
+        commitment: &BatchCommitment,
+        error_message: String,
+        error: AnchorError,
+    ) -> AnchorResult {
+        self.record_anchor_failure(commitment).await;
+        self.record_error_with_context(error, ErrorContext::from(commitment))
+            .await;
+        self.audit(AuditEvent::Failed {
+            batch_id: commitment.batch_id,
+            error: error_message.clone(),
+        })
+        .await;
+        self.record_history(HistoryEntry::failed(
+            commitment.batch_id,
+            error_message.clone(),
+        ))
+        .await;
+
+        AnchorResult {
+            batch_id: commitment.batch_id,
+            tx_hash: String::new(),
+            block_number: 0,
+            gas_used: 0,
+            success: false,
+            error: Some(error_message),
+            deferred: false,
+        }
+    }
+
+    /// Leave a batch for a later cycle because gas is above `max_gas_price_gwei`
+    async fn defer_for_gas_price(
+        &self,
+        commitment: &BatchCommitment,
+        error: &anyhow::Error,
+    ) -> AnchorResult {
+        self.recorder.record(StatsEvent::GasPriceSkip);
+        let deferrals = self.defer_batch(commitment.batch_id).await;
+        warn!(
+            batch_id = %commitment.batch_id,
+            deferrals = deferrals,
+            error = %error,
+            "Deferring batch to the next cycle: gas price above configured maximum"
+        );
+        self.audit(AuditEvent::Deferred {
+            batch_id: commitment.batch_id,
+            reason: error.to_string(),
+        })
+        .await;
+        self.record_history(HistoryEntry::deferred(
+            commitment.batch_id,
+            error.to_string(),
+        ))
+        .await;
+
+        deferred_result(commitment, error.to_string())
+    }
+
+    /// Record this cycle's gas price; the median of the sample window once it has one
+    ///
+    /// Always `None` unless `GAS_AWARE_MAX_DELAY_SECS` is set.
+    async fn sample_gas_price(&self, gas_price: U256) -> Option<U256> {
+        if self.config.gas_aware_max_delay_secs == 0 {
+            return None;
+        }
+        let mut history = self.gas_history.write().await;
+        history.record(
+            u128::try_from(gas_price).unwrap_or(u128::MAX),
+            std::time::Instant::now(),
+        );
+        let median = history.median();
+        self.metrics
+            .gas_price_median_wei
+            .set(median.unwrap_or_default() as f64);
+        median.map(U256::from)
+    }
+
+    /// Keep `eligible` within what `MAX_ANCHORS_PER_HOUR` has left, most urgent first
+    ///
+    /// Returns the batches held back and the reason shown for them in `/pending`.
+    async fn apply_hourly_cap(
+        &self,
+        eligible: &mut Vec<BatchCommitment>,
+    ) -> (HashSet<Uuid>, String) {
+        let Some(ref throttle) = self.throttle else {
+            return (HashSet::new(), String::new());
+        };
+        let now = std::time::Instant::now();
+        let mut throttle = throttle.write().await;
+        let room = throttle.room(now);
+        if eligible.len() <= room {
+            return (HashSet::new(), String::new());
+        }
+        let before: Vec<Uuid> = eligible.iter().map(|c| c.batch_id).collect();
+        if room == 0 {
+            eligible.clear();
+        } else {
+            limit_cycle(eligible, room as u32, &self.config.enterprise_tenants);
+        }
+        let kept: HashSet<Uuid> = eligible.iter().map(|c| c.batch_id).collect();
+        let held: HashSet<Uuid> = before
+            .into_iter()
+            .filter(|batch_id| !kept.contains(batch_id))
+            .collect();
+        for batch_id in &held {
+            throttle.hold(*batch_id, now);
+        }
+        let next_slot = throttle.next_slot(now).as_secs();
+        warn!(
+            held = held.len(),
+            max_anchors_per_hour = self.config.max_anchors_per_hour,
+            next_slot_secs = next_slot,
+            "MAX_ANCHORS_PER_HOUR reached; holding batches for a later cycle"
+        );
+        self.metrics.throttled_batches.set(throttle.held() as i64);
+        let mut reason = format!(
+            "hourly cap of {} anchors reached",
+            self.config.max_anchors_per_hour
+        );
+        // Nothing anchored in the window yet means this cycle used up the cap
+        if next_slot > 0 {
+            reason.push_str(&format!("; next slot in {}s", next_slot));
+        }
+        (held, reason)
+    }
+
+    /// Count this cycle's anchors against `MAX_ANCHORS_PER_HOUR`
+    async fn count_hourly_anchors(&self, results: &[AnchorResult]) {
+        let Some(ref throttle) = self.throttle else {
+            return;
+        };
+        let now = std::time::Instant::now();
+        let mut throttle = throttle.write().await;
+        for result in results.iter().filter(|result| result.success) {
+            if let Some(delay) = throttle.record_anchor(&result.batch_id, now) {
+                self.metrics.throttle_delay.observe(delay.as_secs_f64());
+            }
+        }
+        self.metrics.throttled_batches.set(throttle.held() as i64);
+    }
+
+    /// Add a batch to the deferral queue, keeping the time it was first deferred
+    ///
+    /// Returns how many times the batch has now been deferred.
+    async fn defer_batch(&self, batch_id: Uuid) -> u32 {
+        let mut deferred = self.deferred.write().await;
+        let batch = deferred.entry(batch_id).or_insert_with(|| DeferredBatch {
+            first_deferred: std::time::Instant::now(),
+            deferrals: 0,
+        });
+        batch.deferrals += 1;
+        batch.deferrals
+    }
+
+    /// Whether a batch has waited out `max_defer_secs` and must be anchored at any price
+    async fn is_overdue(&self, batch_id: &Uuid) -> bool {
+        if self.config.max_defer_secs == 0 {
+            return false;
+        }
+        self.deferred
+            .read()
+            .await
+            .get(batch_id)
+            .is_some_and(|batch| {
+                batch.first_deferred.elapsed() >= Duration::from_secs(self.config.max_defer_secs)
+            })
+    }
+
+    /// Drop deferrals for batches the sequencer no longer reports as pending
+    async fn prune_deferrals(&self, pending: &[BatchCommitment]) {
+        let pending: HashSet<Uuid> = pending.iter().map(|c| c.batch_id).collect();
+        self.deferred
+            .write()
+            .await
+            .retain(|batch_id, _| pending.contains(batch_id));
+        if let Some(ref throttle) = self.throttle {
+            let mut throttle = throttle.write().await;
+            throttle.retain_held(|batch_id| pending.contains(batch_id));
+            self.metrics.throttled_batches.set(throttle.held() as i64);
+        }
+    }
+
+    /// Publish what this cycle fetched and decided for `GET /pending`
+    async fn publish_pending_queue(&self, batches: Vec<PendingBatch>) {
+        let mut skipped: HashMap<&'static str, u64> = HashMap::new();
+        for batch in batches
+            .iter()
+            .filter(|batch| batch.status != PendingStatus::Planned)
+        {
+            *skipped.entry(batch.status.as_str()).or_default() += 1;
+        }
+        self.recorder
+            .record(StatsEvent::Skipped(skipped.into_iter().collect()));
+        if let Some(ref health) = self.health_state {
+            health
+                .set_pending_queue(PendingQueue {
+                    fetched_at: Utc::now(),
+                    batches,
+                })
+                .await;
+        }
+    }
+
+    /// Publish deferral queue depth and age
+    async fn publish_deferral_queue(&self) {
+        let deferred = self.deferred.read().await;
+        let oldest_secs = deferred
+            .values()
+            .map(|batch| batch.first_deferred.elapsed().as_secs())
+            .max()
+            .unwrap_or(0);
+        self.recorder.record(StatsEvent::DeferralQueue {
+            depth: deferred.len() as u64,
+            oldest_secs,
+        });
+    }
+
+    /// Wait for a broadcast transaction, replacing it with bumped fees while it is stuck
+    ///
+    /// Returns the hash that was actually mined, which may be a replacement.
+    /// With `stuck_tx_blocks` set, a transaction pending for longer is counted as
+    /// stuck and, if `cancel_stuck_txs` is enabled, cancelled at the same nonce.
+    /// The time to a receipt and its gas are added to the stats.
+    async fn await_confirmation<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        tx_hash: FixedBytes<32>,
+        batch_ids: &[Uuid],
+    ) -> Result<(FixedBytes<32>, u64, u64)> {
+        let start = std::time::Instant::now();
+        let receipt = self
+            .await_confirmation_or_replace(registry, tx_hash, batch_ids)
+            .await?;
+        self.recorder.record(StatsEvent::Confirmed {
+            latency_ms: start.elapsed().as_millis() as u64,
+            gas_used: receipt.2,
+        });
+        Ok(receipt)
+    }
+
+    /// [`Self::await_confirmation`] without the stats
+    async fn await_confirmation_or_replace<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        tx_hash: FixedBytes<32>,
+        batch_ids: &[Uuid],
+    ) -> Result<(FixedBytes<32>, u64, u64)> {
+        let bumping = self.config.tx_stuck_timeout_secs > 0;
+        let stuck_blocks = self.config.stuck_tx_blocks;
+        if !bumping && stuck_blocks == 0 {
+            return Ok(registry
+                .wait_for_receipt(tx_hash, self.config.tx_confirmation_timeout_secs)
+                .await?);
+        }
+
+        let deadline = tokio::time::Instant::now()
+            + Duration::from_secs(self.config.tx_confirmation_timeout_secs);
+        let stuck_timeout = Duration::from_secs(self.config.tx_stuck_timeout_secs);
+        let poll = match (bumping, stuck_blocks > 0) {
+            (true, true) => stuck_timeout.min(STUCK_CHECK_INTERVAL),
+            (true, false) => stuck_timeout,
+            _ => STUCK_CHECK_INTERVAL,
+        };
+        let submitted_block = if stuck_blocks > 0 {
+            Some(registry.block_number().await?)
+        } else {
+            None
+        };
+        let mut tx_hashes = vec![tx_hash];
+        let mut last_broadcast = tokio::time::Instant::now();
+        let mut bumps = 0;
+        let mut stuck_reported = false;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+            if let Some(receipt) = registry
+                .wait_for_any_receipt(&tx_hashes, poll.min(remaining))
+                .await?
+            {
+                return Ok(receipt);
+            }
+
+            if tokio::time::Instant::now() >= deadline {
+                anyhow::bail!("{}", TransactionError::ConfirmationTimeout);
+            }
+
+            let latest = *tx_hashes.last().unwrap_or(&tx_hash);
+            if let Some(submitted_block) = submitted_block {
+                let pending_blocks = registry
+                    .block_number()
+                    .await?
+                    .saturating_sub(submitted_block);
+                if pending_blocks > stuck_blocks {
+                    let stuck = TransactionError::Stuck {
+                        tx_hash: latest.to_string(),
+                        blocks: pending_blocks,
+                    };
+                    if !stuck_reported {
+                        stuck_reported = true;
+                        self.recorder.record(StatsEvent::StuckTx);
+                        warn!(error = %stuck, "Transaction stuck in the mempool");
+                    }
+
+                    if self.config.cancel_stuck_txs {
+                        match registry
+                            .cancel_transaction(latest, self.config.fee_bump_percent)
+                            .await
+                        {
+                            Ok(Some(cancellation)) => {
+                                warn!(
+                                    tx_hash = %latest,
+                                    cancellation = %cancellation,
+                                    "Stuck transaction cancelled with a self-transfer"
+                                );
+                                let message = stuck.to_string();
+                                self.record_error(AnchorError::Transaction(stuck)).await;
+                                anyhow::bail!("{}", message);
+                            }
+                            Ok(None) => {
+                                debug!(tx_hash = %latest, "Nothing to cancel; still waiting for receipt");
+                            }
+                            Err(e) => {
+                                warn!(tx_hash = %latest, error = %e, "Failed to cancel stuck transaction");
+                            }
+                        }
+                    }
+                }
+            }
+
+            if !bumping
+                || bumps >= self.config.max_fee_bumps
+                || last_broadcast.elapsed() < stuck_timeout
+            {
+                continue;
+            }
+
+            match registry
+                .replace_with_higher_fees(latest, self.config.fee_bump_percent)
+                .await
+            {
+                Ok(Some(replacement)) => {
+                    bumps += 1;
+                    last_broadcast = tokio::time::Instant::now();
+                    self.recorder.record(StatsEvent::FeeBump);
+                    let replacement_hex = format!("0x{}", hex::encode(replacement.as_slice()));
+                    warn!(
+                        stuck_tx = %latest,
+                        replacement = %replacement_hex,
+                        bump = bumps,
+                        "Transaction stuck; rebroadcast with bumped fees"
+                    );
+                    for batch_id in batch_ids {
+                        if let Err(e) = self
+                            .journal
+                            .record_submitted(*batch_id, &replacement_hex, registry.chain_id())
+                            .await
+                        {
+                            warn!(
+                                batch_id = %batch_id,
+                                error = %e,
+                                "Failed to journal replacement transaction"
+                            );
+                        }
+                    }
+                    tx_hashes.push(replacement);
+                }
+                Ok(None) => {
+                    debug!(tx_hash = %latest, "Nothing to replace; still waiting for receipt");
+                }
+                Err(e) => {
+                    warn!(tx_hash = %latest, error = %e, "Fee-bump replacement failed");
+                }
+            }
+        }
     }
 
     /// Anchor a single commitment
-    async fn anchor_commitment<P: Provider<HttpTransport> + Clone>(
+    async fn anchor_commitment<P: Provider<RpcTransport> + Clone>(
         &self,
         registry: &RegistryClient<P>,
         commitment: &BatchCommitment,
     ) -> Result<AnchorResult> {
+        // Skip submission if the batch is already on-chain (restart or sequencer re-delivery)
+        if registry.batch_commitment(commitment).await?.is_some() {
+            info!(
+                batch_id = %commitment.batch_id,
+                "Batch already committed on-chain; skipping submission"
+            );
+            return match self.recover_already_anchored(registry, commitment).await? {
+                Some(result) => Ok(result),
+                None => anyhow::bail!(
+                    "Batch {} is committed on-chain but its BatchCommitted event was not found",
+                    commitment.batch_id
+                ),
+            };
+        }
+
+        if self.strict_registry.load(Ordering::SeqCst) {
+            self.check_continuity(registry, commitment).await?;
+        }
+
         info!(
             batch_id = %commitment.batch_id,
             sequence_range = ?(commitment.sequence_start, commitment.sequence_end),
@@ -638,10 +4161,158 @@ impl AnchorService {
             "Anchoring commitment"
         );
 
-        // Submit to chain
-        let (tx_hash, block_number, gas_used) = registry
-            .commit_batch(commitment, self.config.tx_confirmation_timeout_secs)
-            .await?;
+        // Events are fetched once for every consumer of the raw payload
+        let payload =
+            if self.config.da_mode != DaMode::Off || self.ipfs.is_some() || self.celestia.is_some()
+            {
+                Some(self.batch_payload(commitment).await?)
+            } else {
+                None
+            };
+        let da = match (self.config.da_mode, payload.as_deref()) {
+            (DaMode::Blob, Some(payload)) => Some(DaAttachment::Blobs(da::blob_sidecar(payload)?)),
+            (DaMode::Calldata, Some(payload)) => Some(DaAttachment::Calldata(
+                self.compress_payload(commitment, payload)?,
+            )),
+            _ => None,
+        };
+        let blob_versioned_hashes = da
+            .as_ref()
+            .map(DaAttachment::blob_versioned_hashes)
+            .unwrap_or_default();
+        // With a DA contract, compressed events get their own transaction instead
+        let (da, da_post) = match (da, self.config.da_contract_address.as_deref()) {
+            (Some(DaAttachment::Calldata(data)), Some(contract)) => {
+                (None, Some((parse_address(contract)?, data)))
+            }
+            (da, _) => (da, None),
+        };
+        let appended = match da {
+            Some(DaAttachment::Calldata(ref data)) => Some(data.clone()),
+            _ => None,
+        };
+
+        let celestia = match (&self.celestia, payload.as_deref()) {
+            (Some(celestia), Some(payload)) => Some(
+                self.submit_to_celestia(celestia, commitment, payload)
+                    .await?,
+            ),
+            _ => None,
+        };
+
+        // Submit to chain, journaling the hash before waiting so a crash can be recovered
+        let tx_hash = match registry
+            .send_commit_batch_with_da(commitment, da.clone())
+            .await
+            .map_err(anyhow::Error::from)
+        {
+            Err(e) if is_gas_price_too_high(&e) && self.is_overdue(&commitment.batch_id).await => {
+                warn!(
+                    batch_id = %commitment.batch_id,
+                    error = %e,
+                    "Batch deferred past MAX_DEFER_SECS; anchoring regardless of gas price"
+                );
+                self.recorder.record(StatsEvent::DeferralOverride);
+                registry.send_commit_batch_uncapped(commitment, da).await?
+            }
+            result => result?,
+        };
+        if let Err(e) = self
+            .journal
+            .record_submitted(
+                commitment.batch_id,
+                &format!("0x{}", hex::encode(tx_hash.as_slice())),
+                registry.chain_id(),
+            )
+            .await
+        {
+            warn!(
+                batch_id = %commitment.batch_id,
+                error = %e,
+                "Failed to journal submitted transaction"
+            );
+        }
+        if !blob_versioned_hashes.is_empty() {
+            if let Err(e) = self
+                .journal
+                .record_blobs(commitment.batch_id, &blob_versioned_hashes)
+                .await
+            {
+                warn!(
+                    batch_id = %commitment.batch_id,
+                    error = %e,
+                    "Failed to journal blob versioned hashes"
+                );
+            }
+        }
+
+        if let Some(ref blob) = celestia {
+            self.journal_celestia(commitment.batch_id, blob).await;
+        }
+
+        // Pinned once the commitment is broadcast, so deferred batches aren't re-uploaded
+        let ipfs_cid = match (&self.ipfs, payload) {
+            (Some(ipfs), Some(payload)) => self.pin_payload(ipfs, commitment, payload).await,
+            _ => None,
+        };
+
+        // Posted right behind the commitment so a deferred batch never pays for its data
+        let da_post = match da_post {
+            Some((contract, data)) => {
+                match registry
+                    .send_batch_data(contract, commitment, data.clone())
+                    .await
+                {
+                    Ok(da_tx_hash) => Some((da_tx_hash, data)),
+                    Err(e) => {
+                        error!(
+                            batch_id = %commitment.batch_id,
+                            da_contract = %contract,
+                            error = %e,
+                            "Failed to post batch data"
+                        );
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+
+        // A fee-bump replacement keeps the original gas limit
+        let gas_estimate = registry.take_gas_estimate(&tx_hash);
+        let (tx_hash, block_number, gas_used) = match self
+            .await_confirmation(registry, tx_hash, &[commitment.batch_id])
+            .await
+        {
+            Ok(receipt) => receipt,
+            Err(e) => {
+                if let Ok(ReceiptOutcome::Reverted) = registry.receipt_outcome(tx_hash).await {
+                    self.record_gas_spend(
+                        registry,
+                        tx_hash,
+                        std::slice::from_ref(commitment),
+                        false,
+                        appended.as_ref().map(|data| DaCharge::Appended(data)),
+                    )
+                    .await;
+                    self.forget_journal_entry(&commitment.batch_id).await;
+                }
+                return Err(e);
+            }
+        };
+        self.record_gas_spend(
+            registry,
+            tx_hash,
+            std::slice::from_ref(commitment),
+            true,
+            appended.as_ref().map(|data| DaCharge::Appended(data)),
+        )
+        .await;
+        self.record_gas_estimate(gas_estimate, gas_used);
+        if let Some((da_tx_hash, data)) = da_post {
+            self.confirm_batch_data(registry, commitment, da_tx_hash, &data)
+                .await;
+        }
 
         let tx_hash_hex = format!("0x{}", hex::encode(tx_hash.as_slice()));
 
@@ -651,8 +4322,19 @@ impl AnchorService {
             chain_id: registry.chain_id(),
             block_number: Some(block_number),
             gas_used: Some(gas_used),
+            blob_versioned_hashes,
+            ipfs_cid,
+            celestia,
         };
-        self.notify_sequencer_or_queue(commitment.batch_id, notification)
+        self.celestia_blobs
+            .write()
+            .await
+            .remove(&commitment.batch_id);
+        self.audit(AuditEvent::anchored(commitment.batch_id, &notification))
+            .await;
+        self.record_history(HistoryEntry::anchored(commitment.batch_id, &notification))
+            .await;
+        self.notify_sequencer_or_queue(commitment.batch_id, notification.clone())
             .await;
 
         info!(
@@ -663,14 +4345,29 @@ impl AnchorService {
             "Commitment anchored successfully"
         );
 
-        Ok(AnchorResult {
+        let result = AnchorResult {
             batch_id: commitment.batch_id,
             tx_hash: tx_hash_hex,
             block_number,
             gas_used,
             success: true,
             error: None,
-        })
+            deferred: false,
+        };
+        let calldata = encode_commit_batch(commitment, registry.batch_id_scheme())
+            .ok()
+            .map(|calldata| calldata.to_string());
+        self.record_anchor(
+            registry,
+            commitment,
+            &result,
+            registry.batch_id_scheme(),
+            calldata,
+            notification,
+        )
+        .await;
+
+        Ok(result)
     }
 
     /// Get signer address from private key
@@ -683,9 +4380,32 @@ impl AnchorService {
 
     /// Get current statistics
     pub async fn stats(&self) -> AnchorStats {
+        self.recorder.flush().await;
         self.stats.read().await.clone()
     }
 
+    #[cfg(test)]
+    pub(crate) async fn verify_registry_for_test<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        signer_address: Address,
+    ) {
+        let status = read_registry_status(registry, signer_address)
+            .await
+            .expect("registry status");
+        *self.registry_status.write().await = Some(status);
+        self.apply_registry_status(signer_address).await;
+    }
+
+    #[cfg(test)]
+    pub(crate) async fn check_wallet_balance_for_test<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+        signer_address: Address,
+    ) {
+        self.check_wallet_balance(registry, signer_address).await;
+    }
+
     #[cfg(test)]
     pub(crate) async fn queue_notification_for_test(
         &self,
@@ -704,4 +4424,558 @@ impl AnchorService {
     pub(crate) async fn queued_notification_count(&self) -> usize {
         self.pending_notifications.read().await.len()
     }
+
+    #[cfg(test)]
+    pub(crate) async fn recover_in_flight_for_test<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+    ) {
+        self.recover_in_flight(registry).await.unwrap();
+    }
+
+    #[cfg(test)]
+    pub(crate) async fn restore_pause_for_test(&self) {
+        self.restore_pause().await.unwrap();
+    }
+
+    #[cfg(test)]
+    pub(crate) async fn poll_while_paused_for_test<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+    ) {
+        let reason = self.pause.read().await.reason.clone();
+        self.poll_while_held(registry, PendingStatus::Paused, reason)
+            .await;
+    }
+
+    #[cfg(test)]
+    pub(crate) async fn hold_outside_schedule_for_test<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+    ) -> Option<DateTime<Utc>> {
+        self.hold_outside_schedule(registry).await
+    }
+
+    #[cfg(test)]
+    pub(crate) fn cancel_for_test(&self) {
+        self.shutdown.cancel();
+    }
+
+    #[cfg(test)]
+    pub(crate) async fn anchor_pending_for_test<P: Provider<RpcTransport> + Clone>(
+        &self,
+        registry: &RegistryClient<P>,
+    ) -> Vec<AnchorResult> {
+        match self.anchor_pending(registry).await {
+            Ok(AnchorCycleOutcome::Healthy(results)) => results,
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Batch data carried by a mined transaction, for per-byte cost accounting
+#[derive(Debug, Clone, Copy)]
+enum DaCharge<'a> {
+    /// The transaction only posts this payload, so its whole fee is DA cost
+    Posted(&'a [u8]),
+    /// Payload appended to `commitBatch` calldata, charged its calldata gas
+    Appended(&'a [u8]),
+}
+
+/// Parse a configured contract address
+fn parse_address(address: &str) -> Result<Address, ConfigError> {
+    address
+        .parse()
+        .map_err(|_| ConfigError::InvalidAddress(address.to_string()))
+}
+
+/// Wake the anchor loop whenever the sequencer streams a new commitment
+///
+/// Commitments are still fetched from the pending endpoint, so while the stream
+/// is down the loop simply falls back to polling every `ANCHOR_INTERVAL_SECS`.
+async fn watch_commitment_stream(
+    client: Arc<dyn SequencerApi>,
+    wake: Arc<Notify>,
+    idle_timeout: Duration,
+    reconnect_delay: Duration,
+    shutdown: CancellationToken,
+) {
+    let watch = async {
+        loop {
+            match client.stream_commitments(idle_timeout).await {
+                Ok(commitments) => {
+                    info!("Subscribed to sequencer commitment stream");
+                    let mut commitments = std::pin::pin!(commitments);
+                    while let Some(commitment) = commitments.next().await {
+                        match commitment {
+                            Ok(commitment) => {
+                                debug!(batch_id = %commitment.batch_id, "Commitment streamed");
+                                wake.notify_one();
+                            }
+                            Err(e) => warn!(error = %e, "Commitment stream error"),
+                        }
+                    }
+                    warn!("Commitment stream disconnected; polling until it reconnects");
+                }
+                Err(e) => {
+                    warn!(error = %e, "Commitment stream unavailable; polling until it reconnects");
+                }
+            }
+            tokio::time::sleep(reconnect_delay).await;
+        }
+    };
+    tokio::select! {
+        _ = watch => {}
+        _ = shutdown.cancelled() => debug!("Closing commitment stream for shutdown"),
+    }
+}
+
+/// Keep the `max` most urgent commitments (0 = all), leaving the rest for later cycles
+///
+/// Batches of `enterprise` tenants come first, then the rest; within each tier
+/// the oldest go first. Applied after skipped batches are filtered out so they
+/// don't use up the cycle's budget. The sort is stable, so batches of one tier
+/// committed at the same time keep the sequencer's order.
+pub(crate) fn limit_cycle(commitments: &mut Vec<BatchCommitment>, max: u32, enterprise: &[Uuid]) {
+    let max = max as usize;
+    if max == 0 || commitments.len() <= max {
+        return;
+    }
+    commitments.sort_by_key(|commitment| {
+        (
+            !enterprise.contains(&commitment.tenant_id),
+            commitment.committed_at,
+        )
+    });
+    info!(
+        limit = max,
+        deferred = commitments.len() - max,
+        "Limiting cycle to the most urgent commitments; the rest wait for the next cycle"
+    );
+    commitments.truncate(max);
+}
+
+/// Registry reads for `GET /batches/{batch_id}`
+///
+/// The `BatchCommitted` log comes from the indexer when one is running, and
+/// from `eth_getLogs` otherwise.
+struct RegistryBatchLookup<P> {
+    registry: RegistryClient<P>,
+    #[cfg(feature = "indexer")]
+    index: Option<Arc<BatchIndex>>,
+}
+
+#[async_trait::async_trait]
+impl<P: Provider<RpcTransport> + Clone + 'static> BatchLookup for RegistryBatchLookup<P> {
+    fn chain_id(&self) -> u64 {
+        self.registry.chain_id()
+    }
+
+    async fn committed_batch(
+        &self,
+        chain_batch_id: FixedBytes<32>,
+    ) -> crate::error::AnchorResult<Option<CommittedBatch>> {
+        let Some(commitment) = self.registry.commitment_at(chain_batch_id).await? else {
+            return Ok(None);
+        };
+
+        #[cfg(feature = "indexer")]
+        if let Some(ref index) = self.index {
+            match index.batch(chain_batch_id).await {
+                Ok(Some(indexed)) => {
+                    return Ok(Some(CommittedBatch {
+                        commitment,
+                        event: Some(indexed.event),
+                    }));
+                }
+                Ok(None) => {}
+                Err(e) => warn!(error = %e, "Failed to read indexed batch; querying logs"),
+            }
+        }
+
+        let event = self.registry.batch_committed_log(chain_batch_id).await?;
+        Ok(Some(CommittedBatch { commitment, event }))
+    }
+}
+
+/// Wake the anchor loop as soon as a registry control event is mined
+///
+/// The loop re-reads the events with `eth_getLogs` when it runs the registry
+/// guard, so a dropped subscription only delays the check until the next
+/// cycle.
+async fn watch_registry_events(
+    mut events: BoxStream<'static, u64>,
+    wake: Arc<Notify>,
+    shutdown: CancellationToken,
+) {
+    loop {
+        tokio::select! {
+            event = events.next() => match event {
+                Some(block_number) => {
+                    debug!(block_number, "Registry control event mined");
+                    wake.notify_one();
+                }
+                None => break,
+            },
+            _ = shutdown.cancelled() => return,
+        }
+    }
+    warn!("Registry event subscription closed; checking once per cycle");
+}
+
+/// Re-read authorization, contract code, strict mode and pause state every `interval`
+///
+/// Publishes each reading for `/ready` and wakes the anchor loop when the
+/// registry stops (or starts again) being usable or paused, so it can halt or
+/// recover without waiting out the cycle.
+async fn verify_registry_periodically<P: Provider<RpcTransport> + Clone>(
+    registry: RegistryClient<P>,
+    signer_address: Address,
+    interval: Duration,
+    published: Arc<RwLock<Option<RegistryStatus>>>,
+    wake: Arc<Notify>,
+    shutdown: CancellationToken,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = shutdown.cancelled() => return,
+        }
+        let read = tokio::select! {
+            read = read_registry_status(&registry, signer_address) => read,
+            _ = shutdown.cancelled() => return,
+        };
+        let status = match read {
+            Ok(status) => status,
+            Err(e) => {
+                warn!(error = %e, "Failed to re-verify registry state");
+                continue;
+            }
+        };
+        debug!(
+            authorized = status.authorized,
+            code_present = status.code_present,
+            strict_mode = status.strict_mode,
+            "Registry state re-verified"
+        );
+        let previous = published.write().await.replace(status.clone());
+        if previous.is_none_or(|previous| {
+            previous.usable() != status.usable() || previous.paused != status.paused
+        }) {
+            wake.notify_one();
+        }
+    }
+}
+
+/// Compete for the submitter role every renewal interval, submitting only while it is held
+///
+/// Runs until `stop` is cancelled, then releases the role so a standby can
+/// take over without waiting for it to expire.
+async fn elect_leader(
+    mut elector: Box<dyn LeaderElection>,
+    standby: Arc<AtomicBool>,
+    health: Option<Arc<HealthState>>,
+    metrics: Arc<AnchorMetrics>,
+    wake: Arc<Notify>,
+    stop: CancellationToken,
+) {
+    let mut ticker = tokio::time::interval(elector.renew_interval());
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = stop.cancelled() => break,
+        }
+        let (leader, holder) = match elector.try_acquire_or_renew().await {
+            Ok(LeaderRole::Leader) => (true, Some(Some(elector.identity().to_string()))),
+            Ok(LeaderRole::Follower { holder }) => (false, Some(holder)),
+            Err(e) => {
+                warn!(error = %e, "Leader election round failed");
+                // A leader keeps submitting only while its last renewal still covers it
+                (elector.holds_role(), None)
+            }
+        };
+        if let (Some(ref health), Some(holder)) = (&health, holder) {
+            health.set_lease_holder(holder).await;
+        }
+
+        let was_standby = standby.swap(!leader, Ordering::SeqCst);
+        if was_standby && leader {
+            info!(
+                identity = elector.identity(),
+                "Elected submitter; taking over anchoring"
+            );
+            metrics
+                .leadership_transitions
+                .with_label_values(&["acquired"])
+                .inc();
+            wake.notify_one();
+        } else if !was_standby && !leader {
+            warn!(
+                identity = elector.identity(),
+                "Lost submitter role; returning to standby"
+            );
+            metrics
+                .leadership_transitions
+                .with_label_values(&["lost"])
+                .inc();
+        }
+    }
+
+    let held = elector.holds_role();
+    match elector.release().await {
+        Ok(()) => debug!("Submitter role released"),
+        Err(e) => warn!(error = %e, "Failed to release submitter role"),
+    }
+    if held {
+        metrics
+            .leadership_transitions
+            .with_label_values(&["released"])
+            .inc();
+    }
+}
+
+/// Authorization of `signer_address`, plus code presence, strict mode and pause state of the registry
+async fn read_registry_status<P: Provider<RpcTransport> + Clone>(
+    registry: &RegistryClient<P>,
+    signer_address: Address,
+) -> crate::error::AnchorResult<RegistryStatus> {
+    // Calls against an address without code come back empty and fail to decode
+    if !registry.has_code().await? {
+        return Ok(RegistryStatus {
+            authorized: false,
+            code_present: false,
+            strict_mode: false,
+            paused: false,
+            checked_at: Utc::now().to_rfc3339(),
+        });
+    }
+    Ok(RegistryStatus {
+        authorized: registry.is_authorized(signer_address).await?,
+        code_present: true,
+        strict_mode: registry.strict_mode_enabled().await?,
+        // Deployments predating `Pausable` have no `paused()`
+        paused: registry.paused().await.unwrap_or_else(|e| {
+            debug!(error = %e, "Failed to read registry pause state; assuming unpaused");
+            false
+        }),
+        checked_at: Utc::now().to_rfc3339(),
+    })
+}
+
+/// `LOW_BALANCE_THRESHOLD_ETH` in wei (`None` = disabled)
+fn low_balance_threshold_wei(config: &AnchorConfig) -> Option<U256> {
+    (config.low_balance_threshold_eth > 0.0)
+        .then(|| U256::from((config.low_balance_threshold_eth * 1e18).round() as u128))
+}
+
+/// Sequencer client for the configured `SEQUENCER_PROTOCOL`
+fn sequencer_client_from_config(
+    config: &AnchorConfig,
+    metrics: &Arc<AnchorMetrics>,
+) -> Arc<dyn SequencerApi> {
+    let oauth = OAuthTokenSource::from_config(config).map(Arc::new);
+    let rate_limiter = RateLimiter::from_rate(config.sequencer_rate_limit);
+    let tls = config.sequencer_tls().unwrap_or_else(|err| {
+        warn!(error = %err, "Failed to load sequencer TLS files; connecting without them");
+        None
+    });
+
+    #[cfg(feature = "grpc")]
+    if config.sequencer_protocol == SequencerProtocol::Grpc {
+        let url = config.sequencer_grpc_url.as_deref().unwrap_or_default();
+        match SequencerGrpcClient::new(
+            url,
+            Duration::from_secs(config.sequencer_connect_timeout_secs),
+            config.sequencer_timeouts(),
+            tls.as_ref(),
+        ) {
+            Ok(client) => {
+                return Arc::new(
+                    client
+                        .with_credentials(config.sequencer_credentials())
+                        .with_oauth(oauth.clone())
+                        .with_rate_limiter(rate_limiter.clone())
+                        .with_metrics(Arc::clone(metrics)),
+                )
+            }
+            Err(err) => warn!(
+                url = %url,
+                error = %err,
+                "Failed to build sequencer gRPC client; falling back to REST"
+            ),
+        }
+    }
+
+    let client = SequencerApiClient::from_config(config)
+        .with_credentials(config.sequencer_credentials())
+        .with_oauth(oauth)
+        .with_rate_limiter(rate_limiter)
+        .with_gzip_requests(config.sequencer_gzip_requests)
+        .with_signing_secret(config.sequencer_signing_secret.clone())
+        .with_metrics(Arc::clone(metrics));
+    match tls {
+        Some(ref tls) => match client.clone().with_tls(tls) {
+            Ok(client) => Arc::new(client),
+            Err(err) => {
+                warn!(error = %err, "Failed to apply sequencer TLS settings; connecting without them");
+                Arc::new(client)
+            }
+        },
+        None => Arc::new(client),
+    }
+}
+
+/// Breaker configured by the `CIRCUIT_BREAKER_*` settings
+fn circuit_breaker_from_config(config: &AnchorConfig) -> CircuitBreaker {
+    let mut circuit_breaker = CircuitBreaker::new(
+        config.circuit_breaker_failure_threshold,
+        config.circuit_breaker_reset_timeout_secs,
+    );
+    circuit_breaker.half_open_success_threshold =
+        config.circuit_breaker_half_open_success_threshold;
+    circuit_breaker
+}
+
+/// Result for a batch left in the deferral queue this cycle
+fn deferred_result(commitment: &BatchCommitment, error: String) -> AnchorResult {
+    AnchorResult {
+        batch_id: commitment.batch_id,
+        tx_hash: String::new(),
+        block_number: 0,
+        gas_used: 0,
+        success: false,
+        error: Some(error),
+        deferred: true,
+    }
+}
+
+/// Whether a submission was refused because gas is above `max_gas_price_gwei`
+fn is_gas_price_too_high(error: &anyhow::Error) -> bool {
+    matches!(
+        AnchorError::from_anyhow(error),
+        AnchorError::Transaction(TransactionError::GasPriceTooHigh { .. })
+    )
+}
+
+/// Whole seconds since the sequencer committed the batch (0 if in the future)
+fn commitment_age_secs(commitment: &BatchCommitment) -> u64 {
+    (Utc::now() - commitment.committed_at).num_seconds().max(0) as u64
+}
+
+/// Tenant/store pair whose batches form one ordered sequence stream
+fn stream_key(commitment: &BatchCommitment) -> (Uuid, Uuid) {
+    (commitment.tenant_id, commitment.store_id)
+}
+
+/// Check `next` builds on `previous`, the batch before it in the same store
+fn continues_batch(
+    previous: &BatchCommitment,
+    next: &BatchCommitment,
+) -> Result<(), ValidationError> {
+    let root = |value: &str| value.parse::<FixedBytes<32>>().ok();
+    if root(&previous.new_state_root) != root(&next.prev_state_root) {
+        return Err(ValidationError::StateRootMismatch {
+            expected: previous.new_state_root.clone(),
+            provided: next.prev_state_root.clone(),
+        });
+    }
+    ValidationError::check_sequence(previous.sequence_end, next.sequence_start)
+}
+
+/// Sort each tenant/store stream by `sequence_start` in place
+///
+/// Every stream keeps the positions it already occupies, so the interleaving
+/// of different stores (by priority after `limit_cycle`) is unchanged and
+/// only batches of the same store swap places.
+pub(crate) fn order_within_streams(commitments: &mut [BatchCommitment]) {
+    let mut slots: HashMap<(Uuid, Uuid), Vec<usize>> = HashMap::new();
+    for (i, commitment) in commitments.iter().enumerate() {
+        slots.entry(stream_key(commitment)).or_default().push(i);
+    }
+    for positions in slots.values().filter(|positions| positions.len() > 1) {
+        let mut stream: Vec<BatchCommitment> =
+            positions.iter().map(|&i| commitments[i].clone()).collect();
+        stream.sort_by_key(|commitment| commitment.sequence_start);
+        for (&i, commitment) in positions.iter().zip(stream) {
+            commitments[i] = commitment;
+        }
+    }
+}
+
+/// Separate batches that don't follow the previous pending batch of their stream
+///
+/// Expects `order_within_streams` order. The first batch that leaves a gap or
+/// overlaps is returned with its anomaly; later batches of that stream can't
+/// chain onto it either, so they are left pending until the sequencer fills
+/// the gap.
+pub(crate) fn split_sequence_anomalies(
+    commitments: Vec<BatchCommitment>,
+) -> (
+    Vec<BatchCommitment>,
+    Vec<(BatchCommitment, ValidationError)>,
+) {
+    let mut last_end: HashMap<(Uuid, Uuid), u64> = HashMap::new();
+    let mut broken = HashSet::new();
+    let mut kept = Vec::with_capacity(commitments.len());
+    let mut anomalies = Vec::new();
+
+    for commitment in commitments {
+        let key = stream_key(&commitment);
+        if broken.contains(&key) {
+            continue;
+        }
+        if let Some(&end) = last_end.get(&key) {
+            if let Err(error) = ValidationError::check_sequence(end, commitment.sequence_start) {
+                broken.insert(key);
+                anomalies.push((commitment, error));
+                continue;
+            }
+        }
+        last_end.insert(key, commitment.sequence_end);
+        kept.push(commitment);
+    }
+
+    (kept, anomalies)
+}
+
+/// Split commitments into per tenant/store streams, keeping input order within each
+pub(crate) fn group_by_store(commitments: Vec<BatchCommitment>) -> Vec<Vec<BatchCommitment>> {
+    let mut index: HashMap<(Uuid, Uuid), usize> = HashMap::new();
+    let mut streams: Vec<Vec<BatchCommitment>> = Vec::new();
+
+    for commitment in commitments {
+        let key = stream_key(&commitment);
+        match index.get(&key) {
+            Some(&i) => streams[i].push(commitment),
+            None => {
+                index.insert(key, streams.len());
+                streams.push(vec![commitment]);
+            }
+        }
+    }
+
+    streams
+}
+
+/// Build a cost estimate from a gas estimate and the current gas price
+pub(crate) fn cost_estimate(
+    commitment: &BatchCommitment,
+    gas_estimate: u64,
+    gas_price: U256,
+) -> AnchorCostEstimate {
+    let estimated_cost = U256::from(gas_estimate) * gas_price;
+    let cost_per_event = estimated_cost / U256::from(commitment.event_count.max(1));
+
+    AnchorCostEstimate {
+        batch_id: commitment.batch_id,
+        event_count: commitment.event_count,
+        gas_estimate,
+        gas_price_wei: gas_price.to_string(),
+        estimated_cost_wei: estimated_cost.to_string(),
+        cost_per_event_wei: cost_per_event.to_string(),
+        estimated_at: Utc::now(),
+    }
 }
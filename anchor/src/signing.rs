@@ -0,0 +1,95 @@
+//! HMAC-SHA256 message signatures shared with the sequencer
+//!
+//! Signs requests the anchor sends (with `SEQUENCER_SIGNING_SECRET`) and
+//! verifies commitments the sequencer pushes to the webhook. A signature
+//! covers the signing time as well as the message, so a captured request
+//! cannot be replayed once the receiver's allowed clock skew has passed:
+//!
+//! ```text
+//! X-Set-Timestamp: <unix seconds>
+//! X-Set-Signature: sha256=<hex HMAC-SHA256 of "<timestamp>.<message>">
+//! ```
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Header carrying the signature
+pub const SIGNATURE_HEADER: &str = "x-set-signature";
+
+/// Header carrying the unix time the message was signed at
+pub const TIMESTAMP_HEADER: &str = "x-set-timestamp";
+
+/// `X-Set-Signature` value for `message` signed at `timestamp`
+pub fn sign(secret: &[u8], timestamp: i64, message: &[u8]) -> String {
+    let digest = mac(secret, &timestamp.to_string(), message)
+        .finalize()
+        .into_bytes();
+    format!("sha256={}", hex::encode(digest))
+}
+
+/// Whether `signature` was made over `message` and `timestamp` with `secret`
+///
+/// The comparison runs in constant time.
+pub fn verify(secret: &[u8], timestamp: &str, signature: &str, message: &[u8]) -> bool {
+    signature
+        .strip_prefix("sha256=")
+        .and_then(|hex_digest| hex::decode(hex_digest).ok())
+        .is_some_and(|digest| {
+            mac(secret, timestamp, message)
+                .verify_slice(&digest)
+                .is_ok()
+        })
+}
+
+/// Whether a presented shared token matches the expected one, in constant time
+///
+/// Both are MACed under the expected token and the digests compared, so
+/// neither the position of the first difference nor the length leaks.
+pub fn tokens_match(expected: &str, presented: &str) -> bool {
+    let tag = |token: &str| {
+        let mut mac = Hmac::<Sha256>::new_from_slice(expected.as_bytes())
+            .expect("HMAC accepts any key length");
+        mac.update(token.as_bytes());
+        mac
+    };
+    tag(presented)
+        .verify_slice(&tag(expected).finalize().into_bytes())
+        .is_ok()
+}
+
+fn mac(secret: &[u8], timestamp: &str, message: &[u8]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(message);
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify_round_trip() {
+        let signature = sign(b"secret", 1_700_000_000, b"message");
+        assert!(signature.starts_with("sha256="));
+        assert!(verify(b"secret", "1700000000", &signature, b"message"));
+        assert!(!verify(b"secret", "1700000001", &signature, b"message"));
+        assert!(!verify(b"secret", "1700000000", &signature, b"massage"));
+        assert!(!verify(b"other", "1700000000", &signature, b"message"));
+        assert!(!verify(
+            b"secret",
+            "1700000000",
+            signature.trim_start_matches("sha256="),
+            b"message"
+        ));
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("admin-token", "admin-token"));
+        assert!(!tokens_match("admin-token", "admin-tokeN"));
+        assert!(!tokens_match("admin-token", "admin"));
+        assert!(!tokens_match("admin-token", ""));
+    }
+}
@@ -0,0 +1,201 @@
+//! Time-to-anchor tracking
+//!
+//! Every anchored batch adds a `committed_at → anchored_at` sample. `/stats`
+//! and `/metrics` report p50/p95/p99 over the last hour and the last day, and
+//! with `SLA_TARGET_SECS` set, batches that took longer are counted as SLA
+//! violations and the most recent ones are listed in `/stats`.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Rolling windows percentiles are reported over, by label
+pub const WINDOWS: &[(&str, i64)] = &[("1h", 3600), ("24h", 86_400)];
+
+/// Samples kept at most, whatever the window holds
+const MAX_SAMPLES: usize = 100_000;
+
+/// Violations listed in `/stats`
+const MAX_RECENT_VIOLATIONS: usize = 50;
+
+/// How long one batch took from commit to anchor
+#[derive(Debug, Clone, Serialize)]
+pub struct TimeToAnchor {
+    pub batch_id: Uuid,
+    pub tenant_id: Uuid,
+    pub store_id: Uuid,
+    pub committed_at: DateTime<Utc>,
+    pub anchored_at: DateTime<Utc>,
+    pub secs: u64,
+}
+
+impl TimeToAnchor {
+    pub fn new(
+        batch_id: Uuid,
+        tenant_id: Uuid,
+        store_id: Uuid,
+        committed_at: DateTime<Utc>,
+        anchored_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            batch_id,
+            tenant_id,
+            store_id,
+            committed_at,
+            anchored_at,
+            secs: (anchored_at - committed_at).num_seconds().max(0) as u64,
+        }
+    }
+}
+
+/// Time-to-anchor percentiles over one window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WindowPercentiles {
+    pub window: &'static str,
+    pub count: usize,
+    pub p50_secs: u64,
+    pub p95_secs: u64,
+    pub p99_secs: u64,
+}
+
+/// Recent time-to-anchor samples and SLA violations
+#[derive(Debug, Clone, Default)]
+pub struct SlaTracker {
+    /// `(anchored_at, secs)`, oldest first
+    samples: VecDeque<(DateTime<Utc>, u64)>,
+    violations: u64,
+    recent_violations: VecDeque<TimeToAnchor>,
+}
+
+impl SlaTracker {
+    /// Add a sample; a violation if it took longer than `target_secs` (0 = no target)
+    ///
+    /// Returns whether the batch violated the target.
+    pub fn record(&mut self, sample: TimeToAnchor, target_secs: u64) -> bool {
+        let longest = WINDOWS.iter().map(|(_, secs)| *secs).max().unwrap_or(0);
+        let cutoff = sample.anchored_at - Duration::seconds(longest);
+        while self
+            .samples
+            .front()
+            .is_some_and(|(anchored_at, _)| *anchored_at < cutoff)
+            || self.samples.len() >= MAX_SAMPLES
+        {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((sample.anchored_at, sample.secs));
+
+        let violated = target_secs > 0 && sample.secs > target_secs;
+        if violated {
+            self.violations += 1;
+            if self.recent_violations.len() == MAX_RECENT_VIOLATIONS {
+                self.recent_violations.pop_front();
+            }
+            self.recent_violations.push_back(sample);
+        }
+        violated
+    }
+
+    /// Percentiles over each of [`WINDOWS`] as of `now`; windows without samples are left out
+    pub fn percentiles(&self, now: DateTime<Utc>) -> Vec<WindowPercentiles> {
+        WINDOWS
+            .iter()
+            .filter_map(|(window, secs)| {
+                let cutoff = now - Duration::seconds(*secs);
+                let mut durations: Vec<u64> = self
+                    .samples
+                    .iter()
+                    .filter(|(anchored_at, _)| *anchored_at >= cutoff)
+                    .map(|(_, secs)| *secs)
+                    .collect();
+                if durations.is_empty() {
+                    return None;
+                }
+                durations.sort_unstable();
+                Some(WindowPercentiles {
+                    window,
+                    count: durations.len(),
+                    p50_secs: nearest_rank(&durations, 50),
+                    p95_secs: nearest_rank(&durations, 95),
+                    p99_secs: nearest_rank(&durations, 99),
+                })
+            })
+            .collect()
+    }
+
+    /// Batches anchored later than the target since startup
+    pub fn violations(&self) -> u64 {
+        self.violations
+    }
+
+    /// Most recent violations, newest first
+    pub fn recent_violations(&self) -> Vec<TimeToAnchor> {
+        self.recent_violations.iter().rev().cloned().collect()
+    }
+}
+
+/// Nearest-rank percentile of sorted, non-empty `values`
+fn nearest_rank(values: &[u64], percentile: usize) -> u64 {
+    let rank = (percentile * values.len()).div_ceil(100).max(1);
+    values[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(anchored_at: DateTime<Utc>, secs: i64) -> TimeToAnchor {
+        TimeToAnchor::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            anchored_at - Duration::seconds(secs),
+            anchored_at,
+        )
+    }
+
+    #[test]
+    fn test_percentiles_per_window() {
+        let now = Utc::now();
+        let mut tracker = SlaTracker::default();
+        // A slow batch two hours ago only counts towards the day
+        tracker.record(sample(now - Duration::hours(2), 5000), 0);
+        for secs in 1..=100 {
+            tracker.record(sample(now, secs), 0);
+        }
+
+        let percentiles = tracker.percentiles(now);
+        assert_eq!(
+            percentiles[0],
+            WindowPercentiles {
+                window: "1h",
+                count: 100,
+                p50_secs: 50,
+                p95_secs: 95,
+                p99_secs: 99,
+            }
+        );
+        assert_eq!(percentiles[1].count, 101);
+        assert_eq!(percentiles[1].p99_secs, 100);
+
+        // A day later nothing is left to report
+        assert!(tracker.percentiles(now + Duration::days(2)).is_empty());
+    }
+
+    #[test]
+    fn test_violations_against_target() {
+        let now = Utc::now();
+        let mut tracker = SlaTracker::default();
+        assert!(!tracker.record(sample(now, 600), 0));
+        assert!(!tracker.record(sample(now, 300), 300));
+        let late = sample(now, 301);
+        assert!(tracker.record(late.clone(), 300));
+
+        assert_eq!(tracker.violations(), 1);
+        let recent = tracker.recent_violations();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].batch_id, late.batch_id);
+        assert_eq!(recent[0].secs, 301);
+    }
+}
@@ -0,0 +1,281 @@
+//! AMQP (RabbitMQ) commitment source
+//!
+//! Consumes JSON [`BatchCommitment`] messages from `AMQP_QUEUE` with manual
+//! acks:
+//!
+//! - a completed batch is acked;
+//! - a failed batch is requeued, until it has failed `AMQP_MAX_ATTEMPTS` times
+//!   and is rejected without requeue, which routes it to the queue's dead-letter
+//!   exchange (configure `x-dead-letter-exchange` on the queue, or it is dropped);
+//! - an undecodable message is rejected straight away;
+//! - anything else still held at the next fetch (deferred by the gas cap or over
+//!   the per-cycle limit) is requeued rather than held past the broker's
+//!   consumer timeout, and comes straight back in that fetch.
+//!
+//! Dead-lettered messages leave the service's hands, so unlike pauses and skips
+//! they carry no operator metadata; annotate them with the broker's own tooling.
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use lapin::{
+    message::Delivery,
+    options::{
+        BasicAckOptions, BasicConsumeOptions, BasicNackOptions, BasicQosOptions, BasicRejectOptions,
+    },
+    types::FieldTable,
+    Connection, ConnectionProperties, Consumer,
+};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use super::CommitmentSource;
+use crate::config::AnchorConfig;
+use crate::error::{AnchorResult, SequencerApiError};
+use crate::types::{BatchCommitment, CommitmentSourceKind};
+
+/// Most messages taken by a single fetch (also the channel prefetch)
+const MAX_MESSAGES_PER_FETCH: u16 = 500;
+
+/// AMQP queue consumer feeding the anchor loop
+pub struct AmqpCommitmentSource {
+    /// Closing the connection would cancel the consumer
+    _connection: Connection,
+    queue: String,
+    poll_window: Duration,
+    max_attempts: u32,
+    inner: Mutex<Inner>,
+}
+
+struct Inner {
+    consumer: Consumer,
+    /// Deliveries not settled yet, in the order they arrived
+    held: Vec<Held>,
+    /// Failed attempts per batch
+    attempts: HashMap<Uuid, u32>,
+}
+
+/// A delivered commitment awaiting ack, requeue, or rejection
+struct Held {
+    commitment: BatchCommitment,
+    delivery: Delivery,
+}
+
+impl AmqpCommitmentSource {
+    /// Connect and start consuming the configured queue
+    pub async fn connect(config: &AnchorConfig) -> anyhow::Result<Self> {
+        let url = config
+            .amqp_url
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("AMQP_URL is required"))?;
+        let queue = config
+            .amqp_queue
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("AMQP_QUEUE is required"))?;
+
+        let connection = Connection::connect(&url, ConnectionProperties::default()).await?;
+        let channel = connection.create_channel().await?;
+        channel
+            .basic_qos(MAX_MESSAGES_PER_FETCH, BasicQosOptions::default())
+            .await?;
+        let consumer = channel
+            .basic_consume(
+                &queue,
+                "set-anchor",
+                BasicConsumeOptions::default(),
+                FieldTable::default(),
+            )
+            .await?;
+        info!(
+            queue = %queue,
+            max_attempts = config.amqp_max_attempts,
+            "Consuming AMQP commitment queue"
+        );
+
+        Ok(Self {
+            _connection: connection,
+            queue,
+            poll_window: Duration::from_millis(config.source_poll_window_ms),
+            max_attempts: config.amqp_max_attempts,
+            inner: Mutex::new(Inner {
+                consumer,
+                held: Vec::new(),
+                attempts: HashMap::new(),
+            }),
+        })
+    }
+
+    fn amqp_error(&self, error: impl std::fmt::Display) -> SequencerApiError {
+        // The broker URI carries credentials, so errors name the queue instead
+        SequencerApiError::ConnectionFailed {
+            url: format!("amqp queue {}", self.queue),
+            message: error.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl CommitmentSource for AmqpCommitmentSource {
+    fn name(&self) -> &'static str {
+        CommitmentSourceKind::Amqp.as_str()
+    }
+
+    async fn fetch(&self) -> AnchorResult<Vec<BatchCommitment>> {
+        let mut inner = self.inner.lock().await;
+
+        for held in std::mem::take(&mut inner.held) {
+            requeue(&held).await;
+        }
+
+        let deadline = tokio::time::Instant::now() + self.poll_window;
+        for _ in 0..MAX_MESSAGES_PER_FETCH {
+            let delivery = match tokio::time::timeout_at(deadline, inner.consumer.next()).await {
+                Ok(Some(delivery)) => delivery.map_err(|e| self.amqp_error(e))?,
+                Ok(None) => return Err(self.amqp_error("consumer cancelled by broker").into()),
+                Err(_) => break,
+            };
+
+            match serde_json::from_slice::<BatchCommitment>(&delivery.data) {
+                Ok(commitment) => inner.held.push(Held {
+                    commitment,
+                    delivery,
+                }),
+                Err(e) => {
+                    warn!(
+                        delivery_tag = delivery.delivery_tag,
+                        error = %e,
+                        "Rejecting undecodable commitment message"
+                    );
+                    if let Err(e) = delivery
+                        .acker
+                        .reject(BasicRejectOptions { requeue: false })
+                        .await
+                    {
+                        warn!(error = %e, "Failed to reject commitment message");
+                    }
+                }
+            }
+        }
+
+        let mut seen = HashSet::new();
+        Ok(inner
+            .held
+            .iter()
+            .filter(|held| seen.insert(held.commitment.batch_id))
+            .map(|held| held.commitment.clone())
+            .collect())
+    }
+
+    async fn complete(&self, batch_id: Uuid) -> AnchorResult<()> {
+        let done = {
+            let mut inner = self.inner.lock().await;
+            inner.attempts.remove(&batch_id);
+            take_deliveries(&mut inner.held, batch_id)
+        };
+
+        for held in &done {
+            held.delivery
+                .acker
+                .ack(BasicAckOptions::default())
+                .await
+                .map_err(|e| self.amqp_error(e))?;
+        }
+        if !done.is_empty() {
+            debug!(batch_id = %batch_id, "AMQP delivery acked");
+        }
+        Ok(())
+    }
+
+    async fn fail(&self, batch_id: Uuid) -> AnchorResult<()> {
+        let (failed, exhausted) = {
+            let mut inner = self.inner.lock().await;
+            let failed = take_deliveries(&mut inner.held, batch_id);
+            if failed.is_empty() {
+                return Ok(());
+            }
+            let exhausted = record_failure(&mut inner.attempts, batch_id, self.max_attempts);
+            (failed, exhausted)
+        };
+
+        if !exhausted {
+            for held in &failed {
+                requeue(held).await;
+            }
+            return Ok(());
+        }
+
+        warn!(
+            batch_id = %batch_id,
+            attempts = self.max_attempts,
+            "Commitment failed too many times; rejecting to dead-letter exchange"
+        );
+        for held in &failed {
+            held.delivery
+                .acker
+                .reject(BasicRejectOptions { requeue: false })
+                .await
+                .map_err(|e| self.amqp_error(e))?;
+        }
+        Ok(())
+    }
+
+    fn waits_for_work(&self) -> bool {
+        true
+    }
+}
+
+/// Remove every held delivery of `batch_id`
+fn take_deliveries(held: &mut Vec<Held>, batch_id: Uuid) -> Vec<Held> {
+    let (taken, kept) = std::mem::take(held)
+        .into_iter()
+        .partition(|held| held.commitment.batch_id == batch_id);
+    *held = kept;
+    taken
+}
+
+/// Count a failed attempt; true once `batch_id` has used up `max_attempts`
+fn record_failure(attempts: &mut HashMap<Uuid, u32>, batch_id: Uuid, max_attempts: u32) -> bool {
+    let count = attempts.entry(batch_id).or_insert(0);
+    *count += 1;
+    if *count >= max_attempts {
+        attempts.remove(&batch_id);
+        true
+    } else {
+        false
+    }
+}
+
+/// Return a delivery to the queue for another attempt
+async fn requeue(held: &Held) {
+    let options = BasicNackOptions {
+        requeue: true,
+        ..Default::default()
+    };
+    if let Err(e) = held.delivery.acker.nack(options).await {
+        warn!(
+            batch_id = %held.commitment.batch_id,
+            error = %e,
+            "Failed to requeue commitment message"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_failure_exhausts_after_max_attempts() {
+        let mut attempts = HashMap::new();
+        let batch_id = Uuid::new_v4();
+        assert!(!record_failure(&mut attempts, batch_id, 3));
+        assert!(!record_failure(&mut attempts, batch_id, 3));
+        assert!(record_failure(&mut attempts, batch_id, 3));
+        // A redelivered copy after dead-lettering starts over
+        assert!(attempts.is_empty());
+        assert!(record_failure(&mut attempts, Uuid::new_v4(), 1));
+    }
+}
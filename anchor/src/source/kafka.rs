@@ -0,0 +1,307 @@
+//! Kafka commitment source
+//!
+//! Consumes JSON [`BatchCommitment`] messages from `KAFKA_TOPIC` as consumer
+//! group `KAFKA_GROUP_ID`. Auto-commit is off: a partition's offset only moves
+//! past a message once its batch is completed (or the message could not be
+//! decoded), and never past an earlier message that is still open. Fetched
+//! batches that fail to anchor stay buffered and are returned by every fetch
+//! until they complete; after a restart or rebalance they are redelivered from
+//! the last committed offset.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rdkafka::consumer::{CommitMode, Consumer, StreamConsumer};
+use rdkafka::error::KafkaError;
+use rdkafka::{ClientConfig, Message, Offset, TopicPartitionList};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use super::CommitmentSource;
+use crate::config::AnchorConfig;
+use crate::error::{AnchorResult, SequencerApiError};
+use crate::types::{BatchCommitment, CommitmentSourceKind};
+
+/// Most messages read by a single fetch
+const MAX_MESSAGES_PER_FETCH: usize = 500;
+
+/// Kafka consumer feeding the anchor loop
+pub struct KafkaCommitmentSource {
+    consumer: StreamConsumer,
+    brokers: String,
+    topic: String,
+    poll_window: Duration,
+    deliveries: Mutex<Deliveries>,
+}
+
+impl KafkaCommitmentSource {
+    /// Create the consumer and subscribe to the configured topic
+    pub fn from_config(config: &AnchorConfig) -> anyhow::Result<Self> {
+        let brokers = config
+            .kafka_brokers
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("KAFKA_BROKERS is required"))?;
+        let topic = config
+            .kafka_topic
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("KAFKA_TOPIC is required"))?;
+
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &brokers)
+            .set("group.id", &config.kafka_group_id)
+            .set("enable.auto.commit", "false")
+            .set("auto.offset.reset", "earliest")
+            .create()?;
+        consumer.subscribe(&[&topic])?;
+        info!(
+            brokers = %brokers,
+            topic = %topic,
+            group = %config.kafka_group_id,
+            "Subscribed to Kafka commitment topic"
+        );
+
+        Ok(Self {
+            consumer,
+            brokers,
+            topic,
+            poll_window: Duration::from_millis(config.source_poll_window_ms),
+            deliveries: Mutex::new(Deliveries::default()),
+        })
+    }
+
+    fn kafka_error(&self, error: KafkaError) -> SequencerApiError {
+        SequencerApiError::ConnectionFailed {
+            url: self.brokers.clone(),
+            message: error.to_string(),
+        }
+    }
+
+    /// Commit every partition whose completed prefix has grown
+    fn commit_completed(&self) -> AnchorResult<()> {
+        let positions = self.deliveries.lock().unwrap().take_commits();
+        if positions.is_empty() {
+            return Ok(());
+        }
+
+        let mut offsets = TopicPartitionList::new();
+        for (partition, offset) in positions {
+            offsets
+                .add_partition_offset(&self.topic, partition, Offset::Offset(offset))
+                .map_err(|e| self.kafka_error(e))?;
+        }
+        self.consumer
+            .commit(&offsets, CommitMode::Async)
+            .map_err(|e| self.kafka_error(e))?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CommitmentSource for KafkaCommitmentSource {
+    fn name(&self) -> &'static str {
+        CommitmentSourceKind::Kafka.as_str()
+    }
+
+    async fn fetch(&self) -> AnchorResult<Vec<BatchCommitment>> {
+        let deadline = tokio::time::Instant::now() + self.poll_window;
+        for _ in 0..MAX_MESSAGES_PER_FETCH {
+            let message = match tokio::time::timeout_at(deadline, self.consumer.recv()).await {
+                Ok(message) => message.map_err(|e| self.kafka_error(e))?,
+                Err(_) => break,
+            };
+
+            let commitment = match message.payload().map(serde_json::from_slice) {
+                Some(Ok(commitment)) => Some(commitment),
+                Some(Err(e)) => {
+                    warn!(
+                        partition = message.partition(),
+                        offset = message.offset(),
+                        error = %e,
+                        "Skipping undecodable commitment message"
+                    );
+                    None
+                }
+                None => {
+                    warn!(
+                        partition = message.partition(),
+                        offset = message.offset(),
+                        "Skipping empty commitment message"
+                    );
+                    None
+                }
+            };
+            self.deliveries.lock().unwrap().receive(
+                message.partition(),
+                message.offset(),
+                commitment,
+            );
+        }
+
+        self.commit_completed()?;
+        Ok(self.deliveries.lock().unwrap().open_commitments())
+    }
+
+    async fn complete(&self, batch_id: Uuid) -> AnchorResult<()> {
+        if self.deliveries.lock().unwrap().complete(batch_id) {
+            debug!(batch_id = %batch_id, "Kafka delivery completed");
+            self.commit_completed()?;
+        }
+        Ok(())
+    }
+
+    fn waits_for_work(&self) -> bool {
+        true
+    }
+}
+
+/// A message holding a commitment that has not been completed yet
+#[derive(Debug)]
+struct Delivery {
+    commitment: BatchCommitment,
+    partition: i32,
+    offset: i64,
+}
+
+/// Offsets of one partition
+#[derive(Debug, Default)]
+struct PartitionOffsets {
+    /// Offsets received but not completed
+    open: BTreeSet<i64>,
+    /// One past the highest offset received
+    next: i64,
+    /// Last offset committed to the group
+    committed: Option<i64>,
+}
+
+impl PartitionOffsets {
+    /// Everything below this offset is done
+    fn position(&self) -> i64 {
+        self.open.first().copied().unwrap_or(self.next)
+    }
+}
+
+/// Received messages and the offsets that are safe to commit
+#[derive(Debug, Default)]
+struct Deliveries {
+    /// Open deliveries in the order they were received
+    open: Vec<Delivery>,
+    partitions: BTreeMap<i32, PartitionOffsets>,
+}
+
+impl Deliveries {
+    /// Record a message; `None` marks one with no usable commitment, which is
+    /// done as soon as it is received
+    fn receive(&mut self, partition: i32, offset: i64, commitment: Option<BatchCommitment>) {
+        let offsets = self.partitions.entry(partition).or_default();
+        offsets.next = offsets.next.max(offset + 1);
+        if let Some(commitment) = commitment {
+            offsets.open.insert(offset);
+            self.open.push(Delivery {
+                commitment,
+                partition,
+                offset,
+            });
+        }
+    }
+
+    /// Close every delivery of `batch_id`; false if none was open
+    fn complete(&mut self, batch_id: Uuid) -> bool {
+        let before = self.open.len();
+        let partitions = &mut self.partitions;
+        self.open.retain(|delivery| {
+            if delivery.commitment.batch_id != batch_id {
+                return true;
+            }
+            if let Some(offsets) = partitions.get_mut(&delivery.partition) {
+                offsets.open.remove(&delivery.offset);
+            }
+            false
+        });
+        self.open.len() != before
+    }
+
+    /// Open commitments in delivery order, one per batch
+    fn open_commitments(&self) -> Vec<BatchCommitment> {
+        let mut seen = HashSet::new();
+        self.open
+            .iter()
+            .filter(|delivery| seen.insert(delivery.commitment.batch_id))
+            .map(|delivery| delivery.commitment.clone())
+            .collect()
+    }
+
+    /// Partitions whose commit position moved since the last call
+    fn take_commits(&mut self) -> Vec<(i32, i64)> {
+        let mut commits = Vec::new();
+        for (partition, offsets) in &mut self.partitions {
+            let position = offsets.position();
+            if offsets
+                .committed
+                .is_none_or(|committed| position > committed)
+            {
+                offsets.committed = Some(position);
+                commits.push((*partition, position));
+            }
+        }
+        commits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SchemaVersion;
+
+    fn commitment() -> BatchCommitment {
+        BatchCommitment {
+            batch_id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            store_id: Uuid::new_v4(),
+            prev_state_root: format!("0x{}", "0".repeat(64)),
+            new_state_root: format!("0x{}", "22".repeat(32)),
+            events_root: format!("0x{}", "11".repeat(32)),
+            sequence_start: 1,
+            sequence_end: 10,
+            event_count: 10,
+            committed_at: chrono::Utc::now(),
+            chain_tx_hash: None,
+            batch_id_bytes32: None,
+            schema_version: SchemaVersion::CURRENT,
+        }
+    }
+
+    #[test]
+    fn test_commit_waits_for_earlier_open_offsets() {
+        let (first, second) = (commitment(), commitment());
+        let mut deliveries = Deliveries::default();
+        deliveries.receive(0, 10, Some(first.clone()));
+        deliveries.receive(0, 11, Some(second.clone()));
+        deliveries.receive(0, 12, None);
+        assert_eq!(deliveries.take_commits(), vec![(0, 10)]);
+
+        // Completing the later batch cannot move the offset past the earlier one
+        assert!(deliveries.complete(second.batch_id));
+        assert!(deliveries.take_commits().is_empty());
+        assert_eq!(deliveries.open_commitments().len(), 1);
+
+        assert!(deliveries.complete(first.batch_id));
+        assert_eq!(deliveries.take_commits(), vec![(0, 13)]);
+        assert!(deliveries.open_commitments().is_empty());
+        assert!(!deliveries.complete(first.batch_id));
+    }
+
+    #[test]
+    fn test_redelivered_batch_is_returned_once_and_closed_together() {
+        let batch = commitment();
+        let mut deliveries = Deliveries::default();
+        deliveries.receive(0, 5, Some(batch.clone()));
+        deliveries.receive(1, 7, Some(batch.clone()));
+        assert_eq!(deliveries.open_commitments().len(), 1);
+
+        deliveries.take_commits();
+        assert!(deliveries.complete(batch.batch_id));
+        assert_eq!(deliveries.take_commits(), vec![(0, 6), (1, 8)]);
+    }
+}
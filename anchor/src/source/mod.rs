@@ -0,0 +1,136 @@
+//! Commitment sources
+//!
+//! The anchor loop pulls pending commitments from a [`CommitmentSource`] at the
+//! start of every cycle and reports each batch back once it no longer needs to
+//! be delivered: anchored, found already on chain, or skipped on purpose. A
+//! source that tracks delivery (such as a Kafka consumer group) only commits
+//! its position past a batch once that has happened, so a crash or failed
+//! anchor leaves the batch to be delivered again.
+//!
+//! The sequencer API (REST or gRPC) is the default source; it needs no
+//! acknowledgement because the sequencer stops listing a batch as pending once
+//! it has been notified of the anchor.
+
+#[cfg(feature = "amqp")]
+pub mod amqp;
+#[cfg(feature = "kafka")]
+pub mod kafka;
+#[cfg(feature = "nats")]
+pub mod nats;
+pub mod webhook;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::client::SequencerApi;
+use crate::config::AnchorConfig;
+use crate::error::AnchorResult;
+use crate::types::{BatchCommitment, CommitmentSourceKind};
+
+/// Supplier of pending batch commitments
+#[async_trait]
+pub trait CommitmentSource: Send + Sync {
+    /// Source name for logs
+    fn name(&self) -> &'static str;
+
+    /// Commitments waiting to be anchored, in delivery order
+    ///
+    /// Batches fetched but never completed are returned again by later calls.
+    async fn fetch(&self) -> AnchorResult<Vec<BatchCommitment>>;
+
+    /// The batch is done (anchored, already on chain, or deliberately skipped)
+    /// and must not be delivered again
+    async fn complete(&self, batch_id: Uuid) -> AnchorResult<()>;
+
+    /// Anchoring the batch failed this cycle
+    ///
+    /// Sources that bound redelivery count this against the batch; the rest
+    /// simply return it again from a later fetch.
+    async fn fail(&self, _batch_id: Uuid) -> AnchorResult<()> {
+        Ok(())
+    }
+
+    /// Whether `fetch` itself waits for new work, so an empty result can be
+    /// followed by another fetch without the usual poll interval
+    fn waits_for_work(&self) -> bool {
+        false
+    }
+}
+
+/// The sequencer's pending commitments (REST or gRPC), optionally long-polled
+pub struct SequencerCommitmentSource {
+    client: Arc<dyn SequencerApi>,
+    long_poll: Option<Duration>,
+}
+
+impl SequencerCommitmentSource {
+    pub fn new(client: Arc<dyn SequencerApi>, long_poll: Option<Duration>) -> Self {
+        Self { client, long_poll }
+    }
+
+    pub fn from_config(config: &AnchorConfig, client: Arc<dyn SequencerApi>) -> Self {
+        let long_poll = (config.sequencer_long_poll_secs > 0)
+            .then(|| Duration::from_secs(config.sequencer_long_poll_secs));
+        Self::new(client, long_poll)
+    }
+}
+
+#[async_trait]
+impl CommitmentSource for SequencerCommitmentSource {
+    fn name(&self) -> &'static str {
+        CommitmentSourceKind::Http.as_str()
+    }
+
+    async fn fetch(&self) -> AnchorResult<Vec<BatchCommitment>> {
+        match self.long_poll {
+            Some(wait) => self.client.poll_pending_commitments(wait).await,
+            None => self.client.get_pending_commitments().await,
+        }
+    }
+
+    async fn complete(&self, _batch_id: Uuid) -> AnchorResult<()> {
+        // The sequencer stops listing a batch once notified of its anchor
+        Ok(())
+    }
+
+    fn waits_for_work(&self) -> bool {
+        self.long_poll.is_some()
+    }
+}
+
+/// Connect the source selected by `COMMITMENT_SOURCE`
+///
+/// Returns `None` for `http`, which the service sets up from its own sequencer
+/// client, and for `webhook`, which the health server owns.
+pub async fn connect(config: &AnchorConfig) -> anyhow::Result<Option<Arc<dyn CommitmentSource>>> {
+    match config.commitment_source {
+        CommitmentSourceKind::Http | CommitmentSourceKind::Webhook => Ok(None),
+        #[cfg(feature = "kafka")]
+        CommitmentSourceKind::Kafka => Ok(Some(Arc::new(
+            kafka::KafkaCommitmentSource::from_config(config)?,
+        ))),
+        #[cfg(not(feature = "kafka"))]
+        CommitmentSourceKind::Kafka => {
+            anyhow::bail!("COMMITMENT_SOURCE=kafka requires a build with the `kafka` feature")
+        }
+        #[cfg(feature = "amqp")]
+        CommitmentSourceKind::Amqp => Ok(Some(Arc::new(
+            amqp::AmqpCommitmentSource::connect(config).await?,
+        ))),
+        #[cfg(not(feature = "amqp"))]
+        CommitmentSourceKind::Amqp => {
+            anyhow::bail!("COMMITMENT_SOURCE=amqp requires a build with the `amqp` feature")
+        }
+        #[cfg(feature = "nats")]
+        CommitmentSourceKind::Nats => Ok(Some(Arc::new(
+            nats::NatsCommitmentSource::connect(config).await?,
+        ))),
+        #[cfg(not(feature = "nats"))]
+        CommitmentSourceKind::Nats => {
+            anyhow::bail!("COMMITMENT_SOURCE=nats requires a build with the `nats` feature")
+        }
+    }
+}
@@ -0,0 +1,192 @@
+//! NATS JetStream commitment source
+//!
+//! Pulls JSON [`BatchCommitment`] messages through the durable consumer
+//! `NATS_CONSUMER` on `NATS_STREAM` with explicit acks. A message is acked only
+//! once its batch completes; undecodable messages are terminated so they are
+//! not redelivered. Batches waiting for another attempt stay held, and each
+//! fetch marks them in progress so JetStream does not hand them out again.
+//!
+//! Anything not acked within `NATS_ACK_WAIT_SECS` (for instance after a
+//! restart) is redelivered. A redelivered batch that is still held is returned
+//! once, and completing it acks every delivery.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use async_nats::jetstream::{
+    self,
+    consumer::{pull, AckPolicy, PullConsumer},
+    AckKind,
+};
+use async_trait::async_trait;
+use futures::StreamExt;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use super::CommitmentSource;
+use crate::config::AnchorConfig;
+use crate::error::{AnchorResult, SequencerApiError};
+use crate::types::{BatchCommitment, CommitmentSourceKind};
+
+/// Most messages pulled by a single fetch
+const MAX_MESSAGES_PER_FETCH: usize = 500;
+
+/// JetStream pull consumer feeding the anchor loop
+pub struct NatsCommitmentSource {
+    consumer: PullConsumer,
+    url: String,
+    poll_window: Duration,
+    /// Deliveries not acked yet, in the order they arrived
+    held: Mutex<Vec<Held>>,
+}
+
+/// A delivered commitment awaiting its ack
+struct Held {
+    commitment: BatchCommitment,
+    message: jetstream::Message,
+}
+
+impl NatsCommitmentSource {
+    /// Connect and bind (creating if needed) the durable pull consumer
+    pub async fn connect(config: &AnchorConfig) -> anyhow::Result<Self> {
+        let url = config
+            .nats_url
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("NATS_URL is required"))?;
+        let stream_name = config
+            .nats_stream
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("NATS_STREAM is required"))?;
+
+        let client = async_nats::connect(&url).await?;
+        let stream = jetstream::new(client).get_stream(&stream_name).await?;
+        let consumer: PullConsumer = stream
+            .get_or_create_consumer(
+                &config.nats_consumer,
+                pull::Config {
+                    durable_name: Some(config.nats_consumer.clone()),
+                    filter_subject: config.nats_subject.clone().unwrap_or_default(),
+                    ack_policy: AckPolicy::Explicit,
+                    ack_wait: Duration::from_secs(config.nats_ack_wait_secs),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        info!(
+            url = %url,
+            stream = %stream_name,
+            consumer = %config.nats_consumer,
+            "Bound NATS JetStream commitment consumer"
+        );
+
+        Ok(Self {
+            consumer,
+            url,
+            poll_window: Duration::from_millis(config.source_poll_window_ms),
+            held: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn nats_error(&self, error: impl std::fmt::Display) -> SequencerApiError {
+        SequencerApiError::ConnectionFailed {
+            url: self.url.clone(),
+            message: error.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl CommitmentSource for NatsCommitmentSource {
+    fn name(&self) -> &'static str {
+        CommitmentSourceKind::Nats.as_str()
+    }
+
+    async fn fetch(&self) -> AnchorResult<Vec<BatchCommitment>> {
+        let mut held = self.held.lock().await;
+
+        // Keep waiting batches assigned to us for another ack-wait period
+        for delivery in held.iter() {
+            if let Err(e) = delivery.message.ack_with(AckKind::Progress).await {
+                warn!(
+                    batch_id = %delivery.commitment.batch_id,
+                    error = %e,
+                    "Failed to extend JetStream ack deadline"
+                );
+            }
+        }
+
+        let mut messages = self
+            .consumer
+            .batch()
+            .max_messages(MAX_MESSAGES_PER_FETCH)
+            .expires(self.poll_window)
+            .messages()
+            .await
+            .map_err(|e| self.nats_error(e))?;
+        while let Some(message) = messages.next().await {
+            let message = message.map_err(|e| self.nats_error(e))?;
+            let delivered = message.info().map(|info| info.delivered).unwrap_or(1);
+
+            match serde_json::from_slice::<BatchCommitment>(&message.payload) {
+                Ok(commitment) => {
+                    if delivered > 1 {
+                        warn!(
+                            batch_id = %commitment.batch_id,
+                            delivered = delivered,
+                            "Commitment redelivered by JetStream"
+                        );
+                    }
+                    held.push(Held {
+                        commitment,
+                        message,
+                    });
+                }
+                Err(e) => {
+                    warn!(
+                        subject = %message.subject,
+                        error = %e,
+                        "Terminating undecodable commitment message"
+                    );
+                    if let Err(e) = message.ack_with(AckKind::Term).await {
+                        warn!(error = %e, "Failed to terminate commitment message");
+                    }
+                }
+            }
+        }
+
+        let mut seen = HashSet::new();
+        Ok(held
+            .iter()
+            .filter(|delivery| seen.insert(delivery.commitment.batch_id))
+            .map(|delivery| delivery.commitment.clone())
+            .collect())
+    }
+
+    async fn complete(&self, batch_id: Uuid) -> AnchorResult<()> {
+        let done: Vec<Held> = {
+            let mut held = self.held.lock().await;
+            let (done, waiting) = std::mem::take(&mut *held)
+                .into_iter()
+                .partition(|delivery| delivery.commitment.batch_id == batch_id);
+            *held = waiting;
+            done
+        };
+
+        for delivery in &done {
+            delivery
+                .message
+                .ack()
+                .await
+                .map_err(|e| self.nats_error(e))?;
+        }
+        if !done.is_empty() {
+            debug!(batch_id = %batch_id, "JetStream delivery acked");
+        }
+        Ok(())
+    }
+
+    fn waits_for_work(&self) -> bool {
+        true
+    }
+}
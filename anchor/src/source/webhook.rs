@@ -0,0 +1,268 @@
+//! Webhook commitment source
+//!
+//! The sequencer pushes commitments to `POST /v1/commitments` on the health
+//! port, either one JSON [`BatchCommitment`] or an array of them. Each request
+//! body is signed with `WEBHOOK_SECRET` as described in [`crate::signing`].
+//!
+//! Requests with a bad signature, or a timestamp more than five minutes off,
+//! are refused. Accepted commitments are queued in memory and wake the anchor
+//! loop straight away; a batch leaves the queue once it completes. The queue
+//! does not survive a restart, so the sequencer should push again any
+//! commitment it has not been notified as anchored.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use tokio::sync::{Mutex, Notify};
+use tracing::debug;
+use uuid::Uuid;
+
+use super::CommitmentSource;
+use crate::config::AnchorConfig;
+use crate::error::AnchorResult;
+use crate::signing::{self, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use crate::types::{BatchCommitment, CommitmentSourceKind};
+
+/// Largest accepted difference between the signing time and now
+const MAX_TIMESTAMP_SKEW_SECS: i64 = 300;
+
+/// Most commitments held before pushes are refused
+const MAX_QUEUED: usize = 10_000;
+
+/// Reasons a pushed request is refused
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum WebhookError {
+    #[error("missing {0} header")]
+    MissingHeader(&'static str),
+
+    #[error("signature does not match")]
+    InvalidSignature,
+
+    #[error("timestamp is more than {MAX_TIMESTAMP_SKEW_SECS}s from now")]
+    StaleTimestamp,
+
+    #[error("{MAX_QUEUED} commitments already queued")]
+    QueueFull,
+}
+
+/// Request body: a single commitment or a list
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Pushed {
+    One(Box<BatchCommitment>),
+    Many(Vec<BatchCommitment>),
+}
+
+/// Commitments pushed by the sequencer, waiting to be anchored
+pub struct WebhookCommitmentSource {
+    secret: Vec<u8>,
+    queue: Mutex<Vec<BatchCommitment>>,
+    /// Notified on every accepted push; shared with the anchor loop
+    wake: Arc<Notify>,
+}
+
+impl WebhookCommitmentSource {
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            secret: secret.into(),
+            queue: Mutex::new(Vec::new()),
+            wake: Arc::new(Notify::new()),
+        }
+    }
+
+    pub fn from_config(config: &AnchorConfig) -> Self {
+        Self::new(config.webhook_secret.clone().unwrap_or_default())
+    }
+
+    /// Notified whenever a push queues new work
+    pub fn wake(&self) -> Arc<Notify> {
+        Arc::clone(&self.wake)
+    }
+
+    /// Check the signature headers of a pushed request against its raw body
+    pub fn verify(
+        &self,
+        timestamp: Option<&str>,
+        signature: Option<&str>,
+        body: &[u8],
+        now: i64,
+    ) -> Result<(), WebhookError> {
+        let timestamp = timestamp.ok_or(WebhookError::MissingHeader(TIMESTAMP_HEADER))?;
+        let signature = signature.ok_or(WebhookError::MissingHeader(SIGNATURE_HEADER))?;
+
+        if !signing::verify(&self.secret, timestamp, signature, body) {
+            return Err(WebhookError::InvalidSignature);
+        }
+
+        // Checked after the signature so the timestamp is known to be genuine
+        let signed_at: i64 = timestamp
+            .parse()
+            .map_err(|_| WebhookError::InvalidSignature)?;
+        if (now - signed_at).abs() > MAX_TIMESTAMP_SKEW_SECS {
+            return Err(WebhookError::StaleTimestamp);
+        }
+        Ok(())
+    }
+
+    /// Queue pushed commitments, returning how many were new
+    ///
+    /// Batches already queued are ignored, so the sequencer can retry a push
+    /// safely.
+    pub async fn push(&self, commitments: Vec<BatchCommitment>) -> Result<usize, WebhookError> {
+        let mut queue = self.queue.lock().await;
+        let mut queued = 0;
+        for commitment in commitments {
+            if queue.iter().any(|q| q.batch_id == commitment.batch_id) {
+                continue;
+            }
+            if queue.len() >= MAX_QUEUED {
+                return Err(WebhookError::QueueFull);
+            }
+            queue.push(commitment);
+            queued += 1;
+        }
+        if queued > 0 {
+            self.wake.notify_one();
+        }
+        Ok(queued)
+    }
+}
+
+#[async_trait]
+impl CommitmentSource for WebhookCommitmentSource {
+    fn name(&self) -> &'static str {
+        CommitmentSourceKind::Webhook.as_str()
+    }
+
+    async fn fetch(&self) -> AnchorResult<Vec<BatchCommitment>> {
+        Ok(self.queue.lock().await.clone())
+    }
+
+    async fn complete(&self, batch_id: Uuid) -> AnchorResult<()> {
+        let mut queue = self.queue.lock().await;
+        let before = queue.len();
+        queue.retain(|commitment| commitment.batch_id != batch_id);
+        if queue.len() < before {
+            debug!(batch_id = %batch_id, "Pushed commitment completed");
+        }
+        Ok(())
+    }
+}
+
+/// Decode a pushed request body
+pub fn decode(body: &[u8]) -> Result<Vec<BatchCommitment>, serde_json::Error> {
+    Ok(match serde_json::from_slice(body)? {
+        Pushed::One(commitment) => vec![*commitment],
+        Pushed::Many(commitments) => commitments,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signing::sign;
+    use crate::types::SchemaVersion;
+    use chrono::Utc;
+
+    const SECRET: &[u8] = b"0123456789abcdef0123456789abcdef";
+
+    fn commitment() -> BatchCommitment {
+        BatchCommitment {
+            batch_id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            store_id: Uuid::new_v4(),
+            prev_state_root: format!("0x{}", "00".repeat(32)),
+            new_state_root: format!("0x{}", "11".repeat(32)),
+            events_root: format!("0x{}", "22".repeat(32)),
+            sequence_start: 1,
+            sequence_end: 10,
+            event_count: 10,
+            committed_at: Utc::now(),
+            chain_tx_hash: None,
+            batch_id_bytes32: None,
+            schema_version: SchemaVersion::CURRENT,
+        }
+    }
+
+    #[test]
+    fn test_verify_checks_signature_and_timestamp() {
+        let source = WebhookCommitmentSource::new(SECRET);
+        let body = br#"{"hello":"world"}"#;
+        let now = 1_700_000_000;
+        let signature = sign(SECRET, now, body);
+
+        assert_eq!(
+            source.verify(Some(&now.to_string()), Some(&signature), body, now + 60),
+            Ok(())
+        );
+        assert_eq!(
+            source.verify(Some(&now.to_string()), Some(&signature), b"{}", now),
+            Err(WebhookError::InvalidSignature)
+        );
+        assert_eq!(
+            source.verify(Some(&(now + 1).to_string()), Some(&signature), body, now),
+            Err(WebhookError::InvalidSignature)
+        );
+        assert_eq!(
+            source.verify(
+                Some(&now.to_string()),
+                Some(&sign(b"another secret", now, body)),
+                body,
+                now
+            ),
+            Err(WebhookError::InvalidSignature)
+        );
+        assert_eq!(
+            source.verify(Some(&now.to_string()), Some(&signature), body, now + 301),
+            Err(WebhookError::StaleTimestamp)
+        );
+        assert_eq!(
+            source.verify(None, Some(&signature), body, now),
+            Err(WebhookError::MissingHeader(TIMESTAMP_HEADER))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_push_dedupes_and_complete_removes() {
+        let source = WebhookCommitmentSource::new(SECRET);
+        let first = commitment();
+        let second = commitment();
+
+        assert_eq!(source.push(vec![first.clone()]).await, Ok(1));
+        assert_eq!(
+            source.push(vec![first.clone(), second.clone()]).await,
+            Ok(1)
+        );
+        let ids: Vec<Uuid> = source
+            .fetch()
+            .await
+            .unwrap()
+            .iter()
+            .map(|c| c.batch_id)
+            .collect();
+        assert_eq!(ids, vec![first.batch_id, second.batch_id]);
+
+        source.complete(first.batch_id).await.unwrap();
+        let ids: Vec<Uuid> = source
+            .fetch()
+            .await
+            .unwrap()
+            .iter()
+            .map(|c| c.batch_id)
+            .collect();
+        assert_eq!(ids, vec![second.batch_id]);
+    }
+
+    #[test]
+    fn test_decode_accepts_one_or_many() {
+        let one = commitment();
+        let body = serde_json::to_vec(&one).unwrap();
+        assert_eq!(decode(&body).unwrap().len(), 1);
+
+        let body = serde_json::to_vec(&vec![commitment(), commitment()]).unwrap();
+        assert_eq!(decode(&body).unwrap().len(), 2);
+
+        assert!(decode(b"{\"batch_id\":1}").is_err());
+    }
+}
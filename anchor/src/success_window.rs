@@ -0,0 +1,143 @@
+//! Rolling anchor success rates
+//!
+//! The lifetime `success_rate` in `/stats` barely moves after a bad hour once
+//! the process has been up for a week. Anchor outcomes are also counted in
+//! per-minute buckets covering the last day, from which `/stats` and
+//! `/metrics` report the success rate over the last 5 minutes, hour and day.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Rolling windows reported, by label
+pub const WINDOWS: &[(&str, i64)] = &[("5m", 300), ("1h", 3600), ("24h", 86_400)];
+
+/// Bucket width in seconds
+const BUCKET_SECS: i64 = 60;
+
+/// Success rate over one window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WindowSuccessRate {
+    pub window: &'static str,
+    pub anchored: u64,
+    pub failed: u64,
+    /// 1.0 when nothing was attempted in the window
+    pub success_rate: f64,
+}
+
+/// Anchor outcomes per minute over the longest window
+#[derive(Debug, Clone, Default)]
+pub struct SuccessWindows {
+    /// `(minute, anchored, failed)`, oldest first
+    buckets: VecDeque<(i64, u64, u64)>,
+}
+
+impl SuccessWindows {
+    /// Count one anchor outcome at `at`
+    pub fn record(&mut self, success: bool, at: DateTime<Utc>) {
+        let minute = at.timestamp().div_euclid(BUCKET_SECS);
+        let oldest = minute - longest_window() / BUCKET_SECS;
+        while self
+            .buckets
+            .front()
+            .is_some_and(|(bucket, _, _)| *bucket <= oldest)
+        {
+            self.buckets.pop_front();
+        }
+        let bucket = match self.buckets.back_mut() {
+            Some(bucket) if bucket.0 == minute => bucket,
+            _ => {
+                self.buckets.push_back((minute, 0, 0));
+                self.buckets.back_mut().expect("just pushed")
+            }
+        };
+        if success {
+            bucket.1 += 1;
+        } else {
+            bucket.2 += 1;
+        }
+    }
+
+    /// Success rate over each of [`WINDOWS`] as of `now`
+    ///
+    /// A window is made of whole minutes: the one containing `now` and as many
+    /// before it as make up the window's length.
+    pub fn rates(&self, now: DateTime<Utc>) -> Vec<WindowSuccessRate> {
+        let minute = now.timestamp().div_euclid(BUCKET_SECS);
+        WINDOWS
+            .iter()
+            .map(|(window, secs)| {
+                let oldest = minute - secs / BUCKET_SECS;
+                let (anchored, failed) = self
+                    .buckets
+                    .iter()
+                    .filter(|(bucket, _, _)| *bucket > oldest && *bucket <= minute)
+                    .fold((0, 0), |(anchored, failed), (_, a, f)| {
+                        (anchored + a, failed + f)
+                    });
+                let total = anchored + failed;
+                WindowSuccessRate {
+                    window,
+                    anchored,
+                    failed,
+                    success_rate: if total == 0 {
+                        1.0
+                    } else {
+                        anchored as f64 / total as f64
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+fn longest_window() -> i64 {
+    WINDOWS.iter().map(|(_, secs)| *secs).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_bad_hour_fades_from_short_windows_only() {
+        let start = Utc::now();
+        let mut windows = SuccessWindows::default();
+        // A bad stretch two hours ago, then a healthy last hour
+        for i in 0..10 {
+            windows.record(false, start + Duration::minutes(i));
+        }
+        let now = start + Duration::hours(2);
+        for i in (0..30).rev() {
+            windows.record(true, now - Duration::minutes(i));
+        }
+
+        let rates = windows.rates(now);
+        assert_eq!(rates[0].window, "5m");
+        assert_eq!(rates[0].anchored, 5);
+        assert_eq!(rates[0].success_rate, 1.0);
+        assert_eq!(rates[1].anchored, 30);
+        assert_eq!(rates[1].failed, 0);
+        assert_eq!(rates[2].failed, 10);
+        assert_eq!(rates[2].success_rate, 0.75);
+    }
+
+    #[test]
+    fn test_buckets_older_than_a_day_are_dropped() {
+        let start = Utc::now();
+        let mut windows = SuccessWindows::default();
+        windows.record(false, start);
+        windows.record(true, start + Duration::days(2));
+
+        assert_eq!(windows.buckets.len(), 1);
+        let day = &windows.rates(start + Duration::days(2))[2];
+        assert_eq!((day.anchored, day.failed), (1, 0));
+        // Nothing attempted counts as healthy
+        assert_eq!(
+            windows.rates(start + Duration::days(4))[0].success_rate,
+            1.0
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! Hourly anchor cap
+//!
+//! With `MAX_ANCHORS_PER_HOUR` set, the service counts the batches it anchored
+//! over the last hour and leaves any beyond the cap with the source, to go out
+//! once older anchors leave the window. This bounds L2 fee exposure on days
+//! when the sequencer commits far more than usual.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Window the cap applies to
+pub const WINDOW: Duration = Duration::from_secs(3600);
+
+/// Batches anchored over the last hour and those held back by the cap
+#[derive(Debug, Clone)]
+pub struct AnchorThrottle {
+    max_per_hour: u32,
+    anchored: VecDeque<Instant>,
+    /// When each held batch was first held
+    held_since: HashMap<Uuid, Instant>,
+}
+
+impl AnchorThrottle {
+    pub fn new(max_per_hour: u32) -> Self {
+        Self {
+            max_per_hour,
+            anchored: VecDeque::new(),
+            held_since: HashMap::new(),
+        }
+    }
+
+    /// How many more batches may be anchored at `now`
+    pub fn room(&mut self, now: Instant) -> usize {
+        self.expire(now);
+        (self.max_per_hour as usize).saturating_sub(self.anchored.len())
+    }
+
+    /// Time until the oldest anchor in the window leaves it
+    pub fn next_slot(&self, now: Instant) -> Duration {
+        self.anchored
+            .front()
+            .map(|anchored| WINDOW.saturating_sub(now.saturating_duration_since(*anchored)))
+            .unwrap_or_default()
+    }
+
+    /// Note that the cap kept a batch back, keeping the time it was first held
+    pub fn hold(&mut self, batch_id: Uuid, now: Instant) {
+        self.held_since.entry(batch_id).or_insert(now);
+    }
+
+    /// Count an anchored batch; how long the cap delayed it, if it was held
+    pub fn record_anchor(&mut self, batch_id: &Uuid, now: Instant) -> Option<Duration> {
+        self.anchored.push_back(now);
+        self.held_since
+            .remove(batch_id)
+            .map(|since| now.saturating_duration_since(since))
+    }
+
+    /// Forget held batches `keep` rejects (anchored elsewhere, skipped, ...)
+    pub fn retain_held(&mut self, keep: impl Fn(&Uuid) -> bool) {
+        self.held_since.retain(|batch_id, _| keep(batch_id));
+    }
+
+    /// Batches currently held back by the cap
+    pub fn held(&self) -> usize {
+        self.held_since.len()
+    }
+
+    fn expire(&mut self, now: Instant) {
+        while self
+            .anchored
+            .front()
+            .is_some_and(|anchored| now.saturating_duration_since(*anchored) >= WINDOW)
+        {
+            self.anchored.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_room_frees_up_as_anchors_age_out() {
+        let start = Instant::now();
+        let mut throttle = AnchorThrottle::new(3);
+        assert_eq!(throttle.room(start), 3);
+
+        for i in 0..3 {
+            throttle.record_anchor(&Uuid::new_v4(), start + Duration::from_secs(i * 600));
+        }
+        let now = start + Duration::from_secs(1800);
+        assert_eq!(throttle.room(now), 0);
+        assert_eq!(throttle.next_slot(now), Duration::from_secs(1800));
+
+        // The first anchor leaves the window an hour after it went out
+        assert_eq!(throttle.room(start + WINDOW), 1);
+        assert_eq!(throttle.next_slot(start + WINDOW), Duration::from_secs(600));
+    }
+
+    #[test]
+    fn test_delay_is_measured_from_the_first_hold() {
+        let start = Instant::now();
+        let mut throttle = AnchorThrottle::new(1);
+        let held = Uuid::new_v4();
+        throttle.hold(held, start);
+        throttle.hold(held, start + Duration::from_secs(60));
+        assert_eq!(throttle.held(), 1);
+
+        assert_eq!(
+            throttle.record_anchor(&held, start + Duration::from_secs(300)),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(throttle.held(), 0);
+        // Batches the cap never held were not delayed by it
+        assert_eq!(
+            throttle.record_anchor(&Uuid::new_v4(), start + Duration::from_secs(300)),
+            None
+        );
+    }
+}
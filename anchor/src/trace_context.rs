@@ -0,0 +1,191 @@
+//! W3C trace context propagation
+//!
+//! Each anchor cycle runs under one [`TraceContext`]. Requests to the sequencer
+//! carry it as `traceparent` (and `tracestate`, when one was received) so the
+//! sequencer can join them into the same trace:
+//!
+//! ```text
+//! traceparent: 00-<32 hex trace id>-<16 hex parent id>-01
+//! ```
+//!
+//! Requests made in one cycle share its trace id. The parent id is the current
+//! `tracing` span, so each request names the span that sent it. Requests made
+//! outside a cycle start a trace of their own.
+
+use std::future::Future;
+
+use uuid::Uuid;
+
+tokio::task_local! {
+    static CURRENT: TraceContext;
+}
+
+/// `traceparent` version this service emits
+const VERSION: &str = "00";
+
+/// Trace flag marking the trace as sampled
+const FLAG_SAMPLED: u8 = 0x01;
+
+/// Position in a distributed trace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    trace_id: [u8; 16],
+    parent_id: [u8; 8],
+    flags: u8,
+    tracestate: Option<String>,
+}
+
+impl TraceContext {
+    /// Start a new sampled trace
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: random_trace_id(),
+            parent_id: random_parent_id(),
+            flags: FLAG_SAMPLED,
+            tracestate: None,
+        }
+    }
+
+    /// Parse an incoming `traceparent` header, with its `tracestate` if any
+    ///
+    /// Returns `None` for malformed headers or all-zero ids, as the spec requires.
+    pub fn parse(traceparent: &str, tracestate: Option<&str>) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let (version, trace_id, parent_id, flags) =
+            (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+        if version.len() != 2 || version == "ff" || (version == VERSION && parts.next().is_some()) {
+            return None;
+        }
+
+        let trace_id: [u8; 16] = decode_lower_hex(trace_id)?;
+        let parent_id: [u8; 8] = decode_lower_hex(parent_id)?;
+        let [flags] = decode_lower_hex::<1>(flags)?;
+        if trace_id == [0; 16] || parent_id == [0; 8] {
+            return None;
+        }
+
+        Some(Self {
+            trace_id,
+            parent_id,
+            flags,
+            tracestate: tracestate
+                .map(str::trim)
+                .filter(|state| !state.is_empty())
+                .map(str::to_string),
+        })
+    }
+
+    /// Context of the anchor cycle running on this task, if any
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(Clone::clone).ok()
+    }
+
+    /// Run `future` with `self` as the current context
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        CURRENT.scope(self, future).await
+    }
+
+    /// Context for a request sent from the current `tracing` span
+    pub fn child(&self) -> Self {
+        let parent_id = tracing::Span::current()
+            .id()
+            .map(|id| id.into_u64().to_be_bytes())
+            .unwrap_or_else(random_parent_id);
+        Self {
+            parent_id,
+            ..self.clone()
+        }
+    }
+
+    pub fn trace_id_hex(&self) -> String {
+        hex::encode(self.trace_id)
+    }
+
+    /// `traceparent` header value
+    pub fn traceparent(&self) -> String {
+        format!(
+            "{}-{}-{}-{:02x}",
+            VERSION,
+            hex::encode(self.trace_id),
+            hex::encode(self.parent_id),
+            self.flags
+        )
+    }
+
+    /// `tracestate` header value, passed through unchanged
+    pub fn tracestate(&self) -> Option<&str> {
+        self.tracestate.as_deref()
+    }
+}
+
+/// Context for an outgoing request: a child of the current cycle, or a new trace
+pub fn outgoing() -> TraceContext {
+    TraceContext::current()
+        .map(|context| context.child())
+        .unwrap_or_else(TraceContext::new_root)
+}
+
+fn random_trace_id() -> [u8; 16] {
+    *Uuid::new_v4().as_bytes()
+}
+
+fn random_parent_id() -> [u8; 8] {
+    let mut id = [0; 8];
+    id.copy_from_slice(&Uuid::new_v4().as_bytes()[..8]);
+    id
+}
+
+fn decode_lower_hex<const N: usize>(value: &str) -> Option<[u8; N]> {
+    if value.len() != N * 2 || value.bytes().any(|b| b.is_ascii_uppercase()) {
+        return None;
+    }
+    let mut bytes = [0; N];
+    hex::decode_to_slice(value, &mut bytes).ok()?;
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traceparent_round_trips() {
+        let header = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let context = TraceContext::parse(header, Some("vendor=opaque")).unwrap();
+        assert_eq!(context.traceparent(), header);
+        assert_eq!(context.tracestate(), Some("vendor=opaque"));
+        assert_eq!(context.trace_id_hex(), "4bf92f3577b34da6a3ce929d0e0e4736");
+
+        let root = TraceContext::new_root();
+        assert_eq!(TraceContext::parse(&root.traceparent(), None), Some(root));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_headers() {
+        for header in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        ] {
+            assert!(TraceContext::parse(header, None).is_none(), "{}", header);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_outgoing_requests_share_the_cycle_trace() {
+        let cycle = TraceContext::new_root();
+        let (first, second) = cycle
+            .clone()
+            .scope(async { (outgoing(), outgoing()) })
+            .await;
+        assert_eq!(first.trace_id_hex(), cycle.trace_id_hex());
+        assert_eq!(second.trace_id_hex(), cycle.trace_id_hex());
+
+        // Outside a cycle each request starts its own trace
+        assert_ne!(outgoing().trace_id_hex(), cycle.trace_id_hex());
+    }
+}
@@ -1,11 +1,20 @@
 //! Types for the anchor service
 
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::sla::SlaTracker;
+use crate::success_window::SuccessWindows;
+
 /// Batch commitment from stateset-sequencer
+///
+/// Parsed through [`VersionedCommitment`], so payloads of an unsupported
+/// schema major version are rejected instead of read with fields missing.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "VersionedCommitment")]
 pub struct BatchCommitment {
     /// Unique batch identifier
     pub batch_id: Uuid,
@@ -39,15 +48,323 @@ pub struct BatchCommitment {
 
     /// On-chain transaction hash (if anchored)
     pub chain_tx_hash: Option<String>,
+
+    /// Native 32-byte batch id (hex), sent by sequencers that no longer use UUIDs on-chain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_id_bytes32: Option<String>,
+
+    /// Payload schema version the sequencer sent (1.0 when it sent none)
+    pub schema_version: SchemaVersion,
+}
+
+/// `major.minor` version of the commitment payload schema
+///
+/// Minor versions only add optional fields, so every 1.x payload parses with
+/// the 1.0 fields. A different major is refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SchemaVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl SchemaVersion {
+    /// The version this build reads and writes
+    pub const CURRENT: SchemaVersion = SchemaVersion { major: 1, minor: 0 };
+
+    /// Fail on a major version this build has no parser for
+    pub fn ensure_supported(&self) -> Result<(), String> {
+        if self.major == Self::CURRENT.major {
+            return Ok(());
+        }
+        Err(format!(
+            "unsupported commitment schema version {} (this build reads {}.x)",
+            self,
+            Self::CURRENT.major
+        ))
+    }
+}
+
+impl Default for SchemaVersion {
+    fn default() -> Self {
+        Self::CURRENT
+    }
+}
+
+impl std::fmt::Display for SchemaVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl std::str::FromStr for SchemaVersion {
+    type Err = String;
+
+    /// Accepts `1`, `1.2` or `v1.2`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid schema version: {:?}", s);
+        let trimmed = s.trim();
+        let trimmed = trimmed.strip_prefix('v').unwrap_or(trimmed);
+        let (major, minor) = trimmed.split_once('.').unwrap_or((trimmed, "0"));
+        Ok(SchemaVersion {
+            major: major.parse().map_err(|_| invalid())?,
+            minor: minor.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl Serialize for SchemaVersion {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for SchemaVersion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Major(u32),
+            Text(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Major(major) => Ok(SchemaVersion { major, minor: 0 }),
+            Raw::Text(text) => text.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// A commitment as received, before its body is read by version
+#[derive(Deserialize)]
+struct VersionedCommitment {
+    #[serde(default)]
+    schema_version: SchemaVersion,
+    #[serde(flatten)]
+    body: serde_json::Value,
+}
+
+/// Commitment fields of schema 1.x
+#[derive(Deserialize)]
+struct CommitmentV1 {
+    batch_id: Uuid,
+    tenant_id: Uuid,
+    store_id: Uuid,
+    prev_state_root: String,
+    new_state_root: String,
+    events_root: String,
+    sequence_start: u64,
+    sequence_end: u64,
+    event_count: u32,
+    committed_at: DateTime<Utc>,
+    chain_tx_hash: Option<String>,
+    #[serde(default)]
+    batch_id_bytes32: Option<String>,
+}
+
+impl TryFrom<VersionedCommitment> for BatchCommitment {
+    type Error = String;
+
+    fn try_from(versioned: VersionedCommitment) -> Result<Self, Self::Error> {
+        let schema_version = versioned.schema_version;
+        schema_version.ensure_supported()?;
+        let v1: CommitmentV1 = serde_json::from_value(versioned.body).map_err(|e| e.to_string())?;
+        Ok(BatchCommitment {
+            batch_id: v1.batch_id,
+            tenant_id: v1.tenant_id,
+            store_id: v1.store_id,
+            prev_state_root: v1.prev_state_root,
+            new_state_root: v1.new_state_root,
+            events_root: v1.events_root,
+            sequence_start: v1.sequence_start,
+            sequence_end: v1.sequence_end,
+            event_count: v1.event_count,
+            committed_at: v1.committed_at,
+            chain_tx_hash: v1.chain_tx_hash,
+            batch_id_bytes32: v1.batch_id_bytes32,
+            schema_version,
+        })
+    }
+}
+
+/// How a batch id is mapped to the registry's `bytes32` key
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchIdScheme {
+    /// UUID bytes followed by 16 zero bytes (the original mapping)
+    #[default]
+    Padded,
+    /// keccak256 of the 16 UUID bytes
+    Keccak,
+    /// `batch_id_bytes32` exactly as sent by the sequencer
+    Native,
+}
+
+impl BatchIdScheme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BatchIdScheme::Padded => "padded",
+            BatchIdScheme::Keccak => "keccak",
+            BatchIdScheme::Native => "native",
+        }
+    }
+}
+
+impl std::str::FromStr for BatchIdScheme {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "padded" => Ok(BatchIdScheme::Padded),
+            "keccak" => Ok(BatchIdScheme::Keccak),
+            "native" => Ok(BatchIdScheme::Native),
+            other => anyhow::bail!("expected padded, keccak, or native, got: {}", other),
+        }
+    }
+}
+
+/// Wire protocol used to talk to the sequencer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SequencerProtocol {
+    /// JSON over HTTP at `SEQUENCER_API_URL`
+    #[default]
+    Rest,
+    /// gRPC at `SEQUENCER_GRPC_URL`
+    Grpc,
+}
+
+impl SequencerProtocol {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SequencerProtocol::Rest => "rest",
+            SequencerProtocol::Grpc => "grpc",
+        }
+    }
+}
+
+impl std::str::FromStr for SequencerProtocol {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "rest" => Ok(SequencerProtocol::Rest),
+            "grpc" => Ok(SequencerProtocol::Grpc),
+            other => anyhow::bail!("expected rest or grpc, got: {}", other),
+        }
+    }
+}
+
+/// Where the service receives pending commitments from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitmentSourceKind {
+    /// Poll the sequencer API (REST or gRPC, per `SEQUENCER_PROTOCOL`)
+    #[default]
+    Http,
+    /// Consume a Kafka topic
+    Kafka,
+    /// Pull from a NATS JetStream consumer
+    Nats,
+    /// Consume an AMQP (RabbitMQ) queue
+    Amqp,
+    /// Accept commitments pushed by the sequencer
+    Webhook,
+}
+
+impl CommitmentSourceKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommitmentSourceKind::Http => "http",
+            CommitmentSourceKind::Kafka => "kafka",
+            CommitmentSourceKind::Nats => "nats",
+            CommitmentSourceKind::Amqp => "amqp",
+            CommitmentSourceKind::Webhook => "webhook",
+        }
+    }
+}
+
+impl std::str::FromStr for CommitmentSourceKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "http" => Ok(CommitmentSourceKind::Http),
+            "kafka" => Ok(CommitmentSourceKind::Kafka),
+            "nats" => Ok(CommitmentSourceKind::Nats),
+            "amqp" => Ok(CommitmentSourceKind::Amqp),
+            "webhook" => Ok(CommitmentSourceKind::Webhook),
+            other => anyhow::bail!(
+                "expected http, kafka, nats, amqp, or webhook, got: {}",
+                other
+            ),
+        }
+    }
+}
+
+/// How the sequencer API credential is presented
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SequencerAuthScheme {
+    /// `Authorization: Bearer <token>`
+    #[default]
+    Bearer,
+    /// `X-API-Key: <token>`
+    ApiKey,
+}
+
+impl SequencerAuthScheme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SequencerAuthScheme::Bearer => "bearer",
+            SequencerAuthScheme::ApiKey => "api-key",
+        }
+    }
+}
+
+impl std::str::FromStr for SequencerAuthScheme {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "bearer" => Ok(SequencerAuthScheme::Bearer),
+            "api-key" | "x-api-key" => Ok(SequencerAuthScheme::ApiKey),
+            other => anyhow::bail!("expected bearer or api-key, got: {}", other),
+        }
+    }
 }
 
 /// Response from sequencer API listing pending commitments
+///
+/// Entries are left as JSON and read one at a time, so one the sequencer
+/// sent in a schema version this build can't read costs only that batch.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PendingCommitmentsResponse {
-    pub commitments: Vec<BatchCommitment>,
+    pub commitments: Vec<serde_json::Value>,
     pub total: usize,
 }
 
+/// One raw event of a batch, as hashed into its `events_root`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchEvent {
+    /// Sequence number within the tenant/store stream
+    pub sequence: u64,
+
+    /// Commerce event type, e.g. `OrderCreated`
+    pub event_type: String,
+
+    /// Event payload (hex)
+    pub payload: String,
+
+    /// Event metadata (hex)
+    #[serde(default)]
+    pub metadata: String,
+}
+
+/// Response from sequencer API listing the events of one batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchEventsResponse {
+    pub events: Vec<BatchEvent>,
+}
+
 /// Request to notify sequencer of successful anchoring
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnchorNotification {
@@ -55,6 +372,125 @@ pub struct AnchorNotification {
     pub chain_id: u64,
     pub block_number: Option<u64>,
     pub gas_used: Option<u64>,
+    /// Versioned hashes of the blobs carrying the batch's events (`DA_MODE=blob`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blob_versioned_hashes: Vec<String>,
+    /// CID of the batch's events pinned to IPFS (`IPFS_API_URL`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ipfs_cid: Option<String>,
+    /// Celestia blob carrying the batch's events (`CELESTIA_RPC_URL`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub celestia: Option<CelestiaBlobRef>,
+}
+
+/// Where a batch's events were published on Celestia
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CelestiaBlobRef {
+    /// Celestia block height the blob was included at
+    pub height: u64,
+    /// Namespace (29 bytes, hex)
+    pub namespace: String,
+    /// Share commitment of the blob (hex)
+    pub commitment: String,
+}
+
+/// Sent to the sequencer once an anchored batch can no longer be reorged out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FinalizedNotification {
+    pub chain_tx_hash: String,
+    pub chain_id: u64,
+    pub block_number: u64,
+    /// Head of the safe (`l2`) or finalized (`l1`) chain when finality was observed
+    pub finalized_block: u64,
+    pub finality: FinalityMode,
+}
+
+/// How final an anchor must be before the sequencer is sent `notify_finalized`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FinalityMode {
+    /// Anchored batches are only reported once, when mined
+    #[default]
+    Off,
+    /// The anchor block is at or below the L2 node's `safe` head
+    L2,
+    /// The anchor block is at or below the L2 node's `finalized` head, i.e. it
+    /// was derived from finalized L1 blocks
+    L1,
+}
+
+impl FinalityMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FinalityMode::Off => "off",
+            FinalityMode::L2 => "l2",
+            FinalityMode::L1 => "l1",
+        }
+    }
+}
+
+impl std::str::FromStr for FinalityMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" | "false" => Ok(FinalityMode::Off),
+            "l2" => Ok(FinalityMode::L2),
+            "l1" => Ok(FinalityMode::L1),
+            other => anyhow::bail!("expected off, l2, or l1, got: {}", other),
+        }
+    }
+}
+
+/// Where a batch's raw events are published alongside its commitment
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DaMode {
+    /// Only the commitment goes on-chain; events stay with the sequencer
+    #[default]
+    Off,
+    /// Events are posted as EIP-4844 blobs on the `commitBatch` transaction
+    Blob,
+    /// Events are posted zstd-compressed as calldata, for chains without blobs
+    Calldata,
+}
+
+impl DaMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DaMode::Off => "off",
+            DaMode::Blob => "blob",
+            DaMode::Calldata => "calldata",
+        }
+    }
+}
+
+impl std::str::FromStr for DaMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" | "none" => Ok(DaMode::Off),
+            "blob" | "blobs" => Ok(DaMode::Blob),
+            "calldata" => Ok(DaMode::Calldata),
+            other => anyhow::bail!("expected off, blob or calldata, got: {}", other),
+        }
+    }
+}
+
+/// Estimated cost of anchoring a batch, reported to the sequencer before submission
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchorCostEstimate {
+    pub batch_id: Uuid,
+    pub event_count: u32,
+    pub gas_estimate: u64,
+    /// Gas price in wei (decimal string; may exceed u64)
+    pub gas_price_wei: String,
+    /// gas_estimate × gas_price in wei (decimal string)
+    pub estimated_cost_wei: String,
+    /// Estimated cost divided by event count, in wei (decimal string)
+    pub cost_per_event_wei: String,
+    pub estimated_at: DateTime<Utc>,
 }
 
 /// Result of an anchor operation
@@ -66,6 +502,29 @@ pub struct AnchorResult {
     pub gas_used: u64,
     pub success: bool,
     pub error: Option<String>,
+    /// Not submitted this cycle (e.g. gas above the cap); retried on a later cycle
+    pub deferred: bool,
+}
+
+/// Record of a batch this service anchored (or recovered) on-chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchorRecord {
+    pub batch_id: Uuid,
+    pub tenant_id: Uuid,
+    pub store_id: Uuid,
+    pub tx_hash: String,
+    pub chain_id: u64,
+    pub block_number: u64,
+    pub gas_used: u64,
+    pub anchored_at: DateTime<Utc>,
+    /// Hex-encoded transaction input, if known
+    pub calldata: Option<String>,
+    /// Mapping used to derive the on-chain batch key
+    #[serde(default)]
+    pub batch_id_scheme: BatchIdScheme,
+    /// `bytes32` key the batch is stored under in the registry
+    #[serde(default)]
+    pub chain_batch_id: String,
 }
 
 /// Anchor service statistics
@@ -103,13 +562,116 @@ pub struct AnchorStats {
     pub circuit_breaker_state: CircuitBreakerState,
     /// Total cycles skipped due to open circuit breaker
     pub circuit_breaker_open_skips: u64,
+    /// Circuit breaker state for sequencer API calls
+    pub sequencer_breaker_state: CircuitBreakerState,
+    /// Circuit breaker state for L2 RPC calls
+    pub l2_breaker_state: CircuitBreakerState,
+    /// Total fee-bump replacements of stuck transactions
+    pub fee_bumps: u64,
+    /// Total transactions detected stuck in the mempool
+    pub stuck_txs: u64,
+    /// Batches currently waiting for gas to drop below the cap
+    pub deferred_batches: u64,
+    /// Age of the oldest deferred batch in seconds
+    pub oldest_deferred_secs: u64,
+    /// Total deferred batches anchored above the cap after `max_defer_secs`
+    pub deferral_overrides: u64,
+    /// Sum of `eth_estimateGas` results for confirmed anchor transactions
+    pub gas_estimated_total: u64,
+    /// Sum of gas actually used by those same transactions
+    pub gas_used_total: u64,
+    /// Estimate of the most recently confirmed anchor transaction
+    pub last_gas_estimate: Option<u64>,
+    /// Gas used by the most recently confirmed anchor transaction
+    pub last_gas_used: Option<u64>,
+    /// Gas spend attributed to each (tenant, store)
+    pub costs: HashMap<(Uuid, Uuid), CostTotals>,
+    /// Recent commit-to-anchor durations and SLA violations
+    pub time_to_anchor: SlaTracker,
+    /// Anchors and failures per tenant
+    pub tenants: HashMap<Uuid, TenantStats>,
+    /// Anchor outcomes over the last day, for rolling success rates
+    pub success_windows: SuccessWindows,
+    /// Gas used by every mined anchor transaction
+    pub total_gas_used: u64,
+    /// Fees paid for anchor and batch data transactions, including reverted ones
+    pub total_wei_spent: u128,
+    /// Anchor transactions confirmed, and their summed broadcast-to-receipt time
+    pub confirmations: u64,
+    pub confirmation_latency_ms_total: u64,
+    /// Fetched commitments not submitted, summed over cycles, by `/pending` status
+    pub skipped_commitments: HashMap<&'static str, u64>,
+    /// The same for the last cycle only
+    pub last_cycle_skips: HashMap<&'static str, u64>,
+}
+
+/// Anchor outcomes for one tenant
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TenantStats {
+    pub batches_anchored: u64,
+    pub events_anchored: u64,
+    pub batches_failed: u64,
+}
+
+impl TenantStats {
+    /// Anchored share of the tenant's attempted batches (1.0 before any attempt)
+    pub fn success_rate(&self) -> f64 {
+        let total = self.batches_anchored + self.batches_failed;
+        if total == 0 {
+            return 1.0;
+        }
+        self.batches_anchored as f64 / total as f64
+    }
+}
+
+/// Gas spend attributed to a tenant/store
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CostTotals {
+    /// Batches anchored
+    pub batches: u64,
+    /// Events covered by those batches
+    pub events: u64,
+    /// Wei paid, including reverted attempts
+    pub wei_spent: u128,
+    /// Batch event bytes posted for data availability
+    pub da_bytes: u64,
+    /// Share of `wei_spent` paid for posting those bytes
+    pub da_wei_spent: u128,
+}
+
+impl CostTotals {
+    pub fn add(&mut self, other: &CostTotals) {
+        self.batches += other.batches;
+        self.events += other.events;
+        self.wei_spent = self.wei_spent.saturating_add(other.wei_spent);
+        self.da_bytes += other.da_bytes;
+        self.da_wei_spent = self.da_wei_spent.saturating_add(other.da_wei_spent);
+    }
+
+    pub fn cost_per_batch_wei(&self) -> u128 {
+        self.wei_spent
+            .checked_div(self.batches as u128)
+            .unwrap_or(0)
+    }
+
+    pub fn cost_per_event_wei(&self) -> u128 {
+        self.wei_spent.checked_div(self.events as u128).unwrap_or(0)
+    }
+
+    pub fn cost_per_da_byte_wei(&self) -> u128 {
+        self.da_wei_spent
+            .checked_div(self.da_bytes as u128)
+            .unwrap_or(0)
+    }
 }
 
 impl AnchorStats {
     /// Record a successful anchor
     pub fn record_success(&mut self, anchor_time_ms: u64) {
+        let now = Utc::now();
         self.total_anchored += 1;
-        self.last_anchor_time = Some(Utc::now());
+        self.last_anchor_time = Some(now);
+        self.success_windows.record(true, now);
 
         // Update running average
         if self.total_anchored == 1 {
@@ -120,9 +682,24 @@ impl AnchorStats {
         }
     }
 
+    /// Record a mined anchor transaction
+    pub fn record_confirmation(&mut self, latency_ms: u64, gas_used: u64) {
+        self.confirmations += 1;
+        self.confirmation_latency_ms_total += latency_ms;
+        self.total_gas_used += gas_used;
+    }
+
+    /// Mean broadcast-to-receipt time (0 before the first confirmation)
+    pub fn avg_confirmation_latency_ms(&self) -> u64 {
+        self.confirmation_latency_ms_total
+            .checked_div(self.confirmations)
+            .unwrap_or(0)
+    }
+
     /// Record a failed anchor transaction
     pub fn record_anchor_failure(&mut self) {
         self.total_failed += 1;
+        self.success_windows.record(false, Utc::now());
     }
 
     /// Record a successful cycle
@@ -155,6 +732,20 @@ impl AnchorStats {
         self.gas_price_skips += 1;
     }
 
+    /// Record the estimate a confirmed transaction was sent with and its actual usage
+    pub fn record_gas_estimate(&mut self, estimated: u64, used: u64) {
+        self.gas_estimated_total += estimated;
+        self.gas_used_total += used;
+        self.last_gas_estimate = Some(estimated);
+        self.last_gas_used = Some(used);
+    }
+
+    /// Gas used as a fraction of the estimate, over all estimated transactions
+    pub fn gas_used_to_estimate_ratio(&self) -> Option<f64> {
+        (self.gas_estimated_total > 0)
+            .then(|| self.gas_used_total as f64 / self.gas_estimated_total as f64)
+    }
+
     /// Mark L2 as healthy
     pub fn mark_l2_healthy(&mut self) {
         self.last_l2_healthy = Some(Utc::now());
@@ -330,3 +921,60 @@ impl CircuitBreaker {
         matches!(self.state, CircuitBreakerState::Open)
     }
 }
+
+/// External dependency guarded by its own circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dependency {
+    SequencerApi,
+    L2Rpc,
+}
+
+impl Dependency {
+    /// String representation for logs and metric labels
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Dependency::SequencerApi => "sequencer_api",
+            Dependency::L2Rpc => "l2_rpc",
+        }
+    }
+}
+
+/// Circuit breaker around calls to a single dependency
+///
+/// Unlike the cycle breaker, which trips on consecutive failed cycles, this one
+/// counts consecutive failed calls to its dependency.
+#[derive(Debug, Clone)]
+pub struct DependencyBreaker {
+    pub breaker: CircuitBreaker,
+    pub consecutive_failures: u64,
+}
+
+impl DependencyBreaker {
+    pub fn new(breaker: CircuitBreaker) -> Self {
+        Self {
+            breaker,
+            consecutive_failures: 0,
+        }
+    }
+
+    /// Check if a call should be made, moving to half-open once the reset timeout passes
+    pub fn allow_request(&mut self) -> bool {
+        self.breaker.allow_request()
+    }
+
+    /// Record a successful call
+    pub fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.breaker.record_success();
+    }
+
+    /// Record a failed call
+    pub fn record_failure(&mut self) {
+        self.consecutive_failures += 1;
+        self.breaker.record_failure(self.consecutive_failures);
+    }
+
+    pub fn state(&self) -> CircuitBreakerState {
+        self.breaker.state
+    }
+}
@@ -0,0 +1,187 @@
+//! Structural checks on commitments before anything is sent to the chain
+//!
+//! Catches malformed sequencer payloads that would otherwise surface as an
+//! encoding error mid-submission or as a revert after paying for gas. Each
+//! rejection carries a [`ValidationError`] whose `kind()` labels
+//! `set_anchor_validation_failures_total`.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::error::ValidationError;
+use crate::types::BatchCommitment;
+
+/// How far in the future `committed_at` may be before it's treated as bogus
+const MAX_CLOCK_SKEW: Duration = Duration::minutes(5);
+
+/// Check one commitment's fields against each other and the clock
+pub fn validate_commitment(
+    commitment: &BatchCommitment,
+    now: DateTime<Utc>,
+) -> Result<(), ValidationError> {
+    check_root("prev_state_root", &commitment.prev_state_root)?;
+    check_root("new_state_root", &commitment.new_state_root)?;
+    check_root("events_root", &commitment.events_root)?;
+    if is_zero(&commitment.events_root) {
+        return Err(ValidationError::EmptyEventsRoot);
+    }
+
+    if commitment.sequence_end < commitment.sequence_start {
+        return Err(ValidationError::InvalidSequenceRange {
+            start: commitment.sequence_start,
+            end: commitment.sequence_end,
+        });
+    }
+    let span = commitment.sequence_end - commitment.sequence_start + 1;
+    if span != u64::from(commitment.event_count) {
+        return Err(ValidationError::EventCountMismatch {
+            expected: span,
+            provided: commitment.event_count,
+        });
+    }
+
+    if commitment.committed_at > now + MAX_CLOCK_SKEW {
+        return Err(ValidationError::CommittedInFuture {
+            committed_at: commitment.committed_at.to_rfc3339(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Split a fetched list into commitments safe to anchor and rejections
+///
+/// A batch id seen earlier in the list is rejected as a duplicate; the first
+/// copy is still checked and kept on its own merits.
+pub fn partition(
+    commitments: Vec<BatchCommitment>,
+    now: DateTime<Utc>,
+) -> (
+    Vec<BatchCommitment>,
+    Vec<(BatchCommitment, ValidationError)>,
+) {
+    let mut seen = HashSet::new();
+    let mut valid = Vec::with_capacity(commitments.len());
+    let mut rejected = Vec::new();
+
+    for commitment in commitments {
+        if !seen.insert(commitment.batch_id) {
+            let error = ValidationError::DuplicateBatchId {
+                batch_id: commitment.batch_id.to_string(),
+            };
+            rejected.push((commitment, error));
+            continue;
+        }
+        match validate_commitment(&commitment, now) {
+            Ok(()) => valid.push(commitment),
+            Err(error) => rejected.push((commitment, error)),
+        }
+    }
+
+    (valid, rejected)
+}
+
+/// A `0x`-prefixed 32-byte hex value
+fn check_root(field: &'static str, value: &str) -> Result<(), ValidationError> {
+    let invalid = |reason: String| ValidationError::InvalidRoot { field, reason };
+    let digits = value
+        .strip_prefix("0x")
+        .ok_or_else(|| invalid("missing 0x prefix".to_string()))?;
+    if digits.len() != 64 {
+        return Err(invalid(format!(
+            "expected 64 hex digits, got {}",
+            digits.len()
+        )));
+    }
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid("not hex".to_string()));
+    }
+    Ok(())
+}
+
+fn is_zero(root: &str) -> bool {
+    root.trim_start_matches("0x").chars().all(|c| c == '0')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SchemaVersion;
+    use uuid::Uuid;
+
+    fn commitment() -> BatchCommitment {
+        BatchCommitment {
+            batch_id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            store_id: Uuid::new_v4(),
+            prev_state_root: format!("0x{}", "0".repeat(64)),
+            new_state_root: format!("0x{}", "22".repeat(32)),
+            events_root: format!("0x{}", "11".repeat(32)),
+            sequence_start: 1,
+            sequence_end: 10,
+            event_count: 10,
+            committed_at: Utc::now(),
+            chain_tx_hash: None,
+            batch_id_bytes32: None,
+            schema_version: SchemaVersion::CURRENT,
+        }
+    }
+
+    #[test]
+    fn test_rejection_reasons() {
+        let now = Utc::now();
+        assert!(validate_commitment(&commitment(), now).is_ok());
+
+        let reason = |edit: fn(&mut BatchCommitment)| {
+            let mut commitment = commitment();
+            edit(&mut commitment);
+            validate_commitment(&commitment, now).unwrap_err().kind()
+        };
+        assert_eq!(
+            reason(|c| c.new_state_root = "0x1234".into()),
+            "invalid_root"
+        );
+        assert_eq!(reason(|c| c.events_root = "11".repeat(32)), "invalid_root");
+        assert_eq!(
+            reason(|c| c.prev_state_root = format!("0x{}", "zz".repeat(32))),
+            "invalid_root"
+        );
+        assert_eq!(
+            reason(|c| c.events_root = format!("0x{}", "0".repeat(64))),
+            "empty_events_root"
+        );
+        assert_eq!(reason(|c| c.sequence_end = 0), "invalid_sequence_range");
+        assert_eq!(reason(|c| c.event_count = 9), "event_count_mismatch");
+        assert_eq!(
+            reason(|c| c.committed_at = Utc::now() + Duration::hours(1)),
+            "committed_in_future"
+        );
+    }
+
+    #[test]
+    fn test_partition_rejects_later_duplicates() {
+        let first = commitment();
+        let mut broken = commitment();
+        broken.event_count = 3;
+
+        let (valid, rejected) = partition(
+            vec![first.clone(), broken.clone(), first.clone()],
+            Utc::now(),
+        );
+
+        assert_eq!(valid.len(), 1);
+        assert_eq!(valid[0].batch_id, first.batch_id);
+        let reasons: Vec<(Uuid, &str)> = rejected
+            .iter()
+            .map(|(c, error)| (c.batch_id, error.kind()))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                (broken.batch_id, "event_count_mismatch"),
+                (first.batch_id, "duplicate_batch_id"),
+            ]
+        );
+    }
+}
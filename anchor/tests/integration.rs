@@ -12,13 +12,17 @@ mod common;
 use std::sync::Arc;
 use std::time::Duration;
 
-use chrono::Utc;
 use serial_test::serial;
 use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 use set_anchor::{
-    client::SequencerApiClient, config::AnchorConfig, health::HealthState, types::AnchorStats,
+    client::SequencerApiClient,
+    config::AnchorConfig,
+    health::HealthState,
+    recorder::{StatsEvent, StatsRecorder},
+    types::AnchorStats,
     AnchorService,
 };
 
@@ -56,6 +60,7 @@ fn test_config(
         circuit_breaker_reset_timeout_secs: 60,
         circuit_breaker_half_open_success_threshold: 3,
         tx_confirmation_timeout_secs: 60,
+        ..AnchorConfig::default()
     }
 }
 
@@ -122,6 +127,9 @@ async fn test_anchor_notification_recorded() {
         chain_id: 84532001,
         block_number: Some(100),
         gas_used: Some(50000),
+        blob_versioned_hashes: Vec::new(),
+        ipfs_cid: None,
+        celestia: None,
     };
 
     client
@@ -139,6 +147,85 @@ async fn test_anchor_notification_recorded() {
     assert_eq!(mock.pending_count().await, 0);
 }
 
+#[tokio::test]
+async fn test_sequencer_endpoint_timeouts_are_independent() {
+    use wiremock::{
+        matchers::{method, path, path_regex},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/commitments/pending"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "commitments": [], "total": 0 }))
+                .set_delay(Duration::from_millis(300)),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path_regex(r"/v1/commitments/[0-9a-f-]+/anchored"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(300)))
+        .mount(&server)
+        .await;
+
+    let client = SequencerApiClient::new_with_timeouts(
+        &server.uri(),
+        Duration::from_secs(10),
+        Duration::from_secs(3),
+    )
+    .with_endpoint_timeouts(set_anchor::config::SequencerTimeouts {
+        fetch: Duration::from_secs(5),
+        notify: Duration::from_millis(50),
+        health: Duration::from_secs(1),
+    });
+
+    // The slow fetch fits within its own generous timeout
+    assert!(client.get_pending_commitments().await.unwrap().is_empty());
+
+    // The same delay on the notify path trips its short timeout
+    let notification = set_anchor::types::AnchorNotification {
+        chain_tx_hash: "0x1234".to_string(),
+        chain_id: 84532001,
+        block_number: Some(1),
+        gas_used: Some(21_000),
+        blob_versioned_hashes: Vec::new(),
+        ipfs_cid: None,
+        celestia: None,
+    };
+    let started = std::time::Instant::now();
+    assert!(client
+        .notify_anchored(Uuid::new_v4(), &notification)
+        .await
+        .is_err());
+    assert!(started.elapsed() < Duration::from_millis(300));
+}
+
+#[tokio::test]
+async fn test_sequencer_client_from_config_honors_timeouts() {
+    use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "commitments": [], "total": 0 }))
+                .set_delay(Duration::from_millis(1500)),
+        )
+        .mount(&server)
+        .await;
+
+    let mut config = test_config(&server.uri(), "http://127.0.0.1:8545", "", "");
+    config.sequencer_request_timeout_secs = 1;
+    config.sequencer_pool_max_idle_per_host = 2;
+    let client = SequencerApiClient::from_config(&config);
+
+    let started = std::time::Instant::now();
+    assert!(client.get_pending_commitments().await.is_err());
+    assert!(started.elapsed() < Duration::from_millis(1500));
+}
+
 // =============================================================================
 // Health Endpoint Tests
 // =============================================================================
@@ -260,14 +347,14 @@ async fn test_ready_endpoint_becomes_ready() {
     assert_eq!(json["sequencer_connected"], true);
 }
 
+/// Counters on /metrics come from recorded events, not from the `AnchorStats`
+/// handed to `HealthState::new`; `/stats` still reports the stats themselves
 #[tokio::test]
-async fn test_metrics_endpoint_format() {
+async fn test_metrics_counters_follow_recorded_events() {
     let stats = Arc::new(RwLock::new(AnchorStats {
         total_anchored: 42,
         total_failed: 3,
         total_events_anchored: 1000,
-        last_anchor_time: Some(Utc::now()),
-        last_batch_id: Some(Uuid::new_v4()),
         ..AnchorStats::default()
     }));
 
@@ -279,39 +366,56 @@ async fn test_metrics_endpoint_format() {
     );
 
     let health_state = Arc::new(HealthState::new(config, Arc::clone(&stats)));
+    let recorder = StatsRecorder::new(&stats).with_metrics(Arc::clone(&health_state.metrics));
+    for _ in 0..4 {
+        recorder.record(StatsEvent::AnchorSucceeded {
+            batch_id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            event_count: 25,
+            anchor_time_ms: 100,
+        });
+    }
+    recorder.record(StatsEvent::AnchorFailed {
+        tenant_id: Uuid::new_v4(),
+    });
+    recorder.flush().await;
     let router = set_anchor::health::create_router(Arc::clone(&health_state));
 
     use axum::body::Body;
     use axum::http::Request;
     use tower::util::ServiceExt;
 
-    let response = router
-        .oneshot(
-            Request::builder()
-                .uri("/metrics")
-                .body(Body::empty())
-                .unwrap(),
-        )
-        .await
-        .unwrap();
-
+    let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = router.clone().oneshot(get("/metrics")).await.unwrap();
     assert_eq!(response.status(), 200);
-
     let body = axum::body::to_bytes(response.into_body(), usize::MAX)
         .await
         .unwrap();
     let body_str = String::from_utf8(body.to_vec()).unwrap();
 
-    // Verify Prometheus format
     assert!(body_str.contains("# HELP set_anchor_batches_total"));
     assert!(body_str.contains("# TYPE set_anchor_batches_total counter"));
-    assert!(body_str.contains("set_anchor_batches_total{status=\"success\"} 42"));
-    assert!(body_str.contains("set_anchor_batches_total{status=\"failed\"} 3"));
-    assert!(body_str.contains("set_anchor_events_total 1000"));
-    assert!(body_str.contains("set_anchor_gas_price_skips_total 0"));
+    assert!(body_str.contains("set_anchor_batches_total{status=\"success\"} 4"));
+    assert!(body_str.contains("set_anchor_batches_total{status=\"failed\"} 1"));
+    assert!(body_str.contains("set_anchor_events_total 100"));
     assert!(body_str.contains("set_anchor_cycles_total 0"));
-    assert!(body_str.contains("set_anchor_l2_connected 0"));
-    assert!(body_str.contains("set_anchor_sequencer_connected 0"));
+
+    // A second scrape leaves the counters where the events put them
+    let response = router.clone().oneshot(get("/metrics")).await.unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(String::from_utf8(body.to_vec())
+        .unwrap()
+        .contains("set_anchor_batches_total{status=\"success\"} 4"));
+
+    let response = router.oneshot(get("/stats")).await.unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["total_anchored"], 46);
+    assert_eq!(json["total_failed"], 4);
 }
 
 #[tokio::test]
@@ -398,6 +502,7 @@ async fn test_service_skips_below_threshold() {
         circuit_breaker_reset_timeout_secs: 60,
         circuit_breaker_half_open_success_threshold: 3,
         tx_confirmation_timeout_secs: 60,
+        ..AnchorConfig::default()
     };
 
     // We can't run the full service without a real L2, but we can verify
@@ -468,16 +573,19 @@ async fn test_full_anchor_flow_with_anvil() {
     let service = AnchorService::with_health_state(config, Arc::clone(&health_state));
 
     // Run service in background with timeout
-    let service_handle =
-        tokio::spawn(
-            async move { tokio::time::timeout(Duration::from_secs(10), service.run()).await },
-        );
+    let shutdown = CancellationToken::new();
+    let service_handle = tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move { tokio::time::timeout(Duration::from_secs(10), service.run(shutdown)).await }
+    });
 
     // Wait for anchoring to complete
     tokio::time::sleep(Duration::from_secs(3)).await;
 
-    // Abort the service
-    service_handle.abort();
+    // Stop the service once its cycle has finished
+    shutdown.cancel();
+    let stopped = service_handle.await.unwrap();
+    assert!(matches!(stopped, Ok(Ok(()))), "{:?}", stopped);
 
     // Verify commitment was anchored
     let final_count = registry.total_commitments().await.unwrap();
@@ -527,13 +635,16 @@ async fn test_multiple_commitments_anchored_sequentially() {
     let health_state = Arc::new(HealthState::new(config.clone(), Arc::clone(&stats)));
     let service = AnchorService::with_health_state(config, Arc::clone(&health_state));
 
-    let service_handle =
-        tokio::spawn(
-            async move { tokio::time::timeout(Duration::from_secs(15), service.run()).await },
-        );
+    let shutdown = CancellationToken::new();
+    let service_handle = tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move { tokio::time::timeout(Duration::from_secs(15), service.run(shutdown)).await }
+    });
 
     tokio::time::sleep(Duration::from_secs(5)).await;
-    service_handle.abort();
+    shutdown.cancel();
+    let stopped = service_handle.await.unwrap();
+    assert!(matches!(stopped, Ok(Ok(()))), "{:?}", stopped);
 
     // Both commitments should have been anchored
     let final_count = registry.total_commitments().await.unwrap();
@@ -572,7 +683,11 @@ async fn test_unauthorized_sequencer_fails() {
     let service = AnchorService::with_health_state(config, Arc::clone(&health_state));
 
     // Service should fail to start due to authorization check
-    let result = tokio::time::timeout(Duration::from_secs(5), service.run()).await;
+    let result = tokio::time::timeout(
+        Duration::from_secs(5),
+        service.run(CancellationToken::new()),
+    )
+    .await;
 
     // Either timeout or error is acceptable
     match result {